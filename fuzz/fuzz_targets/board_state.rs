@@ -0,0 +1,268 @@
+//! Fuzz target: random boards and command sequences through `BoardState`,
+//! the solvers, and undo/redo.
+//!
+//! The input picks a board size and some givens, then a run of commands
+//! (placing, cycling, and erasing cats, undo, redo, and restarting), applied
+//! the way the controller applies them. After every command the board must
+//! hold together (`BoardState::check_invariants_since`: values in range, cell
+//! types matching the cells, givens untouched), and undo history landing on exactly the boards seen before.
+//! Before and after the commands, both solver backends must agree with a
+//! deliberately simple reference solver on how many solutions the board has
+//! (and, when there's one, on what it is), so the fast solvers can be
+//! rewritten without changing their answers.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nine_lives_core::{
+    BoardState, CellType, GameHistory, GameState, GridSize, Puzzle, SolverBackend, count_solutions_with, solve_unique,
+};
+
+/// Board sizes to fuzz. Mega Clowder boards are left out: the reference
+/// solver is too slow for them to get through many inputs.
+const SIZES: [GridSize; 3] = [GridSize::Mini, GridSize::Small, GridSize::Classic];
+
+/// One step of the command sequence.
+#[derive(Debug, Clone, Copy)]
+enum Command {
+    Set { row: usize, col: usize, value: usize },
+    Cycle { row: usize, col: usize },
+    Clear { row: usize, col: usize },
+    Undo,
+    Redo,
+    Restart,
+}
+
+impl Command {
+    /// Decode a command from two bytes: the low bits of the first choose the
+    /// command and the rest its cat, and the second the cell.
+    fn decode(op: u8, cell: u8, size: usize) -> Command {
+        let (row, col) = (cell as usize / size % size, cell as usize % size);
+        match op % 8 {
+            0..=2 => Command::Set { row, col, value: (op >> 3) as usize % size },
+            3 => Command::Cycle { row, col },
+            4 => Command::Clear { row, col },
+            5 | 6 => Command::Undo,
+            _ if op >> 3 == 0 => Command::Restart,
+            _ => Command::Redo,
+        }
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&size_byte, rest)) = data.split_first() else {
+        return;
+    };
+    let grid_size = SIZES[size_byte as usize % SIZES.len()];
+    let size = grid_size.dimension();
+
+    // Givens: a count, then a cell and a cat for each (clashes allowed)
+    let mut board = BoardState::with_size(grid_size);
+    let Some((&given_count, rest)) = rest.split_first() else {
+        return;
+    };
+    let given_bytes = (given_count as usize % (size * size / 2 + 1)) * 2;
+    let (givens, commands) = rest.split_at(given_bytes.min(rest.len()));
+    for pair in givens.chunks_exact(2) {
+        let index = pair[0] as usize % (size * size);
+        let (row, col) = (index / size, index % size);
+        board.cells[row][col] = Some(pair[1] as usize % size);
+        board.cell_types[row][col] = Some(CellType::Given);
+    }
+    let puzzle = board.clone();
+    let start = puzzle.puzzle();
+
+    check_solvers(&board);
+
+    // Commands, with a record of the board at every point in the history
+    let mut history = GameHistory::new();
+    let mut boards = vec![board.clone()];
+    for pair in commands.chunks_exact(2) {
+        match Command::decode(pair[0], pair[1], size) {
+            Command::Set { row, col, value } => {
+                let game_move = board.set_cell(row, col, Some(value));
+                record(&mut history, &mut boards, &board, game_move);
+            }
+            Command::Cycle { row, col } => {
+                let game_move = board.cycle_cell(row, col, size);
+                record(&mut history, &mut boards, &board, game_move);
+            }
+            Command::Clear { row, col } => {
+                let game_move = board.clear_cell(row, col);
+                assert!(board.cells[row][col].is_none() || board.is_given_cell(row, col));
+                record(&mut history, &mut boards, &board, game_move);
+            }
+            Command::Undo => {
+                if let Some(game_move) = history.peek_undo().cloned() {
+                    board.undo_move(&game_move);
+                    history.mark_undone();
+                }
+            }
+            Command::Redo => {
+                if let Some(game_move) = history.peek_redo().cloned() {
+                    board.apply_move(&game_move);
+                    history.mark_redone();
+                }
+            }
+            Command::Restart => {
+                board.reset_to_givens();
+                history.clear();
+                boards = vec![board.clone()];
+                assert_same(&board, &puzzle, "restarting goes back to the givens");
+            }
+        }
+
+        check_board(&board, &start);
+        let (position, _) = history.position_info();
+        assert_same(&board, &boards[position], "undo and redo land on the boards seen before");
+    }
+
+    check_solvers(&board);
+});
+
+/// Add a move to the history (as the controller does), keeping `boards` in
+/// step: one board per history position, the board before any move first.
+fn record(
+    history: &mut GameHistory,
+    boards: &mut Vec<BoardState>,
+    board: &BoardState,
+    game_move: Option<nine_lives_core::Move>,
+) {
+    let Some(game_move) = game_move else {
+        return;
+    };
+    let (position, _) = history.position_info();
+    history.add_move(game_move);
+    boards.truncate(position + 1);
+    boards.push(board.clone());
+    // The history only keeps so many moves; drop the boards it forgot
+    let (_, len) = history.position_info();
+    while boards.len() > len + 1 {
+        boards.remove(0);
+    }
+}
+
+/// Whether two boards hold the same cats and cell types.
+fn assert_same(board: &BoardState, expected: &BoardState, what: &str) {
+    assert_eq!(board.cells, expected.cells, "{}", what);
+    assert_eq!(board.cell_types, expected.cell_types, "{}", what);
+}
+
+/// Check the invariants every board must keep (see `BoardState::check_invariants_since`).
+fn check_board(board: &BoardState, puzzle: &Puzzle) {
+    if let Err(violations) = board.check_invariants_since(puzzle) {
+        let descriptions: Vec<String> = violations.iter().map(|violation| violation.description()).collect();
+        panic!("board doesn't hold together: {}", descriptions.join("; "));
+    }
+
+    let solved = reference_is_solved(board);
+    assert_eq!(board.is_complete(), solved, "is_complete disagrees with the rules");
+    assert_eq!(board.compute_game_state() == GameState::Won, solved);
+}
+
+/// Check that both solver backends agree with the reference solver.
+fn check_solvers(board: &BoardState) {
+    let (expected, solution) = reference_solve(board, 2);
+    for backend in [SolverBackend::Backtracking, SolverBackend::DancingLinks] {
+        assert_eq!(count_solutions_with(board, backend, 2), expected, "{:?} miscounted", backend);
+    }
+    match solve_unique(board) {
+        Some(found) => {
+            assert_eq!(expected, 1, "solve_unique found a solution to a board without exactly one");
+            let solution = solution.expect("the reference solver found it too");
+            assert!(
+                (0..board.size()).all(|row| (0..board.size()).all(|col| solution[row][col] == found.cells[row][col])),
+                "solve_unique's solution differs from the reference"
+            );
+        }
+        None => assert_ne!(expected, 1, "solve_unique missed the only solution"),
+    }
+}
+
+// --- Reference solver ---
+//
+// Plain rule checks and backtracking, with none of the real solvers' tricks
+// (only picking the emptiest-looking cell first, so it finishes in time).
+
+/// Whether `value` can go at (`row`, `col`) without repeating in its row,
+/// column, or box.
+fn reference_fits(grid: &[Vec<Option<usize>>], grid_size: GridSize, row: usize, col: usize, value: usize) -> bool {
+    let size = grid.len();
+    let (box_rows, box_cols) = (grid_size.box_rows(), grid_size.box_cols());
+    let (top, left) = (row / box_rows * box_rows, col / box_cols * box_cols);
+    (0..size).all(|i| {
+        (i == col || grid[row][i] != Some(value))
+            && (i == row || grid[i][col] != Some(value))
+            && {
+                let (r, c) = (top + i / box_cols, left + i % box_cols);
+                (r, c) == (row, col) || grid[r][c] != Some(value)
+            }
+    })
+}
+
+/// Whether every cell is filled and nothing repeats.
+fn reference_is_solved(board: &BoardState) -> bool {
+    let size = board.size();
+    (0..size).all(|row| {
+        (0..size).all(|col| {
+            board.cells[row][col].is_some_and(|value| reference_fits(&board.cells, board.grid_size, row, col, value))
+        })
+    })
+}
+
+/// Count the board's solutions up to `limit`, returning the first one found.
+fn reference_solve(board: &BoardState, limit: usize) -> (usize, Option<Vec<Vec<usize>>>) {
+    let size = board.size();
+    let clashes = (0..size).any(|row| {
+        (0..size).any(|col| {
+            board.cells[row][col].is_some_and(|value| !reference_fits(&board.cells, board.grid_size, row, col, value))
+        })
+    });
+    if clashes {
+        return (0, None);
+    }
+
+    let mut grid = board.cells.clone();
+    let mut count = 0;
+    let mut first = None;
+    reference_search(&mut grid, board.grid_size, limit, &mut count, &mut first);
+    (count, first)
+}
+
+fn reference_search(
+    grid: &mut Vec<Vec<Option<usize>>>,
+    grid_size: GridSize,
+    limit: usize,
+    count: &mut usize,
+    first: &mut Option<Vec<Vec<usize>>>,
+) {
+    let size = grid.len();
+    let mut best: Option<(usize, usize, Vec<usize>)> = None;
+    for row in 0..size {
+        for col in 0..size {
+            if grid[row][col].is_some() {
+                continue;
+            }
+            let fits: Vec<usize> = (0..size).filter(|&value| reference_fits(grid, grid_size, row, col, value)).collect();
+            if best.as_ref().is_none_or(|(_, _, best_fits)| fits.len() < best_fits.len()) {
+                best = Some((row, col, fits));
+            }
+        }
+    }
+
+    let Some((row, col, fits)) = best else {
+        *count += 1;
+        if first.is_none() {
+            *first = Some(grid.iter().map(|cells| cells.iter().map(|value| value.unwrap()).collect()).collect());
+        }
+        return;
+    };
+    for value in fits {
+        grid[row][col] = Some(value);
+        reference_search(grid, grid_size, limit, count, first);
+        grid[row][col] = None;
+        if *count >= limit {
+            return;
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! Nine Lives Cat Sudoku - Command Line Puzzle Tools
+//!
+//! Generates, rates, and solves puzzles from the command line, and runs the
+//! generator self-test, using only `nine_lives_core` (built without Bevy, so
+//! no window and no GPU needed):
+//!
+//! ```text
+//! nine-lives gen --preset night-prowler --count 100 --seed 42 --format sdk
+//! nine-lives rate < puzzles.txt
+//! nine-lives solve --format sdk < puzzle.sdk
+//! nine-lives self-test 10
+//! ```
+//!
+//! Puzzles are written and read in one of three formats:
+//! - `line`: one puzzle string per line ('.' for empty cells), the default
+//! - `sdk`: one row per line, puzzles separated by a blank line
+//! - `code`: one share code per line, as entered on the start screen
+//!
+//! Lines starting with `#` are comments and skipped when reading.
+
+use std::io::Read;
+use std::process::ExitCode;
+
+use nine_lives_core::{BoardState, PresetKind, PuzzleSettings, SelfTestConfig, rate_difficulty, run_self_test, solve_puzzle};
+use rayon::prelude::*;
+
+const USAGE: &str = "\
+Usage:
+  nine-lives gen [--preset NAME] [--count N] [--seed N] [--format FORMAT]
+  nine-lives rate [--format FORMAT] < puzzles
+  nine-lives solve [--format FORMAT] < puzzles
+  nine-lives self-test [PUZZLES-PER-PRESET]
+
+Presets: cozy-kitten (default), curious-cat, streetwise-stray, night-prowler
+Formats: line (default), sdk, code";
+
+/// How puzzles are written out and read in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Format {
+    /// One puzzle string per line
+    #[default]
+    Line,
+    /// One row per line, puzzles separated by a blank line
+    Sdk,
+    /// One share code per line
+    Code,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "line" => Some(Format::Line),
+            "sdk" => Some(Format::Sdk),
+            "code" => Some(Format::Code),
+            _ => None,
+        }
+    }
+}
+
+/// Options shared by every command (each command uses the ones it needs).
+#[derive(Debug, Clone, PartialEq)]
+struct Options {
+    preset: PresetKind,
+    count: usize,
+    /// Seed for the first puzzle; each later one uses the next seed up
+    seed: Option<u64>,
+    format: Format,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self { preset: PresetKind::CozyKitten, count: 1, seed: None, format: Format::Line }
+    }
+}
+
+/// The command-line name of a preset: "😾 Night Prowler" is `night-prowler`.
+fn preset_name(preset: PresetKind) -> String {
+    preset
+        .display_name()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == ' ')
+        .collect::<String>()
+        .trim()
+        .to_lowercase()
+        .replace(' ', "-")
+}
+
+/// Read the options following a command.
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", flag));
+        match flag.as_str() {
+            "--preset" => {
+                let name = value()?;
+                options.preset = PresetKind::all()
+                    .into_iter()
+                    .find(|&preset| preset_name(preset) == *name)
+                    .ok_or_else(|| format!("unknown preset '{}'", name))?;
+            }
+            "--count" => {
+                let count = value()?;
+                options.count = count.parse().map_err(|_| format!("'{}' isn't a count", count))?;
+            }
+            "--seed" => {
+                let seed = value()?;
+                options.seed = Some(seed.parse().map_err(|_| format!("'{}' isn't a seed", seed))?);
+            }
+            "--format" => {
+                let name = value()?;
+                options.format = Format::from_name(name).ok_or_else(|| format!("unknown format '{}'", name))?;
+            }
+            _ => return Err(format!("unknown option '{}'", flag)),
+        }
+    }
+    Ok(options)
+}
+
+// --- Formats ---
+
+/// Write a board in a format. Share codes need the settings it was made
+/// with; without them (or for a solved board, which has no givens to pack)
+/// it's written as a puzzle string instead.
+fn write_board(board: &BoardState, format: Format, settings: Option<&PuzzleSettings>) -> String {
+    match (format, settings) {
+        (Format::Code, Some(settings)) => board.to_share_code(settings),
+        (Format::Sdk, _) => {
+            let puzzle = board.puzzle_string();
+            let chars: Vec<char> = puzzle.chars().collect();
+            chars
+                .chunks(board.size())
+                .map(|row| row.iter().collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n")
+                + "\n"
+        }
+        _ => board.puzzle_string(),
+    }
+}
+
+/// Read every puzzle in `input`, failing on the first one that can't be read.
+fn read_puzzles(input: &str, format: Format) -> Result<Vec<BoardState>, String> {
+    let lines = input.lines().map(str::trim).filter(|line| !line.starts_with('#'));
+    let entries: Vec<String> = match format {
+        Format::Line | Format::Code => lines.filter(|line| !line.is_empty()).map(str::to_string).collect(),
+        Format::Sdk => lines
+            .collect::<Vec<_>>()
+            .split(|line| line.is_empty())
+            .filter(|rows| !rows.is_empty())
+            .map(|rows| rows.concat())
+            .collect(),
+    };
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let board = match format {
+                Format::Code => BoardState::from_share_code(entry).map(|shared| shared.board),
+                Format::Line | Format::Sdk => BoardState::from_puzzle_string(entry),
+            };
+            board.ok_or_else(|| format!("puzzle {} can't be read: {}", index + 1, entry))
+        })
+        .collect()
+}
+
+// --- Commands ---
+
+/// `gen`: generate `count` puzzles from a preset. The puzzles generate in
+/// parallel but print in order, so a seeded batch always comes out the same.
+fn generate(options: &Options) -> bool {
+    let puzzles: Vec<_> = (0..options.count)
+        .into_par_iter()
+        .map(|index| {
+            let settings = PuzzleSettings {
+                seed: options.seed.map(|seed| seed.wrapping_add(index as u64)),
+                ..PuzzleSettings::from_preset(options.preset)
+            };
+            let mut board = BoardState::new();
+            board
+                .generate_puzzle_with_settings(&settings)
+                .map(|_| write_board(&board, options.format, Some(&settings)))
+        })
+        .collect();
+
+    let mut passed = true;
+    for (index, puzzle) in puzzles.into_iter().enumerate() {
+        match puzzle {
+            Ok(puzzle) => println!("{}", puzzle),
+            Err(e) => {
+                eprintln!("❌ Puzzle {}: {}", index + 1, e);
+                passed = false;
+            }
+        }
+    }
+    passed
+}
+
+/// `rate`: grade each puzzle, one tab-separated line per puzzle with its
+/// difficulty, score, and hardest technique.
+fn rate(puzzles: &[BoardState]) -> bool {
+    let mut passed = true;
+    for board in puzzles {
+        let report = rate_difficulty(board);
+        if report.unique {
+            let hardest = report.hardest_technique().map_or("none", |technique| technique.name());
+            println!("{}\t{}\t{}\t{}", board.puzzle_string(), report.difficulty().name(), report.score, hardest);
+        } else {
+            println!("{}\tnot unique", board.puzzle_string());
+            passed = false;
+        }
+    }
+    passed
+}
+
+/// `solve`: write each puzzle's solution, or a note for puzzles without
+/// exactly one.
+fn solve(puzzles: &[BoardState], format: Format) -> bool {
+    let mut passed = true;
+    for board in puzzles {
+        match solve_puzzle(board) {
+            Ok(solution) => {
+                let mut solved = board.clone();
+                for (row, values) in solution.cells.iter().enumerate() {
+                    for (col, &value) in values.iter().enumerate() {
+                        solved.cells[row][col] = Some(value);
+                    }
+                }
+                println!("{}", write_board(&solved, format, None));
+            }
+            Err(error) => {
+                println!("# {}: {}", error, board.puzzle_string());
+                passed = false;
+            }
+        }
+    }
+    passed
+}
+
+/// `self-test`: check the generator on every board size and variant (see
+/// `run_self_test`), printing each report.
+fn self_test(puzzles_per_preset: usize) -> bool {
+    let mut passed = true;
+    for config in SelfTestConfig::every_board(puzzles_per_preset) {
+        println!(
+            "🧪 Self-test: {} {} ({} puzzles per preset)",
+            config.grid_size.display_name(),
+            config.variant.display_name(),
+            puzzles_per_preset
+        );
+        let report = run_self_test(&config);
+        println!("{}", report.summary());
+        passed &= report.passed();
+    }
+
+    if passed {
+        println!("✅ Self-test passed");
+    } else {
+        println!("❌ Self-test failed");
+    }
+    passed
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(command) = args.first().map(String::as_str) else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+    if command == "help" || command == "--help" {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    if command == "self-test" {
+        let puzzles_per_preset = match args.get(1).map(|count| count.parse()) {
+            None => SelfTestConfig::default().puzzles_per_preset,
+            Some(Ok(count)) => count,
+            Some(Err(_)) => {
+                eprintln!("⚠️ '{}' isn't a count\n\n{}", args[1], USAGE);
+                return ExitCode::FAILURE;
+            }
+        };
+        return if self_test(puzzles_per_preset) { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+    }
+
+    let options = match parse_options(&args[1..]) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("⚠️ {}\n\n{}", error, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let passed = match command {
+        "gen" => generate(&options),
+        "rate" | "solve" => {
+            let mut input = String::new();
+            if let Err(error) = std::io::stdin().read_to_string(&mut input) {
+                eprintln!("⚠️ Failed to read puzzles: {}", error);
+                return ExitCode::FAILURE;
+            }
+            let puzzles = match read_puzzles(&input, options.format) {
+                Ok(puzzles) => puzzles,
+                Err(error) => {
+                    eprintln!("⚠️ {}", error);
+                    return ExitCode::FAILURE;
+                }
+            };
+            if command == "rate" { rate(&puzzles) } else { solve(&puzzles, options.format) }
+        }
+        _ => {
+            eprintln!("⚠️ unknown command '{}'\n\n{}", command, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    if passed { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let options = parse_options(&args("--preset night-prowler --count 100 --seed 42 --format sdk")).unwrap();
+        assert_eq!(options.preset, PresetKind::NightProwler);
+        assert_eq!(options.count, 100);
+        assert_eq!(options.seed, Some(42));
+        assert_eq!(options.format, Format::Sdk);
+        assert_eq!(parse_options(&[]).unwrap(), Options::default());
+
+        assert!(parse_options(&args("--preset grumpy-cat")).is_err());
+        assert!(parse_options(&args("--count")).is_err());
+        assert!(parse_options(&args("--count many")).is_err());
+        assert!(parse_options(&args("--verbose")).is_err());
+        assert_eq!(preset_name(PresetKind::StreetwiseStray), "streetwise-stray");
+    }
+
+    #[test]
+    fn test_formats_round_trip() {
+        let settings = PuzzleSettings { seed: Some(7), ..PuzzleSettings::default() };
+        let mut board = BoardState::new();
+        board.generate_puzzle_with_settings(&settings).expect("Puzzle should generate");
+
+        for format in [Format::Line, Format::Sdk, Format::Code] {
+            // Two puzzles back to back, with a comment for good measure
+            let written = write_board(&board, format, Some(&settings));
+            let input = format!("# two puzzles\n{}\n{}\n", written, written);
+            let puzzles = read_puzzles(&input, format).unwrap();
+            assert_eq!(puzzles.len(), 2, "{:?}", format);
+            assert_eq!(puzzles[1].cells, board.cells, "{:?}", format);
+        }
+        assert_eq!(write_board(&board, Format::Sdk, None).lines().count(), 9);
+        assert!(read_puzzles("123\n", Format::Line).unwrap_err().starts_with("puzzle 1"));
+    }
+
+    #[test]
+    fn test_solve_and_rate() {
+        let puzzle = "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+        let puzzles = read_puzzles(puzzle, Format::Line).unwrap();
+        assert!(solve(&puzzles, Format::Line));
+        assert!(rate(&puzzles));
+
+        // An empty board has many solutions
+        let empty = read_puzzles(&".".repeat(81), Format::Line).unwrap();
+        assert!(!solve(&empty, Format::Line));
+        assert!(!rate(&empty));
+    }
+}
@@ -1,67 +1,757 @@
-/// Nine Lives Cat Sudoku Controller Layer
-///
-/// This crate contains the controller logic for the Nine Lives Cat Sudoku game.
-///It orchestrates the interaction between the core game logic and the UI layer.
-/// Responsibilities:
-/// - Event handling (user input)
-/// - Game state transitions
-/// - Application orchestration
-/// - Connecting model and view layers
+//! Nine Lives Cat Sudoku Controller Layer
+//!
+//! This crate contains the controller logic for the Nine Lives Cat Sudoku game.
+//! It orchestrates the interaction between the core game logic and the UI layer.
+//! Responsibilities:
+//! - Event handling (user input)
+//! - Game state transitions
+//! - Application orchestration
+//! - Connecting model and view layers
 
 use bevy::prelude::*;
-use nine_lives_core::{BoardState, GameSession, GameState, GameHistory, HintSystem, Solution, DebugMode, get_next_hint, PuzzleSettings};
-use nine_lives_ui::{AppState, CatEmojis, Cell, ClearButton, NewGameButton, UndoButton, RedoButton, HintButton};
+use nine_lives_core::{BoardState, GameSession, GameState, GameHistory, HintSystem, Solution, DebugMode, PuzzleSettings, PersistentData, HintExplanation, HintReason, HintTier, TouchGesture, HINT_BUDGET_MS, LastHint, SessionSummary, SummaryExport, Replay, PresetKind, PuzzlePool, GameEvent, GameEventLog, CellCommand, CancellationToken, GenerationReport, NineLivesError, Lives, LeaderboardEntry, CompletedGameRecord, HouseKind, SaveGame, CheckCost, PackLibrary, TimeAttack, Tutorial, GameMode, ScoreTracker, StreakTracker, ProfileRoster, ShortcutAction};
+use nine_lives_ui::{AppState, ErrorToast, ActiveDigit, LeaderboardPlacement, CampaignResult, EraserButton, FocusManager, Focusable, ConfirmAction, ConfirmedAction, PendingConfirmation, SelectedCell, NumberPadButton, Cell, ClearButton, RestartButton, NewGameButton, UndoButton, RedoButton, UndoToCorrectButton, HistoryEntryButton, HintButton, CheckButton, ProgressChecked, ShowSolutionButton, SolutionReveal, ReplayViewer, SelectedGridSize, SelectedVariant, HouseCompleted, StreakReached, TimeAttackAgainButton, TouchMode, MENU_TITLE, PuzzleGeneration, PuzzleProgress, PuzzleSwap, shortcuts_pressed};
+use bevy::tasks::{AsyncComputeTaskPool, Task, futures::check_ready};
+use bevy::ecs::system::SystemParam;
+use bevy::log::LogPlugin;
+use bevy::window::{WindowFocused, WindowOccluded};
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+/// Which diagnostics show by default: the game's own at info level, and
+/// Bevy's defaults for the renderer.
+const LOG_FILTER: &str = "wgpu=error,naga=warn,nine_lives_core=info,nine_lives_ui=info,nine_lives_controller=info";
+
+/// Two clicks on the same cell closer together than this make a double-click, in seconds.
+const DOUBLE_CLICK_SECONDS: f32 = 0.35;
+
+/// How long a cat clicked into a cell has to stay there before it counts as
+/// placed, in seconds (see `UnsettledPlacement`).
+const SETTLE_SECONDS: f32 = 1.5;
+
+/// How long the undo or redo button is held before it starts repeating, in seconds.
+const HOLD_DELAY_SECONDS: f32 = 0.4;
+
+/// How often a held undo or redo button repeats, in seconds.
+const HOLD_REPEAT_SECONDS: f32 = 0.08;
 
 // --- Controller Systems ---
 
 /// A system that handles clicks on the grid cells. This is part of the "Controller".
+/// What a click does is up to the player's input model (see `InputModel`):
+/// cycling through the cats, selecting the cell for typing, or placing the
+/// cat picked on the number pad. With the eraser picked, clicks empty cells.
+/// Double-clicking an empty cell with only one
+/// legal cat fills that cat in (unless turned off in the settings).
 pub fn cell_click_system(
     mut interaction_query: Query<(&Interaction, &Cell), Changed<Interaction>>,
-    cat_emojis: Res<CatEmojis>,
-    mut board: ResMut<BoardState>, // We get mutable access to the game state.
-    mut session: ResMut<GameSession>,
-    mut history: ResMut<GameHistory>,
+    mut cell_input: CellInput,
+    mut replay: ResMut<Replay>,
+    mut last_click: Local<Option<(usize, usize, SystemTime)>>,
 ) {
     for (interaction, cell) in &mut interaction_query {
         if *interaction == Interaction::Pressed {
-            // Try to cycle the cell and track the move in history
-            if let Some(game_move) = board.cycle_cell(cell.row, cell.col, cat_emojis.emojis.len()) {
-                // Add move to history for undo/redo
-                history.add_move(game_move);
-                // Track move count in the session
-                session.increment_move();
+            let now = SystemTime::now();
+            let first_click = last_click
+                .take()
+                .filter(|&(row, col, at)| {
+                    (row, col) == (cell.row, cell.col)
+                        && now.duration_since(at).is_ok_and(|since| since.as_secs_f32() < DOUBLE_CLICK_SECONDS)
+                })
+                .map(|(_, _, at)| at);
+
+            if let Some(first_click) = first_click
+                && !cell_input.active_digit.erasing
+                && !cell_input.active_digit.noting
+                && cell_input.persistent_data.user_settings.double_click_fill
+                && cell_input.tutorial.as_ref().is_none_or(|tutorial| tutorial.allows_cell(cell.row, cell.col))
+                && fill_single_legal_value(
+                    &mut cell_input.board,
+                    &mut cell_input.history,
+                    &mut replay,
+                    cell.row,
+                    cell.col,
+                    first_click,
+                )
+            {
+                info!("✨ Filled the only cat that fits at ({}, {})", cell.row + 1, cell.col + 1);
+                if let Some(game_move) = cell_input.history.peek_undo() {
+                    let (row, col, new_value) = (game_move.row, game_move.col, game_move.new_value);
+                    cell_input.events.write(GameEvent::Move {
+                        row,
+                        col,
+                        old_value: game_move.old_value,
+                        new_value,
+                    });
+                    cell_input.placed(row, col, new_value, false);
+                }
+                continue;
+            }
+            *last_click = Some((cell.row, cell.col, now));
+
+            let command = if cell_input.active_digit.erasing {
+                CellCommand::Erase { row: cell.row, col: cell.col }
+            } else {
+                cell_input.persistent_data.user_settings.input_model.cell_clicked(
+                    cell.row,
+                    cell.col,
+                    cell_input.active_digit.value,
+                    cell_input.board.cells[cell.row][cell.col],
+                )
+            };
+            cell_input.apply_clicked(command);
+        }
+    }
+}
+
+/// A system that handles typed cats: the digit keys (A-G for the cats past
+/// nine on Mega Clowder boards), the eraser button, and, with the
+/// select-then-type input model, the number pad buttons. Backspace, Delete,
+/// and 0 empty the selected cell, or else the one under the mouse, or else
+/// the one with keyboard focus.
+pub fn typed_cat_system(
+    input: Res<ButtonInput<KeyCode>>,
+    pad_query: Query<(&Interaction, &NumberPadButton), Changed<Interaction>>,
+    eraser_query: Query<&Interaction, (Changed<Interaction>, With<EraserButton>)>,
+    cell_query: Query<(Entity, &Cell, &Interaction)>,
+    focus: Res<FocusManager>,
+    mut cell_input: CellInput,
+) {
+    let input_model = cell_input.persistent_data.user_settings.input_model;
+    let selected = cell_input.selected.cell;
+    let size = cell_input.board.size();
+    let mut commands: Vec<CellCommand> = Vec::new();
+    if !input_model.picks_from_number_pad() {
+        commands.extend(
+            pad_query
+                .iter()
+                .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+                .map(|(_, button)| input_model.cat_typed(button.value, selected)),
+        );
+    }
+    if eraser_query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        commands.push(input_model.eraser_pressed(selected));
+    }
+
+    let modifier_pressed = [KeyCode::SuperLeft, KeyCode::SuperRight, KeyCode::ControlLeft, KeyCode::ControlRight]
+        .iter()
+        .any(|&key| input.pressed(key));
+    if !modifier_pressed {
+        for key in input.get_just_pressed() {
+            match key {
+                KeyCode::Backspace | KeyCode::Delete | KeyCode::Digit0 | KeyCode::Numpad0 => {
+                    let hovered = cell_query
+                        .iter()
+                        .find(|(_, _, interaction)| **interaction == Interaction::Hovered)
+                        .map(|(_, cell, _)| (cell.row, cell.col));
+                    let focused = focus
+                        .focused
+                        .and_then(|focused| cell_query.get(focused).ok())
+                        .map(|(_, cell, _)| (cell.row, cell.col));
+                    if let Some((row, col)) = selected.or(hovered).or(focused) {
+                        commands.push(CellCommand::Erase { row, col });
+                    }
+                }
+                _ => {
+                    if let Some(value) = typed_cat(*key).filter(|&value| value < size) {
+                        commands.push(input_model.cat_typed(value, selected));
+                    }
+                }
+            }
+        }
+    }
+
+    for command in commands {
+        cell_input.apply(command);
+    }
+}
+
+/// The cat a key types, if any: 1-9 (on either the main keys or the numpad)
+/// for the first nine cats, then A-G for the rest.
+fn typed_cat(key: KeyCode) -> Option<usize> {
+    const DIGITS: [KeyCode; 9] = [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+        KeyCode::Digit6,
+        KeyCode::Digit7,
+        KeyCode::Digit8,
+        KeyCode::Digit9,
+    ];
+    const NUMPAD: [KeyCode; 9] = [
+        KeyCode::Numpad1,
+        KeyCode::Numpad2,
+        KeyCode::Numpad3,
+        KeyCode::Numpad4,
+        KeyCode::Numpad5,
+        KeyCode::Numpad6,
+        KeyCode::Numpad7,
+        KeyCode::Numpad8,
+        KeyCode::Numpad9,
+    ];
+    const LETTERS: [KeyCode; 7] =
+        [KeyCode::KeyA, KeyCode::KeyB, KeyCode::KeyC, KeyCode::KeyD, KeyCode::KeyE, KeyCode::KeyF, KeyCode::KeyG];
+    DIGITS
+        .iter()
+        .position(|&digit| digit == key)
+        .or_else(|| NUMPAD.iter().position(|&digit| digit == key))
+        .or_else(|| LETTERS.iter().position(|&letter| letter == key).map(|index| index + 9))
+}
+
+/// A finger on the screen, as `touch_gesture_system` follows it.
+#[derive(Debug, Clone, Copy)]
+pub struct TrackedTouch {
+    /// When it touched down, in seconds since the game started
+    started_at: f32,
+    /// Whether it touched down on the number pad
+    on_number_pad: bool,
+    /// Whether it has already erased a cell by being held there
+    long_pressed: bool,
+}
+
+/// A system that notices the game being played by touch, so the interface
+/// can make its buttons finger-sized (see `TouchMode`).
+pub fn detect_touch_system(touches: Res<Touches>, mut touch_mode: ResMut<TouchMode>) {
+    if !touch_mode.active && touches.any_just_pressed() {
+        touch_mode.active = true;
+        info!("👆 Touch screen detected, enlarging buttons");
+    }
+}
+
+/// A system that turns touch gestures into moves (see `TouchGesture`). Taps
+/// on cells go through the input model like clicks and also select the
+/// cell; holding a finger still on a cell erases it; and swiping across the
+/// number pad steps to the next or previous cat.
+pub fn touch_gesture_system(
+    touches: Res<Touches>,
+    time: Res<Time>,
+    mut tracked: Local<HashMap<u64, TrackedTouch>>,
+    pad_query: Query<&Interaction, With<NumberPadButton>>,
+    cell_query: Query<(&Cell, &Interaction)>,
+    mut cell_input: CellInput,
+) {
+    let now = time.elapsed_secs();
+    let pressed_cell = cell_query
+        .iter()
+        .find(|(_, interaction)| **interaction == Interaction::Pressed)
+        .map(|(cell, _)| (cell.row, cell.col));
+
+    for touch in touches.iter_just_pressed() {
+        let on_number_pad = pad_query.iter().any(|interaction| *interaction == Interaction::Pressed);
+        tracked.insert(touch.id(), TrackedTouch { started_at: now, on_number_pad, long_pressed: false });
+        if let Some(cell) = pressed_cell {
+            cell_input.selected.cell = Some(cell);
+        }
+    }
+
+    let mut commands: Vec<CellCommand> = Vec::new();
+    for touch in touches.iter() {
+        let Some(tracked_touch) = tracked.get_mut(&touch.id()) else {
+            continue;
+        };
+        let distance = touch.distance();
+        let gesture = TouchGesture::classify(distance.x, distance.y, now - tracked_touch.started_at);
+        if gesture == TouchGesture::LongPress
+            && !tracked_touch.long_pressed
+            && !tracked_touch.on_number_pad
+            && let Some((row, col)) = pressed_cell
+        {
+            tracked_touch.long_pressed = true;
+            commands.push(CellCommand::Erase { row, col });
+        }
+    }
+
+    let input_model = cell_input.persistent_data.user_settings.input_model;
+    for touch in touches.iter_just_released() {
+        let Some(tracked_touch) = tracked.remove(&touch.id()) else {
+            continue;
+        };
+        let distance = touch.distance();
+        if let TouchGesture::Swipe { forward } =
+            TouchGesture::classify(distance.x, distance.y, now - tracked_touch.started_at)
+            && tracked_touch.on_number_pad
+        {
+            let from = if input_model.picks_from_number_pad() {
+                cell_input.active_digit.value
+            } else {
+                cell_input.selected.cell.and_then(|(row, col)| cell_input.board.cells[row][col])
+            };
+            commands.push(input_model.cat_swiped(from, forward, cell_input.board.size(), cell_input.selected.cell));
+        }
+    }
+    for touch in touches.iter_just_canceled() {
+        tracked.remove(&touch.id());
+    }
+
+    for command in commands {
+        cell_input.apply(command);
+    }
+}
+
+/// What the systems that turn clicks, typed cats, and touches into moves
+/// work with.
+#[derive(SystemParam)]
+pub struct CellInput<'w> {
+    active_digit: ResMut<'w, ActiveDigit>,
+    selected: ResMut<'w, SelectedCell>,
+    persistent_data: Res<'w, PersistentData>,
+    board: ResMut<'w, BoardState>,
+    session: ResMut<'w, GameSession>,
+    history: ResMut<'w, GameHistory>,
+    tutorial: Option<Res<'w, Tutorial>>,
+    unsettled: ResMut<'w, UnsettledPlacement>,
+    time: Res<'w, Time>,
+    events: EventWriter<'w, GameEvent>,
+}
+
+impl CellInput<'_> {
+    /// Carry out a typed command, or one from a touch gesture. A cat it
+    /// places counts straight away.
+    fn apply(&mut self, command: CellCommand) {
+        self.apply_command(command, false);
+    }
+
+    /// Carry out a command from a click. A cat it places only counts once
+    /// it settles (see `UnsettledPlacement`).
+    fn apply_clicked(&mut self, command: CellCommand) {
+        self.apply_command(command, true);
+    }
+
+    /// Carry out a command from the input model (noting cats instead of
+    /// placing them in notes mode), unless the tutorial is waiting for
+    /// something else. Moves go into the history and the move count, and
+    /// are sent out as game events; notes don't.
+    fn apply_command(&mut self, command: CellCommand, clicked: bool) {
+        if self.tutorial.as_ref().is_some_and(|tutorial| !tutorial.allows(&command)) {
+            return;
+        }
+        let (board, active_digit) = (&mut self.board, &mut self.active_digit);
+        let command = if active_digit.noting { command.into_note() } else { command };
+        let game_move = match command {
+            // One cat per row: smaller boards use the first few, Mega Clowder boards use 16
+            CellCommand::Cycle { row, col } => {
+                let num_cats = board.size();
+                board.cycle_cell(row, col, num_cats)
+            }
+            CellCommand::Set { row, col, value } => board.set_cell(row, col, value),
+            CellCommand::Erase { row, col } => board.clear_cell(row, col),
+            CellCommand::Select { row, col } => {
+                self.selected.cell = Some((row, col));
+                None
+            }
+            // Picking the active cat again drops it, like pressing it on the number pad
+            CellCommand::Pick(value) => {
+                active_digit.value = if active_digit.value == value { None } else { value };
+                active_digit.erasing = false;
+                None
+            }
+            CellCommand::ToggleEraser => {
+                active_digit.erasing = !active_digit.erasing;
+                active_digit.value = None;
+                None
+            }
+            CellCommand::ToggleNote { row, col, value } => {
+                board.toggle_note(row, col, value);
+                None
+            }
+            CellCommand::Ignore => None,
+        };
+
+        // Track the move in history
+        if let Some(game_move) = game_move {
+            let (row, col, new_value) = (game_move.row, game_move.col, game_move.new_value);
+            self.events.write(GameEvent::Move {
+                row,
+                col,
+                old_value: game_move.old_value,
+                new_value,
+            });
+            // Add move to history for undo/redo
+            self.history.add_move(game_move);
+            // Track move count in the session
+            self.session.increment_move();
+            self.placed(row, col, new_value, clicked);
+        }
+    }
+
+    /// Note the cat a move put in a cell. A cat clicked in is left to settle;
+    /// any other counts as placed straight away. Either way, a cat still
+    /// settling in another cell settles now, as the player has moved on,
+    /// and one in this cell has been replaced.
+    fn placed(&mut self, row: usize, col: usize, value: Option<usize>, clicked: bool) {
+        if self.unsettled.is_at(row, col) {
+            self.unsettled.clicked = None;
+        } else if let Some(event) = self.unsettled.settle(&self.board) {
+            self.events.write(event);
+        }
+        match value {
+            Some(value) if clicked => {
+                self.unsettled.clicked = Some(ClickedCat { row, col, value, at: self.time.elapsed_secs() });
             }
+            Some(value) => {
+                self.events.write(GameEvent::Placed { row, col, value });
+            }
+            None => {}
+        }
+    }
+}
+
+/// A cat clicked into a cell, and when.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ClickedCat {
+    row: usize,
+    col: usize,
+    value: usize,
+    /// When it was clicked in, in seconds since the game started
+    at: f32,
+}
+
+/// The cat last clicked into a cell, until it settles there.
+///
+/// Clicking cycles through the cats on the way to the one wanted (and the
+/// first click of a double-click is replaced by the fill), so a clicked cat
+/// only counts as placed, for lives, score, and streaks, once it settles:
+/// when the player moves on to another cell, after `SETTLE_SECONDS` without
+/// another click there, or when it fills the board. Typed cats count straight
+/// away. See `settle_placements_system`.
+#[derive(Resource, Debug, Default)]
+pub struct UnsettledPlacement {
+    clicked: Option<ClickedCat>,
+}
+
+impl UnsettledPlacement {
+    /// Whether the unsettled cat is at (`row`, `col`).
+    fn is_at(&self, row: usize, col: usize) -> bool {
+        self.clicked.is_some_and(|clicked| (clicked.row, clicked.col) == (row, col))
+    }
+
+    /// Whether the unsettled cat is due to settle, `now` seconds into the game.
+    fn is_due(&self, now: f32, board: &BoardState) -> bool {
+        self.clicked.is_some_and(|clicked| now - clicked.at >= SETTLE_SECONDS || board.is_complete())
+    }
+
+    /// Settle the unsettled cat, returning the `GameEvent::Placed` for it.
+    /// Nothing settles if the cat has since left its cell (undone, cleared,
+    /// or the puzzle swapped for another).
+    fn settle(&mut self, board: &BoardState) -> Option<GameEvent> {
+        let ClickedCat { row, col, value, .. } = self.clicked.take()?;
+        let still_there = board.cells.get(row).and_then(|cells| cells.get(col)) == Some(&Some(value))
+            && !board.is_given_cell(row, col);
+        still_there.then_some(GameEvent::Placed { row, col, value })
+    }
+}
+
+/// Settles the cat last clicked into a cell once it's been left there for
+/// `SETTLE_SECONDS`, or straight away if it filled the board, so the win
+/// counts it (see `UnsettledPlacement`).
+pub fn settle_placements_system(
+    time: Res<Time>,
+    board: Res<BoardState>,
+    mut unsettled: ResMut<UnsettledPlacement>,
+    mut events: EventWriter<GameEvent>,
+) {
+    if unsettled.is_due(time.elapsed_secs(), &board)
+        && let Some(event) = unsettled.settle(&board)
+    {
+        events.write(event);
+    }
+}
+
+/// Finish a double-click: if the cell was empty before the first click and
+/// has exactly one legal value, take back whatever the first click did and
+/// place that value instead, so the double-click goes into the history, the
+/// replay, and the move counter as a single move. (The first click's cat
+/// never settled, so it cost nothing.) Returns whether the cell was filled.
+fn fill_single_legal_value(
+    board: &mut BoardState,
+    history: &mut GameHistory,
+    replay: &mut Replay,
+    row: usize,
+    col: usize,
+    first_click: SystemTime,
+) -> bool {
+    let first_move = history
+        .peek_undo()
+        .filter(|game_move| (game_move.row, game_move.col) == (row, col) && game_move.timestamp >= first_click)
+        .cloned();
+    let value_before = first_move.as_ref().map_or(board.cells[row][col], |game_move| game_move.old_value);
+    let legal = board.legal_values(row, col);
+    if value_before.is_some() || board.is_given_cell(row, col) || legal.len() != 1 {
+        return false;
+    }
+
+    if let Some(first_move) = &first_move {
+        board.undo_move(first_move);
+        history.mark_undone();
+        replay.take_back(first_move);
+    }
+    match board.set_cell(row, col, Some(legal[0])) {
+        Some(game_move) => {
+            history.add_move(game_move);
+            true
         }
+        None => false,
+    }
+}
+
+/// Whether the board holds any cats the player put there, i.e. whether
+/// clearing or restarting would throw anything away.
+fn has_player_entries(board: &BoardState) -> bool {
+    (0..board.size()).any(|row| (0..board.size()).any(|col| board.cells[row][col].is_some() && !board.is_given_cell(row, col)))
+}
+
+/// Ask before a destructive action, or go straight ahead if the player has
+/// nothing on the board to lose.
+fn request_confirmation(
+    action: ConfirmAction,
+    board: &BoardState,
+    pending: &mut PendingConfirmation,
+    confirmed: &mut EventWriter<ConfirmedAction>,
+) {
+    if has_player_entries(board) {
+        pending.action = Some(action);
+    } else {
+        confirmed.write(ConfirmedAction(action));
     }
 }
 
 /// A system that handles clicks on the "Clear Board" button. This is also a "Controller".
+/// The board is only cleared once the player confirms (see `confirmed_action_system`).
 pub fn clear_button_system(
     mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<ClearButton>)>,
-    mut board: ResMut<BoardState>,
+    board: Res<BoardState>,
+    mut pending: ResMut<PendingConfirmation>,
+    mut confirmed: EventWriter<ConfirmedAction>,
 ) {
     for interaction in &mut interaction_query {
         if *interaction == Interaction::Pressed {
-            // The system calls the `clear` method from our core crate.
-            board.clear();
+            request_confirmation(ConfirmAction::ClearBoard, &board, &mut pending, &mut confirmed);
+        }
+    }
+}
+
+/// A system that handles clicks on the "Restart Puzzle" button, once the
+/// player confirms (see `confirmed_action_system`).
+pub fn restart_button_system(
+    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<RestartButton>)>,
+    board: Res<BoardState>,
+    mut pending: ResMut<PendingConfirmation>,
+    mut confirmed: EventWriter<ConfirmedAction>,
+) {
+    for interaction in &mut interaction_query {
+        if *interaction == Interaction::Pressed {
+            request_confirmation(ConfirmAction::RestartPuzzle, &board, &mut pending, &mut confirmed);
+        }
+    }
+}
+
+/// A system that handles clicks on the "Show Solution" button. Giving up
+/// always asks first, even on an empty board, as it counts against the
+/// player's statistics. A solved puzzle has nothing to give up on.
+pub fn show_solution_button_system(
+    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<ShowSolutionButton>)>,
+    game_state: Res<GameState>,
+    mut pending: ResMut<PendingConfirmation>,
+) {
+    for interaction in &mut interaction_query {
+        if *interaction == Interaction::Pressed && *game_state == GameState::Playing {
+            pending.action = Some(ConfirmAction::ShowSolution);
+        }
+    }
+}
+
+/// A system that carries out confirmed destructive actions.
+///
+/// Clearing empties the cells the player filled in (candidates go with them,
+/// since they're worked out from the board) and keeps the givens, so the
+/// puzzle can still be finished. The clock and move count keep running.
+/// Restarting does the same, then starts the puzzle over with a fresh timer,
+/// move count, replay, score, streak, and nine lives. Hints already spent stay spent.
+/// Either way the undo history goes, as its moves no longer line up with the board.
+///
+/// Showing the solution stops the clock, counts the puzzle as abandoned, and
+/// starts a `SolutionReveal` (see `reveal_solution_system`). Restarting a
+/// given-up puzzle puts the solution away again.
+#[allow(clippy::too_many_arguments)]
+pub fn confirmed_action_system(
+    mut commands: Commands,
+    mut confirmed: EventReader<ConfirmedAction>,
+    mut board: ResMut<BoardState>,
+    solution: Res<Solution>,
+    hint_system: Res<HintSystem>,
+    mut progress: PuzzleProgress,
+    mut persistent_data: ResMut<PersistentData>,
+    mut events: EventWriter<GameEvent>,
+) {
+    for ConfirmedAction(action) in confirmed.read() {
+        match action {
+            // Starting a new puzzle is handled by `new_game_button_system` and
+            // `same_settings_system`, and the saved game is discarded from
+            // the customization screen
+            ConfirmAction::NewGame
+            | ConfirmAction::SameSettings
+            | ConfirmAction::LeavePuzzle
+            | ConfirmAction::DeleteSave => continue,
+            _ => progress.history.clear(),
+        }
+        match action {
+            ConfirmAction::NewGame
+            | ConfirmAction::SameSettings
+            | ConfirmAction::LeavePuzzle
+            | ConfirmAction::DeleteSave => {}
+            ConfirmAction::ClearBoard => {
+                board.reset_to_givens();
+                events.write(GameEvent::BoardCleared);
+            }
+            ConfirmAction::RestartPuzzle => {
+                board.reset_to_givens();
+                progress.restart(&board);
+                commands.remove_resource::<SolutionReveal>();
+                info!("🔁 Puzzle restarted");
+                events.write(GameEvent::Restarted);
+            }
+            ConfirmAction::ShowSolution => {
+                let session = &mut progress.session;
+                session.pause();
+                let hints_used = hint_system.max_hints.saturating_sub(hint_system.hints_remaining);
+                commands.insert_resource(SolutionReveal::new(
+                    &board,
+                    &solution,
+                    session.current_elapsed().as_secs(),
+                    hints_used,
+                ));
+                persistent_data.record_game_abandoned();
+                // A given-up puzzle has nothing left to continue
+                persistent_data.current_save = None;
+                events.write(save_event(&persistent_data));
+                info!("🐾 Gave up - showing the solution");
+            }
         }
     }
 }
 
+/// A system that fills in a given-up puzzle from its solution, one cell
+/// every tick of the reveal's timer.
+pub fn reveal_solution_system(
+    time: Res<Time>,
+    reveal: Option<ResMut<SolutionReveal>>,
+    solution: Res<Solution>,
+    mut board: ResMut<BoardState>,
+) {
+    let Some(mut reveal) = reveal else {
+        return;
+    };
+    if reveal.is_done() {
+        return;
+    }
+    reveal.timer.tick(time.delta());
+    for _ in 0..reveal.timer.times_finished_this_tick() {
+        let Some((row, col)) = reveal.remaining.pop_front() else {
+            break;
+        };
+        board.set_cell(row, col, Some(solution.cells[row][col]));
+    }
+}
+
+/// Whether leaving the puzzle now counts as abandoning it: the player has
+/// started it, and it isn't a time attack puzzle (only ever part of its run,
+/// which simply ends) or the tutorial's (never saved).
+fn abandons_puzzle(game_state: &GameState, session: &GameSession, special: bool) -> bool {
+    *game_state == GameState::Playing && session.move_count > 0 && !special
+}
+
 /// A system that handles clicks on the "New Game" button.
-/// This transitions back to the customization screen where the user can select new settings.
+/// This transitions back to the customization screen where the user can select new settings,
+/// or to the campaign, packs, or Training Grounds screen to pick the next puzzle after one from there.
+///
+/// For an ordinary puzzle, the game screen's New Game button first asks
+/// whether to play again with the same settings (see `same_settings_system`)
+/// or choose new ones. Other puzzles only ask when leaving counts as
+/// abandoning them. Time attack and the tutorial leave straight away, as do
+/// the New Game buttons on the game-over overlays.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn new_game_button_system(
-    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<NewGameButton>)>,
+    mut interaction_query: Query<(&Interaction, &Focusable), (Changed<Interaction>, With<NewGameButton>)>,
+    mut confirmed: EventReader<ConfirmedAction>,
+    mut pending: ResMut<PendingConfirmation>,
     mut app_state: ResMut<NextState<AppState>>,
+    game_state: Res<GameState>,
+    session: Res<GameSession>,
+    settings: Res<PuzzleSettings>,
+    time_attack: Option<Res<TimeAttack>>,
+    tutorial: Option<Res<Tutorial>>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut events: EventWriter<GameEvent>,
 ) {
-    for interaction in &mut interaction_query {
+    let special = time_attack.is_some() || tutorial.is_some();
+    let abandons = abandons_puzzle(&game_state, &session, special);
+    let mut leave = false;
+    for ConfirmedAction(action) in confirmed.read() {
+        leave |= matches!(action, ConfirmAction::NewGame | ConfirmAction::LeavePuzzle);
+    }
+    for (interaction, focusable) in &mut interaction_query {
         if *interaction == Interaction::Pressed {
-            println!("🔄 New Game button pressed - returning to customization screen");
-            
-            // Transition back to customization screen
-            app_state.set(AppState::Customization);
+            debug!("🔄 New Game button pressed");
+            if *focusable != Focusable::NewGame || special {
+                leave = true;
+            } else if settings.campaign_level.is_none() && settings.pack_puzzle.is_none() && settings.practice.is_none() {
+                pending.action = Some(ConfirmAction::NewGame);
+            } else if abandons {
+                pending.action = Some(ConfirmAction::LeavePuzzle);
+            } else {
+                leave = true;
+            }
         }
     }
+    if !leave {
+        return;
+    }
+
+    debug!("🔄 Returning to customization screen");
+    if abandons {
+        persistent_data.record_game_abandoned();
+        persistent_data.current_save = None;
+        events.write(save_event(&persistent_data));
+    }
+
+    // Transition back to customization screen (or the one the puzzle came from)
+    app_state.set(if settings.campaign_level.is_some() {
+        AppState::Campaign
+    } else if settings.pack_puzzle.is_some() {
+        AppState::Packs
+    } else if settings.practice.is_some() {
+        AppState::TrainingGrounds
+    } else {
+        AppState::Customization
+    });
+}
+
+/// A system that starts a new puzzle with the current settings (a fresh seed
+/// aside) when the player picks "Same Settings" in the New Game dialog,
+/// without going back through the customization screen.
+pub fn same_settings_system(
+    mut confirmed: EventReader<ConfirmedAction>,
+    settings: Res<PuzzleSettings>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut puzzles: PuzzleSwap,
+) {
+    let mut restart = false;
+    for ConfirmedAction(action) in confirmed.read() {
+        restart |= *action == ConfirmAction::SameSettings;
+    }
+    if !restart {
+        return;
+    }
+    // The dialog is only offered outside time attack and the tutorial
+    if abandons_puzzle(&puzzles.game_state, &puzzles.progress.session, false) {
+        persistent_data.record_game_abandoned();
+        persistent_data.current_save = None;
+        puzzles.events.write(save_event(&persistent_data));
+    }
+    let settings = PuzzleSettings { seed: None, ..settings.clone() };
+    puzzles.load_fresh(settings);
 }
 
 /// System that handles clicks on the "Undo" button.
@@ -69,16 +759,14 @@ pub fn undo_button_system(
     mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<UndoButton>)>,
     mut board: ResMut<BoardState>,
     mut history: ResMut<GameHistory>,
+    mut events: EventWriter<GameEvent>,
 ) {
     for interaction in &mut interaction_query {
-        if *interaction == Interaction::Pressed {
-            if let Some(game_move) = history.peek_undo().cloned() {
-                // Apply the reverse of the move
-                board.undo_move(&game_move);
-                // Mark as undone in history
-                history.mark_undone();
-                println!("Undid move at ({}, {})", game_move.row, game_move.col);
-            }
+        if *interaction == Interaction::Pressed
+            && let Some(game_move) = history.undo(&mut board)
+        {
+            events.write(GameEvent::Undo { row: game_move.row, col: game_move.col });
+            debug!("Undid move at ({}, {})", game_move.row, game_move.col);
         }
     }
 }
@@ -88,154 +776,899 @@ pub fn redo_button_system(
     mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<RedoButton>)>,
     mut board: ResMut<BoardState>,
     mut history: ResMut<GameHistory>,
+    mut events: EventWriter<GameEvent>,
 ) {
     for interaction in &mut interaction_query {
+        if *interaction == Interaction::Pressed
+            && let Some(game_move) = history.redo(&mut board)
+        {
+            events.write(GameEvent::Redo { row: game_move.row, col: game_move.col });
+            debug!("Redid move at ({}, {})", game_move.row, game_move.col);
+        }
+    }
+}
+
+/// System that keeps undoing (or redoing) while the undo (or redo) button is
+/// held down: after `HOLD_DELAY_SECONDS`, one more move every
+/// `HOLD_REPEAT_SECONDS`. The press itself is handled by `undo_button_system`
+/// and `redo_button_system`.
+pub fn hold_to_repeat_system(
+    time: Res<Time>,
+    undo_query: Query<&Interaction, With<UndoButton>>,
+    redo_query: Query<&Interaction, With<RedoButton>>,
+    mut board: ResMut<BoardState>,
+    mut history: ResMut<GameHistory>,
+    mut events: EventWriter<GameEvent>,
+    mut held_for: Local<Option<f32>>,
+) {
+    let undo_held = undo_query.iter().any(|interaction| *interaction == Interaction::Pressed);
+    let redo_held = redo_query.iter().any(|interaction| *interaction == Interaction::Pressed);
+    if !undo_held && !redo_held {
+        *held_for = None;
+        return;
+    }
+    let Some(before) = *held_for else {
+        *held_for = Some(0.0);
+        return;
+    };
+    let now = before + time.delta_secs();
+    *held_for = Some(now);
+
+    // Repeats that came due since the last frame
+    let repeats_by = |held: f32| {
+        if held < HOLD_DELAY_SECONDS {
+            0
+        } else {
+            ((held - HOLD_DELAY_SECONDS) / HOLD_REPEAT_SECONDS) as usize + 1
+        }
+    };
+    for _ in 0..repeats_by(now) - repeats_by(before) {
+        if undo_held {
+            if let Some(game_move) = history.undo(&mut board) {
+                events.write(GameEvent::Undo { row: game_move.row, col: game_move.col });
+            }
+        } else if let Some(game_move) = history.redo(&mut board) {
+            events.write(GameEvent::Redo { row: game_move.row, col: game_move.col });
+        }
+    }
+}
+
+/// System that handles clicks on the "Last correct" button, undoing every
+/// move since the player's cats last all matched the solution.
+pub fn undo_to_correct_button_system(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<UndoToCorrectButton>)>,
+    mut board: ResMut<BoardState>,
+    mut history: ResMut<GameHistory>,
+    solution: Res<Solution>,
+    mut events: EventWriter<GameEvent>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let steps = history.undo_to_last_correct(&mut board, &solution);
+            if steps.is_empty() {
+                info!("✅ Every cat placed is already right - nothing to undo");
+            } else {
+                info!("⏮ Undid {} moves back to the last correct board", steps.len());
+            }
+            events.write_batch(steps);
+        }
+    }
+}
+
+/// System that jumps back or forward through the history when a move in
+/// the history panel is clicked, undoing or redoing each move on the way.
+pub fn history_jump_system(
+    interaction_query: Query<(&Interaction, &HistoryEntryButton), Changed<Interaction>>,
+    mut board: ResMut<BoardState>,
+    mut history: ResMut<GameHistory>,
+    mut events: EventWriter<GameEvent>,
+) {
+    for (interaction, entry) in &interaction_query {
         if *interaction == Interaction::Pressed {
-            if let Some(game_move) = history.peek_redo().cloned() {
-                // Reapply the move
-                board.apply_move(&game_move);
-                // Mark as redone in history
-                history.mark_redone();
-                println!("Redid move at ({}, {})", game_move.row, game_move.col);
+            let steps = history.jump_to(entry.0, &mut board);
+            if !steps.is_empty() {
+                debug!("Jumped {} moves to move {}", steps.len(), entry.0);
             }
+            events.write_batch(steps);
         }
     }
 }
 
 /// System that handles clicks on the "Hint" button.
+///
+/// Hints come in two tiers: the first press points at the house holding the
+/// next logical deduction (free, and the UI highlights it for a few seconds),
+/// and the second press places that cat, spending the hint. When no simple
+/// deduction is left, or finding one takes longer than `HINT_BUDGET_MS`, a
+/// hint reveals a cat from the solution straight away.
+#[allow(clippy::too_many_arguments)]
 pub fn hint_button_system(
     mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<HintButton>)>,
     mut board: ResMut<BoardState>,
+    mut history: ResMut<GameHistory>,
     solution: Res<Solution>,
+    settings: Res<PuzzleSettings>,
     mut hint_system: ResMut<HintSystem>,
     debug_mode: Res<DebugMode>,
+    mut last_hint: ResMut<LastHint>,
+    mut events: EventWriter<GameEvent>,
 ) {
     for interaction in &mut interaction_query {
         if *interaction == Interaction::Pressed {
-            if hint_system.use_hint(&debug_mode) {
-                if let Some((row, col, correct_value)) = get_next_hint(&board, &solution) {
-                    // Apply the hint directly to the board
-                    board.cells[row][col] = Some(correct_value);
-                    board.cell_types[row][col] = Some(nine_lives_core::CellType::Player);
-                    
-                    if debug_mode.unlimited_hints {
-                        println!(
-                            "DEBUG HINT: Placed cat #{} at ({}, {}). [Unlimited hints enabled]",
-                            correct_value + 1,
-                            row + 1,
-                            col + 1
-                        );
-                    } else {
-                        println!(
-                            "Hint: Placed cat #{} at ({}, {}). {} hints remaining.",
-                            correct_value + 1,
-                            row + 1,
-                            col + 1,
-                            hint_system.hints_remaining
-                        );
-                    }
+            if !hint_system.can_use_hint(&debug_mode) {
+                info!("No hints remaining!");
+                continue;
+            }
+
+            // Look for a deduction to nudge towards, but only for as long as the
+            // budget allows; past that, the hint reveals a cat straight away
+            let pending = last_hint.pending_nudge(&board, &solution);
+            let step = match pending {
+                Some(_) => None,
+                None => HintExplanation::next_step_with_budget(&board, &solution, HINT_BUDGET_MS, settings.hint_strategy),
+            };
+            if let Some(deduction) = step.filter(|step| step.reason != HintReason::Solution) {
+                info!("Hint: {}", deduction.render_nudge(&board));
+                events.write(GameEvent::Hint {
+                    row: deduction.row,
+                    col: deduction.col,
+                    value: deduction.value,
+                    revealed: false,
+                });
+                last_hint.explanation = Some(deduction);
+                last_hint.tier = HintTier::Nudge;
+                continue;
+            }
+
+            let next_hint = pending
+                .or(step)
+                .map(|explanation| (explanation.row, explanation.col, explanation.value));
+            if let Some((row, col, correct_value)) = next_hint {
+                hint_system.use_hint(&debug_mode);
+
+                // Explain the deduction before the board changes
+                last_hint.explanation = Some(HintExplanation::for_hint(&board, row, col, correct_value));
+                last_hint.tier = HintTier::Reveal;
+
+                // Place the cat like any other, so it comes out of its peers'
+                // notes and undoes in one go
+                if let Some(game_move) = board.set_cell(row, col, Some(correct_value)) {
+                    history.add_move(game_move);
+                }
+                events.write(GameEvent::Hint { row, col, value: correct_value, revealed: true });
+
+                if debug_mode.unlimited_hints {
+                    info!(
+                        "DEBUG HINT: Placed cat #{} at ({}, {}). [Unlimited hints enabled]",
+                        correct_value + 1,
+                        row + 1,
+                        col + 1
+                    );
                 } else {
-                    println!("No hints available - puzzle may be complete!");
+                    info!(
+                        "Hint: Placed cat #{} at ({}, {}). {} hints remaining.",
+                        correct_value + 1,
+                        row + 1,
+                        col + 1,
+                        hint_system.hints_remaining
+                    );
                 }
             } else {
-                println!("No hints remaining!");
+                info!("No hints available - puzzle may be complete!");
+            }
+        }
+    }
+}
+
+/// System that handles clicks on the "Check" button: compares the player's
+/// cats against the solution and has the UI mark the wrong ones. Depending on
+/// the settings, a check uses up a hint (none left, no check) or costs a
+/// life (only while nine lives is on; losing the last one ends the game).
+#[allow(clippy::too_many_arguments)]
+pub fn check_button_system(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<CheckButton>)>,
+    board: Res<BoardState>,
+    solution: Res<Solution>,
+    persistent_data: Res<PersistentData>,
+    game_mode: Res<GameMode>,
+    debug_mode: Res<DebugMode>,
+    mut hint_system: ResMut<HintSystem>,
+    mut lives: ResMut<Lives>,
+    mut session: ResMut<GameSession>,
+    mut checked: EventWriter<ProgressChecked>,
+    mut events: EventWriter<GameEvent>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        // Checks are always free in Zen mode
+        let cost = if game_mode.has_penalties() { persistent_data.user_settings.check_cost } else { CheckCost::Free };
+        match cost {
+            CheckCost::Free => {}
+            CheckCost::Hint => {
+                if !hint_system.can_use_hint(&debug_mode) {
+                    info!("No hints left to spend on a check!");
+                    continue;
+                }
+                hint_system.use_hint(&debug_mode);
+            }
+            CheckCost::Life => {
+                if persistent_data.user_settings.nine_lives && lives.lose() {
+                    info!("💔 Checked the board - {} lives left", lives.remaining);
+                    if lives.is_out() {
+                        info!("😿 Out of lives");
+                        session.pause();
+                    }
+                }
             }
         }
+
+        let wrong = board.diff_against(&solution);
+        info!("✔ Checked the board: {} wrong", wrong.len());
+        events.write(GameEvent::Checked { wrong: wrong.len() });
+        checked.write(ProgressChecked { wrong });
     }
 }
 
-/// System to handle debug mode toggle (Cmd+D or Ctrl+D).
+/// System to handle debug mode toggle (Cmd+D or Ctrl+D, unless rebound in the settings).
 pub fn debug_mode_system(
     input: Res<ButtonInput<KeyCode>>,
+    persistent_data: Res<PersistentData>,
     mut debug_mode: ResMut<DebugMode>,
 ) {
-    let cmd_pressed = input.pressed(KeyCode::SuperLeft) || input.pressed(KeyCode::SuperRight);
-    let ctrl_pressed = input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight);
-    
-    // Use Cmd on Mac, Ctrl on other platforms
-    let modifier_pressed = if cfg!(target_os = "macos") {
-        cmd_pressed
-    } else {
-        ctrl_pressed
-    };
-    
-    if modifier_pressed && input.just_pressed(KeyCode::KeyD) {
+    let bindings = &persistent_data.user_settings.key_bindings;
+    if shortcuts_pressed(&input, bindings).contains(&ShortcutAction::ToggleDebug) {
         debug_mode.toggle_unlimited_hints();
         if debug_mode.unlimited_hints {
-            println!("🐛=== DEBUG MODE ACTIVATED ===");
-            println!("   • Unlimited hints enabled");
-            println!("   • Perfect for testing and solving puzzles");
-            println!("   • Press ⌘D/Ctrl+D again to disable");
-            println!("================================");
+            info!("🐛=== DEBUG MODE ACTIVATED ===");
+            info!("   • Unlimited hints enabled");
+            info!("   • Perfect for testing and solving puzzles");
+            info!("   • Press {} again to disable", bindings.label(ShortcutAction::ToggleDebug));
+            info!("================================");
         } else {
-            println!("✅=== DEBUG MODE DISABLED ===");
-            println!("   • Back to normal gameplay");
-            println!("   • Limited hints restored");
-            println!("===============================");
+            info!("✅=== DEBUG MODE DISABLED ===");
+            info!("   • Back to normal gameplay");
+            info!("   • Limited hints restored");
+            info!("===============================");
         }
     }
 }
 
-/// System to handle keyboard shortcuts (Undo: Cmd+Z, Redo: Cmd+Shift+Z).
+/// System to handle keyboard shortcuts (Undo: Cmd+Z, Redo: Cmd+Shift+Z or
+/// Cmd+Y, unless rebound in the settings; see `KeyBindings`).
 pub fn keyboard_shortcuts_system(
     input: Res<ButtonInput<KeyCode>>,
+    persistent_data: Res<PersistentData>,
     mut board: ResMut<BoardState>,
     mut history: ResMut<GameHistory>,
+    mut events: EventWriter<GameEvent>,
 ) {
-    let cmd_pressed = input.pressed(KeyCode::SuperLeft) || input.pressed(KeyCode::SuperRight);
-    let ctrl_pressed = input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight);
-    let shift_pressed = input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight);
-    
-    // Use Cmd on Mac, Ctrl on other platforms
-    let modifier_pressed = if cfg!(target_os = "macos") {
-        cmd_pressed
+    for action in shortcuts_pressed(&input, &persistent_data.user_settings.key_bindings) {
+        match action {
+            ShortcutAction::Undo => {
+                if let Some(game_move) = history.undo(&mut board) {
+                    events.write(GameEvent::Undo { row: game_move.row, col: game_move.col });
+                    debug!("Keyboard: Undid move at ({}, {})", game_move.row, game_move.col);
+                }
+            }
+            ShortcutAction::Redo => {
+                if let Some(game_move) = history.redo(&mut board) {
+                    events.write(GameEvent::Redo { row: game_move.row, col: game_move.col });
+                    debug!("Keyboard: Redid move at ({}, {})", game_move.row, game_move.col);
+                }
+            }
+            ShortcutAction::ToggleDebug => {}
+        }
+    }
+}
+
+/// Keeps GameState in sync with BoardState when it changes.
+pub fn game_state_system(
+    board: Res<BoardState>,
+    reveal: Option<Res<SolutionReveal>>,
+    mut state: ResMut<GameState>,
+    mut events: EventWriter<GameEvent>,
+) {
+    // A given-up puzzle stays given up while its solution is shown, even
+    // once the board is full
+    let new_state = if reveal.is_some() {
+        GameState::GaveUp
+    } else if board.is_changed() || *state == GameState::GaveUp {
+        board.compute_game_state()
     } else {
-        ctrl_pressed
+        return;
     };
-    
-    if modifier_pressed && input.just_pressed(KeyCode::KeyZ) {
-        if shift_pressed {
-            // Redo (Cmd+Shift+Z or Ctrl+Shift+Z)
-            if let Some(game_move) = history.peek_redo().cloned() {
-                board.apply_move(&game_move);
-                history.mark_redone();
-                println!("Keyboard: Redid move at ({}, {})", game_move.row, game_move.col);
+    if new_state != *state {
+        events.write(GameEvent::StateChanged(new_state));
+        *state = new_state;
+    }
+}
+
+/// The houses complete on `board` that weren't on `previous`. A board with
+/// different givens is a new puzzle, so nothing on it counts as newly complete.
+pub fn newly_completed_houses(previous: &BoardState, board: &BoardState) -> Vec<(HouseKind, usize)> {
+    let same_puzzle = board.grid_size == previous.grid_size
+        && (0..board.size()).all(|row| {
+            (0..board.size()).all(|col| board.is_given_cell(row, col) == previous.is_given_cell(row, col))
+        });
+    if !same_puzzle {
+        return Vec::new();
+    }
+    let before = previous.complete_houses();
+    board
+        .complete_houses()
+        .into_iter()
+        .filter(|house| !before.contains(house))
+        .collect()
+}
+
+/// Tells the UI about every house a move completes, so it can flash it.
+pub fn house_completion_system(
+    board: Res<BoardState>,
+    mut previous: Local<BoardState>,
+    mut completed: EventWriter<HouseCompleted>,
+) {
+    if !board.is_changed() {
+        return;
+    }
+    for (kind, index) in newly_completed_houses(&previous, &board) {
+        completed.write(HouseCompleted { kind, index });
+    }
+    *previous = board.clone();
+}
+
+/// Keeps the score as the game goes: cats the player places (once they
+/// settle, see `UnsettledPlacement`), hints that reveal a cat, and houses
+/// completed (see `ScoreTracker` for the rules).
+pub fn score_system(
+    mut events: EventReader<GameEvent>,
+    mut completed: EventReader<HouseCompleted>,
+    board: Res<BoardState>,
+    solution: Res<Solution>,
+    session: Res<GameSession>,
+    mut score: ResMut<ScoreTracker>,
+) {
+    // Hints first, so a house a hint finished doesn't count as clean
+    for event in events.read() {
+        match *event {
+            GameEvent::Placed { row, col, value } => {
+                score.record_placement(&solution, row, col, value, session.current_elapsed());
             }
-        } else {
-            // Undo (Cmd+Z or Ctrl+Z)
-            if let Some(game_move) = history.peek_undo().cloned() {
-                board.undo_move(&game_move);
-                history.mark_undone();
-                println!("Keyboard: Undid move at ({}, {})", game_move.row, game_move.col);
+            GameEvent::Hint { row, col, revealed: true, .. } => score.record_hint(row, col),
+            _ => {}
+        }
+    }
+    for house in completed.read() {
+        score.record_house(house.kind, house.index, &house.kind.cells_at(&board, house.index));
+    }
+}
+
+/// Counts the right cats the player places in a row (once they settle, see
+/// `UnsettledPlacement`), cheering the streak's milestones and keeping the
+/// longest ever in the statistics.
+pub fn streak_system(
+    mut events: EventReader<GameEvent>,
+    solution: Res<Solution>,
+    mut streak: ResMut<StreakTracker>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut reached: EventWriter<StreakReached>,
+) {
+    for event in events.read() {
+        if let GameEvent::Placed { row, col, value } = *event {
+            let correct = solution.cells.get(row).and_then(|cells| cells.get(col)) == Some(&value);
+            if let Some(milestone) = streak.record(row, col, correct) {
+                info!("🔥 {}", milestone.message());
+                reached.write(StreakReached(milestone));
             }
         }
     }
-    
-    // Alternative Redo shortcut: Cmd+Y or Ctrl+Y
-    if modifier_pressed && input.just_pressed(KeyCode::KeyY) {
-        if let Some(game_move) = history.peek_redo().cloned() {
-            board.apply_move(&game_move);
-            history.mark_redone();
-            println!("Keyboard: Redid move at ({}, {})", game_move.row, game_move.col);
+    if streak.longest > persistent_data.statistics.best_placement_streak {
+        persistent_data.statistics.best_placement_streak = streak.longest;
+    }
+}
+
+/// With nine lives on, every cat placed where the solution has a different
+/// one costs a life (except in Zen mode), once it settles (see `UnsettledPlacement`). Losing the last one stops the
+/// clock, and the game-over overlay takes it from there.
+pub fn lose_lives_system(
+    mut events: EventReader<GameEvent>,
+    solution: Res<Solution>,
+    persistent_data: Res<PersistentData>,
+    game_mode: Res<GameMode>,
+    mut lives: ResMut<Lives>,
+    mut session: ResMut<GameSession>,
+) {
+    for event in events.read() {
+        if let GameEvent::Placed { row, col, value } = *event
+            && persistent_data.user_settings.nine_lives
+            && game_mode.has_penalties()
+            && lives.check_placement(&solution, row, col, value)
+        {
+            info!("💔 Wrong cat at ({}, {}) - {} lives left", row + 1, col + 1, lives.remaining);
+            if lives.is_out() {
+                info!("😿 Out of lives");
+                session.pause();
+            }
         }
     }
 }
 
-/// Keeps GameState in sync with BoardState when it changes.
-pub fn game_state_system(board: Res<BoardState>, mut state: ResMut<GameState>) {
+/// Run condition: whether nine lives is on and every life is gone, so the
+/// board takes no more input.
+fn out_of_lives(lives: Res<Lives>, persistent_data: Res<PersistentData>, game_mode: Res<GameMode>) -> bool {
+    persistent_data.user_settings.nine_lives && game_mode.has_penalties() && lives.is_out()
+}
+
+/// Run condition: whether a time attack run's clock has run out, so the
+/// board takes no more input.
+fn time_attack_over(run: Option<Res<TimeAttack>>) -> bool {
+    run.is_some_and(|run| run.is_over())
+}
+
+/// Whether the game window is out of the player's sight: in the background,
+/// or minimized.
+#[derive(Default)]
+pub struct WindowAway {
+    unfocused: bool,
+    occluded: bool,
+    /// Whether this system paused the clock (and so should start it again)
+    paused_clock: bool,
+}
+
+/// Pauses the clock while the game window is in the background or minimized,
+/// and starts it again when the player comes back, unless they turned that
+/// off in the settings. Only a puzzle still being played is paused, and a
+/// clock stopped for another reason (running out of lives) stays stopped.
+pub fn pause_when_away_system(
+    mut focus_events: EventReader<WindowFocused>,
+    mut occluded_events: EventReader<WindowOccluded>,
+    persistent_data: Res<PersistentData>,
+    game_state: Res<GameState>,
+    mut session: ResMut<GameSession>,
+    mut away: Local<WindowAway>,
+) {
+    for event in focus_events.read() {
+        away.unfocused = !event.focused;
+    }
+    for event in occluded_events.read() {
+        away.occluded = event.occluded;
+    }
+
+    let is_away = away.unfocused || away.occluded;
+    if is_away && !away.paused_clock && !session.is_paused {
+        if persistent_data.user_settings.pause_when_away && *game_state == GameState::Playing {
+            session.pause();
+            away.paused_clock = true;
+            info!("⏸️ Window in the background - clock paused");
+        }
+    } else if !is_away && away.paused_clock {
+        session.resume();
+        away.paused_clock = false;
+        info!("▶️ Welcome back - clock running again");
+    }
+}
+
+/// Save the persistent data, returning the event that reports how it went.
+fn save_event(persistent_data: &PersistentData) -> GameEvent {
+    match persistent_data.save() {
+        Ok(()) => GameEvent::Saved,
+        Err(e) => GameEvent::SaveFailed(e.to_string()),
+    }
+}
+
+/// Tells the player about any save that failed (see `save_event`).
+pub fn toast_failed_saves(mut events: EventReader<GameEvent>, mut toasts: EventWriter<ErrorToast>) {
+    for event in events.read() {
+        if let GameEvent::SaveFailed(error) = event {
+            toasts.write(ErrorToast::new("Statistics", error));
+        }
+    }
+}
+
+/// The game being played, as needed to save it.
+#[derive(SystemParam)]
+pub struct CurrentGame<'w> {
+    board: Res<'w, BoardState>,
+    solution: Res<'w, Solution>,
+    settings: Res<'w, PuzzleSettings>,
+    session: Res<'w, GameSession>,
+    hint_system: Res<'w, HintSystem>,
+    history: Res<'w, GameHistory>,
+    lives: Res<'w, Lives>,
+    score: Res<'w, ScoreTracker>,
+    streak: Res<'w, StreakTracker>,
+    game_state: Res<'w, GameState>,
+}
+
+impl CurrentGame<'_> {
+    /// Whether there's a game worth saving: started, and not yet won.
+    pub fn in_progress(&self) -> bool {
+        *self.game_state == GameState::Playing && self.session.move_count > 0
+    }
+
+    /// The game as a save, undo history, lives, and score and all.
+    pub fn to_save(&self) -> SaveGame {
+        self.board
+            .create_save_game(
+                &self.solution,
+                &self.settings,
+                self.session.current_elapsed().as_secs(),
+                self.session.move_count,
+                self.hint_system.hints_remaining,
+            )
+            .with_history(&self.history)
+            .with_lives(&self.lives)
+            .with_score(&self.score, &self.streak)
+    }
+}
+
+/// Resource tracking whether the game in progress has changed since it was
+/// last saved, so autosaves only write when there's something new.
+#[derive(Resource, Debug, Default)]
+pub struct AutosaveTracker {
+    /// The board, undo history, hints, or lives changed since the last save
+    pub dirty: bool,
+    /// Seconds since the last autosave
+    pub since_save: f32,
+}
+
+impl AutosaveTracker {
+    /// Whether an autosave is due: something changed, and either the
+    /// interval has passed or the player just left the window (the moment
+    /// before a force-quit or a crash is often the last chance).
+    pub fn due(&self, interval_secs: u32, left_window: bool) -> bool {
+        self.dirty && (left_window || self.since_save >= interval_secs as f32)
+    }
+
+    /// Note that the game was just saved.
+    pub fn saved(&mut self) {
+        self.dirty = false;
+        self.since_save = 0.0;
+    }
+}
+
+/// Marks the game as unsaved whenever the board, undo history, hints, or lives change.
+pub fn track_unsaved_changes_system(
+    board: Res<BoardState>,
+    history: Res<GameHistory>,
+    hint_system: Res<HintSystem>,
+    lives: Res<Lives>,
+    mut tracker: ResMut<AutosaveTracker>,
+) {
+    if board.is_changed() || history.is_changed() || hint_system.is_changed() || lives.is_changed() {
+        tracker.dirty = true;
+    }
+}
+
+/// Saves the game in progress every `autosave_interval_secs`, and when the
+/// window loses focus, if it changed since the last save (and the player
+/// hasn't turned autosave off), so it can be continued from the
+/// customization screen.
+pub fn autosave_system(
+    time: Res<Time>,
+    mut focus_events: EventReader<WindowFocused>,
+    game: CurrentGame,
+    mut tracker: ResMut<AutosaveTracker>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut events: EventWriter<GameEvent>,
+) {
+    tracker.since_save += time.delta_secs();
+    let left_window = focus_events.read().any(|event| !event.focused);
+    let settings = &persistent_data.user_settings;
+    if !settings.auto_save_enabled || !game.in_progress() || !tracker.due(settings.autosave_interval_secs, left_window) {
+        return;
+    }
+    tracker.saved();
+    persistent_data.current_save = Some(game.to_save());
+    info!("💾 Autosaved the game in progress");
+    events.write(save_event(&persistent_data));
+}
+
+/// Saves the game in progress, along with the rest of the persistent data,
+/// as the app closes, if it changed since the last save (and the player
+/// hasn't turned autosave off).
+pub fn autosave_on_exit_system(
+    mut exits: EventReader<AppExit>,
+    app_state: Res<State<AppState>>,
+    game: CurrentGame,
+    mut tracker: ResMut<AutosaveTracker>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    if exits.read().last().is_none() {
+        return;
+    }
+    if *app_state.get() == AppState::Ready
+        && game.in_progress()
+        && tracker.dirty
+        && persistent_data.user_settings.auto_save_enabled
+    {
+        tracker.saved();
+        persistent_data.current_save = Some(game.to_save());
+        match persistent_data.save() {
+            Ok(()) => info!("💾 Saved the game in progress for next time"),
+            Err(e) => {
+                toasts.write(ErrorToast::new("Game in progress", &e));
+            }
+        }
+    }
+}
+
+/// Collects game events into the debug event log, stamped with the time since startup.
+pub fn log_game_events_system(time: Res<Time>, mut events: EventReader<GameEvent>, mut log: ResMut<GameEventLog>) {
+    for event in events.read() {
+        log.record(time.elapsed(), event.clone());
+    }
+}
+
+/// Records every change to the board in the solve's replay, noting which
+/// cells were filled by hints.
+pub fn record_replay_system(
+    board: Res<BoardState>,
+    solution: Res<Solution>,
+    last_hint: Res<LastHint>,
+    mut replay: ResMut<Replay>,
+) {
+    if !board.is_changed() {
+        return;
+    }
+    let hinted = last_hint
+        .explanation
+        .filter(|_| last_hint.is_changed() && last_hint.tier == HintTier::Reveal)
+        .map(|explanation| (explanation.row, explanation.col));
+    replay.track(&board, &solution, hinted);
+}
+
+/// Records a completed game in the persistent statistics when the puzzle is
+/// won, and offers it to its preset's leaderboard. A campaign puzzle also
+/// earns its stars, and a pack puzzle is ticked off in its pack.
+#[allow(clippy::too_many_arguments)]
+pub fn record_game_result_system(
+    game_state: Res<GameState>,
+    mut previous_state: Local<GameState>,
+    board: Res<BoardState>,
+    session: Res<GameSession>,
+    settings: Res<PuzzleSettings>,
+    hint_system: Res<HintSystem>,
+    replay: Res<Replay>,
+    mut placement: ResMut<LeaderboardPlacement>,
+    mut campaign_result: ResMut<CampaignResult>,
+    mut pack_library: ResMut<PackLibrary>,
+    time_attack: Option<Res<TimeAttack>>,
+    tutorial: Option<Res<Tutorial>>,
+    game_mode: Res<GameMode>,
+    score: Res<ScoreTracker>,
+    mut persistent_data: ResMut<PersistentData>,
+    (mut events, mut toasts): (EventWriter<GameEvent>, EventWriter<ErrorToast>),
+) {
+    if !game_state.is_changed() || *game_state == *previous_state {
+        return;
+    }
+    *previous_state = *game_state;
+
+    // Time attack solves count toward their run instead (see `time_attack_system`),
+    // and the tutorial's puzzle doesn't count at all
+    if *game_state == GameState::Won && time_attack.is_none() && tutorial.is_none() {
+        let play_time = session.current_elapsed().as_secs();
+        let hints_used = hint_system.max_hints.saturating_sub(hint_system.hints_remaining) as u32;
+        persistent_data.record_game_completion(&settings, play_time, hints_used);
+        info!("🏆 Puzzle solved in {}s with {} hints, scoring {}", play_time, hints_used, score.score());
+
+        let preset = PresetKind::for_difficulty(settings.difficulty);
+        let entry = LeaderboardEntry {
+            seconds: play_time,
+            completed_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            hints_used,
+            mistakes: replay.mistakes() as u32,
+            score: score.score(),
+        };
+        persistent_data.statistics.record_game(CompletedGameRecord {
+            date: entry.completed_at,
+            preset,
+            duration: play_time,
+            hints_used,
+            mistakes: entry.mistakes,
+            score: entry.score,
+        });
+        // Zen solves weren't played against the clock, so they stay off the leaderboard,
+        // and only classic 9x9 puzzles compare with the presets' times
+        if game_mode.shows_counters()
+            && let Some(preset) = settings.ranked_preset()
+        {
+            let rank = persistent_data.statistics.leaderboard.record_with_replay(preset, entry, &replay);
+            *placement = LeaderboardPlacement { preset, rank };
+        }
+        if let Some(level) = settings.campaign_level {
+            let stars = level.chapter().par.stars(play_time, hints_used, entry.mistakes);
+            let improved = persistent_data.campaign.record(level, stars);
+            *campaign_result = CampaignResult { level: Some(level), stars, improved };
+            info!("🗺️ Campaign puzzle {} solved for {} stars", level.label(), stars);
+        }
+        if let Some(pack_puzzle) = &settings.pack_puzzle
+            && pack_library.mark_completed(pack_puzzle)
+            && let Err(e) = pack_library.save()
+        {
+            toasts.write(ErrorToast::new("Puzzle packs", &e));
+        }
+        if let Some(technique) = settings.practice {
+            persistent_data.statistics.record_practice(technique, hints_used, entry.mistakes);
+            info!("🥋 Practice puzzle for {} solved", technique.name());
+        }
+        persistent_data.statistics.cell_stats.record_replay(board.grid_size, &replay);
+        // A solved puzzle has nothing left to continue
+        persistent_data.current_save = None;
+        events.write(save_event(&persistent_data));
+
+        // Write a journal entry for players who keep a record of their solves
+        let export = persistent_data.user_settings.summary_export;
+        if export != SummaryExport::Off {
+            let summary = SessionSummary::new(
+                &board,
+                &settings,
+                &persistent_data.statistics,
+                play_time,
+                session.move_count,
+                hints_used,
+            );
+            match summary.export(export) {
+                Ok(Some(path)) => info!("📓 Wrote session summary to {:?}", path),
+                Ok(None) => {}
+                Err(e) => {
+                    toasts.write(ErrorToast::new("Session summary", &e));
+                }
+            }
+        }
+    }
+}
+
+/// Runs a time attack run: each solve scores its points and goes straight
+/// on to the next puzzle, and the clock runs down unless the game is paused.
+/// When it runs out, the run is recorded in the statistics.
+///
+/// While the next puzzle is still being generated, the run waits for it with
+/// the clock stopped.
+pub fn time_attack_system(
+    time: Res<Time>,
+    mut run: ResMut<TimeAttack>,
+    settings: Res<PuzzleSettings>,
+    generation: Option<Res<PuzzleGeneration>>,
+    mut puzzles: PuzzleSwap,
+    mut persistent_data: ResMut<PersistentData>,
+) {
+    if generation.is_some() {
+        return;
+    }
+    if *puzzles.game_state == GameState::Won && !run.is_over() {
+        let points = run.record_solve(settings.difficulty);
+        info!("⏱️ Solved for {} points ({} so far)", points, run.score);
+        puzzles.load_next(&run);
+    }
+    if !puzzles.progress.session.is_paused && run.tick(time.delta()) {
+        puzzles.progress.session.pause();
+        if persistent_data.statistics.time_attack.record(&run) {
+            info!("🏆 New best time attack score: {}", run.score);
+        }
+        info!("⏱️ Time's up: {} puzzles solved for {} points", run.solved, run.score);
+        // The puzzle left unfinished goes with the run
+        persistent_data.current_save = None;
+        puzzles.events.write(save_event(&persistent_data));
+    }
+}
+
+/// System that starts a new run from the time's-up overlay.
+pub fn time_attack_again_system(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<TimeAttackAgainButton>)>,
+    mut run: ResMut<TimeAttack>,
+    mut puzzles: PuzzleSwap,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            *run = TimeAttack::default();
+            puzzles.load_next(&run);
+        }
+    }
+}
+
+/// Moves the tutorial along (or back) as cats go in or come out of the
+/// cells its steps point at.
+pub fn tutorial_system(board: Res<BoardState>, mut tutorial: ResMut<Tutorial>) {
     if board.is_changed() {
-        *state = board.compute_game_state();
+        tutorial.sync(&board);
+    }
+}
+
+/// A puzzle being generated in the background for the pool.
+pub struct PoolFill {
+    settings: PuzzleSettings,
+    task: Task<Result<(BoardState, Solution, GenerationReport), NineLivesError>>,
+    cancel: CancellationToken,
+}
+
+/// Dropping a task doesn't stop a search already running on another
+/// thread, so let the generator know it's no longer wanted.
+impl Drop for PoolFill {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+/// Keeps the puzzle pool stocked for every preset at the selected board size
+/// and variant, generating one puzzle at a time on a background task so the
+/// menu and the game never stall. The pool is saved after each new puzzle.
+///
+/// A puzzle for a board size or variant that's no longer selected is
+/// cancelled rather than left to finish, and so is any puzzle still
+/// generating when the game quits.
+pub fn fill_puzzle_pool_system(
+    mut pool: ResMut<PuzzlePool>,
+    selected_grid_size: Res<SelectedGridSize>,
+    selected_variant: Res<SelectedVariant>,
+    mut exits: EventReader<AppExit>,
+    mut in_flight: Local<Option<PoolFill>>,
+) {
+    if exits.read().next().is_some() {
+        *in_flight = None;
+        return;
+    }
+
+    if let Some(fill) = in_flight.as_ref()
+        && (fill.settings.grid_size != selected_grid_size.grid_size || fill.settings.variant != selected_variant.variant)
+    {
+        info!("🛑 Cancelled background generation for {}", fill.settings.description());
+        *in_flight = None;
+    }
+
+    if let Some(fill) = in_flight.as_mut() {
+        let Some(generated) = check_ready(&mut fill.task) else {
+            return;
+        };
+        match generated {
+            Ok((board, solution, report)) => {
+                pool.push(&board, &fill.settings, &solution, report);
+                if let Err(e) = pool.save() {
+                    warn!("⚠️ Failed to save puzzle pool: {}", e);
+                }
+            }
+            Err(NineLivesError::Cancelled) => {}
+            Err(e) => warn!("⚠️ Background generation for {}: {}", fill.settings.description(), e),
+        }
+        *in_flight = None;
+    }
+
+    let wanted = PresetKind::all().map(|preset| {
+        PuzzleSettings::from_preset(preset)
+            .with_grid_size(selected_grid_size.grid_size)
+            .with_variant(selected_variant.variant)
+    });
+    if let Some(settings) = pool.next_to_fill(wanted) {
+        let task_settings = settings.clone();
+        let cancel = CancellationToken::new();
+        let task_cancel = cancel.clone();
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let mut board = BoardState::new();
+            board
+                .generate_puzzle_cancellable(&task_settings, &task_cancel)
+                .map(|(solution, report)| (board, solution, report))
+        });
+        *in_flight = Some(PoolFill { settings, task, cancel });
     }
 }
 
 /// Adds controller systems to the provided Bevy App.
 pub fn add_controller(app: &mut App) {
-    app.add_systems(
+    app.add_event::<GameEvent>().init_resource::<UnsettledPlacement>().add_systems(
         Update,
         (
             cell_click_system,
             clear_button_system,
+            restart_button_system,
+            confirmed_action_system,
             new_game_button_system,
+            same_settings_system,
             game_state_system,
         )
             .run_if(in_state(AppState::Ready)),
@@ -250,15 +1683,25 @@ pub fn add_controller(app: &mut App) {
 /// 4. Adding the controller layer (event handling)
 /// 5. Running the game loop
 pub fn run_game() {
+    // Each profile has its own persistent data; play on as whoever played last
+    let roster = ProfileRoster::load();
     App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Nine Lives: Cat Sudoku".to_string(),
-                resolution: (700., 800.).into(),
-                ..default()
-            }),
-            ..default()
-        }))
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: Some(Window {
+                        title: MENU_TITLE.to_string(),
+                        resolution: (1000., 800.).into(),
+                        ..default()
+                    }),
+                    ..default()
+                })
+                // RUST_LOG overrides this when set, e.g. RUST_LOG=nine_lives_core=debug
+                .set(LogPlugin {
+                    filter: LOG_FILTER.to_string(),
+                    ..default()
+                }),
+        )
         // Initialize the core game state from the model layer
         .init_resource::<BoardState>()
         .init_resource::<GameState>()
@@ -268,30 +1711,140 @@ pub fn run_game() {
         .init_resource::<HintSystem>()
         .init_resource::<DebugMode>()
         .init_resource::<PuzzleSettings>()
+        .init_resource::<LastHint>()
+        .init_resource::<Replay>()
+        .init_resource::<GameEventLog>()
+        .init_resource::<Lives>()
+        .init_resource::<AutosaveTracker>()
+        .init_resource::<GameMode>()
+        .init_resource::<ScoreTracker>()
+        .init_resource::<StreakTracker>()
+        .init_resource::<UnsettledPlacement>()
+        .add_event::<GameEvent>()
+        .insert_resource(roster.load_active_data())
+        .insert_resource(roster)
+        .insert_resource(PuzzlePool::load())
+        .insert_resource(PackLibrary::load())
         // Add the UI layer (view)
         .add_plugins(nine_lives_ui::UiPlugin)
-        // Add controller systems
+        // Add controller systems (the board is the replay's while one is being watched,
+        // so everything but the game state pauses)
         .add_systems(
             Update,
             (
-                cell_click_system,
-                clear_button_system,
-                new_game_button_system,
-                undo_button_system,
-                redo_button_system,
-                hint_button_system,
-                keyboard_shortcuts_system,
-                debug_mode_system,
+                (
+                    cell_click_system,
+                    typed_cat_system
+                        .run_if(not(out_of_lives))
+                        .run_if(not(resource_exists::<SolutionReveal>))
+                        .run_if(not(time_attack_over)),
+                    clear_button_system,
+                    restart_button_system,
+                    confirmed_action_system,
+                    (new_game_button_system, same_settings_system),
+                    undo_button_system,
+                    redo_button_system,
+                    history_jump_system,
+                    hint_button_system,
+                    check_button_system.run_if(not(out_of_lives)),
+                    keyboard_shortcuts_system,
+                    debug_mode_system,
+                    record_game_result_system.after(game_state_system).after(score_system),
+                    record_replay_system.after(hint_button_system),
+                    (
+                        settle_placements_system.after(cell_click_system).after(typed_cat_system),
+                        lose_lives_system.after(settle_placements_system),
+                    ),
+                    pause_when_away_system,
+                    house_completion_system
+                        .after(cell_click_system)
+                        .after(typed_cat_system)
+                        .after(hint_button_system),
+                    track_unsaved_changes_system,
+                    autosave_system
+                        .after(track_unsaved_changes_system)
+                        .run_if(not(resource_exists::<TimeAttack>))
+                        .run_if(not(resource_exists::<Tutorial>)),
+                )
+                    .run_if(not(resource_exists::<ReplayViewer>)),
                 game_state_system,
             )
                 .run_if(in_state(AppState::Ready)),
         )
+        // Undoing further: holding undo or redo down, and undoing back to the last correct board
+        .add_systems(
+            Update,
+            (
+                hold_to_repeat_system.after(undo_button_system).after(redo_button_system),
+                undo_to_correct_button_system,
+            )
+                .run_if(not(resource_exists::<ReplayViewer>))
+                .run_if(in_state(AppState::Ready)),
+        )
+        // Touch screens
+        .add_systems(Update, detect_touch_system)
+        .add_systems(
+            Update,
+            touch_gesture_system
+                .after(cell_click_system)
+                .before(house_completion_system)
+                .run_if(not(out_of_lives))
+                .run_if(not(resource_exists::<SolutionReveal>))
+                .run_if(not(resource_exists::<ReplayViewer>))
+                .run_if(not(time_attack_over))
+                .run_if(in_state(AppState::Ready)),
+        )
+        // Keeping score (as the houses a move completes are found) and streaks
+        .add_systems(
+            Update,
+            (
+                score_system.after(house_completion_system).after(settle_placements_system),
+                streak_system.after(settle_placements_system),
+            )
+                .run_if(not(resource_exists::<ReplayViewer>))
+                .run_if(in_state(AppState::Ready)),
+        )
+        // Giving up and showing the solution
+        .add_systems(
+            Update,
+            (show_solution_button_system, reveal_solution_system.before(game_state_system))
+                .run_if(in_state(AppState::Ready)),
+        )
+        // Time attack runs
+        .add_systems(
+            Update,
+            (time_attack_system.after(game_state_system), time_attack_again_system)
+                .run_if(resource_exists::<TimeAttack>)
+                .run_if(in_state(AppState::Ready)),
+        )
+        // The tutorial, following along with the board
+        .add_systems(
+            Update,
+            tutorial_system
+                .after(cell_click_system)
+                .after(typed_cat_system)
+                .after(touch_gesture_system)
+                .run_if(resource_exists::<Tutorial>)
+                .run_if(in_state(AppState::Ready)),
+        )
+        // Stock up on puzzles in the background, on the menu and mid-game alike
+        .add_systems(Update, fill_puzzle_pool_system)
+        .add_systems(PostUpdate, (log_game_events_system, toast_failed_saves))
+        // Keep the game in progress when the window closes
+        .add_systems(
+            Last,
+            autosave_on_exit_system
+                .run_if(not(resource_exists::<TimeAttack>))
+                .run_if(not(resource_exists::<Tutorial>)),
+        )
         .run();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use nine_lives_core::{CellType, TIME_ATTACK_SECONDS};
+    use nine_lives_ui::{CatEmojis, LastGeneration, finish_puzzle_generation};
 
     #[test]
     fn test_controller_systems() {
@@ -299,7 +1852,245 @@ mod tests {
         let mut app = App::new();
         add_controller(&mut app);
         // If we get here, the systems were added successfully
-        assert!(true);
+        assert!(app.world().contains_resource::<UnsettledPlacement>());
+    }
+
+    #[test]
+    fn test_current_game_saves_in_progress() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut streak = StreakTracker::default();
+        streak.record(0, 1, true);
+        streak.record(0, 2, true);
+        let mut app = App::new();
+        app.init_resource::<BoardState>()
+            .init_resource::<Solution>()
+            .init_resource::<GameSession>()
+            .init_resource::<GameHistory>()
+            .init_resource::<GameState>()
+            .insert_resource(PuzzleSettings::from_preset(PresetKind::StreetwiseStray))
+            .insert_resource(HintSystem::new(2))
+            .insert_resource(Lives { remaining: 4 })
+            .init_resource::<ScoreTracker>()
+            .insert_resource(streak.clone());
+        let unstarted = app.world_mut().run_system_once(|game: CurrentGame| game.in_progress()).unwrap();
+        assert!(!unstarted, "A puzzle without moves isn't worth saving");
+
+        let world = app.world_mut();
+        let game_move = world.resource_mut::<BoardState>().set_cell(0, 0, Some(4)).unwrap();
+        world.resource_mut::<GameHistory>().add_move(game_move);
+        world.resource_mut::<GameSession>().increment_move();
+        let (in_progress, save) =
+            world.run_system_once(|game: CurrentGame| (game.in_progress(), game.to_save())).unwrap();
+        assert!(in_progress);
+        assert_eq!(save.board_cells[0][0], Some(4));
+        assert_eq!((save.move_count, save.hints_remaining), (1, 2));
+        assert_eq!(save.settings.difficulty, nine_lives_core::Difficulty::Hard);
+        assert!(save.restore_history().can_undo());
+        assert_eq!(save.restore_lives().remaining, 4, "Lost lives stay lost across a save");
+        assert_eq!(save.restore_streak(), streak);
+    }
+
+    #[test]
+    fn test_given_up_puzzle_is_never_won() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut board = BoardState::new();
+        let settings = PuzzleSettings { seed: Some(3), ..PuzzleSettings::default() };
+        let solution = board.generate_puzzle_with_settings(&settings).expect("Puzzle should generate");
+        let reveal = SolutionReveal::new(&board, &solution, 0, 0);
+        let mut app = App::new();
+        app.insert_resource(board)
+            .insert_resource(solution)
+            .insert_resource(reveal)
+            .init_resource::<GameState>()
+            .init_resource::<Time>()
+            .add_event::<GameEvent>();
+
+        let world = app.world_mut();
+        world.resource_mut::<Time>().advance_by(std::time::Duration::from_secs(60));
+        world.run_system_once(reveal_solution_system).unwrap();
+        world.run_system_once(game_state_system).unwrap();
+        assert!(world.resource::<SolutionReveal>().is_done());
+        assert!(world.resource::<BoardState>().is_complete());
+        assert_eq!(*world.resource::<GameState>(), GameState::GaveUp, "A full board isn't a win after giving up");
+
+        // Putting the solution away (as restarting does) goes back to the board's own state
+        world.remove_resource::<SolutionReveal>();
+        world.run_system_once(game_state_system).unwrap();
+        assert_eq!(*world.resource::<GameState>(), GameState::Won);
+    }
+
+    /// Run `finish_puzzle_generation` until the puzzle being generated is swapped in.
+    fn wait_for_generation(world: &mut World) {
+        use bevy::ecs::system::RunSystemOnce;
+
+        assert!(world.contains_resource::<PuzzleGeneration>(), "The pool is empty, so the puzzle is generated");
+        while world.contains_resource::<PuzzleGeneration>() {
+            world.run_system_once(finish_puzzle_generation).unwrap();
+        }
+        assert!(world.resource::<LastGeneration>().report.is_some());
+    }
+
+    #[test]
+    fn test_hint_clears_peer_notes_and_undoes() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let settings = PuzzleSettings { seed: Some(3), ..PuzzleSettings::from_preset(PresetKind::CozyKitten) };
+        let mut board = BoardState::new();
+        let solution = board.generate_puzzle_with_settings(&settings).expect("Puzzle should generate");
+        let mut app = App::new();
+        app.insert_resource(board)
+            .insert_resource(solution)
+            .insert_resource(settings)
+            .init_resource::<GameHistory>()
+            .init_resource::<HintSystem>()
+            .init_resource::<DebugMode>()
+            .init_resource::<LastHint>()
+            .add_event::<GameEvent>();
+        let world = app.world_mut();
+        let button = world.spawn((HintButton, Interaction::Pressed)).id();
+
+        // The first press points at the deduction
+        world.run_system_once(hint_button_system).unwrap();
+        let hint = world.resource::<LastHint>().explanation.expect("A hint should be found");
+        assert_eq!(world.resource::<LastHint>().tier, HintTier::Nudge);
+        let (row, col, value) = (hint.row, hint.col, hint.value);
+        let peer = {
+            let board = world.resource::<BoardState>();
+            board
+                .peers_of(row, col)
+                .into_iter()
+                .find(|&(r, c)| board.cells[r][c].is_none())
+                .expect("An empty peer")
+        };
+        world.resource_mut::<BoardState>().set_note(peer.0, peer.1, value, true);
+
+        // The second press places the cat, taking it out of the peer's notes
+        world.get_mut::<Interaction>(button).unwrap().set_changed();
+        world.run_system_once(hint_button_system).unwrap();
+        let board = world.resource::<BoardState>();
+        assert_eq!(board.cells[row][col], Some(value));
+        assert!(!board.has_note(peer.0, peer.1, value));
+
+        // and undoing it puts the note back along with the empty cell
+        world.resource_scope(|world, mut history: Mut<GameHistory>| {
+            history.undo(&mut world.resource_mut::<BoardState>());
+        });
+        let board = world.resource::<BoardState>();
+        assert_eq!(board.cells[row][col], None);
+        assert!(board.has_note(peer.0, peer.1, value));
+    }
+
+    #[test]
+    fn test_time_attack_moves_on_and_records_the_run() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut board = BoardState::new();
+        let solution = board
+            .generate_puzzle_with_settings(&PuzzleSettings { seed: Some(5), ..PuzzleSettings::default() })
+            .expect("Puzzle should generate");
+        board.cells = solution.cells.iter().map(|row| row.iter().map(|&cat| Some(cat)).collect()).collect();
+        let mut app = App::new();
+        app.insert_resource(board)
+            .insert_resource(solution)
+            .insert_resource(GameState::Won)
+            .insert_resource(TimeAttack::default())
+            .insert_resource(PuzzleSettings::from_preset(PresetKind::CozyKitten))
+            .init_resource::<GameSession>()
+            .init_resource::<GameHistory>()
+            .init_resource::<HintSystem>()
+            .init_resource::<LastHint>()
+            .init_resource::<Replay>()
+            .init_resource::<Lives>()
+            .init_resource::<ScoreTracker>()
+            .init_resource::<StreakTracker>()
+            .init_resource::<PuzzlePool>()
+            .init_resource::<LastGeneration>()
+            .init_resource::<PersistentData>()
+            .init_resource::<Time>()
+            .add_plugins((TaskPoolPlugin::default(), bevy::state::app::StatesPlugin))
+            .insert_state(AppState::Ready)
+            .add_event::<GameEvent>()
+            .add_event::<ErrorToast>();
+
+        // A solve scores and starts on the next puzzle, the run waiting for it
+        let world = app.world_mut();
+        world.run_system_once(time_attack_system).unwrap();
+        world.run_system_once(time_attack_system).unwrap();
+        let run = world.resource::<TimeAttack>();
+        assert_eq!((run.solved, run.score), (1, 100));
+        wait_for_generation(world);
+        assert_eq!(*world.resource::<GameState>(), GameState::Playing);
+        assert!(!world.resource::<BoardState>().is_complete());
+
+        // Once the clock runs out the run goes into the statistics
+        world.resource_mut::<Time>().advance_by(std::time::Duration::from_secs(TIME_ATTACK_SECONDS));
+        world.run_system_once(time_attack_system).unwrap();
+        assert!(world.resource::<TimeAttack>().is_over());
+        assert!(world.resource::<GameSession>().is_paused);
+        let record = &world.resource::<PersistentData>().statistics.time_attack;
+        assert_eq!((record.runs, record.best_score, record.best_solved), (1, 100, 1));
+    }
+
+    #[test]
+    fn test_same_settings_starts_a_fresh_puzzle() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let settings = PuzzleSettings { seed: Some(9), ..PuzzleSettings::from_preset(PresetKind::CozyKitten) };
+        let mut board = BoardState::new();
+        let solution = board.generate_puzzle_with_settings(&settings).expect("Puzzle should generate");
+        let old_cells = board.cells.clone();
+        let mut session = GameSession::default();
+        session.increment_move();
+        let mut app = App::new();
+        app.insert_resource(board)
+            .insert_resource(solution)
+            .insert_resource(session)
+            .insert_resource(settings)
+            .init_resource::<GameState>()
+            .init_resource::<GameHistory>()
+            .init_resource::<HintSystem>()
+            .init_resource::<LastHint>()
+            .init_resource::<Replay>()
+            .init_resource::<Lives>()
+            .init_resource::<ScoreTracker>()
+            .init_resource::<StreakTracker>()
+            .init_resource::<PuzzlePool>()
+            .init_resource::<LastGeneration>()
+            .init_resource::<PersistentData>()
+            .add_plugins((TaskPoolPlugin::default(), bevy::state::app::StatesPlugin))
+            .insert_state(AppState::Ready)
+            .add_event::<ConfirmedAction>()
+            .add_event::<GameEvent>()
+            .add_event::<ErrorToast>();
+
+        // Nothing happens until the player picks it in the dialog
+        let world = app.world_mut();
+        world.run_system_once(same_settings_system).unwrap();
+        assert_eq!(world.resource::<GameSession>().move_count, 1);
+
+        world.send_event(ConfirmedAction(ConfirmAction::SameSettings));
+        world.run_system_once(same_settings_system).unwrap();
+        wait_for_generation(world);
+        assert_eq!(world.resource::<GameSession>().move_count, 0);
+        assert_ne!(world.resource::<BoardState>().cells, old_cells, "The shared seed isn't reused");
+        assert_eq!(world.resource::<PuzzleSettings>().difficulty, nine_lives_core::Difficulty::Easy);
+        assert_eq!(world.resource::<PuzzleSettings>().seed, None);
+        assert_eq!(world.resource::<PersistentData>().statistics.games_abandoned, 1);
+    }
+
+    #[test]
+    fn test_autosave_only_when_changed() {
+        let mut tracker = AutosaveTracker { dirty: false, since_save: 120.0 };
+        assert!(!tracker.due(60, true), "Nothing new, nothing to save");
+
+        tracker.dirty = true;
+        assert!(tracker.due(60, false));
+        tracker.saved();
+        tracker.dirty = true;
+        assert!(!tracker.due(60, false), "Changes wait for the interval");
+        assert!(tracker.due(60, true), "...unless the player leaves the window");
     }
 
     #[test]
@@ -318,16 +2109,223 @@ mod tests {
         assert_eq!(board.cells[0][0], Some(1));
     }
 
+    #[test]
+    fn test_newly_completed_houses() {
+        let mut board = BoardState::new();
+        for col in 0..8 {
+            board.set_cell(0, col, Some(col));
+        }
+        let previous = board.clone();
+        board.set_cell(0, 8, Some(8));
+        assert_eq!(newly_completed_houses(&previous, &board), vec![(HouseKind::Row, 0)]);
+        assert!(newly_completed_houses(&board, &board).is_empty(), "Complete houses only count once");
+
+        // A new puzzle doesn't count as completing anything
+        let mut new_puzzle = board.clone();
+        new_puzzle.cell_types[0][0] = Some(CellType::Given);
+        assert!(newly_completed_houses(&previous, &new_puzzle).is_empty());
+    }
+
+    #[test]
+    fn test_typed_cat_keys() {
+        assert_eq!(typed_cat(KeyCode::Digit1), Some(0));
+        assert_eq!(typed_cat(KeyCode::Numpad9), Some(8));
+        assert_eq!(typed_cat(KeyCode::KeyA), Some(9));
+        assert_eq!(typed_cat(KeyCode::KeyG), Some(15));
+        assert_eq!(typed_cat(KeyCode::Digit0), None);
+        assert_eq!(typed_cat(KeyCode::KeyZ), None);
+    }
+
+    #[test]
+    fn test_double_click_fills_single_legal_value() {
+        let mut board = BoardState::new();
+        let mut history = GameHistory::new();
+        for col in 0..8 {
+            board.set_cell(0, col, Some(col));
+        }
+        let mut solution = Solution::new();
+        solution.cells[0][8] = 8;
+        let mut replay = Replay::new(&board);
+
+        // The first click of the double-click cycles the empty cell to cat 1
+        let first_click = SystemTime::now();
+        history.add_move(board.cycle_cell(0, 8, 9).unwrap());
+        replay.track(&board, &solution, None);
+        assert!(fill_single_legal_value(&mut board, &mut history, &mut replay, 0, 8, first_click));
+        replay.track(&board, &solution, None);
+        assert_eq!(board.cells[0][8], Some(8));
+
+        // Both clicks end up as one move from empty to the filled cat, in the
+        // history and in the replay
+        assert_eq!(history.position_info(), (1, 1));
+        let game_move = history.peek_undo().unwrap();
+        assert_eq!((game_move.old_value, game_move.new_value), (None, Some(8)));
+        assert_eq!(replay.len(), 1);
+        assert_eq!(replay.mistakes(), 0);
+
+        // Cells with a choice of cats are left alone
+        let first_click = SystemTime::now();
+        history.add_move(board.cycle_cell(1, 0, 9).unwrap());
+        assert!(!fill_single_legal_value(&mut board, &mut history, &mut replay, 1, 0, first_click));
+        assert_eq!(board.cells[1][0], Some(0));
+    }
+
+    /// An app with a puzzle to play on and what the cell input needs, with
+    /// nine lives on. Returns an empty cell whose right cat isn't the first.
+    fn playing_app() -> (App, (usize, usize)) {
+        let mut board = BoardState::new();
+        let settings = PuzzleSettings { seed: Some(11), ..PuzzleSettings::default() };
+        let solution = board.generate_puzzle_with_settings(&settings).expect("Puzzle should generate");
+        let cell = (0..81)
+            .map(|index| (index / 9, index % 9))
+            .find(|&(row, col)| board.cells[row][col].is_none() && solution.cells[row][col] > 0)
+            .expect("Some empty cell isn't cat 1");
+        let mut persistent_data = PersistentData::default();
+        persistent_data.user_settings.nine_lives = true;
+        let mut app = App::new();
+        app.insert_resource(board)
+            .insert_resource(solution)
+            .insert_resource(persistent_data)
+            .init_resource::<GameSession>()
+            .init_resource::<GameHistory>()
+            .init_resource::<GameMode>()
+            .init_resource::<Lives>()
+            .init_resource::<ActiveDigit>()
+            .init_resource::<SelectedCell>()
+            .init_resource::<UnsettledPlacement>()
+            .init_resource::<Time>()
+            .add_event::<GameEvent>();
+        (app, cell)
+    }
+
+    #[test]
+    fn test_cycling_to_the_right_cat_costs_no_lives() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let (mut app, (row, col)) = playing_app();
+        let world = app.world_mut();
+        let right = world.resource::<Solution>().cells[row][col];
+
+        // Click through the wrong cats to the right one, then leave it there
+        for _ in 0..=right {
+            world
+                .run_system_once(move |mut input: CellInput| input.apply_clicked(CellCommand::Cycle { row, col }))
+                .unwrap();
+            world.run_system_once(settle_placements_system).unwrap();
+        }
+        assert_eq!(world.resource::<BoardState>().cells[row][col], Some(right));
+        world.resource_mut::<Time>().advance_by(std::time::Duration::from_secs_f32(SETTLE_SECONDS));
+        world.run_system_once(settle_placements_system).unwrap();
+        world.run_system_once(lose_lives_system).unwrap();
+        assert_eq!(world.resource::<Lives>().remaining, nine_lives_core::STARTING_LIVES);
+
+        // A wrong cat clicked in and left costs a life once it settles, and
+        // a typed one straight away
+        world
+            .run_system_once(move |mut input: CellInput| {
+                input.apply_clicked(CellCommand::Set { row, col, value: Some(right - 1) })
+            })
+            .unwrap();
+        world.run_system_once(lose_lives_system).unwrap();
+        assert_eq!(world.resource::<Lives>().lost(), 0, "Nothing counts before the cat settles");
+        world
+            .run_system_once(move |mut input: CellInput| input.apply(CellCommand::Set { row, col, value: Some(right) }))
+            .unwrap();
+        world
+            .run_system_once(move |mut input: CellInput| input.apply(CellCommand::Set { row, col, value: Some(0) }))
+            .unwrap();
+        world.run_system_once(lose_lives_system).unwrap();
+        assert_eq!(world.resource::<Lives>().lost(), 1, "Only the typed wrong cat counts");
+    }
+
+    #[test]
+    fn test_cycling_scores_only_the_settled_cat() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let (mut app, (row, col)) = playing_app();
+        app.init_resource::<ScoreTracker>().add_event::<HouseCompleted>();
+        let world = app.world_mut();
+        let right = world.resource::<Solution>().cells[row][col];
+        for _ in 0..=right {
+            world
+                .run_system_once(move |mut input: CellInput| input.apply_clicked(CellCommand::Cycle { row, col }))
+                .unwrap();
+        }
+        world.run_system_once(score_system).unwrap();
+        assert_eq!(*world.resource::<ScoreTracker>(), ScoreTracker::default(), "Nothing scores before the cat settles");
+
+        // Clicking another cell settles the first, wrong cats passed on the way and all
+        let board = world.resource::<BoardState>();
+        let (other_row, other_col) = (0..81)
+            .map(|index| (index / 9, index % 9))
+            .find(|&(other_row, other_col)| board.cells[other_row][other_col].is_none())
+            .expect("Another empty cell");
+        world
+            .run_system_once(move |mut input: CellInput| {
+                input.apply_clicked(CellCommand::Cycle { row: other_row, col: other_col })
+            })
+            .unwrap();
+        world.run_system_once(score_system).unwrap();
+        let score = world.resource::<ScoreTracker>();
+        assert_eq!((score.placements, score.mistakes), (1, 0));
+        assert_eq!(score.score(), nine_lives_core::PLACEMENT_POINTS);
+    }
+
+    #[test]
+    fn test_streak_counts_settled_cats_once_per_cell() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let (mut app, (row, col)) = playing_app();
+        app.init_resource::<StreakTracker>().add_event::<StreakReached>();
+        let world = app.world_mut();
+        let (board, solution) = (world.resource::<BoardState>(), world.resource::<Solution>());
+        let typed: Vec<CellCommand> = (0..81)
+            .map(|index| (index / 9, index % 9))
+            .filter(|&cell| cell != (row, col) && board.cells[cell.0][cell.1].is_none())
+            .take(2)
+            .map(|(row, col)| CellCommand::Set { row, col, value: Some(solution.cells[row][col]) })
+            .collect();
+        let right = solution.cells[row][col];
+        for command in typed {
+            world.run_system_once(move |mut input: CellInput| input.apply(command)).unwrap();
+        }
+
+        // Clicking past wrong cats to the right one doesn't break the streak
+        for _ in 0..=right {
+            world
+                .run_system_once(move |mut input: CellInput| input.apply_clicked(CellCommand::Cycle { row, col }))
+                .unwrap();
+        }
+        world.resource_mut::<Time>().advance_by(std::time::Duration::from_secs_f32(SETTLE_SECONDS));
+        world.run_system_once(settle_placements_system).unwrap();
+        world.run_system_once(streak_system).unwrap();
+        assert_eq!(world.resource::<StreakTracker>().current, 3);
+
+        // Erasing the right cat and typing it in again doesn't count it twice
+        world.resource_mut::<Events<GameEvent>>().clear();
+        for command in [CellCommand::Erase { row, col }, CellCommand::Set { row, col, value: Some(right) }] {
+            world.run_system_once(move |mut input: CellInput| input.apply(command)).unwrap();
+        }
+        world.run_system_once(streak_system).unwrap();
+        assert_eq!(world.resource::<StreakTracker>().current, 3);
+    }
+
     #[test]
     fn test_clear_board_logic() {
         // Test the clear board logic
         let mut board = BoardState::new();
+        board.cells[0][0] = Some(4);
+        board.cell_types[0][0] = Some(CellType::Given);
+        assert!(!has_player_entries(&board), "Givens alone are nothing to lose");
         board.cycle_cell(1, 1, 3);
         board.cycle_cell(2, 2, 3);
+        assert!(has_player_entries(&board));
 
-        // Now clear the board
-        board.clear();
+        // Now clear the board: the player's cats go, the given stays
+        board.reset_to_givens();
         assert_eq!(board.cells[1][1], None);
         assert_eq!(board.cells[2][2], None);
+        assert_eq!(board.cells[0][0], Some(4));
+        assert!(!has_player_entries(&board));
     }
 }
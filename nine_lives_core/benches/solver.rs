@@ -0,0 +1,72 @@
+//! Generation and solver benchmarks.
+//!
+//! Every puzzle here comes from `generate_with_seed` with a fixed seed, so
+//! runs measure the same work and a slowdown in the generator or solver
+//! shows up as a regression rather than noise.
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+use nine_lives_core::{
+    BoardState, PresetKind, PuzzleSettings, SolverBackend, count_solutions_with, generate_with_seed,
+    rate_difficulty, solve_unique,
+};
+
+/// Seeds for the puzzles each benchmark works on.
+const SEEDS: [u64; 4] = [1, 7, 42, 2024];
+
+/// A fixed set of puzzles from a preset to solve and rate.
+fn puzzles(preset: PresetKind) -> Vec<BoardState> {
+    let settings = PuzzleSettings::from_preset(preset);
+    SEEDS
+        .iter()
+        .filter_map(|&seed| generate_with_seed(&settings, seed).ok().map(|(board, _)| board))
+        .collect()
+}
+
+fn generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate");
+    group.sample_size(10);
+    for preset in PresetKind::all() {
+        let settings = PuzzleSettings::from_preset(preset);
+        group.bench_function(BenchmarkId::from_parameter(format!("{:?}", preset)), |b| {
+            let mut seeds = SEEDS.iter().cycle();
+            b.iter(|| generate_with_seed(&settings, *seeds.next().unwrap()))
+        });
+    }
+    group.finish();
+}
+
+fn solving(c: &mut Criterion) {
+    let mut group = c.benchmark_group("count_solutions");
+    for preset in PresetKind::all() {
+        let boards = puzzles(preset);
+        for backend in SolverBackend::all() {
+            let id = BenchmarkId::new(format!("{:?}", backend), format!("{:?}", preset));
+            group.bench_function(id, |b| {
+                b.iter(|| boards.iter().map(|board| count_solutions_with(black_box(board), backend, 2)).sum::<usize>())
+            });
+        }
+    }
+    group.finish();
+
+    // The hardest preset, where a unique solve takes the most search
+    let boards = puzzles(*PresetKind::all().last().unwrap());
+    c.bench_function("solve_unique", |b| {
+        b.iter(|| boards.iter().filter_map(|board| solve_unique(black_box(board))).count())
+    });
+}
+
+fn rating(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rate_difficulty");
+    for preset in PresetKind::all() {
+        let boards = puzzles(preset);
+        group.bench_function(BenchmarkId::from_parameter(format!("{:?}", preset)), |b| {
+            b.iter(|| boards.iter().map(|board| rate_difficulty(black_box(board)).score).sum::<u32>())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, generation, solving, rating);
+criterion_main!(benches);
@@ -0,0 +1,80 @@
+//! Text descriptions of the board, for screen readers.
+//!
+//! Describes the board the way it would be read out: a row at a time ("Row
+//! 3: 5, empty, 2, ..."), or a cell at a time ("Cell R4C5 selected,
+//! candidates 2 3 7"). Cats are given by number, counting from 1, whatever
+//! glyphs or art the board is drawn with.
+
+use crate::BoardState;
+
+/// How a cell's cat is read out: its number, or "empty".
+fn describe_value(value: Option<usize>) -> String {
+    value.map_or_else(|| "empty".to_string(), |value| (value + 1).to_string())
+}
+
+/// Read out a row, like "Row 3: 5, empty, 2, ...". Rows count from 1.
+pub fn describe_row(board: &BoardState, row: usize) -> String {
+    let cells: Vec<String> = board.cells[row].iter().map(|&value| describe_value(value)).collect();
+    format!("Row {}: {}", row + 1, cells.join(", "))
+}
+
+/// Read out a column, like "Column 4: empty, 9, ...". Columns count from 1.
+pub fn describe_column(board: &BoardState, col: usize) -> String {
+    let cells: Vec<String> = board.cells.iter().map(|row| describe_value(row[col])).collect();
+    format!("Column {}: {}", col + 1, cells.join(", "))
+}
+
+/// Read out a cell: where it is, whether it's selected, and its cat (and
+/// whether that was given) or, when empty, the cats that could go there.
+/// Like "Cell R4C5 selected, candidates 2 3 7" or "Cell R1C1, 5, given".
+pub fn describe_cell(board: &BoardState, row: usize, col: usize, selected: bool) -> String {
+    let mut description = format!("Cell R{}C{}", row + 1, col + 1);
+    if selected {
+        description.push_str(" selected");
+    }
+    match board.cells[row][col] {
+        Some(value) if board.is_given_cell(row, col) => description.push_str(&format!(", {}, given", value + 1)),
+        Some(value) => description.push_str(&format!(", {}", value + 1)),
+        None => {
+            let candidates = board.candidates_at(row, col);
+            if candidates.is_empty() {
+                description.push_str(", empty, no candidates");
+            } else {
+                let numbers: Vec<String> = candidates.iter().map(|value| (value + 1).to_string()).collect();
+                description.push_str(&format!(", candidates {}", numbers.join(" ")));
+            }
+        }
+    }
+    description
+}
+
+/// Read out the whole board, a row at a time.
+pub fn describe_board(board: &BoardState) -> Vec<String> {
+    (0..board.size()).map(|row| describe_row(board, row)).collect()
+}
+
+/// How far along the board is, like "42 of 81 cells filled".
+pub fn describe_progress(board: &BoardState) -> String {
+    let filled = board.cells.iter().flatten().filter(|value| value.is_some()).count();
+    format!("{} of {} cells filled", filled, board.size() * board.size())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_descriptions() {
+        let mut board = BoardState::new();
+        board.cells[2][0] = Some(4);
+        board.cells[2][2] = Some(1);
+        assert!(describe_row(&board, 2).starts_with("Row 3: 5, empty, 2, empty"));
+        assert!(describe_column(&board, 0).starts_with("Column 1: empty, empty, 5, empty"));
+        assert_eq!(describe_board(&board).len(), 9);
+        assert_eq!(describe_progress(&board), "2 of 81 cells filled");
+
+        // Empty cells list what could go there
+        assert_eq!(describe_cell(&board, 2, 1, true), "Cell R3C2 selected, candidates 1 3 4 6 7 8 9");
+        assert_eq!(describe_cell(&board, 2, 0, false), "Cell R3C1, 5");
+    }
+}
@@ -0,0 +1,232 @@
+//! Campaign mode: chapters of set puzzles, played in order.
+//!
+//! Each chapter plays on one preset, from Cozy Kitten up to Night Prowler,
+//! and each of its puzzles comes from a fixed seed, so every player meets
+//! the same puzzles. Solving a puzzle earns one to three stars, one for each
+//! of the chapter's par marks it meets (time, hints, and mistakes), and
+//! unlocks the next. The best stars for each puzzle are kept in
+//! `PersistentData::campaign`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{PresetKind, PuzzleSettings};
+
+/// The most stars a campaign puzzle can earn.
+pub const MAX_STARS: u8 = 3;
+
+/// The marks a solve is held to for its stars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StarThresholds {
+    /// Solved within this many seconds
+    pub seconds: u64,
+    /// Using at most this many hints
+    pub hints: u32,
+    /// Making at most this many mistakes
+    pub mistakes: u32,
+}
+
+impl StarThresholds {
+    /// Stars for a solve: one for each mark it meets, and never fewer than
+    /// one, since solving the puzzle at all is worth a star.
+    pub fn stars(&self, seconds: u64, hints: u32, mistakes: u32) -> u8 {
+        let met = [seconds <= self.seconds, hints <= self.hints, mistakes <= self.mistakes]
+            .into_iter()
+            .filter(|&met| met)
+            .count() as u8;
+        met.max(1)
+    }
+}
+
+/// A chapter of the campaign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chapter {
+    pub name: &'static str,
+    pub blurb: &'static str,
+    /// The preset every puzzle in the chapter is played on
+    pub preset: PresetKind,
+    /// One seed per puzzle, in order
+    pub seeds: &'static [u64],
+    pub par: StarThresholds,
+}
+
+/// The campaign's chapters, easiest first.
+pub const CHAPTERS: &[Chapter] = &[
+    Chapter {
+        name: "Kitten Steps",
+        blurb: "Plenty of clues to find your paws.",
+        preset: PresetKind::CozyKitten,
+        seeds: &[1101, 1102, 1103, 1104, 1105],
+        par: StarThresholds { seconds: 10 * 60, hints: 2, mistakes: 3 },
+    },
+    Chapter {
+        name: "Curious Paws",
+        blurb: "Fewer clues, more to explore.",
+        preset: PresetKind::CuriousCat,
+        seeds: &[2201, 2202, 2203, 2204, 2205],
+        par: StarThresholds { seconds: 15 * 60, hints: 1, mistakes: 2 },
+    },
+    Chapter {
+        name: "Back Alleys",
+        blurb: "Streetwise puzzles with few hints to lean on.",
+        preset: PresetKind::StreetwiseStray,
+        seeds: &[3301, 3302, 3303, 3304, 3305],
+        par: StarThresholds { seconds: 20 * 60, hints: 1, mistakes: 1 },
+    },
+    Chapter {
+        name: "Midnight Prowl",
+        blurb: "Expert puzzles, no hints. Only the sharpest claws.",
+        preset: PresetKind::NightProwler,
+        seeds: &[4401, 4402, 4403, 4404, 4405],
+        par: StarThresholds { seconds: 30 * 60, hints: 0, mistakes: 0 },
+    },
+];
+
+/// A puzzle in the campaign: its chapter, and its place in the chapter
+/// (both counted from 0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct CampaignLevel {
+    pub chapter: usize,
+    pub puzzle: usize,
+}
+
+impl CampaignLevel {
+    /// Every puzzle in the campaign, in order.
+    pub fn all() -> Vec<CampaignLevel> {
+        CHAPTERS
+            .iter()
+            .enumerate()
+            .flat_map(|(chapter, info)| (0..info.seeds.len()).map(move |puzzle| CampaignLevel { chapter, puzzle }))
+            .collect()
+    }
+
+    /// The chapter this puzzle belongs to.
+    pub fn chapter(&self) -> &'static Chapter {
+        &CHAPTERS[self.chapter]
+    }
+
+    /// Short label counting from 1, like "2-3".
+    pub fn label(&self) -> String {
+        format!("{}-{}", self.chapter + 1, self.puzzle + 1)
+    }
+
+    /// The puzzle before this one, if it isn't the very first.
+    pub fn previous(&self) -> Option<CampaignLevel> {
+        if self.puzzle > 0 {
+            Some(CampaignLevel { puzzle: self.puzzle - 1, ..*self })
+        } else if self.chapter > 0 {
+            let chapter = self.chapter - 1;
+            Some(CampaignLevel { chapter, puzzle: CHAPTERS[chapter].seeds.len() - 1 })
+        } else {
+            None
+        }
+    }
+
+    /// Settings to generate and play this puzzle with.
+    pub fn settings(&self) -> PuzzleSettings {
+        PuzzleSettings {
+            seed: Some(self.chapter().seeds[self.puzzle]),
+            campaign_level: Some(*self),
+            ..PuzzleSettings::from_preset(self.chapter().preset)
+        }
+    }
+}
+
+/// The player's progress through the campaign: the best stars earned on
+/// each puzzle solved, keyed by label (see `CampaignLevel::label`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CampaignProgress {
+    #[serde(default)]
+    pub stars: BTreeMap<String, u8>,
+}
+
+impl CampaignProgress {
+    /// Best stars earned on a puzzle, or 0 if it hasn't been solved.
+    pub fn stars(&self, level: CampaignLevel) -> u8 {
+        self.stars.get(&level.label()).copied().unwrap_or(0)
+    }
+
+    /// Stars earned across the whole campaign.
+    pub fn total_stars(&self) -> u32 {
+        CampaignLevel::all().into_iter().map(|level| self.stars(level) as u32).sum()
+    }
+
+    /// Whether a puzzle can be played: the first always can, and every
+    /// other once the one before it is solved.
+    pub fn is_unlocked(&self, level: CampaignLevel) -> bool {
+        level.previous().is_none_or(|previous| self.stars(previous) > 0)
+    }
+
+    /// Record a solve, keeping the best stars. Returns whether it beat the
+    /// puzzle's previous best.
+    pub fn record(&mut self, level: CampaignLevel, stars: u8) -> bool {
+        let best = self.stars.entry(level.label()).or_insert(0);
+        if stars > *best {
+            *best = stars.min(MAX_STARS);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Stars written out, like "★★☆".
+pub fn star_string(stars: u8) -> String {
+    (0..MAX_STARS).map(|index| if index < stars { '★' } else { '☆' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BoardState;
+
+    #[test]
+    fn test_star_thresholds() {
+        let par = StarThresholds { seconds: 600, hints: 1, mistakes: 2 };
+        assert_eq!(par.stars(600, 1, 2), 3);
+        assert_eq!(par.stars(601, 1, 2), 2);
+        assert_eq!(par.stars(601, 2, 2), 1);
+        assert_eq!(par.stars(9999, 9, 9), 1, "A solve always earns a star");
+        assert_eq!(star_string(2), "★★☆");
+    }
+
+    #[test]
+    fn test_campaign_unlocks_in_order() {
+        let mut progress = CampaignProgress::default();
+        let first = CampaignLevel { chapter: 0, puzzle: 0 };
+        let second = CampaignLevel { chapter: 0, puzzle: 1 };
+        let next_chapter = CampaignLevel { chapter: 1, puzzle: 0 };
+        assert!(progress.is_unlocked(first));
+        assert!(!progress.is_unlocked(second));
+
+        assert!(progress.record(first, 2));
+        assert!(progress.is_unlocked(second));
+        assert!(!progress.record(first, 1), "A worse solve keeps the best stars");
+        assert!(progress.record(first, 3));
+        assert_eq!(progress.total_stars(), 3);
+
+        // A chapter opens once the last puzzle of the one before is solved
+        let last = CampaignLevel { chapter: 0, puzzle: CHAPTERS[0].seeds.len() - 1 };
+        assert_eq!(next_chapter.previous(), Some(last));
+        assert!(!progress.is_unlocked(next_chapter));
+        progress.record(last, 1);
+        assert!(progress.is_unlocked(next_chapter));
+        assert_eq!(last.label(), "1-5");
+    }
+
+    #[test]
+    fn test_campaign_puzzles_are_fixed() {
+        // The same level always makes the same puzzle
+        let level = CampaignLevel { chapter: 1, puzzle: 2 };
+        let settings = level.settings();
+        assert_eq!(settings.campaign_level, Some(level));
+        assert_eq!(settings.difficulty, PuzzleSettings::from_preset(PresetKind::CuriousCat).difficulty);
+        let mut board = BoardState::new();
+        let mut again = BoardState::new();
+        board.generate_puzzle_with_settings(&settings).expect("Campaign puzzle should generate");
+        again.generate_puzzle_with_settings(&settings).expect("Campaign puzzle should generate");
+        assert_eq!(board.cells, again.cells);
+        assert_eq!(CampaignLevel::all().len(), CHAPTERS.iter().map(|chapter| chapter.seeds.len()).sum::<usize>());
+    }
+}
@@ -0,0 +1,54 @@
+//! Cancelling a generation that's no longer wanted.
+//!
+//! Expert and 16x16 puzzles can take a while, and a background task keeps
+//! running its search even after the game has stopped waiting for it. A
+//! `CancellationToken` is shared between the generator and whoever started
+//! it: cancelling it makes every attempt stop at its next clue, and the
+//! generation comes back as `NineLivesError::Cancelled`.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A flag for stopping work early. Clones share the flag, so cancelling any
+/// one of them cancels them all.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// A token that hasn't been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the work holding this token (or a clone of it) to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the work should stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardState, NineLivesError, PresetKind, PuzzleSettings};
+
+    #[test]
+    fn test_cancelled_generation_leaves_the_board_alone() {
+        let token = CancellationToken::new();
+        let shared = token.clone();
+        assert!(!shared.is_cancelled());
+        token.cancel();
+        assert!(shared.is_cancelled(), "Clones share the flag");
+
+        let mut board = BoardState::new();
+        board.set_cell(0, 0, Some(4));
+        let settings = PuzzleSettings::from_preset(PresetKind::NightProwler);
+        assert_eq!(board.generate_puzzle_cancellable(&settings, &shared).err(), Some(NineLivesError::Cancelled));
+        assert_eq!(board.cells[0][0], Some(4));
+        assert!(board.generate_puzzle_cancellable(&settings, &CancellationToken::new()).is_ok());
+    }
+}
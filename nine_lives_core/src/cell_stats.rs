@@ -0,0 +1,117 @@
+//! Where on the board the player tends to go wrong.
+//!
+//! `CellStats` counts, for each cell of each grid size, the mistakes (cats
+//! that didn't match the solution, see `ReplayEventKind::Mistake`) made there
+//! across every solved game. After a win the game screen can lay these over
+//! the board as a heatmap, showing whether the trouble is in the corners or
+//! the center boxes.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GridSize, Replay, ReplayEventKind};
+
+/// Mistakes made in each cell, across games, for each grid size.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellStats {
+    /// Mistake counts by row and column, for each grid size played
+    pub mistakes: HashMap<GridSize, Vec<Vec<u32>>>,
+}
+
+impl CellStats {
+    /// Count the mistakes made in a game's replay.
+    pub fn record_replay(&mut self, grid_size: GridSize, replay: &Replay) {
+        let size = grid_size.dimension();
+        let counts = self.mistakes.entry(grid_size).or_insert_with(|| vec![vec![0; size]; size]);
+        for step in replay.steps.iter().filter(|step| step.kind == ReplayEventKind::Mistake) {
+            if let Some(count) = counts.get_mut(step.game_move.row).and_then(|row| row.get_mut(step.game_move.col)) {
+                *count += 1;
+            }
+        }
+    }
+
+    /// Mistakes made in a cell.
+    pub fn mistakes_at(&self, grid_size: GridSize, row: usize, col: usize) -> u32 {
+        self.mistakes
+            .get(&grid_size)
+            .and_then(|counts| counts.get(row))
+            .and_then(|cells| cells.get(col))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// How hot a cell is, from 0.0 (no mistakes) to 1.0 (as many as the
+    /// worst cell).
+    pub fn heat(&self, grid_size: GridSize, row: usize, col: usize) -> f32 {
+        let most = self
+            .mistakes
+            .get(&grid_size)
+            .and_then(|counts| counts.iter().flatten().max().copied())
+            .unwrap_or(0);
+        if most == 0 {
+            0.0
+        } else {
+            self.mistakes_at(grid_size, row, col) as f32 / most as f32
+        }
+    }
+
+    /// Mistakes made in each box, counted left to right, top to bottom (as
+    /// in `GridSize::box_index`).
+    pub fn box_totals(&self, grid_size: GridSize) -> Vec<u32> {
+        let size = grid_size.dimension();
+        let mut totals = vec![0; size];
+        for row in 0..size {
+            for col in 0..size {
+                totals[grid_size.box_index(row, col)] += self.mistakes_at(grid_size, row, col);
+            }
+        }
+        totals
+    }
+
+    /// The box with the most mistakes, with how many, if any were made.
+    pub fn worst_box(&self, grid_size: GridSize) -> Option<(usize, u32)> {
+        self.box_totals(grid_size)
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, total)| total > 0)
+            .max_by_key(|&(index, total)| (total, std::cmp::Reverse(index)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardState, Solution};
+
+    #[test]
+    fn test_cell_stats() {
+        let board = BoardState::new();
+        let mut solution = Solution::new();
+        solution.cells[0][0] = 3;
+        solution.cells[4][4] = 5;
+
+        // Two mistakes in the corner, one in the center, and a right cat
+        let mut replay = Replay::new(&board);
+        let mut playing = board.clone();
+        for (row, col, value) in [(0, 0, 1), (0, 0, 2), (4, 4, 0), (4, 4, 5)] {
+            playing.cells[row][col] = Some(value);
+            replay.track(&playing, &solution, None);
+        }
+        let mut stats = CellStats::default();
+        stats.record_replay(GridSize::Classic, &replay);
+        assert_eq!(stats.mistakes_at(GridSize::Classic, 0, 0), 2);
+        assert_eq!(stats.mistakes_at(GridSize::Classic, 4, 4), 1);
+        assert_eq!(stats.mistakes_at(GridSize::Mega, 0, 0), 0);
+        assert_eq!(stats.heat(GridSize::Classic, 4, 4), 0.5);
+        assert_eq!(stats.heat(GridSize::Small, 0, 0), 0.0);
+        assert_eq!(stats.worst_box(GridSize::Classic), Some((0, 2)));
+        assert_eq!(stats.box_totals(GridSize::Classic)[4], 1);
+
+        // Counts build up across games, and survive a save
+        stats.record_replay(GridSize::Classic, &replay);
+        let json = serde_json::to_string(&stats).unwrap();
+        let loaded: CellStats = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.mistakes_at(GridSize::Classic, 0, 0), 4);
+    }
+}
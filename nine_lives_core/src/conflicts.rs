@@ -0,0 +1,213 @@
+//! Conflict tracking: which cats clash, kept up to date one cell at a time.
+//!
+//! The tracker counts each cat in each house (see `houses`), plus every
+//! Killer cage's running total. A cat clashes when its count in any of its
+//! houses is above one, or its cage can no longer reach its sum, so checking
+//! a cell is a handful of lookups and a placement only touches the counts
+//! of the houses through it. `sync` brings the tracker up to date with a
+//! board by applying just the cells that changed since it last looked, and
+//! starts over only when the layout itself (size, regions, windows, or
+//! cages) has changed.
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::Resource;
+
+use crate::{BoardState, Cage, RegionMap, SmallVec};
+
+/// Counts of each cat in each house of a board, for finding clashes quickly.
+#[cfg_attr(feature = "bevy", derive(Resource))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictTracker {
+    /// The board's cats as last seen
+    cells: Vec<Vec<Option<usize>>>,
+    /// The layout the counts were built for
+    regions: RegionMap,
+    windows: Vec<Vec<(usize, usize)>>,
+    cages: Vec<Cage>,
+    /// The houses through each cell (rows, columns, boxes, windows, then cages)
+    houses_of: Vec<Vec<SmallVec<[usize; 5]>>>,
+    /// How many of each cat are in each house, indexed `[house][cat]`
+    counts: Vec<Vec<usize>>,
+    /// The cage holding each cell, if any
+    cage_of: Vec<Vec<Option<usize>>>,
+    /// Filled cells and their total (counting cat 1 as 1) in each cage
+    cage_totals: Vec<(usize, usize)>,
+}
+
+impl Default for ConflictTracker {
+    fn default() -> Self {
+        Self::new(&BoardState::default())
+    }
+}
+
+impl ConflictTracker {
+    /// A tracker counting the cats on `board`.
+    pub fn new(board: &BoardState) -> Self {
+        let size = board.size();
+        let mut tracker = Self {
+            cells: vec![vec![None; size]; size],
+            regions: board.regions.clone(),
+            windows: board.windows.clone(),
+            cages: board.cages.clone(),
+            houses_of: vec![vec![SmallVec::new(); size]; size],
+            counts: Vec::new(),
+            cage_of: vec![vec![None; size]; size],
+            cage_totals: vec![(0, 0); board.cages.len()],
+        };
+        let cage_houses = board.cages.iter().map(|cage| cage.cells.clone());
+        for (house, cells) in board.houses().map(|(_, cells)| cells).chain(cage_houses).enumerate() {
+            for (row, col) in cells {
+                tracker.houses_of[row][col].push(house);
+            }
+            tracker.counts.push(vec![0; size]);
+        }
+        for (index, cage) in board.cages.iter().enumerate() {
+            for &(row, col) in &cage.cells {
+                tracker.cage_of[row][col] = Some(index);
+            }
+        }
+        tracker.sync(board);
+        tracker
+    }
+
+    /// Whether the tracker was built for the board's layout.
+    fn fits(&self, board: &BoardState) -> bool {
+        self.cells.len() == board.size()
+            && self.regions == board.regions
+            && self.windows == board.windows
+            && self.cages == board.cages
+    }
+
+    /// Catch up with `board`, applying each cell that changed since the
+    /// tracker last looked (or starting over, if the layout changed).
+    pub fn sync(&mut self, board: &BoardState) {
+        if !self.fits(board) {
+            *self = Self::new(board);
+            return;
+        }
+        for (row, cells) in board.cells.iter().enumerate() {
+            for (col, &value) in cells.iter().enumerate() {
+                if self.cells[row][col] != value {
+                    self.place(row, col, value);
+                }
+            }
+        }
+    }
+
+    /// Put a cat in a cell (or empty it with `None`), updating the counts of
+    /// the houses through it.
+    pub fn place(&mut self, row: usize, col: usize, value: Option<usize>) {
+        let old = std::mem::replace(&mut self.cells[row][col], value);
+        if old == value {
+            return;
+        }
+        if let Some(old) = old {
+            self.count(row, col, old, false);
+        }
+        if let Some(value) = value {
+            self.count(row, col, value, true);
+        }
+    }
+
+    /// Add a cat in a cell to its houses' counts, or take it away.
+    fn count(&mut self, row: usize, col: usize, value: usize, add: bool) {
+        for &house in &self.houses_of[row][col] {
+            if let Some(count) = self.counts[house].get_mut(value) {
+                *count = if add { *count + 1 } else { count.saturating_sub(1) };
+            }
+        }
+        if let Some(cage) = self.cage_of[row][col] {
+            let (filled, total) = &mut self.cage_totals[cage];
+            if add {
+                *filled += 1;
+                *total += value + 1;
+            } else {
+                *filled = filled.saturating_sub(1);
+                *total = total.saturating_sub(value + 1);
+            }
+        }
+    }
+
+    /// Whether a cage's total can still come out right: exactly its sum once
+    /// it's full, and with room for at least a 1 in each empty cell before.
+    fn cage_fits(&self, cage: usize) -> bool {
+        let (filled, total) = self.cage_totals[cage];
+        let empty = self.cages[cage].cells.len().saturating_sub(filled);
+        if empty == 0 { total == self.cages[cage].sum } else { total + empty <= self.cages[cage].sum }
+    }
+
+    /// Whether the cat in (row, col) clashes with its row, column, box,
+    /// window, or cage. Empty cells never do.
+    pub fn conflicts_for(&self, row: usize, col: usize) -> bool {
+        let Some(value) = self.cells.get(row).and_then(|cells| cells.get(col)).copied().flatten() else {
+            return false;
+        };
+        self.houses_of[row][col].iter().any(|&house| self.counts[house].get(value).is_some_and(|&count| count > 1))
+            || self.cage_of[row][col].is_some_and(|cage| !self.cage_fits(cage))
+    }
+
+    /// Every cell whose cat clashes, in reading order.
+    pub fn conflicts(&self) -> Vec<(usize, usize)> {
+        let size = self.cells.len();
+        (0..size)
+            .flat_map(|row| (0..size).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.conflicts_for(row, col))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GridSize, PresetKind, PuzzleSettings, Variant};
+
+    #[test]
+    fn test_conflict_tracker_follows_the_board() {
+        let mut board = BoardState::new();
+        let mut tracker = ConflictTracker::new(&board);
+        assert!(tracker.conflicts().is_empty());
+
+        board.set_cell(0, 0, Some(3));
+        board.set_cell(0, 7, Some(3));
+        board.set_cell(2, 2, Some(5));
+        tracker.sync(&board);
+        assert!(tracker.conflicts_for(0, 0) && tracker.conflicts_for(0, 7));
+        assert!(!tracker.conflicts_for(2, 2) && !tracker.conflicts_for(4, 4));
+        assert_eq!(tracker.conflicts(), board.get_conflicts());
+
+        // Clearing one of the pair clears both
+        board.clear_cell(0, 7);
+        tracker.sync(&board);
+        assert!(tracker.conflicts().is_empty());
+
+        // A new layout starts the counts over
+        let mut mini = BoardState::with_size(GridSize::Mini);
+        mini.cells[0][0] = Some(1);
+        mini.cells[1][1] = Some(1);
+        tracker.sync(&mini);
+        assert_eq!(tracker.conflicts(), vec![(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn test_conflict_tracker_matches_a_full_scan() {
+        for variant in [Variant::Classic, Variant::Killer, Variant::Hyper, Variant::Jigsaw] {
+            let settings = PuzzleSettings::from_preset(PresetKind::CozyKitten).with_variant(variant);
+            let mut board = BoardState::new();
+            let solution = board.generate_puzzle_with_settings(&settings).expect("Puzzle should generate");
+            let mut tracker = ConflictTracker::new(&board);
+
+            // Fill the empty cells with a mix of right and wrong cats
+            let empty: Vec<_> = board.empty_cells().collect();
+            for (i, (row, col)) in empty.into_iter().enumerate() {
+                let value = if i % 3 == 0 { (solution.cells[row][col] + 1) % 9 } else { solution.cells[row][col] };
+                board.set_cell(row, col, Some(value));
+                tracker.sync(&board);
+                let scanned: Vec<_> = (0..9)
+                    .flat_map(|r| (0..9).map(move |c| (r, c)))
+                    .filter(|&(r, c)| board.cells[r][c].is_some_and(|v| !board.is_valid_placement(r, c, v)))
+                    .collect();
+                assert_eq!(tracker.conflicts(), scanned, "{:?} after ({}, {})", variant, row, col);
+            }
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! Dancing Links (Algorithm X) solver backend.
+//!
+//! Sudoku is an exact cover problem: every choice "cat v in cell (r, c)"
+//! covers four constraints (the cell is filled, and row r, column c, and the
+//! cell's box each hold v), and a solution picks choices covering every
+//! constraint exactly once. Knuth's dancing links keep the matrix of choices
+//! and constraints as circular doubly linked lists, so covering and
+//! uncovering a constraint during the search is a handful of pointer swaps.
+//!
+//! Variants slot in as extra columns: jigsaw regions replace boxes, Hyper
+//! windows add one constraint per window and cat, and Killer cages add
+//! optional ("secondary") columns that forbid repeats without demanding
+//! every cat appear. Cage sums become exact cover too, by making each cage
+//! pick one of the sets of cats that add up to its sum.
+
+use crate::search::{SearchGrid, legal_mask};
+use crate::{BoardState, count_solutions_with};
+
+/// Which engine answers uniqueness and solution-count questions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SolverBackend {
+    /// Bitmask backtracking with most-constrained-first branching.
+    #[default]
+    Backtracking,
+    /// Knuth's Dancing Links exact cover search; fastest on low-clue puzzles.
+    DancingLinks,
+}
+
+impl SolverBackend {
+    /// Get all solver backends.
+    pub fn all() -> [SolverBackend; 2] {
+        [SolverBackend::Backtracking, SolverBackend::DancingLinks]
+    }
+
+    /// Get the display name for this backend.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SolverBackend::Backtracking => "Backtracking",
+            SolverBackend::DancingLinks => "Dancing Links",
+        }
+    }
+}
+
+/// Validates that a puzzle has exactly one solution, using the chosen backend.
+pub fn validate_unique_solution_with(board: &BoardState, backend: SolverBackend) -> bool {
+    count_solutions_with(board, backend, 2) == 1
+}
+
+/// Count the board's solutions with Dancing Links, stopping once `limit` are found.
+/// Boards whose filled cells already clash have no solutions.
+pub(crate) fn dlx_count_solutions(board: &BoardState, limit: usize) -> usize {
+    if limit == 0 || SearchGrid::from_board(board).is_none() {
+        return 0;
+    }
+    let mut links = DancingLinks::from_board(board);
+    let mut count = 0;
+    links.search(limit, &mut count);
+    count
+}
+
+/// Every set of distinct cats (as a bitmask) that fills a cage of `cells`
+/// cells and adds up to `sum`, counting cat numbers from 1.
+fn cage_combinations(size: usize, cells: usize, sum: usize) -> Vec<u32> {
+    fn extend(size: usize, next: usize, cells: usize, sum: usize, mask: u32, found: &mut Vec<u32>) {
+        if cells == 0 {
+            if sum == 0 {
+                found.push(mask);
+            }
+            return;
+        }
+        for value in next..size {
+            if value + 1 > sum {
+                break;
+            }
+            extend(size, value + 1, cells - 1, sum - (value + 1), mask | (1 << value), found);
+        }
+    }
+
+    let mut found = Vec::new();
+    extend(size, 0, cells, sum, 0, &mut found);
+    found
+}
+
+/// The exact cover matrix for a board. Node 0 is the root, nodes
+/// `1..=columns` are column headers, and the rest are the matrix's ones.
+struct DancingLinks {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The column header each node belongs to.
+    column: Vec<usize>,
+    /// Ones left in each column, indexed by header node.
+    column_size: Vec<usize>,
+}
+
+impl DancingLinks {
+    /// Build the matrix for the board's empty cells. Constraints the givens
+    /// already satisfy are left out, and only cats that are legal in a cell
+    /// get a row, so the matrix starts as small as it can be.
+    ///
+    /// Each Killer cage also gets one row per set of cats that could fill
+    /// it: the row covers the cage's "combination" column and shuts out every
+    /// cat not in the set, so the cage's cells can only be filled with
+    /// exactly that set (and so add up to the sum).
+    fn from_board(board: &BoardState) -> Self {
+        let size = board.size();
+        let cells = size * size;
+
+        // Constraint keys: cell, row/cat, column/cat, box/cat, window/cat, cage/cat, cage combination
+        let row_key = cells;
+        let col_key = row_key + cells;
+        let box_key = col_key + cells;
+        let window_key = box_key + cells;
+        let cage_key = window_key + board.windows.len() * size;
+        let combination_key = cage_key + board.cages.len() * size;
+        let key_count = combination_key + board.cages.len();
+
+        let mut satisfied = vec![false; key_count];
+        let window_of: Vec<Option<usize>> =
+            (0..cells).map(|index| board.window_index(index / size, index % size)).collect();
+        let cage_of: Vec<Option<usize>> =
+            (0..cells).map(|index| board.cage_index(index / size, index % size)).collect();
+        for index in 0..cells {
+            let (row, col) = (index / size, index % size);
+            if let Some(value) = board.cells[row][col] {
+                satisfied[index] = true;
+                satisfied[row_key + row * size + value] = true;
+                satisfied[col_key + col * size + value] = true;
+                satisfied[box_key + board.regions.region_of(row, col) * size + value] = true;
+                if let Some(window) = window_of[index] {
+                    satisfied[window_key + window * size + value] = true;
+                }
+                if let Some(cage) = cage_of[index] {
+                    satisfied[cage_key + cage * size + value] = true;
+                }
+            }
+        }
+        for (cage_index, cage) in board.cages.iter().enumerate() {
+            satisfied[combination_key + cage_index] = cage.cells.iter().all(|&(r, c)| board.cells[r][c].is_some());
+        }
+
+        // A window smaller than the board can't hold every cat, so like the
+        // cage/cat columns it only forbids repeats
+        let secondary = |key: usize| {
+            (cage_key..combination_key).contains(&key)
+                || (key >= window_key && key < cage_key && board.windows[(key - window_key) / size].len() != size)
+        };
+
+        let mut links = Self {
+            left: vec![0],
+            right: vec![0],
+            up: vec![0],
+            down: vec![0],
+            column: vec![0],
+            column_size: vec![0],
+        };
+
+        let mut header_of = vec![None; key_count];
+        for key in (0..key_count).filter(|&key| !satisfied[key]) {
+            let header = links.push_node(0);
+            links.column[header] = header;
+            links.column_size.push(0);
+            if !secondary(key) {
+                let last = links.left[0];
+                links.right[last] = header;
+                links.left[header] = last;
+                links.right[header] = 0;
+                links.left[0] = header;
+            }
+            header_of[key] = Some(header);
+        }
+
+        for index in (0..cells).filter(|&index| board.cells[index / size][index % size].is_none()) {
+            let (row, col) = (index / size, index % size);
+            let mask = legal_mask(board, row, col);
+            for value in (0..size).filter(|value| mask & (1 << value) != 0) {
+                let keys = [
+                    Some(index),
+                    Some(row_key + row * size + value),
+                    Some(col_key + col * size + value),
+                    Some(box_key + board.regions.region_of(row, col) * size + value),
+                    window_of[index].map(|window| window_key + window * size + value),
+                    cage_of[index].map(|cage| cage_key + cage * size + value),
+                ];
+                links.push_row(keys.into_iter().flatten().filter_map(|key| header_of[key]));
+            }
+        }
+
+        for (cage_index, cage) in board.cages.iter().enumerate() {
+            let Some(combination_header) = header_of[combination_key + cage_index] else {
+                continue;
+            };
+            let given: u32 = cage.cells.iter().filter_map(|&(r, c)| board.cells[r][c]).map(|value| 1 << value).sum();
+            for combination in cage_combinations(size, cage.cells.len(), cage.sum) {
+                if combination & given != given {
+                    continue;
+                }
+                let shut_out = (0..size)
+                    .filter(|value| combination & (1 << value) == 0)
+                    .filter_map(|value| header_of[cage_key + cage_index * size + value]);
+                links.push_row(std::iter::once(combination_header).chain(shut_out));
+            }
+        }
+        links
+    }
+
+    /// Add a node linked only to itself under `column` and return its index.
+    fn push_node(&mut self, column: usize) -> usize {
+        let node = self.left.len();
+        self.left.push(node);
+        self.right.push(node);
+        self.up.push(node);
+        self.down.push(node);
+        self.column.push(column);
+        node
+    }
+
+    /// Add a row with a one in each of the given columns.
+    fn push_row(&mut self, headers: impl Iterator<Item = usize>) {
+        let mut first = None;
+        for header in headers {
+            let node = self.push_node(header);
+            let above = self.up[header];
+            self.down[above] = node;
+            self.up[node] = above;
+            self.down[node] = header;
+            self.up[header] = node;
+            self.column_size[header] += 1;
+
+            if let Some(first) = first {
+                let last = self.left[first];
+                self.right[last] = node;
+                self.left[node] = last;
+                self.right[node] = first;
+                self.left[first] = node;
+            } else {
+                first = Some(node);
+            }
+        }
+    }
+
+    /// Remove a column, and every row with a one in it, from the matrix.
+    fn cover(&mut self, header: usize) {
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = right;
+        self.left[right] = left;
+
+        let mut row = self.down[header];
+        while row != header {
+            let mut node = self.right[row];
+            while node != row {
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = down;
+                self.up[down] = up;
+                self.column_size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    /// Put back a column removed by `cover`, in exactly the reverse order.
+    fn uncover(&mut self, header: usize) {
+        let mut row = self.up[header];
+        while row != header {
+            let mut node = self.left[row];
+            while node != row {
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = node;
+                self.up[down] = node;
+                self.column_size[self.column[node]] += 1;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = header;
+        self.left[right] = header;
+    }
+
+    /// Algorithm X: cover the column with the fewest ones and try each of
+    /// its rows in turn, counting solutions until `limit` are found.
+    fn search(&mut self, limit: usize, count: &mut usize) {
+        if self.right[0] == 0 {
+            *count += 1;
+            return;
+        }
+
+        let mut header = self.right[0];
+        let mut best = header;
+        while header != 0 {
+            if self.column_size[header] < self.column_size[best] {
+                best = header;
+            }
+            header = self.right[header];
+        }
+        if self.column_size[best] == 0 {
+            return;
+        }
+
+        self.cover(best);
+        let mut row = self.down[best];
+        while row != best && *count < limit {
+            let mut node = self.right[row];
+            while node != row {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+
+            self.search(limit, count);
+
+            let mut node = self.left[row];
+            while node != row {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+            row = self.down[row];
+        }
+        self.uncover(best);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PresetKind, PuzzleSettings, Variant};
+
+    #[test]
+    fn test_dancing_links_agrees_with_backtracking() {
+        for variant in Variant::all() {
+            let settings = PuzzleSettings::from_preset(PresetKind::NightProwler).with_variant(variant);
+            let mut board = BoardState::new();
+            let solution = board.generate_puzzle_with_settings(&settings).expect("Puzzle should generate");
+            assert!(validate_unique_solution_with(&board, SolverBackend::DancingLinks), "{:?}", variant);
+
+            // Taking away every given leaves many solutions (except on Killer
+            // boards, whose cages alone take far too long to count)
+            if variant != Variant::Killer {
+                let mut open = board.clone();
+                open.cells = vec![vec![None; 9]; 9];
+                assert_eq!(dlx_count_solutions(&open, 5), 5, "{:?}", variant);
+            }
+
+            // A wrong cat in a cell of a unique puzzle leaves no solutions
+            let (row, col) = (0..81)
+                .map(|index| (index / 9, index % 9))
+                .find(|&(row, col)| board.cells[row][col].is_none())
+                .unwrap();
+            board.cells[row][col] = Some((solution.cells[row][col] + 1) % 9);
+            let expected = SearchGrid::from_board(&board).map_or(0, |mut grid| grid.count_solutions(2));
+            assert_eq!(dlx_count_solutions(&board, 2), expected, "{:?}", variant);
+        }
+
+        // Clashing givens have no solutions at all
+        let mut board = BoardState::new();
+        board.cells[0][0] = Some(3);
+        board.cells[0][5] = Some(3);
+        assert_eq!(dlx_count_solutions(&board, 2), 0);
+        assert_eq!(dlx_count_solutions(&BoardState::new(), 3), 3);
+    }
+}
@@ -0,0 +1,63 @@
+//! Errors the core hands back instead of giving up quietly.
+//!
+//! Generation, solving, saving, and loading each used to fail with `None` or a
+//! message on the console. They return a `NineLivesError` instead, so
+//! callers can tell a cancelled generation from a failed one and show the
+//! player what went wrong.
+
+use std::fmt;
+
+/// Something the core couldn't do, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NineLivesError {
+    /// Every generation attempt fell short of the settings
+    GenerationFailed { attempts: usize, reason: String },
+    /// Generation was cancelled (see `CancellationToken`) before a puzzle came out
+    Cancelled,
+    /// A puzzle that can't be played: unreadable, clashing, or without
+    /// exactly one solution
+    InvalidPuzzle { reason: String },
+    /// Something couldn't be written to storage
+    SaveError { reason: String },
+    /// Something stored couldn't be read back: unreadable or damaged
+    LoadError { reason: String },
+}
+
+impl NineLivesError {
+    /// An invalid puzzle, for `reason`.
+    pub fn invalid_puzzle(reason: impl Into<String>) -> Self {
+        NineLivesError::InvalidPuzzle { reason: reason.into() }
+    }
+
+    /// A failed save, for `reason`.
+    pub fn save_error(reason: impl fmt::Display) -> Self {
+        NineLivesError::SaveError { reason: reason.to_string() }
+    }
+
+    /// A failed load, for `reason`.
+    pub fn load_error(reason: impl fmt::Display) -> Self {
+        NineLivesError::LoadError { reason: reason.to_string() }
+    }
+}
+
+impl fmt::Display for NineLivesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NineLivesError::GenerationFailed { attempts, reason } => {
+                write!(f, "couldn't generate a puzzle in {} attempts ({})", attempts, reason)
+            }
+            NineLivesError::Cancelled => write!(f, "generation was cancelled"),
+            NineLivesError::InvalidPuzzle { reason } => write!(f, "that puzzle {}", reason),
+            NineLivesError::SaveError { reason } => write!(f, "couldn't save ({})", reason),
+            NineLivesError::LoadError { reason } => write!(f, "couldn't load ({})", reason),
+        }
+    }
+}
+
+impl std::error::Error for NineLivesError {}
+
+impl From<std::io::Error> for NineLivesError {
+    fn from(error: std::io::Error) -> Self {
+        NineLivesError::SaveError { reason: error.to_string() }
+    }
+}
@@ -0,0 +1,210 @@
+//! Game events and the debug log that collects them.
+//!
+//! Controller systems send a `GameEvent` whenever something worth knowing
+//! about happens (a move, a hint, a state change, a save). The most recent
+//! ones are kept in a `GameEventLog` with timestamps, so the debug panel can
+//! show the event stream live.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::{Event, Resource};
+
+use crate::GameState;
+
+/// How many events the log keeps before dropping the oldest.
+pub const EVENT_LOG_CAPACITY: usize = 200;
+
+/// The kinds of event the debug log can be filtered by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventCategory {
+    Move,
+    Hint,
+    State,
+    Save,
+}
+
+impl EventCategory {
+    /// Get all event categories.
+    pub fn all() -> [EventCategory; 4] {
+        [EventCategory::Move, EventCategory::Hint, EventCategory::State, EventCategory::Save]
+    }
+
+    /// Get the display name for this category.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            EventCategory::Move => "Moves",
+            EventCategory::Hint => "Hints",
+            EventCategory::State => "State",
+            EventCategory::Save => "Saves",
+        }
+    }
+}
+
+/// Something that happened during play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy", derive(Event))]
+pub enum GameEvent {
+    /// A cell changed, from a click, the number pad, or a double-click fill.
+    Move {
+        row: usize,
+        col: usize,
+        old_value: Option<usize>,
+        new_value: Option<usize>,
+    },
+    /// A cat the player put in a cell settled there: typed in, or clicked in
+    /// and left. Lives, score, and streaks count these rather than every
+    /// `Move`, so cycling past the wrong cats on the way to the right one
+    /// costs nothing.
+    Placed { row: usize, col: usize, value: usize },
+    /// A move was undone.
+    Undo { row: usize, col: usize },
+    /// An undone move was made again.
+    Redo { row: usize, col: usize },
+    /// Every player entry was cleared.
+    BoardCleared,
+    /// The puzzle was restarted from its givens.
+    Restarted,
+    /// A hint pointed at a cell (`revealed` false) or placed its cat.
+    Hint {
+        row: usize,
+        col: usize,
+        value: usize,
+        revealed: bool,
+    },
+    /// The player checked their cats against the solution, and `wrong` of
+    /// them didn't match.
+    Checked { wrong: usize },
+    /// The game moved between playing, won, and so on.
+    StateChanged(GameState),
+    /// Persistent data was written to disk.
+    Saved,
+    /// Persistent data failed to save.
+    SaveFailed(String),
+}
+
+impl GameEvent {
+    /// Which category the event is filed under.
+    pub fn category(&self) -> EventCategory {
+        match self {
+            GameEvent::Move { .. }
+            | GameEvent::Placed { .. }
+            | GameEvent::Undo { .. }
+            | GameEvent::Redo { .. }
+            | GameEvent::BoardCleared
+            | GameEvent::Restarted => EventCategory::Move,
+            GameEvent::Hint { .. } | GameEvent::Checked { .. } => EventCategory::Hint,
+            GameEvent::StateChanged(_) => EventCategory::State,
+            GameEvent::Saved | GameEvent::SaveFailed(_) => EventCategory::Save,
+        }
+    }
+
+    /// A one-line description for the log (cells and cats counted from 1).
+    pub fn describe(&self) -> String {
+        let cat = |value: Option<usize>| value.map_or("empty".to_string(), |value| format!("cat {}", value + 1));
+        match self {
+            GameEvent::Move { row, col, old_value, new_value } => {
+                format!("R{}C{}: {} → {}", row + 1, col + 1, cat(*old_value), cat(*new_value))
+            }
+            GameEvent::Placed { row, col, value } => format!("R{}C{}: cat {} settled", row + 1, col + 1, value + 1),
+            GameEvent::Undo { row, col } => format!("Undo at R{}C{}", row + 1, col + 1),
+            GameEvent::Redo { row, col } => format!("Redo at R{}C{}", row + 1, col + 1),
+            GameEvent::BoardCleared => "Board cleared".to_string(),
+            GameEvent::Restarted => "Puzzle restarted".to_string(),
+            GameEvent::Hint { row, col, value, revealed } => format!(
+                "{} cat {} at R{}C{}",
+                if *revealed { "Hint placed" } else { "Hint nudged towards" },
+                value + 1,
+                row + 1,
+                col + 1
+            ),
+            GameEvent::Checked { wrong } => format!("Checked the board: {} wrong", wrong),
+            GameEvent::StateChanged(state) => format!("State → {:?}", state),
+            GameEvent::Saved => "Saved persistent data".to_string(),
+            GameEvent::SaveFailed(error) => format!("Save failed: {}", error),
+        }
+    }
+}
+
+/// A logged event and when it happened, as time since the app started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedEvent {
+    pub at: Duration,
+    pub event: GameEvent,
+}
+
+impl LoggedEvent {
+    /// The log line, like "02:13.045 R3C4: empty → cat 5".
+    pub fn line(&self) -> String {
+        let millis = self.at.as_millis();
+        format!(
+            "{:02}:{:02}.{:03} {}",
+            millis / 60_000,
+            millis / 1000 % 60,
+            millis % 1000,
+            self.event.describe()
+        )
+    }
+}
+
+/// The most recent game events, oldest first.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct GameEventLog {
+    entries: VecDeque<LoggedEvent>,
+}
+
+impl GameEventLog {
+    /// Add an event, dropping the oldest once the log is full.
+    pub fn record(&mut self, at: Duration, event: GameEvent) {
+        if self.entries.len() == EVENT_LOG_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LoggedEvent { at, event });
+    }
+
+    /// Number of events in the log.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the log is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The logged events in the given categories, oldest first.
+    pub fn filtered<'a>(&'a self, categories: &'a [EventCategory]) -> impl Iterator<Item = &'a LoggedEvent> + 'a {
+        self.entries.iter().filter(|entry| categories.contains(&entry.event.category()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_log() {
+        let mut log = GameEventLog::default();
+        let placed = GameEvent::Move { row: 2, col: 3, old_value: None, new_value: Some(4) };
+        log.record(Duration::from_millis(133_045), placed.clone());
+        log.record(Duration::from_secs(140), GameEvent::StateChanged(GameState::Won));
+        log.record(Duration::from_secs(141), GameEvent::Saved);
+
+        let moves: Vec<&LoggedEvent> = log.filtered(&[EventCategory::Move]).collect();
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].line(), "02:13.045 R3C4: empty → cat 5");
+        let settled = GameEvent::Placed { row: 2, col: 3, value: 4 };
+        assert_eq!((settled.category(), settled.describe()), (EventCategory::Move, "R3C4: cat 5 settled".to_string()));
+        assert_eq!(log.filtered(&[EventCategory::State, EventCategory::Save]).count(), 2);
+        assert_eq!(log.filtered(&[]).count(), 0);
+
+        // Only the newest events are kept
+        for _ in 0..EVENT_LOG_CAPACITY {
+            log.record(Duration::from_secs(200), placed.clone());
+        }
+        assert_eq!(log.len(), EVENT_LOG_CAPACITY);
+        assert_eq!(log.filtered(&[EventCategory::Save]).count(), 0);
+    }
+}
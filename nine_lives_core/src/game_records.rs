@@ -0,0 +1,85 @@
+//! A log of the games the player has finished.
+//!
+//! `GameStatistics` mostly keeps running totals, which can't say whether the
+//! player is getting faster. Each solved puzzle also leaves a
+//! `CompletedGameRecord` in `GameStatistics::game_records`, the most recent
+//! `GAME_RECORD_LIMIT` of them, for the statistics screen's recent games and
+//! trends and for exporting (see `GameStatistics::export`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GameStatistics, PresetKind};
+
+/// Most games kept in the log; older ones are dropped as new ones come in
+pub const GAME_RECORD_LIMIT: usize = 200;
+
+/// Number of games the statistics screen averages solve times over
+pub const TREND_WINDOW: usize = 20;
+
+/// One finished game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletedGameRecord {
+    /// When the puzzle was finished, as a Unix timestamp
+    pub date: u64,
+    pub preset: PresetKind,
+    /// How long the solve took, in seconds
+    pub duration: u64,
+    pub hints_used: u32,
+    pub mistakes: u32,
+    /// The game's score (see `ScoreTracker`)
+    pub score: u32,
+}
+
+impl GameStatistics {
+    /// Add a finished game to the log, dropping the oldest past `GAME_RECORD_LIMIT`.
+    pub fn record_game(&mut self, record: CompletedGameRecord) {
+        self.game_records.push_back(record);
+        while self.game_records.len() > GAME_RECORD_LIMIT {
+            self.game_records.pop_front();
+        }
+    }
+
+    /// The most recent `count` games, newest first.
+    pub fn recent_games(&self, count: usize) -> impl Iterator<Item = &CompletedGameRecord> {
+        self.game_records.iter().rev().take(count)
+    }
+
+    /// Average solve time, in seconds, of the `count` games before the
+    /// latest `skip`. None until there have been that many games.
+    pub fn average_duration(&self, skip: usize, count: usize) -> Option<u64> {
+        if count == 0 || self.game_records.len() < skip + count {
+            return None;
+        }
+        let total: u64 = self.recent_games(skip + count).skip(skip).map(|record| record.duration).sum();
+        Some(total / count as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_records() {
+        let mut statistics = GameStatistics::default();
+        let game = |duration| CompletedGameRecord {
+            date: 1_760_000_000 + duration,
+            preset: PresetKind::CuriousCat,
+            duration,
+            hints_used: 0,
+            mistakes: 0,
+            score: 100,
+        };
+        for duration in 1..=(GAME_RECORD_LIMIT as u64 + 10) {
+            statistics.record_game(game(duration));
+        }
+        assert_eq!(statistics.game_records.len(), GAME_RECORD_LIMIT);
+        assert_eq!(statistics.game_records[0].duration, 11, "The oldest games are dropped");
+        assert_eq!(statistics.recent_games(2).map(|record| record.duration).collect::<Vec<_>>(), vec![210, 209]);
+
+        // The last two average 209.5, the two before 207.5
+        assert_eq!(statistics.average_duration(0, 2), Some(209));
+        assert_eq!(statistics.average_duration(2, 2), Some(207));
+        assert_eq!(statistics.average_duration(GAME_RECORD_LIMIT, 1), None, "Not that many games yet");
+    }
+}
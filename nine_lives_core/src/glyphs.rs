@@ -0,0 +1,120 @@
+//! Glyph sets for the numbers printed on each cat.
+//!
+//! Cats are stored as 0-based indices; a `GlyphSet` decides how the number
+//! on a cat is written, so players can read the board in a numeral system
+//! they're comfortable with. Coordinates like "R3C7" always stay in Arabic
+//! digits.
+
+use serde::{Deserialize, Serialize};
+
+/// How the number on each cat is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum GlyphSet {
+    /// 1 2 3 ...
+    #[default]
+    Arabic,
+    /// ١ ٢ ٣ ... (Eastern Arabic numerals)
+    EasternArabic,
+    /// १ २ ३ ... (Devanagari numerals)
+    Devanagari,
+    /// 一 二 三 ... (Chinese/Japanese numerals)
+    Hanzi,
+    /// イ ロ ハ ... (katakana in iroha order)
+    Kana,
+}
+
+impl GlyphSet {
+    /// Get all glyph sets in display order.
+    pub fn all() -> [GlyphSet; 5] {
+        [
+            GlyphSet::Arabic,
+            GlyphSet::EasternArabic,
+            GlyphSet::Devanagari,
+            GlyphSet::Hanzi,
+            GlyphSet::Kana,
+        ]
+    }
+
+    /// Cycle to the next glyph set (wraps around).
+    pub fn next(&self) -> Self {
+        let all = Self::all();
+        let index = all.iter().position(|set| set == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+
+    /// Get the display name for this glyph set.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            GlyphSet::Arabic => "123",
+            GlyphSet::EasternArabic => "١٢٣",
+            GlyphSet::Devanagari => "१२३",
+            GlyphSet::Hanzi => "一二三",
+            GlyphSet::Kana => "イロハ",
+        }
+    }
+
+    /// The glyph for a 0-based cat value (so `glyph(0)` is cat 1).
+    /// Numbers past a set's table (cats 10-16 on Mega Clowder boards) are
+    /// composed from its digits where the numeral system allows it, and
+    /// otherwise fall back to Arabic digits.
+    pub fn glyph(&self, value: usize) -> String {
+        let number = value + 1;
+        match self {
+            GlyphSet::Arabic => number.to_string(),
+            GlyphSet::EasternArabic => Self::map_digits(number, ["٠", "١", "٢", "٣", "٤", "٥", "٦", "٧", "٨", "٩"]),
+            GlyphSet::Devanagari => Self::map_digits(number, ["०", "१", "२", "३", "४", "५", "६", "७", "८", "९"]),
+            GlyphSet::Hanzi => {
+                const DIGITS: [&str; 10] = ["", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+                match number {
+                    1..=9 => DIGITS[number].to_string(),
+                    10..=19 => format!("十{}", DIGITS[number - 10]),
+                    _ => number.to_string(),
+                }
+            }
+            GlyphSet::Kana => {
+                const IROHA: [&str; 16] = [
+                    "イ", "ロ", "ハ", "ニ", "ホ", "ヘ", "ト", "チ", "リ", "ヌ", "ル", "ヲ", "ワ", "カ", "ヨ", "タ",
+                ];
+                IROHA.get(value).map(|glyph| glyph.to_string()).unwrap_or_else(|| number.to_string())
+            }
+        }
+    }
+
+    /// Write a number digit by digit using another script's digits.
+    fn map_digits(number: usize, digits: [&str; 10]) -> String {
+        number
+            .to_string()
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|d| digits[d as usize])
+            .collect()
+    }
+
+    /// Swap the Arabic number drawn inside a piece of cat art for this set's glyph.
+    pub fn localize_art(&self, art: &str, value: usize) -> String {
+        if *self == GlyphSet::Arabic {
+            return art.to_string();
+        }
+        art.replacen(&(value + 1).to_string(), &self.glyph(value), 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glyph_sets() {
+        assert_eq!(GlyphSet::Arabic.glyph(0), "1");
+        assert_eq!(GlyphSet::Hanzi.glyph(8), "九");
+        assert_eq!(GlyphSet::Hanzi.glyph(11), "十二");
+        assert_eq!(GlyphSet::EasternArabic.glyph(15), "١٦");
+        assert_eq!(GlyphSet::Kana.glyph(15), "タ");
+        assert_eq!(GlyphSet::Kana.glyph(20), "21", "Out-of-range values fall back to digits");
+        assert_eq!(GlyphSet::Kana.next(), GlyphSet::Arabic);
+
+        let art = "( o.o )\n(  5  )";
+        assert_eq!(GlyphSet::Devanagari.localize_art(art, 4), "( o.o )\n(  ५  )");
+        assert_eq!(GlyphSet::Arabic.localize_art(art, 4), art);
+    }
+}
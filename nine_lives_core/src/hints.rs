@@ -0,0 +1,617 @@
+//! Hint explanations and the glossary they link into.
+//!
+//! A hint places the correct cat from the stored solution; this module works
+//! out *why* that cat belongs there so the UI can explain it at the player's
+//! preferred level of detail.
+//!
+//! Hints come in two tiers: the first press only points at the row, column,
+//! or box holding the next logical deduction, and a second press reveals it.
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::Resource;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::{BoardState, GlyphSet, Solution};
+
+/// Time the hint button may spend looking for a deduction before settling for
+/// revealing a cat, in milliseconds. Keeps the UI from stalling on Expert boards.
+pub const HINT_BUDGET_MS: u64 = 30;
+
+/// How much explanation accompanies a hint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum HintVerbosity {
+    /// Just the placement: "Cat 5 goes in R3C7."
+    Terse,
+    /// The placement plus the deduction that justifies it.
+    #[default]
+    Normal,
+    /// The deduction plus glossary entries for every term used.
+    Teaching,
+}
+
+impl HintVerbosity {
+    /// Cycle to the next verbosity level (wraps around).
+    pub fn next(&self) -> Self {
+        match self {
+            HintVerbosity::Terse => HintVerbosity::Normal,
+            HintVerbosity::Normal => HintVerbosity::Teaching,
+            HintVerbosity::Teaching => HintVerbosity::Terse,
+        }
+    }
+
+    /// Get the display name for this level.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            HintVerbosity::Terse => "Terse",
+            HintVerbosity::Normal => "Normal",
+            HintVerbosity::Teaching => "Teaching",
+        }
+    }
+}
+
+/// Which empty cell a hint goes for, set per puzzle in `PuzzleSettings`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum HintStrategy {
+    /// Any empty cell, picked at random.
+    Random,
+    /// The most constrained cell: fewest candidates first, so naked singles
+    /// come before anything that takes more looking.
+    #[default]
+    Easiest,
+    /// The least constrained cell: most candidates first, and hidden singles
+    /// before naked ones.
+    Hardest,
+}
+
+impl HintStrategy {
+    /// Get all strategies in display order.
+    pub fn all() -> [HintStrategy; 3] {
+        [HintStrategy::Random, HintStrategy::Easiest, HintStrategy::Hardest]
+    }
+
+    /// Cycle to the next strategy (wraps around).
+    pub fn next(&self) -> Self {
+        let all = Self::all();
+        let index = all.iter().position(|strategy| strategy == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+
+    /// Get the display name for this strategy.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            HintStrategy::Random => "Random",
+            HintStrategy::Easiest => "Easiest first",
+            HintStrategy::Hardest => "Hardest first",
+        }
+    }
+
+    /// Put empty cells in the order hints should try them. Cells with as
+    /// many candidates as each other stay in random order.
+    pub fn order_cells(&self, board: &BoardState, cells: &mut [(usize, usize)]) {
+        cells.shuffle(&mut thread_rng());
+        match self {
+            HintStrategy::Random => {}
+            HintStrategy::Easiest => cells.sort_by_key(|&(row, col)| board.legal_values(row, col).len()),
+            HintStrategy::Hardest => {
+                cells.sort_by_key(|&(row, col)| std::cmp::Reverse(board.legal_values(row, col).len()))
+            }
+        }
+    }
+}
+
+/// The kinds of house (unit) a cell can belong to: every cell is in a row,
+/// a column, and a box, and on Hyper boards some are also in a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HouseKind {
+    Row,
+    Column,
+    Box,
+    Window,
+}
+
+impl HouseKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HouseKind::Row => "row",
+            HouseKind::Column => "column",
+            HouseKind::Box => "box",
+            HouseKind::Window => "window",
+        }
+    }
+
+    /// Every cell in this kind of house through (row, col).
+    pub fn cells(&self, board: &BoardState, row: usize, col: usize) -> Vec<(usize, usize)> {
+        self.index(board, row, col).map(|index| self.cells_at(board, index)).unwrap_or_default()
+    }
+
+    /// 1-based number of the house through (row, col), as players count them.
+    fn number(&self, board: &BoardState, row: usize, col: usize) -> usize {
+        self.index(board, row, col).map_or(0, |index| index + 1)
+    }
+
+    /// Get all house kinds.
+    pub fn all() -> [HouseKind; 4] {
+        [HouseKind::Row, HouseKind::Column, HouseKind::Box, HouseKind::Window]
+    }
+
+    /// Index of the house of this kind through (row, col), counting from 0.
+    /// `None` for a window, when the cell isn't in one.
+    pub fn index(&self, board: &BoardState, row: usize, col: usize) -> Option<usize> {
+        match self {
+            HouseKind::Row => Some(row),
+            HouseKind::Column => Some(col),
+            HouseKind::Box => Some(board.regions.region_of(row, col)),
+            HouseKind::Window => board.window_index(row, col),
+        }
+    }
+
+    /// How many houses of this kind the board has.
+    pub fn count(&self, board: &BoardState) -> usize {
+        match self {
+            HouseKind::Window => board.windows.len(),
+            _ => board.size(),
+        }
+    }
+
+    /// Every cell in the house of this kind with the given index.
+    pub fn cells_at(&self, board: &BoardState, index: usize) -> Vec<(usize, usize)> {
+        match self {
+            HouseKind::Row => board.row(index).map(|(cell, _)| cell).collect(),
+            HouseKind::Column => board.col(index).map(|(cell, _)| cell).collect(),
+            HouseKind::Box => board.box_(index).map(|(cell, _)| cell).collect(),
+            HouseKind::Window => board.windows.get(index).cloned().unwrap_or_default(),
+        }
+    }
+}
+
+impl BoardState {
+    /// Whether a house is filled with no cat repeated. Houses that don't
+    /// exist on this board are never complete.
+    pub fn is_house_complete(&self, kind: HouseKind, index: usize) -> bool {
+        if index >= kind.count(self) {
+            return false;
+        }
+        let mut seen = vec![false; self.size()];
+        kind.cells_at(self, index).into_iter().all(|(row, col)| match self.cells[row][col] {
+            Some(value) if value < seen.len() && !seen[value] => {
+                seen[value] = true;
+                true
+            }
+            _ => false,
+        })
+    }
+
+    /// Every complete house on the board, rows first.
+    pub fn complete_houses(&self) -> Vec<(HouseKind, usize)> {
+        HouseKind::all()
+            .into_iter()
+            .flat_map(|kind| (0..kind.count(self)).map(move |index| (kind, index)))
+            .filter(|&(kind, index)| self.is_house_complete(kind, index))
+            .collect()
+    }
+}
+
+/// The deduction that justifies a hinted placement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintReason {
+    /// Every other cat is already present among the cell's peers.
+    NakedSingle,
+    /// No other cell in this house can take the cat.
+    HiddenSingle(HouseKind),
+    /// No simple deduction applies; the cat was revealed from the solution.
+    Solution,
+}
+
+/// Terms the hint explanations refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlossaryTerm {
+    House,
+    Peer,
+    Candidate,
+    NakedSingle,
+    HiddenSingle,
+}
+
+impl GlossaryTerm {
+    /// Get all glossary terms in display order.
+    pub fn all() -> [GlossaryTerm; 5] {
+        [
+            GlossaryTerm::House,
+            GlossaryTerm::Peer,
+            GlossaryTerm::Candidate,
+            GlossaryTerm::NakedSingle,
+            GlossaryTerm::HiddenSingle,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GlossaryTerm::House => "House",
+            GlossaryTerm::Peer => "Peer",
+            GlossaryTerm::Candidate => "Candidate",
+            GlossaryTerm::NakedSingle => "Naked single",
+            GlossaryTerm::HiddenSingle => "Hidden single",
+        }
+    }
+
+    pub fn definition(&self) -> &'static str {
+        match self {
+            GlossaryTerm::House => "A row, column, or box. Each house holds every cat exactly once.",
+            GlossaryTerm::Peer => "Any cell sharing a row, column, or box with another cell.",
+            GlossaryTerm::Candidate => "A cat that could still go in an empty cell without clashing with its peers.",
+            GlossaryTerm::NakedSingle => "A cell with only one candidate left - that cat must go there.",
+            GlossaryTerm::HiddenSingle => "A cat that fits in only one cell of a house - it must go there.",
+        }
+    }
+}
+
+/// Explanation of a single hinted placement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HintExplanation {
+    pub row: usize,
+    pub col: usize,
+    pub value: usize,
+    pub reason: HintReason,
+}
+
+impl HintExplanation {
+    /// Work out why `value` belongs at (row, col), using the board as it was
+    /// *before* the hint was placed.
+    pub fn for_hint(board: &BoardState, row: usize, col: usize, value: usize) -> Self {
+        let reason = if board.candidates_at(row, col).len() == 1 {
+            HintReason::NakedSingle
+        } else if let Some(kind) = [HouseKind::Row, HouseKind::Column, HouseKind::Box, HouseKind::Window]
+            .into_iter()
+            .find(|kind| is_only_spot_in_house(board, row, col, value, *kind))
+        {
+            HintReason::HiddenSingle(kind)
+        } else {
+            HintReason::Solution
+        };
+
+        Self { row, col, value, reason }
+    }
+
+    /// Pick the next logical deduction on the board: a random naked or
+    /// hidden single that agrees with the solution. None when only
+    /// guesswork (or nothing) is left.
+    pub fn next_deduction(board: &BoardState, solution: &Solution) -> Option<Self> {
+        let deductions: Vec<Self> = (0..board.size())
+            .flat_map(|row| (0..board.size()).map(move |col| (row, col)))
+            .filter(|&(row, col)| board.cells[row][col].is_none())
+            .filter(|&(row, col)| board.legal_values(row, col).contains(&solution.cells[row][col]))
+            .map(|(row, col)| Self::for_hint(board, row, col, solution.cells[row][col]))
+            .filter(|explanation| explanation.reason != HintReason::Solution)
+            .collect();
+        deductions.choose(&mut thread_rng()).copied()
+    }
+
+    /// Find the next hint within a time budget. Searches in passes of
+    /// increasing cost (naked singles, then hidden singles; the other way
+    /// round for `HintStrategy::Hardest`) over the empty cells in the
+    /// strategy's order. If the budget runs out first, or no deduction
+    /// exists, it settles for revealing a cat from the solution
+    /// (`HintReason::Solution`), again picking the cell by strategy. None
+    /// only when the board is full.
+    pub fn next_step_with_budget(
+        board: &BoardState,
+        solution: &Solution,
+        budget_ms: u64,
+        strategy: HintStrategy,
+    ) -> Option<Self> {
+        let deadline = Instant::now() + Duration::from_millis(budget_ms);
+        let mut empty: Vec<(usize, usize)> = (0..board.size())
+            .flat_map(|row| (0..board.size()).map(move |col| (row, col)))
+            .filter(|&(row, col)| board.cells[row][col].is_none())
+            .collect();
+        strategy.order_cells(board, &mut empty);
+
+        let naked_single = |row: usize, col: usize, value: usize| {
+            (board.candidates_at(row, col) == [value]).then_some(HintReason::NakedSingle)
+        };
+        let hidden_single = |row: usize, col: usize, value: usize| {
+            if !board.legal_values(row, col).contains(&value) {
+                return None;
+            }
+            [HouseKind::Row, HouseKind::Column, HouseKind::Box, HouseKind::Window]
+                .into_iter()
+                .find(|kind| is_only_spot_in_house(board, row, col, value, *kind))
+                .map(HintReason::HiddenSingle)
+        };
+        let mut passes: [&dyn Fn(usize, usize, usize) -> Option<HintReason>; 2] = [&naked_single, &hidden_single];
+        if strategy == HintStrategy::Hardest {
+            passes.reverse();
+        }
+
+        for pass in passes {
+            for &(row, col) in &empty {
+                if Instant::now() >= deadline {
+                    return empty.first().map(|&(row, col)| Self::revealed(row, col, solution));
+                }
+                let value = solution.cells[row][col];
+                if let Some(reason) = pass(row, col, value) {
+                    return Some(Self { row, col, value, reason });
+                }
+            }
+        }
+        empty.first().map(|&(row, col)| Self::revealed(row, col, solution))
+    }
+
+    /// A cat revealed straight from the solution, without a deduction behind it.
+    fn revealed(row: usize, col: usize, solution: &Solution) -> Self {
+        Self {
+            row,
+            col,
+            value: solution.cells[row][col],
+            reason: HintReason::Solution,
+        }
+    }
+
+    /// The cells to point at before the hint is revealed: the house a hidden
+    /// single lives in, or every peer of a naked single.
+    pub fn focus_cells(&self, board: &BoardState) -> Vec<(usize, usize)> {
+        let kinds: &[HouseKind] = match self.reason {
+            HintReason::HiddenSingle(kind) => &[kind][..],
+            HintReason::NakedSingle => &[HouseKind::Row, HouseKind::Column, HouseKind::Box, HouseKind::Window],
+            HintReason::Solution => &[HouseKind::Box],
+        };
+        let mut cells: Vec<(usize, usize)> =
+            kinds.iter().flat_map(|kind| kind.cells(board, self.row, self.col)).collect();
+        cells.sort();
+        cells.dedup();
+        cells
+    }
+
+    /// The first-tier hint: where to look, without giving the cat away.
+    pub fn render_nudge(&self, board: &BoardState) -> String {
+        match self.reason {
+            HintReason::HiddenSingle(kind) => format!(
+                "Look at {} {}: one of its cats has only one place left.",
+                kind.name(),
+                kind.number(board, self.row, self.col)
+            ),
+            HintReason::NakedSingle => {
+                "Look at the highlighted cells: one empty cell there has only one cat that fits.".to_string()
+            }
+            HintReason::Solution => format!(
+                "Look at box {}: there's a cat to find there.",
+                HouseKind::Box.number(board, self.row, self.col)
+            ),
+        }
+    }
+
+    /// Glossary terms this explanation relies on.
+    pub fn glossary_terms(&self) -> Vec<GlossaryTerm> {
+        match self.reason {
+            HintReason::NakedSingle => vec![GlossaryTerm::NakedSingle, GlossaryTerm::Candidate, GlossaryTerm::Peer],
+            HintReason::HiddenSingle(_) => vec![GlossaryTerm::HiddenSingle, GlossaryTerm::House],
+            HintReason::Solution => vec![GlossaryTerm::Candidate],
+        }
+    }
+
+    /// Render the explanation at the requested level of detail,
+    /// writing cat numbers with the player's glyph set.
+    pub fn render(&self, verbosity: HintVerbosity, glyphs: GlyphSet) -> String {
+        let cat = glyphs.glyph(self.value);
+        let placement = format!("Cat {} goes in R{}C{}.", cat, self.row + 1, self.col + 1);
+        if verbosity == HintVerbosity::Terse {
+            return placement;
+        }
+
+        let reasoning = match self.reason {
+            HintReason::NakedSingle => {
+                "Every other cat already appears among its peers, so it's the only one that fits (naked single).".to_string()
+            }
+            HintReason::HiddenSingle(kind) => format!(
+                "No other cell in this {} can take cat {} (hidden single).",
+                kind.name(),
+                cat
+            ),
+            HintReason::Solution => {
+                "No simple deduction applies here yet, so the cat was revealed from the solution.".to_string()
+            }
+        };
+
+        let mut text = format!("{} {}", placement, reasoning);
+        if verbosity == HintVerbosity::Teaching {
+            for term in self.glossary_terms() {
+                text.push_str(&format!("\n📖 {}: {}", term.name(), term.definition()));
+            }
+        }
+        text
+    }
+}
+
+/// How far along a hint the player is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HintTier {
+    /// The house holding the deduction has been pointed out; nothing is placed yet.
+    Nudge,
+    /// The cat has been placed and the deduction explained.
+    #[default]
+    Reveal,
+}
+
+/// The most recent hint explanation, shown by the UI until the next hint.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct LastHint {
+    pub explanation: Option<HintExplanation>,
+    pub tier: HintTier,
+}
+
+impl LastHint {
+    /// The nudged deduction waiting to be revealed, if it still applies
+    /// (its cell is empty and the cat matches the solution).
+    pub fn pending_nudge(&self, board: &BoardState, solution: &Solution) -> Option<HintExplanation> {
+        self.explanation.filter(|explanation| {
+            self.tier == HintTier::Nudge
+                && board.cells[explanation.row][explanation.col].is_none()
+                && solution.cells[explanation.row][explanation.col] == explanation.value
+        })
+    }
+}
+
+/// Whether (row, col) is the only empty cell in the given house that can take `value`.
+fn is_only_spot_in_house(board: &BoardState, row: usize, col: usize, value: usize, kind: HouseKind) -> bool {
+    let cells = kind.cells(board, row, col);
+    if !cells.contains(&(row, col)) {
+        return false; // Not in a window
+    }
+
+    cells
+        .into_iter()
+        .filter(|&(r, c)| (r, c) != (row, col) && board.cells[r][c].is_none())
+        .all(|(r, c)| !board.is_valid_placement(r, c, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GRID_SIZE, get_next_hint_with};
+
+    #[test]
+    fn test_house_completion() {
+        let mut board = BoardState::new();
+        for col in 0..GRID_SIZE - 1 {
+            board.cells[0][col] = Some(col);
+        }
+        assert!(!board.is_house_complete(HouseKind::Row, 0), "A row with a gap isn't complete");
+
+        board.cells[0][8] = Some(0);
+        assert!(!board.is_house_complete(HouseKind::Row, 0), "A row with a repeat isn't complete");
+
+        board.cells[0][8] = Some(8);
+        assert!(board.is_house_complete(HouseKind::Row, 0));
+        assert_eq!(board.complete_houses(), vec![(HouseKind::Row, 0)]);
+        assert!(!board.is_house_complete(HouseKind::Column, 0));
+        assert!(!board.is_house_complete(HouseKind::Row, GRID_SIZE), "Rows past the board don't exist");
+        assert!(!board.is_house_complete(HouseKind::Window, 0), "Classic boards have no windows");
+
+        // Boxes are counted left to right, top to bottom
+        assert_eq!(HouseKind::Box.index(&board, 4, 7), Some(5));
+        assert_eq!(HouseKind::Box.cells_at(&board, 5).len(), GRID_SIZE);
+    }
+
+    #[test]
+    fn test_naked_single_explanation() {
+        let mut board = BoardState::new();
+        // Fill row 0 with cats 0..8, leaving (0, 8) needing cat 8
+        for col in 0..GRID_SIZE - 1 {
+            board.cells[0][col] = Some(col);
+        }
+
+        let explanation = HintExplanation::for_hint(&board, 0, 8, 8);
+        assert_eq!(explanation.reason, HintReason::NakedSingle);
+
+        let terse = explanation.render(HintVerbosity::Terse, GlyphSet::Arabic);
+        let normal = explanation.render(HintVerbosity::Normal, GlyphSet::Arabic);
+        let teaching = explanation.render(HintVerbosity::Teaching, GlyphSet::Arabic);
+        assert_eq!(terse, "Cat 9 goes in R1C9.");
+        assert_eq!(explanation.render(HintVerbosity::Terse, GlyphSet::Hanzi), "Cat 九 goes in R1C9.");
+        assert!(normal.starts_with(&terse) && normal.contains("naked single"));
+        assert!(teaching.starts_with(&normal) && teaching.contains("📖 Naked single"));
+    }
+
+    #[test]
+    fn test_hidden_single_and_fallback_explanations() {
+        let mut board = BoardState::new();
+        // Cat 0 in the top-middle and top-right boxes and in columns 1 and 2
+        // leaves (0, 0) as the only spot for cat 0 in row 0.
+        board.cells[1][4] = Some(0);
+        board.cells[2][7] = Some(0);
+        board.cells[4][1] = Some(0);
+        board.cells[7][2] = Some(0);
+        let explanation = HintExplanation::for_hint(&board, 0, 0, 0);
+        assert_eq!(explanation.reason, HintReason::HiddenSingle(HouseKind::Row));
+        assert!(explanation.render(HintVerbosity::Normal, GlyphSet::Arabic).contains("this row"));
+
+        let empty = BoardState::new();
+        let fallback = HintExplanation::for_hint(&empty, 4, 4, 3);
+        assert_eq!(fallback.reason, HintReason::Solution);
+    }
+
+    #[test]
+    fn test_nudge_points_at_the_next_deduction() {
+        let mut board = BoardState::new();
+        assert!(board.fill_board());
+        let solution = Solution::from_board(&board).unwrap();
+        board.cells[4][4] = None;
+        board.cells[4][5] = None;
+
+        let explanation = HintExplanation::next_deduction(&board, &solution).expect("Two blanks are always deducible");
+        assert!(explanation.row == 4 && [4, 5].contains(&explanation.col));
+        assert_eq!(explanation.value, solution.cells[4][explanation.col]);
+        assert!(explanation.focus_cells(&board).contains(&(4, explanation.col)));
+        assert!(explanation.render_nudge(&board).starts_with("Look at"));
+
+        let mut last_hint = LastHint { explanation: Some(explanation), tier: HintTier::Nudge };
+        assert_eq!(last_hint.pending_nudge(&board, &solution), Some(explanation));
+        last_hint.tier = HintTier::Reveal;
+        assert_eq!(last_hint.pending_nudge(&board, &solution), None, "Revealed hints aren't pending");
+
+        board.cells[4][4] = Some(solution.cells[4][4]);
+        board.cells[4][5] = Some(solution.cells[4][5]);
+        assert_eq!(HintExplanation::next_deduction(&board, &solution), None);
+    }
+
+    #[test]
+    fn test_budgeted_hint_falls_back_to_revealing() {
+        let mut board = BoardState::new();
+        assert!(board.fill_board());
+        let solution = Solution::from_board(&board).unwrap();
+        board.cells[2][3] = None;
+
+        // With time to think, the hint is the naked single
+        let hint = HintExplanation::next_step_with_budget(&board, &solution, 1000, HintStrategy::Easiest).unwrap();
+        assert_eq!((hint.row, hint.col, hint.reason), (2, 3, HintReason::NakedSingle));
+
+        // Without any budget it settles for revealing the cat
+        let hint = HintExplanation::next_step_with_budget(&board, &solution, 0, HintStrategy::Easiest).unwrap();
+        assert_eq!((hint.row, hint.col, hint.value, hint.reason), (2, 3, solution.cells[2][3], HintReason::Solution));
+
+        // An empty board has no deductions, so the hint comes from the solution
+        let empty = BoardState::new();
+        let hint = HintExplanation::next_step_with_budget(&empty, &solution, 1000, HintStrategy::Easiest).unwrap();
+        assert_eq!(hint.reason, HintReason::Solution);
+        assert_eq!(hint.value, solution.cells[hint.row][hint.col]);
+
+        board.cells[2][3] = Some(solution.cells[2][3]);
+        assert_eq!(HintExplanation::next_step_with_budget(&board, &solution, 1000, HintStrategy::Easiest), None);
+    }
+
+    #[test]
+    fn test_hint_strategies() {
+        let mut filled = BoardState::new();
+        assert!(filled.fill_board());
+        let solution = Solution::from_board(&filled).unwrap();
+        // All but the last cat of the top row: R1C9 is the only cell with one candidate
+        let mut board = BoardState::new();
+        for col in 0..8 {
+            board.cells[0][col] = filled.cells[0][col];
+        }
+        assert_eq!(board.legal_values(0, 8).len(), 1);
+        assert_eq!(board.legal_values(1, 0).len(), 6);
+        assert_eq!(board.legal_values(8, 8).len(), 9);
+
+        let mut cells = vec![(1, 0), (8, 8), (0, 8)];
+        HintStrategy::Easiest.order_cells(&board, &mut cells);
+        assert_eq!(cells, vec![(0, 8), (1, 0), (8, 8)]);
+        HintStrategy::Hardest.order_cells(&board, &mut cells);
+        assert_eq!(cells, vec![(8, 8), (1, 0), (0, 8)]);
+        assert_eq!(get_next_hint_with(&board, &solution, HintStrategy::Easiest), Some((0, 8, solution.cells[0][8])));
+        let (row, col, _) = get_next_hint_with(&board, &solution, HintStrategy::Hardest).unwrap();
+        assert_eq!(board.legal_values(row, col).len(), 9, "Hardest goes for a wide-open cell");
+
+        // Hardest goes looking for hidden singles before naked ones
+        let easiest = HintExplanation::next_step_with_budget(&board, &solution, 1000, HintStrategy::Easiest).unwrap();
+        assert_eq!((easiest.row, easiest.col, easiest.reason), (0, 8, HintReason::NakedSingle));
+        let hardest = HintExplanation::next_step_with_budget(&board, &solution, 1000, HintStrategy::Hardest).unwrap();
+        assert_eq!((hardest.row, hardest.col), (0, 8));
+        assert!(matches!(hardest.reason, HintReason::HiddenSingle(_)), "{:?}", hardest);
+        assert_eq!(HintStrategy::Hardest.next(), HintStrategy::Random);
+    }
+}
@@ -0,0 +1,109 @@
+//! Walking the board by house.
+//!
+//! A house is a row, a column, a box (a region, on Jigsaw boards), or a
+//! Hyper window: a group of cells that holds each cat once. The iterators
+//! here hand out each cell of a house as its position and what's in it,
+//! so the validator, solver, rater, and hints don't each redo the loops and
+//! the box arithmetic.
+
+use crate::{BoardState, HouseKind};
+
+impl BoardState {
+    /// The cells of a row, left to right, with their cats.
+    pub fn row(&self, row: usize) -> impl Iterator<Item = ((usize, usize), Option<usize>)> + '_ {
+        (0..self.size()).map(move |col| ((row, col), self.cells[row][col]))
+    }
+
+    /// The cells of a column, top to bottom, with their cats.
+    pub fn col(&self, col: usize) -> impl Iterator<Item = ((usize, usize), Option<usize>)> + '_ {
+        (0..self.size()).map(move |row| ((row, col), self.cells[row][col]))
+    }
+
+    /// The cells of a box (or Jigsaw region), in reading order, with their cats.
+    pub fn box_(&self, index: usize) -> impl Iterator<Item = ((usize, usize), Option<usize>)> + '_ {
+        self.regions.cells_in(index).map(|(row, col)| ((row, col), self.cells[row][col]))
+    }
+
+    /// Every house on the board with its cells: rows, then columns, boxes,
+    /// and finally any Hyper windows.
+    pub fn houses(&self) -> impl Iterator<Item = (HouseKind, Vec<(usize, usize)>)> + '_ {
+        HouseKind::all()
+            .into_iter()
+            .flat_map(move |kind| (0..kind.count(self)).map(move |index| (kind, kind.cells_at(self, index))))
+    }
+
+    /// Every other cell that shares a row, column, box (or Jigsaw region), or
+    /// Hyper window with the cell, without repeats.
+    pub fn peers_of(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        let mut peers: Vec<(usize, usize)> = Vec::new();
+        let mut add = |cell: (usize, usize)| {
+            if cell != (row, col) && !peers.contains(&cell) {
+                peers.push(cell);
+            }
+        };
+        self.row(row).chain(self.col(col)).for_each(|(cell, _)| add(cell));
+        self.box_(self.regions.region_of(row, col)).for_each(|(cell, _)| add(cell));
+        for window in self.windows.iter().filter(|window| window.contains(&(row, col))) {
+            window.iter().copied().for_each(&mut add);
+        }
+        peers
+    }
+
+    /// The cell's peers (see `peers_of`) with their cats.
+    pub fn peers(&self, row: usize, col: usize) -> impl Iterator<Item = ((usize, usize), Option<usize>)> + '_ {
+        self.peers_of(row, col).into_iter().map(|(r, c)| ((r, c), self.cells[r][c]))
+    }
+
+    /// Every empty cell, in reading order.
+    pub fn empty_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let size = self.size();
+        (0..size)
+            .flat_map(move |row| (0..size).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.cells[row][col].is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BoardState, GridSize, HouseKind, RegionMap};
+
+    #[test]
+    fn test_houses() {
+        let mut board = BoardState::new();
+        board.cells[4][7] = Some(2);
+        assert_eq!(board.row(4).nth(7), Some(((4, 7), Some(2))));
+        assert_eq!(board.col(7).nth(4), Some(((4, 7), Some(2))));
+        assert!(board.box_(5).any(|cell| cell == ((4, 7), Some(2))));
+        assert_eq!(board.box_(5).count(), 9);
+        assert_eq!(board.empty_cells().count(), 80);
+        assert!(!board.empty_cells().any(|cell| cell == (4, 7)));
+
+        // 27 houses on a classic board, in order
+        let houses: Vec<_> = board.houses().collect();
+        assert_eq!(houses.len(), 27);
+        assert_eq!(houses[0], (HouseKind::Row, board.row(0).map(|(cell, _)| cell).collect()));
+        assert_eq!(houses[9].0, HouseKind::Column);
+        assert_eq!(houses[26], (HouseKind::Box, board.box_(8).map(|(cell, _)| cell).collect()));
+
+        // Peers come with their cats
+        assert!(board.peers(4, 0).any(|peer| peer == ((4, 7), Some(2))));
+        assert_eq!(board.peers(4, 0).count(), 20);
+    }
+
+    #[test]
+    fn test_peers_of() {
+        let board = BoardState::new();
+        let peers = board.peers_of(4, 4);
+        assert_eq!(peers.len(), 20, "8 in the row, 8 in the column, and 4 more in the box");
+        assert!(!peers.contains(&(4, 4)));
+        assert!(peers.contains(&(3, 5)) && peers.contains(&(0, 4)) && peers.contains(&(4, 8)));
+        assert!(!peers.contains(&(0, 0)));
+
+        let mut mini = BoardState::with_size(GridSize::Mini);
+        assert_eq!(mini.peers_of(0, 0).len(), 7);
+        mini.regions = RegionMap::boxes(GridSize::Mini);
+        mini.windows = vec![vec![(0, 0), (3, 3)]];
+        assert!(mini.peers_of(0, 0).contains(&(3, 3)), "Hyper windows make peers too");
+        assert_eq!(mini.houses().count(), 13);
+    }
+}
@@ -0,0 +1,243 @@
+//! Input models: how clicks and typed cats turn into moves.
+//!
+//! Players pick the model that suits them in the settings. The controller
+//! asks the chosen `InputModel` what a click or key press means, gets back a
+//! `CellCommand`, and applies that to the board, so adding a model never
+//! touches the systems that read the mouse and keyboard.
+//!
+//! On touch screens, taps go through the input model like clicks; a finger
+//! held still on a cell erases it, and a swipe across the number pad steps
+//! to the next or previous cat (see `TouchGesture`).
+
+use serde::{Deserialize, Serialize};
+
+/// The ways a player can fill in cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum InputModel {
+    /// Clicking a cell cycles through the cats (or places the cat picked on
+    /// the number pad, if there is one).
+    #[default]
+    Cycle,
+    /// Clicking a cell selects it; typing a cat (or pressing it on the
+    /// number pad) fills the selected cell.
+    SelectAndType,
+    /// Pick a cat first, then click cells to place it. Clicking a cell that
+    /// already holds the picked cat empties it again.
+    NumberFirst,
+}
+
+/// What a click or typed cat asks for, once the input model has had its say.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellCommand {
+    /// Move the cell on to the next cat
+    Cycle { row: usize, col: usize },
+    /// Put a cat in the cell, or empty it with `None`
+    Set { row: usize, col: usize, value: Option<usize> },
+    /// Empty the cell
+    Erase { row: usize, col: usize },
+    /// Make the cell the target for the next typed cat
+    Select { row: usize, col: usize },
+    /// Make the cat the one placed by the next clicks (`None` drops it)
+    Pick(Option<usize>),
+    /// Turn the erase tool on (so the next clicks empty cells) or off
+    ToggleEraser,
+    /// Note the cat in the cell, or take the note away (see `notes`)
+    ToggleNote { row: usize, col: usize, value: usize },
+    /// Nothing to do
+    Ignore,
+}
+
+/// How long a finger has to stay still for a long press, in seconds.
+pub const LONG_PRESS_SECONDS: f32 = 0.5;
+/// How far a finger has to travel for a swipe, in logical pixels. Fingers
+/// that move less than this are still tapping.
+pub const SWIPE_DISTANCE: f32 = 40.0;
+
+/// What a finger on the screen did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchGesture {
+    /// A short touch without moving
+    Tap,
+    /// A touch held without moving for `LONG_PRESS_SECONDS`
+    LongPress,
+    /// A sideways swipe, rightwards (`forward`) or leftwards
+    Swipe { forward: bool },
+    /// A move that isn't a sideways swipe, like scrolling
+    Drag,
+}
+
+impl TouchGesture {
+    /// Tell the gesture from how far the finger moved (`dx`, `dy`, in
+    /// logical pixels, with x growing rightwards) and how long it's been down.
+    pub fn classify(dx: f32, dy: f32, held_seconds: f32) -> Self {
+        if dx.hypot(dy) >= SWIPE_DISTANCE {
+            if dx.abs() > dy.abs() {
+                TouchGesture::Swipe { forward: dx > 0.0 }
+            } else {
+                TouchGesture::Drag
+            }
+        } else if held_seconds >= LONG_PRESS_SECONDS {
+            TouchGesture::LongPress
+        } else {
+            TouchGesture::Tap
+        }
+    }
+}
+
+impl CellCommand {
+    /// The command in notes mode: putting a cat in a cell notes it there
+    /// instead. Other commands are unchanged.
+    pub fn into_note(self) -> Self {
+        match self {
+            CellCommand::Set { row, col, value: Some(value) } => CellCommand::ToggleNote { row, col, value },
+            command => command,
+        }
+    }
+}
+
+impl InputModel {
+    /// Get all input models in display order.
+    pub fn all() -> [InputModel; 3] {
+        [InputModel::Cycle, InputModel::SelectAndType, InputModel::NumberFirst]
+    }
+
+    /// Cycle to the next input model (wraps around).
+    pub fn next(&self) -> Self {
+        let all = Self::all();
+        let index = all.iter().position(|model| model == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+
+    /// Get the display name for this model.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            InputModel::Cycle => "Click to cycle",
+            InputModel::SelectAndType => "Select, then type",
+            InputModel::NumberFirst => "Cat first",
+        }
+    }
+
+    /// What a click on a cell means. `picked` is the cat picked on the
+    /// number pad and `current` the cat already in the cell.
+    pub fn cell_clicked(&self, row: usize, col: usize, picked: Option<usize>, current: Option<usize>) -> CellCommand {
+        match self {
+            InputModel::Cycle => match picked {
+                Some(value) => CellCommand::Set { row, col, value: Some(value) },
+                None => CellCommand::Cycle { row, col },
+            },
+            InputModel::SelectAndType => CellCommand::Select { row, col },
+            InputModel::NumberFirst => match picked {
+                Some(value) if current == Some(value) => CellCommand::Set { row, col, value: None },
+                Some(value) => CellCommand::Set { row, col, value: Some(value) },
+                None => CellCommand::Ignore,
+            },
+        }
+    }
+
+    /// What typing a cat means (a digit key, or a number pad button).
+    /// `selected` is the selected cell.
+    pub fn cat_typed(&self, value: usize, selected: Option<(usize, usize)>) -> CellCommand {
+        match (self, selected) {
+            (InputModel::SelectAndType, Some((row, col))) => CellCommand::Set { row, col, value: Some(value) },
+            (InputModel::SelectAndType, None) => CellCommand::Ignore,
+            (InputModel::Cycle | InputModel::NumberFirst, _) => CellCommand::Pick(Some(value)),
+        }
+    }
+
+    /// What pressing the eraser button means: it empties the selected cell
+    /// straight away with select-then-type, and is a tool for the next
+    /// clicks otherwise.
+    pub fn eraser_pressed(&self, selected: Option<(usize, usize)>) -> CellCommand {
+        match (self, selected) {
+            (InputModel::SelectAndType, Some((row, col))) => CellCommand::Erase { row, col },
+            (InputModel::SelectAndType, None) => CellCommand::Ignore,
+            (InputModel::Cycle | InputModel::NumberFirst, _) => CellCommand::ToggleEraser,
+        }
+    }
+
+    /// What swiping across the number pad means: step to the next (or
+    /// previous) cat, wrapping around. It's picked, or typed into the selected
+    /// cell with select-then-type. `from` is the cat picked (or in the selected
+    /// cell); with none, swiping starts at the first or last cat.
+    pub fn cat_swiped(
+        &self,
+        from: Option<usize>,
+        forward: bool,
+        num_cats: usize,
+        selected: Option<(usize, usize)>,
+    ) -> CellCommand {
+        if num_cats == 0 {
+            return CellCommand::Ignore;
+        }
+        let value = match (from, forward) {
+            (Some(value), true) => (value + 1) % num_cats,
+            (Some(value), false) => (value + num_cats - 1) % num_cats,
+            (None, true) => 0,
+            (None, false) => num_cats - 1,
+        };
+        self.cat_typed(value, selected)
+    }
+
+    /// Whether pressing a number pad button picks the cat for later clicks
+    /// (rather than typing it into the selected cell).
+    pub fn picks_from_number_pad(&self) -> bool {
+        !matches!(self, InputModel::SelectAndType)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_models() {
+        // Click-to-cycle keeps its old behavior, number pad included
+        assert_eq!(InputModel::Cycle.cell_clicked(1, 2, None, None), CellCommand::Cycle { row: 1, col: 2 });
+        assert_eq!(
+            InputModel::Cycle.cell_clicked(1, 2, Some(4), None),
+            CellCommand::Set { row: 1, col: 2, value: Some(4) }
+        );
+
+        // Select, then type
+        let model = InputModel::SelectAndType;
+        assert_eq!(model.cell_clicked(3, 3, Some(4), None), CellCommand::Select { row: 3, col: 3 });
+        assert_eq!(model.cat_typed(6, Some((3, 3))), CellCommand::Set { row: 3, col: 3, value: Some(6) });
+        assert_eq!(model.eraser_pressed(Some((3, 3))), CellCommand::Erase { row: 3, col: 3 });
+        assert_eq!(model.cat_typed(6, None), CellCommand::Ignore);
+        assert!(!model.picks_from_number_pad());
+
+        // Cat first: clicks do nothing until a cat is picked, and toggle it off again
+        let model = InputModel::NumberFirst;
+        assert_eq!(model.cell_clicked(0, 0, None, None), CellCommand::Ignore);
+        assert_eq!(model.cat_typed(2, None), CellCommand::Pick(Some(2)));
+        assert_eq!(model.eraser_pressed(None), CellCommand::ToggleEraser);
+
+        // Notes mode notes the cat instead of placing it
+        assert_eq!(
+            InputModel::SelectAndType.cat_typed(6, Some((3, 3))).into_note(),
+            CellCommand::ToggleNote { row: 3, col: 3, value: 6 }
+        );
+        assert_eq!(CellCommand::Erase { row: 3, col: 3 }.into_note(), CellCommand::Erase { row: 3, col: 3 });
+        assert_eq!(model.cell_clicked(0, 0, Some(2), None), CellCommand::Set { row: 0, col: 0, value: Some(2) });
+        assert_eq!(model.cell_clicked(0, 0, Some(2), Some(2)), CellCommand::Set { row: 0, col: 0, value: None });
+
+        assert_eq!(InputModel::NumberFirst.next(), InputModel::Cycle);
+
+        // Swiping the number pad steps through the cats, wrapping around
+        assert_eq!(InputModel::Cycle.cat_swiped(Some(8), true, 9, None), CellCommand::Pick(Some(0)));
+        assert_eq!(InputModel::Cycle.cat_swiped(None, false, 9, None), CellCommand::Pick(Some(8)));
+        assert_eq!(
+            InputModel::SelectAndType.cat_swiped(Some(2), false, 9, Some((1, 1))),
+            CellCommand::Set { row: 1, col: 1, value: Some(1) }
+        );
+    }
+
+    #[test]
+    fn test_touch_gestures() {
+        assert_eq!(TouchGesture::classify(3.0, -2.0, 0.1), TouchGesture::Tap);
+        assert_eq!(TouchGesture::classify(3.0, -2.0, LONG_PRESS_SECONDS), TouchGesture::LongPress);
+        assert_eq!(TouchGesture::classify(-60.0, 10.0, 0.2), TouchGesture::Swipe { forward: false });
+        assert_eq!(TouchGesture::classify(SWIPE_DISTANCE, 0.0, 2.0), TouchGesture::Swipe { forward: true });
+        assert_eq!(TouchGesture::classify(5.0, 80.0, 0.2), TouchGesture::Drag);
+    }
+}
@@ -0,0 +1,177 @@
+//! Invariant checks and deterministic entry points for test harnesses.
+//!
+//! A board keeps several things in step: `cells` and `cell_types` (a cell
+//! has a type exactly when it has a cat), the shapes of its per-cell grids,
+//! and a layout that fits its size. `check_invariants` lists everything that
+//! has drifted, and `check_invariants_since` also checks that the givens are
+//! the ones the puzzle started with. Fuzzing and property tests can call
+//! them after every step, and the game refuses a saved board that fails
+//! them rather than crashing on it later.
+//!
+//! `generate_with_seed` generates a puzzle as a pure function of its
+//! settings and seed, so harnesses can reproduce any case from one number.
+
+use crate::{BoardState, CellType, NineLivesError, PuzzleSettings, Puzzle, Solution};
+
+/// One way a board has stopped holding together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// A per-cell grid (cats, cell types, or notes) isn't the board's size
+    WrongShape { grid: &'static str },
+    /// A cat too big for the board
+    CatOutOfRange { row: usize, col: usize, value: usize },
+    /// A cell with a cat but no type, or a type but no cat
+    CellTypeMismatch { row: usize, col: usize },
+    /// The regions don't split the board into proper boxes
+    BrokenRegions,
+    /// A cage or window reaching off the board
+    CellOffBoard { row: usize, col: usize },
+    /// A given that isn't what the puzzle started with
+    GivenChanged { row: usize, col: usize },
+    /// A cell that wasn't a given has become one
+    GivenAdded { row: usize, col: usize },
+}
+
+impl InvariantViolation {
+    pub fn description(&self) -> String {
+        match self {
+            InvariantViolation::WrongShape { grid } => format!("{} grid is the wrong size", grid),
+            InvariantViolation::CatOutOfRange { row, col, value } => {
+                format!("cat {} at R{}C{} is too big for the board", value + 1, row + 1, col + 1)
+            }
+            InvariantViolation::CellTypeMismatch { row, col } => {
+                format!("cell type at R{}C{} doesn't match its cat", row + 1, col + 1)
+            }
+            InvariantViolation::BrokenRegions => "regions don't fit the board".to_string(),
+            InvariantViolation::CellOffBoard { row, col } => {
+                format!("cage or window cell R{}C{} is off the board", row + 1, col + 1)
+            }
+            InvariantViolation::GivenChanged { row, col } => format!("given at R{}C{} changed", row + 1, col + 1),
+            InvariantViolation::GivenAdded { row, col } => {
+                format!("R{}C{} became a given", row + 1, col + 1)
+            }
+        }
+    }
+}
+
+/// Whether a per-cell grid has `size` rows of `size` cells.
+fn is_square<T>(grid: &[Vec<T>], size: usize) -> bool {
+    grid.len() == size && grid.iter().all(|row| row.len() == size)
+}
+
+impl BoardState {
+    /// Everything about the board that doesn't hold together, or `Ok` if it
+    /// all does.
+    pub fn check_invariants(&self) -> Result<(), Vec<InvariantViolation>> {
+        let size = self.size();
+        let mut violations = Vec::new();
+        if !is_square(&self.cells, size) {
+            violations.push(InvariantViolation::WrongShape { grid: "cells" });
+        }
+        if !is_square(&self.cell_types, size) {
+            violations.push(InvariantViolation::WrongShape { grid: "cell_types" });
+        }
+        // Boards from before notes have none at all, which is fine
+        if !self.notes.is_empty() && !is_square(&self.notes, size) {
+            violations.push(InvariantViolation::WrongShape { grid: "notes" });
+        }
+        if !violations.is_empty() {
+            return Err(violations);
+        }
+
+        for (row, col) in (0..size).flat_map(|row| (0..size).map(move |col| (row, col))) {
+            let value = self.cells[row][col];
+            if let Some(value) = value.filter(|&value| value >= size) {
+                violations.push(InvariantViolation::CatOutOfRange { row, col, value });
+            }
+            if value.is_some() != self.cell_types[row][col].is_some() {
+                violations.push(InvariantViolation::CellTypeMismatch { row, col });
+            }
+        }
+        if !self.regions.is_valid_for(self.grid_size) {
+            violations.push(InvariantViolation::BrokenRegions);
+        }
+        let layout_cells = self.cages.iter().flat_map(|cage| cage.cells.iter()).chain(self.windows.iter().flatten());
+        for &(row, col) in layout_cells {
+            if row >= size || col >= size {
+                violations.push(InvariantViolation::CellOffBoard { row, col });
+            }
+        }
+
+        if violations.is_empty() { Ok(()) } else { Err(violations) }
+    }
+
+    /// Like `check_invariants`, and also that the givens are exactly those
+    /// of `start`, the puzzle as it was generated.
+    pub fn check_invariants_since(&self, start: &Puzzle) -> Result<(), Vec<InvariantViolation>> {
+        let mut violations = self.check_invariants().err().unwrap_or_default();
+        let misshapen = violations.iter().any(|violation| matches!(violation, InvariantViolation::WrongShape { .. }));
+        if misshapen || self.grid_size != start.grid_size() {
+            violations.push(InvariantViolation::WrongShape { grid: "givens" });
+            return Err(violations);
+        }
+        for ((row, col), given) in start.givens.cells() {
+            let is_given = self.cell_types[row][col] == Some(CellType::Given);
+            match given {
+                Some(value) if !is_given || self.cells[row][col] != Some(value) => {
+                    violations.push(InvariantViolation::GivenChanged { row, col });
+                }
+                None if is_given => violations.push(InvariantViolation::GivenAdded { row, col }),
+                _ => {}
+            }
+        }
+        if violations.is_empty() { Ok(()) } else { Err(violations) }
+    }
+}
+
+/// Generate a puzzle from `settings` and `seed` alone: the same pair always
+/// gives the same puzzle and solution, or the same error.
+pub fn generate_with_seed(settings: &PuzzleSettings, seed: u64) -> Result<(BoardState, Solution), NineLivesError> {
+    let settings = PuzzleSettings { seed: Some(seed), ..settings.clone() };
+    let mut board = BoardState::with_size(settings.grid_size);
+    let solution = board.generate_puzzle_with_settings(&settings)?;
+    Ok((board, solution))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PresetKind, Variant};
+
+    #[test]
+    fn test_check_invariants() {
+        let settings = PuzzleSettings::from_preset(PresetKind::CuriousCat).with_variant(Variant::Killer);
+        let (mut board, solution) = generate_with_seed(&settings, 11).expect("Puzzle should generate");
+        let start = board.puzzle();
+        assert_eq!(board.check_invariants_since(&start), Ok(()));
+
+        // Playing keeps it together
+        let (row, col) = board.empty_cells().next().unwrap();
+        board.set_cell(row, col, Some(solution.cells[row][col]));
+        assert_eq!(board.check_invariants_since(&start), Ok(()));
+
+        // Drift between cells and cell types, and a changed given
+        let mut broken = board.clone();
+        broken.cell_types[row][col] = None;
+        let given = (0..81).map(|index| (index / 9, index % 9)).find(|&(r, c)| broken.is_given_cell(r, c)).unwrap();
+        broken.cells[given.0][given.1] = broken.cells[given.0][given.1].map(|value| (value + 1) % 9);
+        let Err(violations) = broken.check_invariants_since(&start) else {
+            panic!("A broken board should fail");
+        };
+        assert!(violations.contains(&InvariantViolation::CellTypeMismatch { row, col }));
+        assert!(violations.contains(&InvariantViolation::GivenChanged { row: given.0, col: given.1 }));
+
+        broken.cell_types.pop();
+        assert_eq!(broken.check_invariants(), Err(vec![InvariantViolation::WrongShape { grid: "cell_types" }]));
+    }
+
+    #[test]
+    fn test_generate_with_seed_is_pure() {
+        let settings = PuzzleSettings::from_preset(PresetKind::CozyKitten);
+        let (first, first_solution) = generate_with_seed(&settings, 3).unwrap();
+        let (second, second_solution) = generate_with_seed(&settings, 3).unwrap();
+        assert_eq!(first.puzzle_string(), second.puzzle_string());
+        assert_eq!(first_solution.cells, second_solution.cells);
+        assert_eq!(first.check_invariants(), Ok(()));
+    }
+}
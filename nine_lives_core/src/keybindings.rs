@@ -0,0 +1,186 @@
+//! Keyboard shortcuts the player can rebind.
+//!
+//! Each `ShortcutAction` has one or more `KeyBinding`s: a key, named as Bevy
+//! names it (`KeyZ`, `KeyY`, ...), and whether the platform's command key
+//! (⌘ on Mac, Ctrl elsewhere) and Shift are held with it. The bindings are
+//! saved in `UserSettings::key_bindings` and changed on the settings screen,
+//! which refuses a key that's already taken (see `KeyBindings::rebind`).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Keys the game uses without a modifier (typing cats, moving focus, and
+/// emptying cells), which can't be taken by a shortcut unless it holds the
+/// command key.
+const RESERVED_KEYS: [&str; 27] = [
+    "Digit0", "Digit1", "Digit2", "Digit3", "Digit4", "Digit5", "Digit6", "Digit7", "Digit8", "Digit9", "KeyA", "KeyB",
+    "KeyC", "KeyD", "KeyE", "KeyF", "KeyG", "Tab", "Enter", "Space", "Escape", "Backspace", "Delete", "ArrowUp",
+    "ArrowDown", "ArrowLeft", "ArrowRight",
+];
+
+/// Something a keyboard shortcut does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ShortcutAction {
+    Undo,
+    Redo,
+    /// Turn debug mode (unlimited hints) on or off
+    ToggleDebug,
+}
+
+impl ShortcutAction {
+    /// Get all actions in the order they're listed on screen.
+    pub fn all() -> [ShortcutAction; 3] {
+        [ShortcutAction::Undo, ShortcutAction::Redo, ShortcutAction::ToggleDebug]
+    }
+
+    /// Get the display name for this action.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ShortcutAction::Undo => "Undo",
+            ShortcutAction::Redo => "Redo",
+            ShortcutAction::ToggleDebug => "Debug mode",
+        }
+    }
+}
+
+/// A key, with the modifiers held along with it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KeyBinding {
+    /// The key, by Bevy's name for it (like `KeyZ`)
+    pub key: String,
+    /// The platform's command key: ⌘ on Mac, Ctrl elsewhere
+    #[serde(default)]
+    pub command: bool,
+    #[serde(default)]
+    pub shift: bool,
+}
+
+impl KeyBinding {
+    pub fn new(key: &str, command: bool, shift: bool) -> Self {
+        Self { key: key.to_string(), command, shift }
+    }
+
+    /// The binding as shown to the player, like "⌘⇧Z" on Mac or
+    /// "Ctrl+Shift+Z" elsewhere.
+    pub fn label(&self) -> String {
+        let key = self
+            .key
+            .strip_prefix("Key")
+            .or_else(|| self.key.strip_prefix("Digit"))
+            .unwrap_or(&self.key);
+        if cfg!(target_os = "macos") {
+            format!("{}{}{}", if self.command { "⌘" } else { "" }, if self.shift { "⇧" } else { "" }, key)
+        } else {
+            format!("{}{}{}", if self.command { "Ctrl+" } else { "" }, if self.shift { "Shift+" } else { "" }, key)
+        }
+    }
+}
+
+/// The keyboard shortcuts, and the keys bound to each.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBindings {
+    pub bindings: BTreeMap<ShortcutAction, Vec<KeyBinding>>,
+}
+
+impl Default for KeyBindings {
+    /// ⌘Z to undo, ⌘⇧Z or ⌘Y to redo, and ⌘D for debug mode (Ctrl off the Mac).
+    fn default() -> Self {
+        Self {
+            bindings: BTreeMap::from([
+                (ShortcutAction::Undo, vec![KeyBinding::new("KeyZ", true, false)]),
+                (
+                    ShortcutAction::Redo,
+                    vec![KeyBinding::new("KeyZ", true, true), KeyBinding::new("KeyY", true, false)],
+                ),
+                (ShortcutAction::ToggleDebug, vec![KeyBinding::new("KeyD", true, false)]),
+            ]),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// The action a key press is bound to, if any.
+    pub fn action_for(&self, binding: &KeyBinding) -> Option<ShortcutAction> {
+        self.bindings
+            .iter()
+            .find(|(_, bindings)| bindings.contains(binding))
+            .map(|(action, _)| *action)
+    }
+
+    /// The keys bound to an action, as shown to the player ("⌘⇧Z / ⌘Y").
+    pub fn label(&self, action: ShortcutAction) -> String {
+        match self.bindings.get(&action) {
+            Some(bindings) if !bindings.is_empty() => {
+                bindings.iter().map(KeyBinding::label).collect::<Vec<_>>().join(" / ")
+            }
+            _ => "—".to_string(),
+        }
+    }
+
+    /// Bind `action` to `binding` alone, in place of its old keys. Refuses
+    /// keys bound to another action, and keys the game uses without a
+    /// modifier unless the binding holds the command key.
+    pub fn rebind(&mut self, action: ShortcutAction, binding: KeyBinding) -> Result<(), String> {
+        if let Some(other) = self.action_for(&binding).filter(|other| *other != action) {
+            return Err(format!("{} is already used for {}", binding.label(), other.display_name()));
+        }
+        if !binding.command && RESERVED_KEYS.contains(&binding.key.as_str()) {
+            return Err(format!("{} is used for playing; hold {} with it", binding.label(), command_key_name()));
+        }
+        self.bindings.insert(action, vec![binding]);
+        Ok(())
+    }
+
+    /// Keys bound to more than one action (from a hand-edited save, say),
+    /// with the actions they're bound to.
+    pub fn conflicts(&self) -> Vec<(KeyBinding, Vec<ShortcutAction>)> {
+        let mut actions_per_key: BTreeMap<String, (KeyBinding, Vec<ShortcutAction>)> = BTreeMap::new();
+        for (action, bindings) in &self.bindings {
+            for binding in bindings {
+                let entry = actions_per_key
+                    .entry(binding.label())
+                    .or_insert_with(|| (binding.clone(), Vec::new()));
+                if !entry.1.contains(action) {
+                    entry.1.push(*action);
+                }
+            }
+        }
+        actions_per_key.into_values().filter(|(_, actions)| actions.len() > 1).collect()
+    }
+}
+
+/// The platform's command key, as named to the player.
+fn command_key_name() -> &'static str {
+    if cfg!(target_os = "macos") { "⌘" } else { "Ctrl" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_bindings() {
+        let mut bindings = KeyBindings::default();
+        let command_z = KeyBinding::new("KeyZ", true, false);
+        assert_eq!(bindings.action_for(&command_z), Some(ShortcutAction::Undo));
+        assert_eq!(bindings.action_for(&KeyBinding::new("KeyY", true, false)), Some(ShortcutAction::Redo));
+        assert_eq!(bindings.action_for(&KeyBinding::new("KeyZ", false, false)), None);
+        assert!(bindings.conflicts().is_empty());
+
+        // Taken keys and playing keys are refused
+        assert!(bindings.rebind(ShortcutAction::ToggleDebug, command_z.clone()).is_err());
+        assert!(bindings.rebind(ShortcutAction::Undo, KeyBinding::new("Digit5", false, false)).is_err());
+        assert_eq!(bindings.label(ShortcutAction::ToggleDebug), KeyBinding::new("KeyD", true, false).label());
+
+        // Rebinding replaces the old keys, freeing them for other actions
+        bindings.rebind(ShortcutAction::Undo, KeyBinding::new("KeyU", true, false)).unwrap();
+        assert_eq!(bindings.action_for(&command_z), None);
+        bindings.rebind(ShortcutAction::ToggleDebug, command_z.clone()).unwrap();
+        assert_eq!(bindings.action_for(&command_z), Some(ShortcutAction::ToggleDebug));
+
+        // Conflicts in hand-edited bindings are found
+        bindings.bindings.insert(ShortcutAction::Redo, vec![command_z.clone()]);
+        assert_eq!(bindings.conflicts(), vec![(command_z, vec![ShortcutAction::Redo, ShortcutAction::ToggleDebug])]);
+    }
+}
@@ -0,0 +1,194 @@
+//! Local best-time leaderboards, one per preset.
+//!
+//! Every completed classic 9x9 puzzle is offered to the leaderboard of the
+//! preset it was played on (see `PuzzleSettings::ranked_preset`); other board
+//! sizes, variants, and campaign, pack, and practice puzzles aren't ranked,
+//! since their times don't compare with the presets'. Each board keeps the fastest `LEADERBOARD_SIZE` completions,
+//! with fewer hints and then fewer mistakes breaking ties on time; a later
+//! completion that exactly ties an earlier one ranks below it.
+//!
+//! A completion that sets a new record also keeps its replay, so the
+//! record-setting solve can be watched from the statistics screen.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Difficulty, GridSize, PresetKind, PuzzleSettings, Replay, SavedReplay, Variant};
+
+/// How many completions each preset's leaderboard keeps.
+pub const LEADERBOARD_SIZE: usize = 5;
+
+/// One completion on a leaderboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub seconds: u64,
+    /// When the puzzle was finished, as a Unix timestamp
+    pub completed_at: u64,
+    pub hints_used: u32,
+    pub mistakes: u32,
+    /// The game's score (see `ScoreTracker`)
+    #[serde(default)]
+    pub score: u32,
+}
+
+impl LeaderboardEntry {
+    /// What the entry is ranked by, best first.
+    fn rank_key(&self) -> (u64, u32, u32) {
+        (self.seconds, self.hints_used, self.mistakes)
+    }
+}
+
+/// The best completions for each preset, fastest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub entries: BTreeMap<PresetKind, Vec<LeaderboardEntry>>,
+    /// The replay of each preset's current record, when it was set with one.
+    #[serde(default)]
+    pub best_replays: BTreeMap<PresetKind, SavedReplay>,
+}
+
+impl Leaderboard {
+    /// The best completions for a preset, fastest first.
+    pub fn top(&self, preset: PresetKind) -> &[LeaderboardEntry] {
+        self.entries.get(&preset).map_or(&[], Vec::as_slice)
+    }
+
+    /// Offer a completion to a preset's leaderboard. Returns its place
+    /// (0 for a new record) if it made the board.
+    pub fn record(&mut self, preset: PresetKind, entry: LeaderboardEntry) -> Option<usize> {
+        let board = self.entries.entry(preset).or_default();
+        let rank = board.iter().take_while(|existing| existing.rank_key() <= entry.rank_key()).count();
+        if rank >= LEADERBOARD_SIZE {
+            return None;
+        }
+        board.insert(rank, entry);
+        board.truncate(LEADERBOARD_SIZE);
+        Some(rank)
+    }
+
+    /// Offer a completion along with the replay of its solve. A new record
+    /// keeps the replay in place of the old record's.
+    pub fn record_with_replay(&mut self, preset: PresetKind, entry: LeaderboardEntry, replay: &Replay) -> Option<usize> {
+        let rank = self.record(preset, entry);
+        if rank == Some(0) {
+            self.best_replays.insert(preset, replay.to_saved());
+        }
+        rank
+    }
+
+    /// The replay of a preset's record, if one was kept.
+    pub fn best_replay(&self, preset: PresetKind) -> Option<&SavedReplay> {
+        self.best_replays.get(&preset)
+    }
+}
+
+impl PresetKind {
+    /// The preset with this difficulty. Every preset has its own difficulty,
+    /// so puzzles started from a share code are ranked alongside generated
+    /// ones (when they're ranked at all; see `PuzzleSettings::ranked_preset`).
+    pub fn for_difficulty(difficulty: Difficulty) -> PresetKind {
+        match difficulty {
+            Difficulty::Easy => PresetKind::CozyKitten,
+            Difficulty::Medium => PresetKind::CuriousCat,
+            Difficulty::Hard => PresetKind::StreetwiseStray,
+            Difficulty::Expert => PresetKind::NightProwler,
+        }
+    }
+}
+
+impl PuzzleSettings {
+    /// The preset whose leaderboard and best times a solve of these settings
+    /// counts toward: only classic 9x9 puzzles played on their own (not in
+    /// the campaign, a pack, or practice) are ranked.
+    pub fn ranked_preset(&self) -> Option<PresetKind> {
+        let standalone = self.campaign_level.is_none() && self.pack_puzzle.is_none() && self.practice.is_none();
+        (self.grid_size == GridSize::Classic && self.variant == Variant::Classic && standalone)
+            .then(|| PresetKind::for_difficulty(self.difficulty))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(seconds: u64, hints_used: u32) -> LeaderboardEntry {
+        LeaderboardEntry { seconds, completed_at: 1_700_000_000 + seconds, hints_used, mistakes: 0, score: 0 }
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        let mut leaderboard = Leaderboard::default();
+        let preset = PresetKind::CuriousCat;
+        assert!(leaderboard.top(preset).is_empty());
+
+        assert_eq!(leaderboard.record(preset, entry(300, 0)), Some(0));
+        assert_eq!(leaderboard.record(preset, entry(200, 1)), Some(0), "Faster is a new record");
+        assert_eq!(leaderboard.record(preset, entry(200, 0)), Some(0), "Fewer hints break a tie");
+        assert_eq!(leaderboard.record(preset, entry(300, 0)), Some(3), "An exact tie ranks below");
+        assert_eq!(leaderboard.record(preset, entry(250, 0)), Some(2));
+        assert_eq!(leaderboard.top(preset).len(), LEADERBOARD_SIZE);
+
+        // A full board only takes completions that beat its slowest, dropping that one
+        assert_eq!(leaderboard.record(preset, entry(400, 0)), None);
+        assert_eq!(leaderboard.record(preset, entry(100, 0)), Some(0));
+        let times: Vec<u64> = leaderboard.top(preset).iter().map(|entry| entry.seconds).collect();
+        assert_eq!(times, vec![100, 200, 200, 250, 300]);
+
+        // Other presets have boards of their own
+        assert!(leaderboard.top(PresetKind::NightProwler).is_empty());
+
+        // Round trip through a save
+        let json = serde_json::to_string(&leaderboard).unwrap();
+        assert_eq!(serde_json::from_str::<Leaderboard>(&json).unwrap(), leaderboard);
+    }
+
+    #[test]
+    fn test_best_replay_follows_record() {
+        let mut leaderboard = Leaderboard::default();
+        let preset = PresetKind::CozyKitten;
+        let mut board = crate::BoardState::new();
+        let first = Replay::new(&board);
+        board.set_cell(0, 0, Some(3));
+        let mut second = Replay::new(&board);
+        second.steps.push(crate::ReplayStep {
+            game_move: crate::SavedMove { row: 0, col: 1, old_value: None, new_value: Some(4) },
+            kind: crate::ReplayEventKind::Move,
+        });
+
+        leaderboard.record_with_replay(preset, entry(300, 0), &first);
+        assert_eq!(leaderboard.best_replay(preset), Some(&first.to_saved()));
+
+        // A slower time doesn't replace the record's replay; a faster one does
+        assert_eq!(leaderboard.record_with_replay(preset, entry(400, 0), &second), Some(1));
+        assert_eq!(leaderboard.best_replay(preset), Some(&first.to_saved()));
+        assert_eq!(leaderboard.record_with_replay(preset, entry(200, 0), &second), Some(0));
+        assert_eq!(leaderboard.best_replay(preset), Some(&second.to_saved()));
+        assert!(leaderboard.best_replay(PresetKind::NightProwler).is_none());
+
+        // Leaderboards saved before replays were kept still load
+        let json = r#"{"entries":{}}"#;
+        assert!(serde_json::from_str::<Leaderboard>(json).unwrap().best_replays.is_empty());
+    }
+
+    #[test]
+    fn test_preset_for_difficulty() {
+        for preset in PresetKind::all() {
+            assert_eq!(PresetKind::for_difficulty(PuzzleSettings::from_preset(preset).difficulty), preset);
+            assert_eq!(PuzzleSettings::from_preset(preset).ranked_preset(), Some(preset));
+        }
+    }
+
+    #[test]
+    fn test_only_classic_puzzles_are_ranked() {
+        let expert = PuzzleSettings::from_preset(PresetKind::NightProwler);
+        for grid_size in [GridSize::Mini, GridSize::Small, GridSize::Mega] {
+            assert_eq!(expert.clone().with_grid_size(grid_size).ranked_preset(), None, "{:?} isn't ranked", grid_size);
+        }
+        for variant in [Variant::Killer, Variant::Jigsaw, Variant::Hyper] {
+            assert_eq!(expert.clone().with_variant(variant).ranked_preset(), None, "{:?} isn't ranked", variant);
+        }
+        let practice = PuzzleSettings { practice: Some(crate::Technique::NakedSingle), ..expert.clone() };
+        assert_eq!(practice.ranked_preset(), None);
+    }
+}
@@ -0,0 +1,82 @@
+//! Nine lives: an optional error policy.
+//!
+//! With lives turned on in the settings, every cat placed where the solution
+//! has a different one costs a life. Only cats that settle count (see
+//! `GameEvent::Placed`), so clicking past the wrong cats on the way to the
+//! right one is free. The player starts each puzzle with nine,
+//! and once they're all gone the game is over (restarting the puzzle or
+//! starting a new one brings them back). Taking the wrong cat back with undo
+//! doesn't return the life, and neither does quitting: the lives left are
+//! saved with the game.
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::Solution;
+
+/// How many lives a puzzle starts with.
+pub const STARTING_LIVES: usize = 9;
+
+/// The lives left in the current puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct Lives {
+    pub remaining: usize,
+}
+
+impl Default for Lives {
+    fn default() -> Self {
+        Self { remaining: STARTING_LIVES }
+    }
+}
+
+impl Lives {
+    /// Lose a life. Returns whether one was lost (none are, once they're gone).
+    pub fn lose(&mut self) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+
+    /// Whether every life is gone.
+    pub fn is_out(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Lives lost so far.
+    pub fn lost(&self) -> usize {
+        STARTING_LIVES - self.remaining
+    }
+
+    /// Put `value` at (`row`, `col`), and lose a life if it isn't the
+    /// solution's cat there. Returns whether a life was lost.
+    pub fn check_placement(&mut self, solution: &Solution, row: usize, col: usize, value: usize) -> bool {
+        solution.cells[row][col] != value && self.lose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lives() {
+        let mut solution = Solution::new();
+        solution.cells[0][0] = 3;
+        let mut lives = Lives::default();
+
+        // The right cat is free, a wrong one costs a life
+        assert!(!lives.check_placement(&solution, 0, 0, 3));
+        assert!(lives.check_placement(&solution, 0, 0, 5));
+        assert_eq!((lives.remaining, lives.lost()), (STARTING_LIVES - 1, 1));
+
+        // Lives run out, and stay out
+        while lives.lose() {}
+        assert!(lives.is_out());
+        assert!(!lives.check_placement(&solution, 0, 0, 5));
+        assert_eq!(lives.lost(), STARTING_LIVES);
+    }
+}
@@ -0,0 +1,102 @@
+//! Notes: cats the player pencils into empty cells as candidates.
+//!
+//! Unlike auto-candidates (`BoardState::compute_candidates`), notes are the
+//! player's own, kept in `BoardState::notes` as one bit per cat. Placing a
+//! cat takes that cat out of the notes of every peer (see `peers_of`). The cells it was cleared from
+//! go into the move (`Move::cleared_notes`), so undoing the placement puts
+//! the notes back and redoing it clears them again.
+
+use crate::BoardState;
+
+impl BoardState {
+    /// Whether the player has noted a cat in a cell.
+    pub fn has_note(&self, row: usize, col: usize, value: usize) -> bool {
+        self.notes.get(row).and_then(|cells| cells.get(col)).is_some_and(|notes| notes & (1 << value) != 0)
+    }
+
+    /// The cats noted in a cell, in order.
+    pub fn notes_at(&self, row: usize, col: usize) -> Vec<usize> {
+        (0..self.size()).filter(|&value| self.has_note(row, col, value)).collect()
+    }
+
+    /// Note a cat in a cell, or take the note away.
+    pub fn set_note(&mut self, row: usize, col: usize, value: usize, noted: bool) {
+        if self.notes.len() != self.size() {
+            self.notes = vec![vec![0; self.size()]; self.size()];
+        }
+        let notes = &mut self.notes[row][col];
+        if noted {
+            *notes |= 1 << value;
+        } else {
+            *notes &= !(1 << value);
+        }
+    }
+
+    /// Note a cat in an empty cell, or take the note away if it's there.
+    /// Returns whether the notes changed (cells holding a cat take no notes).
+    pub fn toggle_note(&mut self, row: usize, col: usize, value: usize) -> bool {
+        if self.cells[row][col].is_some() || value >= self.size() {
+            return false;
+        }
+        let noted = self.has_note(row, col, value);
+        self.set_note(row, col, value, !noted);
+        true
+    }
+
+    /// Take a cat just placed out of its peers' notes. Returns the cells it
+    /// was cleared from.
+    pub fn clear_note_from_peers(&mut self, row: usize, col: usize, value: usize) -> Vec<(usize, usize)> {
+        let cleared: Vec<(usize, usize)> = self
+            .peers_of(row, col)
+            .into_iter()
+            .filter(|&(r, c)| self.has_note(r, c, value))
+            .collect();
+        for &(r, c) in &cleared {
+            self.set_note(r, c, value, false);
+        }
+        cleared
+    }
+
+    /// Take every note off the board.
+    pub fn clear_notes(&mut self) {
+        self.notes = vec![vec![0; self.size()]; self.size()];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BoardState, GameHistory};
+
+    #[test]
+    fn test_placing_a_cat_clears_peer_notes() {
+        let mut board = BoardState::new();
+        let mut history = GameHistory::new();
+        assert!(board.toggle_note(0, 8, 4));
+        assert!(board.toggle_note(8, 0, 4));
+        assert!(board.toggle_note(1, 1, 4));
+        assert!(board.toggle_note(1, 1, 2));
+        assert!(board.toggle_note(5, 5, 4));
+
+        // Cat 5 at the top left clears it from its row, column, and box, but
+        // not from (5, 5), or the other notes
+        let game_move = board.set_cell(0, 0, Some(4)).unwrap();
+        assert_eq!(game_move.cleared_notes.len(), 3);
+        assert_eq!(board.notes_at(1, 1), vec![2]);
+        assert!(!board.has_note(0, 8, 4) && !board.has_note(8, 0, 4));
+        assert!(board.has_note(5, 5, 4));
+        history.add_move(game_move);
+
+        // Undo puts the notes back, and redo clears them again
+        history.undo(&mut board);
+        assert_eq!(board.notes_at(1, 1), vec![2, 4]);
+        assert!(board.has_note(0, 8, 4) && board.has_note(8, 0, 4));
+        history.redo(&mut board);
+        assert_eq!(board.notes_at(1, 1), vec![2]);
+        assert!(!board.has_note(8, 0, 4));
+
+        // Filled cells take no notes
+        assert!(!board.toggle_note(0, 0, 1));
+        assert!(board.toggle_note(1, 1, 2));
+        assert!(board.notes_at(1, 1).is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+//! Puzzle packs: collections of puzzles made elsewhere, imported to play.
+//!
+//! Packs come in two formats:
+//! - `.sdm`: one puzzle per line, as a puzzle string ('0' or '.' for empty
+//!   cells), the format most puzzle collections are shared in. The pack is
+//!   named after the file.
+//! - `.json`: a pack with its details, like
+//!   `{"name": "Alley Cats", "author": "Tom", "difficulty": "Hard", "puzzles": ["..."]}`.
+//!   Only `name` and `puzzles` are required.
+//!
+//! Imported packs are kept in the save directory (under `PackLibrary::SAVE_KEY`)
+//! along with which of their puzzles have been solved.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::save_files::read_with_backup;
+use crate::{BoardState, Difficulty, NineLivesError, PresetKind, PuzzleSettings, StorageBackend, default_storage, rate_difficulty};
+
+/// A collection of puzzles.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuzzlePack {
+    pub name: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    /// How hard the pack says its puzzles are. Without one, each puzzle is
+    /// rated when it's played.
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+    /// The puzzles, as puzzle strings
+    pub puzzles: Vec<String>,
+    /// Which puzzles have been solved, by index
+    #[serde(default)]
+    pub completed: BTreeSet<usize>,
+}
+
+/// A puzzle from a pack: the pack's name and the puzzle's index in it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackPuzzle {
+    pub pack: String,
+    pub index: usize,
+}
+
+impl PuzzlePack {
+    /// Read an `.sdm` pack. Blank lines and lines starting with `#` are skipped.
+    pub fn from_sdm(name: &str, text: &str) -> Result<PuzzlePack, String> {
+        let puzzles = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        PuzzlePack { name: name.to_string(), author: None, difficulty: None, puzzles, completed: BTreeSet::new() }.checked()
+    }
+
+    /// Read a JSON pack.
+    pub fn from_json(text: &str) -> Result<PuzzlePack, String> {
+        let pack: PuzzlePack = serde_json::from_str(text).map_err(|e| format!("not a puzzle pack: {}", e))?;
+        PuzzlePack { completed: BTreeSet::new(), ..pack }.checked()
+    }
+
+    /// Read a pack file, picking the format by its extension.
+    pub fn import(path: &Path) -> Result<PuzzlePack, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+        match path.extension().and_then(|extension| extension.to_str()).map(str::to_lowercase).as_deref() {
+            Some("sdm") => {
+                let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("Puzzle pack");
+                PuzzlePack::from_sdm(name, &text)
+            }
+            Some("json") => PuzzlePack::from_json(&text),
+            _ => Err(format!("{} isn't an .sdm or .json pack", path.display())),
+        }
+    }
+
+    /// The pack, if it has a name, any puzzles, and every puzzle reads.
+    fn checked(self) -> Result<PuzzlePack, String> {
+        if self.name.trim().is_empty() {
+            return Err("the pack has no name".to_string());
+        }
+        if self.puzzles.is_empty() {
+            return Err(format!("{} has no puzzles", self.name));
+        }
+        if let Some(index) = self.puzzles.iter().position(|puzzle| BoardState::from_puzzle_string(puzzle).is_none()) {
+            return Err(format!("puzzle {} of {} can't be read", index + 1, self.name));
+        }
+        Ok(self)
+    }
+
+    /// One of the pack's puzzles, ready to play.
+    pub fn board(&self, index: usize) -> Option<BoardState> {
+        BoardState::from_puzzle_string(self.puzzles.get(index)?)
+    }
+
+    /// Settings to play one of the pack's puzzles with: the preset for the
+    /// pack's difficulty (or the puzzle's rating), on the puzzle's board size.
+    pub fn settings(&self, index: usize, board: &BoardState) -> PuzzleSettings {
+        let difficulty = self.difficulty.unwrap_or_else(|| rate_difficulty(board).difficulty());
+        PuzzleSettings {
+            pack_puzzle: Some(PackPuzzle { pack: self.name.clone(), index }),
+            ..PuzzleSettings::from_preset(PresetKind::for_difficulty(difficulty)).with_grid_size(board.grid_size)
+        }
+    }
+
+    /// How far through the pack the player is, like "3/20 solved".
+    pub fn progress_label(&self) -> String {
+        format!("{}/{} solved", self.completed.len(), self.puzzles.len())
+    }
+}
+
+/// Every imported pack.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct PackLibrary {
+    pub packs: Vec<PuzzlePack>,
+}
+
+impl PackLibrary {
+    /// Name the library is saved under
+    pub const SAVE_KEY: &'static str = "nine_lives_packs.json";
+
+    /// Add a pack, returning its place in the library. A pack with the same
+    /// name is replaced, keeping its solved puzzles if its puzzles are unchanged.
+    pub fn add(&mut self, mut pack: PuzzlePack) -> usize {
+        match self.packs.iter().position(|existing| existing.name == pack.name) {
+            Some(index) => {
+                if self.packs[index].puzzles == pack.puzzles {
+                    pack.completed = std::mem::take(&mut self.packs[index].completed);
+                }
+                self.packs[index] = pack;
+                index
+            }
+            None => {
+                self.packs.push(pack);
+                self.packs.len() - 1
+            }
+        }
+    }
+
+    /// Mark a pack puzzle solved. Returns whether it wasn't already.
+    pub fn mark_completed(&mut self, puzzle: &PackPuzzle) -> bool {
+        self.packs
+            .iter_mut()
+            .find(|pack| pack.name == puzzle.pack && puzzle.index < pack.puzzles.len())
+            .is_some_and(|pack| pack.completed.insert(puzzle.index))
+    }
+
+    /// Load the library from this target's standard storage.
+    pub fn load() -> Self {
+        Self::load_from(default_storage().as_ref())
+    }
+
+    /// Load the library from `storage`, or start an empty one.
+    pub fn load_from(storage: &dyn StorageBackend) -> Self {
+        match read_with_backup(storage, Self::SAVE_KEY, |text| serde_json::from_str::<PackLibrary>(text)) {
+            Some(library) => {
+                info!("✅ Loaded {} puzzle packs", library.packs.len());
+                library
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Save the library to this target's standard storage.
+    pub fn save(&self) -> Result<(), NineLivesError> {
+        self.save_to(default_storage().as_ref())
+    }
+
+    /// Save the library to `storage`.
+    pub fn save_to(&self, storage: &dyn StorageBackend) -> Result<(), NineLivesError> {
+        storage.write(Self::SAVE_KEY, &serde_json::to_string(self).map_err(NineLivesError::save_error)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+
+    #[test]
+    fn test_read_packs() {
+        let sdm = format!("# two puzzles\n{}\n\n{}\n", PUZZLE.replace('.', "0"), PUZZLE);
+        let pack = PuzzlePack::from_sdm("Weekend", &sdm).unwrap();
+        assert_eq!((pack.name.as_str(), pack.puzzles.len()), ("Weekend", 2));
+        assert_eq!(pack.board(0).unwrap().cells, pack.board(1).unwrap().cells);
+        assert!(PuzzlePack::from_sdm("Broken", "123\n").unwrap_err().starts_with("puzzle 1"));
+        assert!(PuzzlePack::from_sdm("Empty", "# nothing\n").is_err());
+
+        let json = format!(r#"{{"name": "Alley Cats", "author": "Tom", "difficulty": "Hard", "puzzles": ["{}"]}}"#, PUZZLE);
+        let pack = PuzzlePack::from_json(&json).unwrap();
+        assert_eq!(pack.author.as_deref(), Some("Tom"));
+        assert_eq!(pack.difficulty, Some(Difficulty::Hard));
+        let settings = pack.settings(0, &pack.board(0).unwrap());
+        assert_eq!(settings.difficulty, Difficulty::Hard);
+        assert_eq!(settings.pack_puzzle, Some(PackPuzzle { pack: "Alley Cats".to_string(), index: 0 }));
+        assert!(PuzzlePack::from_json(r#"{"puzzles": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_pack_library_tracks_completion() {
+        let mut library = PackLibrary::default();
+        let pack = PuzzlePack::from_sdm("Weekend", &format!("{}\n{}\n", PUZZLE, PUZZLE)).unwrap();
+        assert_eq!(library.add(pack.clone()), 0);
+
+        let first = PackPuzzle { pack: "Weekend".to_string(), index: 0 };
+        assert!(library.mark_completed(&first));
+        assert!(!library.mark_completed(&first), "Already solved");
+        assert!(!library.mark_completed(&PackPuzzle { index: 5, ..first.clone() }));
+        assert_eq!(library.packs[0].progress_label(), "1/2 solved");
+
+        // Importing the same pack again keeps its progress; a changed one starts over
+        assert_eq!(library.add(pack.clone()), 0);
+        assert_eq!(library.packs[0].completed.len(), 1);
+        let changed = PuzzlePack { puzzles: vec![PUZZLE.to_string()], ..pack };
+        library.add(changed);
+        assert!(library.packs[0].completed.is_empty());
+        assert_eq!(library.packs.len(), 1);
+    }
+}
@@ -0,0 +1,150 @@
+//! A small stock of ready-made puzzles, so starting a game never waits on
+//! the generator.
+//!
+//! The pool is filled in the background while the player is on the menu or
+//! mid-game, a couple of puzzles per preset, and saved to disk so the stock
+//! survives restarts. Puzzles are stored as share codes next to their
+//! solutions.
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{BoardState, Difficulty, GenerationReport, GridSize, NineLivesError, PuzzleSettings, Solution, Variant, default_storage};
+use crate::save_files::read_with_backup;
+
+/// Name the pool is saved under
+const POOL_SAVE_KEY: &str = "nine_lives_pool.json";
+
+/// How many puzzles the pool keeps ready for each kind of game.
+pub const POOL_DEPTH: usize = 2;
+
+/// A pre-generated puzzle waiting in the pool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PooledPuzzle {
+    pub difficulty: Difficulty,
+    pub grid_size: GridSize,
+    pub variant: Variant,
+    #[serde(default)]
+    pub minimal: bool,
+    /// The puzzle's givens (and cages or regions), as a share code
+    pub code: String,
+    pub solution: Vec<Vec<usize>>,
+    /// How the puzzle's generation went (missing from older pool files)
+    #[serde(default)]
+    pub report: Option<GenerationReport>,
+}
+
+impl PooledPuzzle {
+    /// Whether this puzzle is the kind of game the settings ask for.
+    fn matches(&self, settings: &PuzzleSettings) -> bool {
+        self.difficulty == settings.difficulty
+            && self.grid_size == settings.grid_size
+            && self.variant == settings.variant
+            && self.minimal == settings.minimal
+    }
+}
+
+/// Puzzles generated ahead of time, ready for Start Game to hand out.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct PuzzlePool {
+    pub puzzles: Vec<PooledPuzzle>,
+}
+
+impl PuzzlePool {
+    /// Number of puzzles ready for the given settings.
+    pub fn count(&self, settings: &PuzzleSettings) -> usize {
+        self.puzzles.iter().filter(|puzzle| puzzle.matches(settings)).count()
+    }
+
+    /// The first of the wanted settings that's short of puzzles, if any.
+    pub fn next_to_fill(&self, wanted: impl IntoIterator<Item = PuzzleSettings>) -> Option<PuzzleSettings> {
+        wanted.into_iter().find(|settings| self.count(settings) < POOL_DEPTH)
+    }
+
+    /// Add a freshly generated puzzle to the pool.
+    pub fn push(&mut self, board: &BoardState, settings: &PuzzleSettings, solution: &Solution, report: GenerationReport) {
+        self.puzzles.push(PooledPuzzle {
+            difficulty: settings.difficulty,
+            grid_size: settings.grid_size,
+            variant: settings.variant,
+            minimal: settings.minimal,
+            code: board.to_share_code(settings),
+            solution: solution.cells.clone(),
+            report: Some(report),
+        });
+    }
+
+    /// Take the oldest puzzle for the given settings out of the pool, with
+    /// its generation report if it has one. Puzzles that no longer decode
+    /// (say, from an older version's pool file) are thrown away along the way.
+    pub fn take(&mut self, settings: &PuzzleSettings) -> Option<(BoardState, Solution, Option<GenerationReport>)> {
+        while let Some(index) = self.puzzles.iter().position(|puzzle| puzzle.matches(settings)) {
+            let puzzle = self.puzzles.remove(index);
+            let size = settings.grid_size.dimension();
+            if let Some(shared) = BoardState::from_share_code(&puzzle.code)
+                && shared.board.grid_size == settings.grid_size
+                && puzzle.solution.len() == size
+                && puzzle.solution.iter().all(|row| row.len() == size)
+            {
+                return Some((shared.board, Solution { cells: puzzle.solution }, puzzle.report));
+            }
+        }
+        None
+    }
+
+    /// Load the pool from the standard location, or start an empty one.
+    pub fn load() -> Self {
+        let storage = default_storage();
+        match read_with_backup(storage.as_ref(), POOL_SAVE_KEY, |text| serde_json::from_str::<PuzzlePool>(text)) {
+            Some(pool) => {
+                info!("✅ Loaded {} pre-generated puzzles", pool.puzzles.len());
+                pool
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Save the pool to disk.
+    pub fn save(&self) -> Result<(), NineLivesError> {
+        default_storage().write(POOL_SAVE_KEY, &serde_json::to_string(self).map_err(NineLivesError::save_error)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PresetKind;
+
+    #[test]
+    fn test_puzzle_pool() {
+        let easy = PuzzleSettings::from_preset(PresetKind::CozyKitten);
+        let killer = PuzzleSettings::from_preset(PresetKind::CuriousCat).with_variant(Variant::Killer);
+        let mut pool = PuzzlePool::default();
+        assert_eq!(pool.next_to_fill([easy.clone(), killer.clone()]).map(|s| s.difficulty), Some(Difficulty::Easy));
+
+        let mut board = BoardState::new();
+        let (solution, report) = board.generate_puzzle_with_report(&killer).expect("Puzzle should generate");
+        pool.push(&board, &killer, &solution, report.clone());
+        assert_eq!((pool.count(&killer), pool.count(&easy)), (1, 0));
+
+        // The pool survives a round trip through JSON, as it would across a restart
+        let mut pool: PuzzlePool = serde_json::from_str(&serde_json::to_string(&pool).unwrap()).unwrap();
+        assert!(pool.take(&easy).is_none());
+        let (taken, taken_solution, taken_report) = pool.take(&killer).expect("Pooled puzzle should come back");
+        assert_eq!(taken_report, Some(report.clone()));
+        assert_eq!(taken.cells, board.cells);
+        assert_eq!(taken.cages, board.cages);
+        assert_eq!(taken_solution.cells, solution.cells);
+        assert_eq!(pool.count(&killer), 0);
+
+        // Puzzles that don't decode are dropped rather than handed out
+        pool.push(&board, &killer, &solution, report);
+        pool.puzzles[0].code = "not a code".to_string();
+        assert!(pool.take(&killer).is_none());
+        assert!(pool.puzzles.is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+//! Practice puzzles: puzzles that call for one particular technique.
+//!
+//! The Training Grounds offer a puzzle for each technique the rater knows
+//! (see `rating`), short of guessing. A practice puzzle is one whose logical
+//! solve needs that technique and nothing harder, so the player can't get
+//! through it without using it. Puzzles are generated and checked with the
+//! rater; should no generated one fit, a puzzle from a fixed, known-good seed
+//! is played instead. Each technique's solves are kept in
+//! `GameStatistics::practice`, and the clean ones (no hints, no mistakes)
+//! show how well the player knows it.
+
+use rand::{Rng, thread_rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{BoardState, GameStatistics, GenerationReport, PresetKind, PuzzleSettings, Solution, Technique, rate_difficulty};
+
+/// The techniques that can be practiced, easiest first.
+pub const PRACTICE_TECHNIQUES: [Technique; 4] =
+    [Technique::HiddenSingle, Technique::NakedSingle, Technique::LockedCandidates, Technique::NakedPair];
+
+/// Most puzzles generated when looking for one that needs a technique
+pub const PRACTICE_ATTEMPTS: usize = 200;
+
+impl Technique {
+    /// What the technique is, for the Training Grounds.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Technique::HiddenSingle => "A cat that can only go in one cell of a row, column, or box.",
+            Technique::NakedSingle => "A cell where only one cat still fits.",
+            Technique::LockedCandidates => {
+                "A cat stuck in one line of a box can't go anywhere else on that line, and the other way round."
+            }
+            Technique::NakedPair => "Two cells in a house sharing the same two cats: no other cell there can have them.",
+            Technique::Guess => "No technique helps, so a cell has to be guessed.",
+        }
+    }
+
+    /// The preset practice puzzles for the technique are generated with:
+    /// the one whose puzzles most often need it.
+    fn practice_preset(&self) -> PresetKind {
+        match self {
+            Technique::HiddenSingle => PresetKind::CozyKitten,
+            Technique::NakedSingle => PresetKind::CuriousCat,
+            Technique::LockedCandidates | Technique::NakedPair => PresetKind::StreetwiseStray,
+            Technique::Guess => PresetKind::NightProwler,
+        }
+    }
+
+    /// Seeds known to give a puzzle that needs the technique, for when
+    /// generating one doesn't find any.
+    fn practice_seeds(&self) -> &'static [u64] {
+        match self {
+            Technique::HiddenSingle => &[1, 2, 3, 4, 5],
+            Technique::NakedSingle => &[23, 45, 51, 52, 99],
+            Technique::LockedCandidates => &[6, 8, 28, 36, 60],
+            Technique::NakedPair => &[74, 127, 166, 169, 240],
+            Technique::Guess => &[],
+        }
+    }
+}
+
+/// Settings to play a practice puzzle for `technique` with, generated from `seed`.
+pub fn practice_settings(technique: Technique, seed: u64) -> PuzzleSettings {
+    PuzzleSettings {
+        seed: Some(seed),
+        practice: Some(technique),
+        ..PuzzleSettings::from_preset(technique.practice_preset())
+    }
+}
+
+/// Whether a puzzle needs `technique`, and nothing harder, to solve.
+pub fn requires_technique(board: &BoardState, technique: Technique) -> bool {
+    let report = rate_difficulty(board);
+    report.unique && report.hardest_technique() == Some(technique)
+}
+
+/// Generate a practice puzzle for `technique`, along with the settings to
+/// play it with. Tries up to `PRACTICE_ATTEMPTS` random puzzles, then falls
+/// back to a known-good seed, picked by how many have been solved.
+pub fn generate_practice_puzzle(
+    technique: Technique,
+    solved: u32,
+) -> Option<(PuzzleSettings, BoardState, Solution, GenerationReport)> {
+    let mut rng = thread_rng();
+    for _ in 0..PRACTICE_ATTEMPTS {
+        let settings = practice_settings(technique, rng.gen_range(0..u64::MAX));
+        let mut board = BoardState::new();
+        if let Ok((solution, report)) = board.generate_puzzle_with_report(&settings)
+            && requires_technique(&board, technique)
+        {
+            return Some((settings, board, solution, report));
+        }
+    }
+
+    let seeds = technique.practice_seeds();
+    let settings = practice_settings(technique, *seeds.get(solved as usize % seeds.len().max(1))?);
+    let mut board = BoardState::new();
+    let (solution, report) = board.generate_puzzle_with_report(&settings).ok()?;
+    Some((settings, board, solution, report))
+}
+
+/// The player's practice with one technique.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PracticeRecord {
+    /// Practice puzzles solved
+    pub solved: u32,
+    /// Those solved without hints or mistakes
+    pub clean: u32,
+}
+
+/// How well the player knows a technique, going by clean practice solves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Proficiency {
+    Untried,
+    Learning,
+    Practiced,
+    Mastered,
+}
+
+impl Proficiency {
+    /// Get the display name for this level.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Proficiency::Untried => "Untried",
+            Proficiency::Learning => "Learning",
+            Proficiency::Practiced => "Practiced",
+            Proficiency::Mastered => "Mastered",
+        }
+    }
+}
+
+impl PracticeRecord {
+    /// How well the player knows the technique: Practiced after 3 clean
+    /// solves, Mastered after 10.
+    pub fn proficiency(&self) -> Proficiency {
+        match self.clean {
+            _ if self.solved == 0 => Proficiency::Untried,
+            0..3 => Proficiency::Learning,
+            3..10 => Proficiency::Practiced,
+            _ => Proficiency::Mastered,
+        }
+    }
+}
+
+impl GameStatistics {
+    /// The player's practice with a technique.
+    pub fn practice_record(&self, technique: Technique) -> PracticeRecord {
+        self.practice.get(&technique).copied().unwrap_or_default()
+    }
+
+    /// Record a solved practice puzzle.
+    pub fn record_practice(&mut self, technique: Technique, hints_used: u32, mistakes: u32) {
+        let record = self.practice.entry(technique).or_default();
+        record.solved += 1;
+        if hints_used == 0 && mistakes == 0 {
+            record.clean += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_practice_seeds_need_their_technique() {
+        for technique in PRACTICE_TECHNIQUES {
+            for &seed in technique.practice_seeds() {
+                let mut board = BoardState::new();
+                board
+                    .generate_puzzle_with_report(&practice_settings(technique, seed))
+                    .expect("Practice puzzle should generate");
+                assert!(requires_technique(&board, technique), "Seed {} should need {}", seed, technique.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_practice_records() {
+        let (settings, board, _, _) = generate_practice_puzzle(Technique::HiddenSingle, 0).unwrap();
+        assert_eq!(settings.practice, Some(Technique::HiddenSingle));
+        assert!(requires_technique(&board, Technique::HiddenSingle));
+
+        let mut statistics = GameStatistics::default();
+        assert_eq!(statistics.practice_record(Technique::NakedPair).proficiency(), Proficiency::Untried);
+        statistics.record_practice(Technique::NakedPair, 1, 0);
+        assert_eq!(statistics.practice_record(Technique::NakedPair).proficiency(), Proficiency::Learning);
+        for _ in 0..3 {
+            statistics.record_practice(Technique::NakedPair, 0, 0);
+        }
+        let record = statistics.practice_record(Technique::NakedPair);
+        assert_eq!((record.solved, record.clean), (4, 3));
+        assert_eq!(record.proficiency(), Proficiency::Practiced);
+
+        // Records survive a save
+        let json = serde_json::to_string(&statistics).unwrap();
+        let loaded: GameStatistics = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.practice_record(Technique::NakedPair), record);
+    }
+}
@@ -0,0 +1,166 @@
+//! Player profiles, for several people sharing one machine.
+//!
+//! Each profile has a `PersistentData` of its own (settings, statistics,
+//! saved game, and campaign stars), saved under the profile's own name. The
+//! `ProfileRoster` lists the profiles and which one was played last, and is
+//! saved as `nine_lives_profiles.json`. The first profile keeps the save from
+//! before there were profiles (`nine_lives_data.json`), so nothing is lost.
+//!
+//! The puzzle pool and imported packs aren't anyone's in particular, and stay
+//! shared.
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::save_files::read_with_backup;
+use crate::{NineLivesError, PersistentData, StorageBackend, default_storage};
+
+/// The profile there is before anyone makes one.
+pub const DEFAULT_PROFILE_NAME: &str = "Player";
+/// Longest name a profile can have, in characters.
+pub const MAX_PROFILE_NAME_LENGTH: usize = 20;
+/// Most profiles there can be.
+pub const MAX_PROFILES: usize = 8;
+
+/// A player.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// Name the profile's `PersistentData` is saved under
+    pub save_key: String,
+}
+
+/// Every profile, and which one is playing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct ProfileRoster {
+    pub profiles: Vec<Profile>,
+    /// The profile playing (and played last), by its place in `profiles`
+    pub active: usize,
+}
+
+impl Default for ProfileRoster {
+    /// Just the default profile, with the save from before profiles.
+    fn default() -> Self {
+        Self {
+            profiles: vec![Profile {
+                name: DEFAULT_PROFILE_NAME.to_string(),
+                save_key: PersistentData::SAVE_KEY.to_string(),
+            }],
+            active: 0,
+        }
+    }
+}
+
+impl ProfileRoster {
+    /// Name the roster is saved under
+    pub const SAVE_KEY: &'static str = "nine_lives_profiles.json";
+
+    /// The profile playing.
+    pub fn active_profile(&self) -> Option<&Profile> {
+        self.profiles.get(self.active)
+    }
+
+    /// Add a profile, returning its place in the roster. Names are trimmed,
+    /// and must be new (ignoring case) and no longer than `MAX_PROFILE_NAME_LENGTH`.
+    pub fn add(&mut self, name: &str) -> Result<usize, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("a profile needs a name".to_string());
+        }
+        if name.chars().count() > MAX_PROFILE_NAME_LENGTH {
+            return Err(format!("names can be at most {} characters", MAX_PROFILE_NAME_LENGTH));
+        }
+        if self.profiles.iter().any(|profile| profile.name.eq_ignore_ascii_case(name)) {
+            return Err(format!("there's already a profile called {}", name));
+        }
+        if self.profiles.len() >= MAX_PROFILES {
+            return Err(format!("there can be at most {} profiles", MAX_PROFILES));
+        }
+
+        // Named after the profile, as far as file names allow, and numbered if that's taken
+        let stem: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        let save_key = (1..)
+            .map(|n| match n {
+                1 => format!("nine_lives_profile_{}.json", stem),
+                n => format!("nine_lives_profile_{}_{}.json", stem, n),
+            })
+            .find(|key| !self.profiles.iter().any(|profile| &profile.save_key == key))
+            .unwrap_or_default();
+        self.profiles.push(Profile { name: name.to_string(), save_key });
+        Ok(self.profiles.len() - 1)
+    }
+
+    /// Load the active profile's data from this target's standard storage.
+    pub fn load_active_data(&self) -> PersistentData {
+        self.load_active_data_from(default_storage().as_ref())
+    }
+
+    /// Load the active profile's data from `storage`.
+    pub fn load_active_data_from(&self, storage: &dyn StorageBackend) -> PersistentData {
+        match self.active_profile() {
+            Some(profile) => PersistentData::load_profile_from(storage, &profile.save_key),
+            None => PersistentData::load_from(storage),
+        }
+    }
+
+    /// Load the roster from this target's standard storage.
+    pub fn load() -> Self {
+        Self::load_from(default_storage().as_ref())
+    }
+
+    /// Load the roster from `storage`, or start with just the default profile.
+    pub fn load_from(storage: &dyn StorageBackend) -> Self {
+        match read_with_backup(storage, Self::SAVE_KEY, |text| serde_json::from_str::<ProfileRoster>(text)) {
+            Some(roster) if !roster.profiles.is_empty() => {
+                info!("✅ Loaded {} profiles", roster.profiles.len());
+                roster
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// Save the roster to this target's standard storage.
+    pub fn save(&self) -> Result<(), NineLivesError> {
+        self.save_to(default_storage().as_ref())
+    }
+
+    /// Save the roster to `storage`.
+    pub fn save_to(&self, storage: &dyn StorageBackend) -> Result<(), NineLivesError> {
+        storage.write(Self::SAVE_KEY, &serde_json::to_string(self).map_err(NineLivesError::save_error)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adding_profiles() {
+        let mut roster = ProfileRoster::default();
+        assert_eq!(roster.active_profile().unwrap().save_key, PersistentData::SAVE_KEY);
+
+        assert_eq!(roster.add("  Whiskers "), Ok(1));
+        assert_eq!(roster.profiles[1].name, "Whiskers");
+        assert_eq!(roster.profiles[1].save_key, "nine_lives_profile_whiskers.json");
+        assert!(roster.add("WHISKERS").is_err(), "Names are unique, ignoring case");
+        assert!(roster.add("   ").is_err());
+        assert!(roster.add(&"x".repeat(MAX_PROFILE_NAME_LENGTH + 1)).is_err());
+
+        // Names that make the same file name still get files of their own
+        assert_eq!(roster.add("Mr. Paws"), Ok(2));
+        assert_eq!(roster.add("Mr_ Paws"), Ok(3));
+        assert_eq!(roster.profiles[3].save_key, "nine_lives_profile_mr__paws_2.json");
+
+        while roster.profiles.len() < MAX_PROFILES {
+            roster.add(&format!("Cat {}", roster.profiles.len())).unwrap();
+        }
+        assert!(roster.add("One too many").is_err());
+    }
+}
@@ -0,0 +1,331 @@
+//! Plain puzzle values for using the core as a library.
+//!
+//! `BoardState` is what the game plays on: a Bevy resource carrying the
+//! player's notes and who filled each cell alongside the cats. Code that only
+//! needs the puzzle itself can use these instead:
+//!
+//! - `Grid`: just the cats, in a fixed-size array, so it's `Copy` and costs
+//!   nothing to pass around or snapshot. The solver and generator search
+//!   over one (see `search.rs`).
+//! - `Puzzle`: the givens and the grid being filled in, with the layout
+//!   (regions, Killer cages, and Hyper windows) the rules need.
+//!
+//! Both convert to and from `BoardState`, so a puzzle built here can be
+//! solved, rated, or shared with the rest of the core. `BoardState` keeps its
+//! own rows of cells rather than wrapping a `Grid`: the game, saves, and UI
+//! index those rows directly, so these types stay alongside it for library
+//! users and the solver instead of replacing it.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BoardState, Cage, CellType, GridSize, NineLivesError, RegionMap, Solution, puzzle_char};
+
+/// Rows (and columns) a `Grid` has room for: enough for the largest board.
+pub const MAX_DIMENSION: usize = 16;
+
+/// The cats on a board, and nothing else. Cells past the board's size are
+/// always empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Grid {
+    grid_size: GridSize,
+    cells: [[Option<u8>; MAX_DIMENSION]; MAX_DIMENSION],
+}
+
+impl Grid {
+    /// An empty grid of the given size.
+    pub fn new(grid_size: GridSize) -> Self {
+        Self { grid_size, cells: [[None; MAX_DIMENSION]; MAX_DIMENSION] }
+    }
+
+    /// The size and box shape of the grid.
+    pub fn grid_size(&self) -> GridSize {
+        self.grid_size
+    }
+
+    /// Number of rows (and columns).
+    pub fn size(&self) -> usize {
+        self.grid_size.dimension()
+    }
+
+    /// The cat in a cell, if any.
+    pub fn get(&self, row: usize, col: usize) -> Option<usize> {
+        self.cells[row][col].map(usize::from)
+    }
+
+    /// Put a cat in a cell, or empty it with `None`. Cats that don't fit the
+    /// board are left out.
+    pub fn set(&mut self, row: usize, col: usize, value: Option<usize>) {
+        if row < self.size() && col < self.size() {
+            self.cells[row][col] = value.filter(|&value| value < self.size()).map(|value| value as u8);
+        }
+    }
+
+    /// Every cell, in reading order, with its cat.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), Option<usize>)> + '_ {
+        let size = self.size();
+        (0..size).flat_map(move |row| (0..size).map(move |col| ((row, col), self.get(row, col))))
+    }
+
+    /// How many cells hold a cat.
+    pub fn filled_count(&self) -> usize {
+        self.cells().filter(|(_, value)| value.is_some()).count()
+    }
+
+    /// Whether every cell holds a cat.
+    pub fn is_full(&self) -> bool {
+        self.filled_count() == self.size() * self.size()
+    }
+
+    /// The grid as a `Solution`, if every cell holds a cat.
+    pub fn to_solution(&self) -> Option<Solution> {
+        let cells = (0..self.size()).map(|row| (0..self.size()).map(|col| self.get(row, col)).collect());
+        cells.collect::<Option<Vec<Vec<usize>>>>().map(|cells| Solution { cells })
+    }
+
+    /// The cats as the rows of `BoardState::cells`.
+    pub fn to_rows(&self) -> Vec<Vec<Option<usize>>> {
+        (0..self.size()).map(|row| (0..self.size()).map(|col| self.get(row, col)).collect()).collect()
+    }
+}
+
+/// Written as a puzzle string (see `BoardState::puzzle_string`).
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cells().try_for_each(|(_, value)| write!(f, "{}", puzzle_char(value)))
+    }
+}
+
+/// Read from a puzzle string (see `BoardState::from_puzzle_string`).
+impl FromStr for Grid {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        BoardState::from_puzzle_string(text)
+            .map(|board| Grid::from(&board))
+            .ok_or_else(|| "Not a puzzle string".to_string())
+    }
+}
+
+impl From<&BoardState> for Grid {
+    fn from(board: &BoardState) -> Self {
+        let mut grid = Grid::new(board.grid_size);
+        for (row, cells) in board.cells.iter().enumerate() {
+            for (col, &value) in cells.iter().enumerate() {
+                grid.set(row, col, value);
+            }
+        }
+        grid
+    }
+}
+
+impl TryFrom<&Solution> for Grid {
+    type Error = NineLivesError;
+
+    /// The solved grid. Its size follows from the solution's, so a solution
+    /// that isn't square, isn't a board size, or has cats that don't fit is
+    /// an error.
+    fn try_from(solution: &Solution) -> Result<Self, Self::Error> {
+        let size = solution.cells.len();
+        let grid_size = GridSize::all()
+            .into_iter()
+            .find(|grid_size| grid_size.dimension() == size)
+            .ok_or_else(|| NineLivesError::invalid_puzzle(format!("has a solution {} rows tall, which isn't a board size", size)))?;
+        let mut grid = Grid::new(grid_size);
+        for (row, cells) in solution.cells.iter().enumerate() {
+            if cells.len() != size {
+                return Err(NineLivesError::invalid_puzzle(format!("has a solution row {} cells long, on a {}-row board", cells.len(), size)));
+            }
+            for (col, &value) in cells.iter().enumerate() {
+                if value >= size {
+                    return Err(NineLivesError::invalid_puzzle(format!("has a solution with cat {} on a {}-row board", value + 1, size)));
+                }
+                grid.set(row, col, Some(value));
+            }
+        }
+        Ok(grid)
+    }
+}
+
+/// A puzzle: its givens, the grid being filled in, and the layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Puzzle {
+    /// The cats the puzzle starts with
+    pub givens: Grid,
+    /// The givens plus every cat filled in since
+    pub grid: Grid,
+    /// Which cells make up each box (irregular on Jigsaw puzzles)
+    pub regions: RegionMap,
+    /// Killer Sudoku cages. Empty for other variants.
+    pub cages: Vec<Cage>,
+    /// Hyper Sudoku windows. Empty for other variants.
+    pub windows: Vec<Vec<(usize, usize)>>,
+}
+
+impl Puzzle {
+    /// A classic puzzle (standard boxes) starting from `givens`.
+    pub fn new(givens: Grid) -> Self {
+        Self {
+            givens,
+            grid: givens,
+            regions: RegionMap::boxes(givens.grid_size()),
+            cages: Vec::new(),
+            windows: Vec::new(),
+        }
+    }
+
+    /// The size and box shape of the puzzle.
+    pub fn grid_size(&self) -> GridSize {
+        self.givens.grid_size()
+    }
+
+    /// Whether a cell holds one of the puzzle's givens.
+    pub fn is_given(&self, row: usize, col: usize) -> bool {
+        self.givens.get(row, col).is_some()
+    }
+
+    /// Fill in a cell, or empty it with `None`. Returns false (changing
+    /// nothing) for a given.
+    pub fn place(&mut self, row: usize, col: usize, value: Option<usize>) -> bool {
+        if self.is_given(row, col) {
+            return false;
+        }
+        self.grid.set(row, col, value);
+        true
+    }
+
+    /// Take away everything filled in, back to the givens.
+    pub fn reset(&mut self) {
+        self.grid = self.givens;
+    }
+
+    /// Every cell whose cat clashes with another (see `BoardState::get_conflicts`).
+    pub fn conflicts(&self) -> Vec<(usize, usize)> {
+        BoardState::from(self).get_conflicts()
+    }
+
+    /// Whether the grid is full with no clashes.
+    pub fn is_solved(&self) -> bool {
+        self.grid.is_full() && self.conflicts().is_empty()
+    }
+}
+
+impl From<&BoardState> for Puzzle {
+    fn from(board: &BoardState) -> Self {
+        let mut givens = Grid::new(board.grid_size);
+        for (row, col) in (0..board.size()).flat_map(|row| (0..board.size()).map(move |col| (row, col))) {
+            if board.is_given_cell(row, col) {
+                givens.set(row, col, board.cells[row][col]);
+            }
+        }
+        Self {
+            givens,
+            grid: Grid::from(board),
+            regions: board.regions.clone(),
+            cages: board.cages.clone(),
+            windows: board.windows.clone(),
+        }
+    }
+}
+
+impl From<&Puzzle> for BoardState {
+    /// A board to play the puzzle on: givens marked as such, everything else
+    /// as filled in by the player, and no notes.
+    fn from(puzzle: &Puzzle) -> Self {
+        let mut board = BoardState::with_size(puzzle.grid_size());
+        board.cells = puzzle.grid.to_rows();
+        for ((row, col), value) in puzzle.grid.cells() {
+            board.cell_types[row][col] = match value {
+                Some(_) if puzzle.is_given(row, col) => Some(CellType::Given),
+                Some(_) => Some(CellType::Player),
+                None => None,
+            };
+        }
+        board.regions = puzzle.regions.clone();
+        board.cages = puzzle.cages.clone();
+        board.windows = puzzle.windows.clone();
+        board
+    }
+}
+
+impl BoardState {
+    /// The board's cats as a `Grid`.
+    pub fn grid(&self) -> Grid {
+        Grid::from(self)
+    }
+
+    /// The board as a `Puzzle`, leaving the play state behind.
+    pub fn puzzle(&self) -> Puzzle {
+        Puzzle::from(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PresetKind, PuzzleSettings, Variant};
+
+    #[test]
+    fn test_grid() {
+        let mut grid = Grid::new(GridSize::Mini);
+        grid.set(0, 0, Some(2));
+        grid.set(3, 3, Some(9)); // Too big for a 4x4 board
+        grid.set(5, 5, Some(0)); // Off the board
+        let copy = grid;
+        assert_eq!(copy.get(0, 0), Some(2));
+        assert_eq!(copy.filled_count(), 1);
+        assert!(!copy.is_full());
+        assert_eq!(copy.to_string(), "3...............");
+        assert_eq!("3... .... .... ....".parse::<Grid>(), Ok(grid));
+        assert!("3..".parse::<Grid>().is_err());
+    }
+
+    #[test]
+    fn test_grid_from_solution() {
+        let solution = Solution { cells: vec![vec![0, 1, 2, 3], vec![2, 3, 0, 1], vec![1, 0, 3, 2], vec![3, 2, 1, 0]] };
+        let grid = Grid::try_from(&solution).expect("Solution should fit a grid");
+        assert_eq!(grid.grid_size(), GridSize::Mini);
+        assert_eq!(grid.to_solution().map(|solved| solved.cells), Some(solution.cells));
+
+        // Solutions that aren't a board are errors, not guesses at one
+        let odd_size = Solution { cells: vec![vec![0; 5]; 5] };
+        assert!(matches!(Grid::try_from(&odd_size), Err(NineLivesError::InvalidPuzzle { .. })));
+        let ragged = Solution { cells: vec![vec![0; 4], vec![0; 4], vec![0; 3], vec![0; 4]] };
+        assert!(Grid::try_from(&ragged).is_err());
+        let too_big = Solution { cells: vec![vec![4; 4]; 4] };
+        assert!(Grid::try_from(&too_big).is_err());
+    }
+
+    #[test]
+    fn test_puzzle_round_trips_through_a_board() {
+        let settings = PuzzleSettings::from_preset(PresetKind::CozyKitten).with_variant(Variant::Killer);
+        let mut board = BoardState::new();
+        let solution = board.generate_puzzle_with_settings(&settings).expect("Puzzle should generate");
+        let (row, col) = board.empty_cells().next().unwrap();
+        board.set_cell(row, col, Some(solution.cells[row][col]));
+
+        let mut puzzle = board.puzzle();
+        let given = (0..81).map(|index| (index / 9, index % 9)).find(|&(r, c)| board.is_given_cell(r, c)).unwrap();
+        assert!(puzzle.is_given(given.0, given.1));
+        assert!(!puzzle.is_given(row, col));
+        assert_eq!(puzzle.grid.get(row, col), Some(solution.cells[row][col]));
+        assert_eq!(puzzle.cages, board.cages);
+
+        let played = BoardState::from(&puzzle);
+        assert_eq!(played.cells, board.cells);
+        assert_eq!(played.cell_types, board.cell_types);
+        assert_eq!(played.regions, board.regions);
+
+        // Givens stay put, and filling in the solution solves it
+        assert!(!puzzle.place(given.0, given.1, None));
+        let solved = Grid::try_from(&solution).expect("Solution should fit a grid");
+        for ((r, c), value) in solved.cells() {
+            puzzle.place(r, c, value);
+        }
+        assert!(puzzle.is_solved());
+        puzzle.reset();
+        assert_eq!(puzzle.grid, puzzle.givens);
+    }
+}
@@ -0,0 +1,543 @@
+//! Difficulty rating: how hard a puzzle is for a person solving it by logic.
+//!
+//! The rater solves the puzzle the way a player would, always reaching for
+//! the easiest technique that makes progress. When no technique in its
+//! repertoire applies, it has to guess, and it notes how many candidates the
+//! guessed cell had (the branching factor). The techniques it needed, the
+//! guesses, and a weighted tally of every step make up the report.
+//!
+//! The same solve can be taken a step at a time with a `StepSolver`, which
+//! reports each step as a `SolverStep`: the technique, the cells it looked
+//! at, what it placed or ruled out, and a caption explaining it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::search::legal_mask;
+use crate::{BoardState, Difficulty, Solution, solve_unique};
+
+/// A solving technique, from easiest to hardest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Technique {
+    /// A cat with only one place left in a house.
+    HiddenSingle,
+    /// A cell with only one candidate left.
+    NakedSingle,
+    /// A cat confined to one line of a box (or one box of a line), ruling it
+    /// out of the rest of that line (or box).
+    LockedCandidates,
+    /// Two cells in a house sharing the same two candidates, ruling those
+    /// cats out of the rest of the house.
+    NakedPair,
+    /// No technique applies, so a cell has to be guessed.
+    Guess,
+}
+
+impl Technique {
+    /// Get all techniques from easiest to hardest.
+    pub fn all() -> [Technique; 5] {
+        [
+            Technique::HiddenSingle,
+            Technique::NakedSingle,
+            Technique::LockedCandidates,
+            Technique::NakedPair,
+            Technique::Guess,
+        ]
+    }
+
+    /// Get the display name for this technique.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Technique::HiddenSingle => "Hidden single",
+            Technique::NakedSingle => "Naked single",
+            Technique::LockedCandidates => "Locked candidates",
+            Technique::NakedPair => "Naked pair",
+            Technique::Guess => "Guess",
+        }
+    }
+
+    /// How much one use of the technique adds to a puzzle's score.
+    pub fn weight(&self) -> u32 {
+        match self {
+            Technique::HiddenSingle => 1,
+            Technique::NakedSingle => 2,
+            Technique::LockedCandidates => 4,
+            Technique::NakedPair => 5,
+            Technique::Guess => 10,
+        }
+    }
+}
+
+/// How hard a puzzle is, as worked out by `rate_difficulty`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifficultyReport {
+    /// Whether the puzzle has exactly one solution. Puzzles that don't
+    /// aren't rated, and the rest of the report is empty.
+    pub unique: bool,
+    /// Each technique the solve needed, easiest first.
+    pub techniques: Vec<Technique>,
+    /// How many cells had to be guessed.
+    pub guesses: usize,
+    /// Average number of candidates in the guessed cells (1.0 without guesses).
+    pub branching_factor: f32,
+    /// Every step of the solve, weighted by its technique. Higher is harder.
+    pub score: u32,
+}
+
+impl DifficultyReport {
+    /// The hardest technique the solve needed, if it needed any.
+    pub fn hardest_technique(&self) -> Option<Technique> {
+        self.techniques.last().copied()
+    }
+
+    /// The difficulty level the puzzle plays like: singles alone are Easy
+    /// (hidden singles) or Medium (naked singles), eliminations are Hard,
+    /// and guessing is Expert.
+    pub fn difficulty(&self) -> Difficulty {
+        match self.hardest_technique() {
+            None | Some(Technique::HiddenSingle) => Difficulty::Easy,
+            Some(Technique::NakedSingle) => Difficulty::Medium,
+            Some(Technique::LockedCandidates | Technique::NakedPair) => Difficulty::Hard,
+            Some(Technique::Guess) => Difficulty::Expert,
+        }
+    }
+}
+
+/// Rate how hard a puzzle is to solve by logic. Player entries on the board
+/// are treated like givens, so rating a half-solved board rates what's left.
+pub fn rate_difficulty(board: &BoardState) -> DifficultyReport {
+    let Some(solution) = solve_unique(board) else {
+        return DifficultyReport {
+            unique: false,
+            techniques: Vec::new(),
+            guesses: 0,
+            branching_factor: 1.0,
+            score: 0,
+        };
+    };
+
+    let mut rater = Rater::new(board);
+    let mut used = Vec::new();
+    let mut branches = Vec::new();
+    let mut score = 0;
+
+    while rater.has_empty_cells() {
+        rater.refresh_candidates();
+        let technique = match rater.logical_step() {
+            Some(step) => step.technique,
+            None => {
+                let (index, candidates) = rater.most_constrained_cell();
+                rater.guess(index, &solution);
+                branches.push(candidates);
+                Technique::Guess
+            }
+        };
+        score += technique.weight();
+        if !used.contains(&technique) {
+            used.push(technique);
+        }
+    }
+
+    used.sort();
+    DifficultyReport {
+        unique: true,
+        techniques: used,
+        guesses: branches.len(),
+        branching_factor: if branches.is_empty() {
+            1.0
+        } else {
+            branches.iter().sum::<u32>() as f32 / branches.len() as f32
+        },
+        score,
+    }
+}
+
+/// One step of a logical solve (see `StepSolver`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolverStep {
+    pub technique: Technique,
+    /// The cells the technique looked at: the house of a hidden single, the
+    /// cells a locked cat is confined to, the cells of a naked pair, ...
+    pub cells: Vec<(usize, usize)>,
+    /// The cat placed, as (row, col, cat), for singles and guesses
+    pub placed: Option<(usize, usize, usize)>,
+    /// The candidates ruled out, as (row, col, cat), for eliminations
+    pub eliminated: Vec<(usize, usize, usize)>,
+    /// What the step did and why, like "Naked single: R3C4 has only one
+    /// candidate left, so cat 5 goes there."
+    pub caption: String,
+}
+
+/// Cats, counting from 1, listed like "3 and 7".
+fn cat_list(bits: u32) -> String {
+    let cats: Vec<String> = (0..32).filter(|value| bits & (1 << value) != 0).map(|value| (value + 1).to_string()).collect();
+    cats.join(" and ")
+}
+
+/// A cell as shown to the player, like "R3C4".
+fn cell_name((row, col): (usize, usize)) -> String {
+    format!("R{}C{}", row + 1, col + 1)
+}
+
+/// A puzzle solved a step at a time, the way `rate_difficulty` solves it,
+/// for watching the technique engine at work.
+pub struct StepSolver {
+    rater: Rater,
+    solution: Option<Solution>,
+}
+
+impl StepSolver {
+    /// Start solving from a board. Player entries are treated like givens.
+    pub fn new(board: &BoardState) -> Self {
+        let mut rater = Rater::new(board);
+        rater.refresh_candidates();
+        Self { rater, solution: solve_unique(board) }
+    }
+
+    /// The board as the solve has filled it so far.
+    pub fn board(&self) -> &BoardState {
+        &self.rater.board
+    }
+
+    /// The cats still possible in a cell, counting from 0.
+    pub fn candidates(&self, row: usize, col: usize) -> Vec<usize> {
+        let bits = self.rater.candidates[row * self.rater.size + col];
+        (0..self.rater.size).filter(|value| bits & (1 << value) != 0).collect()
+    }
+
+    /// Whether every cell is filled.
+    pub fn is_solved(&self) -> bool {
+        !self.rater.has_empty_cells()
+    }
+
+    /// Take the next step: the easiest technique that makes progress, or a
+    /// guess from the solution when none does. None once the board is full,
+    /// or when it's stuck without a unique solution to guess from.
+    pub fn step(&mut self) -> Option<SolverStep> {
+        if self.is_solved() {
+            return None;
+        }
+        self.rater.refresh_candidates();
+        let step = match self.rater.logical_step() {
+            Some(step) => step,
+            None => {
+                let solution = self.solution.as_ref()?;
+                let (index, _) = self.rater.most_constrained_cell();
+                self.rater.guess(index, solution)
+            }
+        };
+        self.rater.refresh_candidates();
+        Some(step)
+    }
+}
+
+/// A board being solved by logic, with a candidate bitmask for every cell.
+struct Rater {
+    board: BoardState,
+    size: usize,
+    /// Candidates of each cell, indexed `row * size + col` (0 for filled cells)
+    candidates: Vec<u32>,
+    /// Every house as a list of cell indices: rows, columns, boxes, then windows
+    houses: Vec<Vec<usize>>,
+}
+
+impl Rater {
+    fn new(board: &BoardState) -> Self {
+        let size = board.size();
+        let index = |(row, col): (usize, usize)| row * size + col;
+        let houses = board.houses().map(|(_, cells)| cells.into_iter().map(index).collect()).collect();
+
+        Self {
+            board: board.clone(),
+            size,
+            candidates: vec![u32::MAX; size * size],
+            houses,
+        }
+    }
+
+    fn has_empty_cells(&self) -> bool {
+        self.board.cells.iter().flatten().any(Option::is_none)
+    }
+
+    /// Narrow every empty cell's candidates to what's still legal, keeping
+    /// earlier eliminations.
+    fn refresh_candidates(&mut self) {
+        for index in 0..self.candidates.len() {
+            let (row, col) = (index / self.size, index % self.size);
+            self.candidates[index] = match self.board.cells[row][col] {
+                Some(_) => 0,
+                None => self.candidates[index] & legal_mask(&self.board, row, col),
+            };
+        }
+    }
+
+    fn place(&mut self, index: usize, value: usize) {
+        let (row, col) = (index / self.size, index % self.size);
+        self.board.cells[row][col] = Some(value);
+        self.candidates[index] = 0;
+    }
+
+    /// The (row, col) of a cell index.
+    fn cell(&self, index: usize) -> (usize, usize) {
+        (index / self.size, index % self.size)
+    }
+
+    /// A house as shown to the player, like "row 3" or "box 5".
+    fn house_name(&self, house: usize) -> String {
+        let (kind, number) = match house / self.size {
+            0 => ("row", house),
+            1 => ("column", house - self.size),
+            2 => ("box", house - 2 * self.size),
+            _ => ("window", house - 3 * self.size),
+        };
+        format!("{} {}", kind, number + 1)
+    }
+
+    /// Take the easiest technique that makes progress, if any does.
+    fn logical_step(&mut self) -> Option<SolverStep> {
+        self.hidden_single()
+            .or_else(|| self.naked_single())
+            .or_else(|| self.locked_candidates())
+            .or_else(|| self.naked_pair())
+    }
+
+    /// Fill a cell from the solution, when no technique applies.
+    fn guess(&mut self, index: usize, solution: &Solution) -> SolverStep {
+        let (row, col) = self.cell(index);
+        let candidates = self.candidates[index].count_ones();
+        let value = solution.cells[row][col];
+        self.place(index, value);
+        SolverStep {
+            technique: Technique::Guess,
+            cells: vec![(row, col)],
+            placed: Some((row, col, value)),
+            eliminated: Vec::new(),
+            caption: format!(
+                "Guess: no technique applies, so {} (with {} candidates) is filled in with cat {} from the solution.",
+                cell_name((row, col)),
+                candidates,
+                value + 1
+            ),
+        }
+    }
+
+    /// Place a cat that has only one spot left in some house.
+    fn hidden_single(&mut self) -> Option<SolverStep> {
+        for house in 0..self.houses.len() {
+            for value in 0..self.size {
+                let bit = 1 << value;
+                let mut spots = self.houses[house].iter().filter(|&&index| self.candidates[index] & bit != 0);
+                if let (Some(&index), None) = (spots.next(), spots.next()) {
+                    self.place(index, value);
+                    let (row, col) = self.cell(index);
+                    return Some(SolverStep {
+                        technique: Technique::HiddenSingle,
+                        cells: self.houses[house].iter().map(|&index| self.cell(index)).collect(),
+                        placed: Some((row, col, value)),
+                        eliminated: Vec::new(),
+                        caption: format!(
+                            "Hidden single: cat {} has only one place left in {}, so it goes in {}.",
+                            value + 1,
+                            self.house_name(house),
+                            cell_name((row, col))
+                        ),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Place the only candidate of a cell that has just one.
+    fn naked_single(&mut self) -> Option<SolverStep> {
+        let index = (0..self.candidates.len()).find(|&index| self.candidates[index].count_ones() == 1)?;
+        let value = self.candidates[index].trailing_zeros() as usize;
+        self.place(index, value);
+        let (row, col) = self.cell(index);
+        Some(SolverStep {
+            technique: Technique::NakedSingle,
+            cells: vec![(row, col)],
+            placed: Some((row, col, value)),
+            eliminated: Vec::new(),
+            caption: format!(
+                "Naked single: {} has only one candidate left, so cat {} goes there.",
+                cell_name((row, col)),
+                value + 1
+            ),
+        })
+    }
+
+    /// When a cat's spots in one house all lie inside another house, it
+    /// can't go anywhere else in that second house. Steps only when some
+    /// candidate is ruled out.
+    fn locked_candidates(&mut self) -> Option<SolverStep> {
+        for value in 0..self.size {
+            let bit = 1 << value;
+            for house in 0..self.houses.len() {
+                let spots: Vec<usize> =
+                    self.houses[house].iter().copied().filter(|&index| self.candidates[index] & bit != 0).collect();
+                if spots.len() < 2 {
+                    continue;
+                }
+                for other in 0..self.houses.len() {
+                    if other == house || !spots.iter().all(|index| self.houses[other].contains(index)) {
+                        continue;
+                    }
+                    let eliminated = self.eliminate(other, bit, &spots);
+                    if !eliminated.is_empty() {
+                        return Some(SolverStep {
+                            technique: Technique::LockedCandidates,
+                            cells: spots.iter().map(|&index| self.cell(index)).collect(),
+                            placed: None,
+                            eliminated,
+                            caption: format!(
+                                "Locked candidates: in {}, cat {} can only go in cells that are also in {}, \
+                                 so it's ruled out of the rest of {}.",
+                                self.house_name(house),
+                                value + 1,
+                                self.house_name(other),
+                                self.house_name(other)
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// When two cells in a house have the same two candidates, those cats go
+    /// in those two cells. Steps only when some candidate is ruled out.
+    fn naked_pair(&mut self) -> Option<SolverStep> {
+        for house in 0..self.houses.len() {
+            let pairs: Vec<usize> =
+                self.houses[house].iter().copied().filter(|&index| self.candidates[index].count_ones() == 2).collect();
+            for (i, &first) in pairs.iter().enumerate() {
+                for &second in &pairs[i + 1..] {
+                    let bits = self.candidates[first];
+                    if bits != self.candidates[second] {
+                        continue;
+                    }
+                    let eliminated = self.eliminate(house, bits, &[first, second]);
+                    if !eliminated.is_empty() {
+                        let cells = vec![self.cell(first), self.cell(second)];
+                        return Some(SolverStep {
+                            technique: Technique::NakedPair,
+                            caption: format!(
+                                "Naked pair: {} and {} can only hold cats {}, so those cats are ruled out of the rest of {}.",
+                                cell_name(cells[0]),
+                                cell_name(cells[1]),
+                                cat_list(bits),
+                                self.house_name(house)
+                            ),
+                            cells,
+                            placed: None,
+                            eliminated,
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Rule the cats in `bits` out of every cell of a house except `keep`.
+    /// Returns what was ruled out, as (row, col, cat).
+    fn eliminate(&mut self, house: usize, bits: u32, keep: &[usize]) -> Vec<(usize, usize, usize)> {
+        let mut eliminated = Vec::new();
+        for i in 0..self.houses[house].len() {
+            let index = self.houses[house][i];
+            let removed = self.candidates[index] & bits;
+            if keep.contains(&index) || removed == 0 {
+                continue;
+            }
+            self.candidates[index] &= !bits;
+            let (row, col) = self.cell(index);
+            eliminated.extend((0..self.size).filter(|value| removed & (1 << value) != 0).map(|value| (row, col, value)));
+        }
+        eliminated
+    }
+
+    /// The empty cell with the fewest candidates, and how many it has.
+    fn most_constrained_cell(&self) -> (usize, u32) {
+        (0..self.candidates.len())
+            .filter(|&index| self.board.cells[index / self.size][index % self.size].is_none())
+            .map(|index| (index, self.candidates[index].count_ones()))
+            .min_by_key(|&(_, count)| count)
+            .unwrap_or((0, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PresetKind, PuzzleSettings};
+
+    /// A board from an 81-character puzzle string ('.' for empty cells).
+    fn board_from(puzzle: &str) -> BoardState {
+        let mut board = BoardState::new();
+        for (index, c) in puzzle.chars().enumerate() {
+            if let Some(digit) = c.to_digit(10) {
+                board.cells[index / 9][index % 9] = Some(digit as usize - 1);
+                board.cell_types[index / 9][index % 9] = Some(crate::CellType::Given);
+            }
+        }
+        board
+    }
+
+    #[test]
+    fn test_rate_difficulty() {
+        // A generated puzzle needs at least one technique and no contradictions
+        let mut board = BoardState::new();
+        board.generate_puzzle_with_settings(&PuzzleSettings::from_preset(PresetKind::CozyKitten)).expect("Puzzle should generate");
+        let report = rate_difficulty(&board);
+        assert!(report.unique);
+        assert!(report.score > 0);
+        assert!(!report.techniques.is_empty());
+
+        // One of the hardest known classic puzzles is far beyond singles
+        let hard = board_from("8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..");
+        let report = rate_difficulty(&hard);
+        assert!(report.unique);
+        assert_eq!(report.hardest_technique(), Some(Technique::Guess));
+        assert_eq!(report.difficulty(), Difficulty::Expert);
+        assert!(report.branching_factor >= 2.0);
+
+        // A solved board has nothing left to rate; an empty one has no unique solution
+        let mut solved = BoardState::new();
+        solved.generate_puzzle(81);
+        let report = rate_difficulty(&solved);
+        assert_eq!((report.score, report.difficulty()), (0, Difficulty::Easy));
+        assert!(!rate_difficulty(&BoardState::new()).unique);
+    }
+
+    #[test]
+    fn test_step_solver() {
+        let hard = board_from("8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..");
+        let solution = solve_unique(&hard).unwrap();
+        let mut solver = StepSolver::new(&hard);
+        assert!(solver.candidates(0, 0).is_empty(), "Givens have no candidates");
+        assert!(solver.candidates(0, 1).len() > 1);
+
+        // Stepping through takes the same steps the rating counts, and never strays from the solution
+        let mut score = 0;
+        let mut techniques = Vec::new();
+        while let Some(step) = solver.step() {
+            score += step.technique.weight();
+            techniques.push(step.technique);
+            assert!(!step.cells.is_empty() && !step.caption.is_empty());
+            if let Some((row, col, value)) = step.placed {
+                assert_eq!(solution.cells[row][col], value, "{}", step.caption);
+            }
+            for &(row, col, value) in &step.eliminated {
+                assert_ne!(solution.cells[row][col], value, "{}", step.caption);
+            }
+        }
+        assert!(solver.is_solved());
+        assert_eq!(score, rate_difficulty(&hard).score);
+        assert!(techniques.contains(&Technique::Guess));
+        assert!(solver.step().is_none());
+
+        // Without a unique solution there's nothing to guess from
+        assert!(StepSolver::new(&BoardState::new()).step().is_none());
+    }
+}
@@ -0,0 +1,177 @@
+//! Region layouts: which cells make up each of a board's boxes.
+//!
+//! Classic boards split into the usual rectangular boxes. Jigsaw puzzles swap
+//! those for irregular regions: connected shapes with as many cells as a
+//! row, each holding every cat exactly once, just like a box.
+
+use std::collections::VecDeque;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::GridSize;
+use crate::variants::neighbours;
+
+/// How many trades to try per cell when drawing a jigsaw layout.
+const JIGSAW_TRADES_PER_CELL: usize = 20;
+
+/// Which region every cell belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionMap {
+    /// Region index of each cell, indexed as `[row][col]`.
+    regions: Vec<Vec<usize>>,
+}
+
+impl RegionMap {
+    /// The standard rectangular boxes for a board size.
+    pub fn boxes(grid_size: GridSize) -> Self {
+        let size = grid_size.dimension();
+        Self {
+            regions: (0..size)
+                .map(|row| (0..size).map(|col| grid_size.box_index(row, col)).collect())
+                .collect(),
+        }
+    }
+
+    /// A random jigsaw layout shaped around a solved grid. Starts from the
+    /// standard boxes and repeatedly trades cells between neighbouring
+    /// regions: a cell on a region's edge joins its neighbour, which gives
+    /// back its own cell with the same cat. Every region still holds each cat
+    /// exactly once, so the solution stays valid; a trade is kept only if
+    /// both regions stay in one piece.
+    pub fn jigsaw<R: Rng>(solution: &[Vec<usize>], grid_size: GridSize, rng: &mut R) -> Self {
+        let size = grid_size.dimension();
+        let mut map = Self::boxes(grid_size);
+
+        for _ in 0..size * size * JIGSAW_TRADES_PER_CELL {
+            let (row, col) = (rng.gen_range(0..size), rng.gen_range(0..size));
+            let region = map.regions[row][col];
+            let across_edge: Vec<usize> = neighbours(row, col, size)
+                .map(|(r, c)| map.regions[r][c])
+                .filter(|&other| other != region)
+                .collect();
+            let Some(&other) = across_edge.choose(rng) else {
+                continue; // Not on a region edge
+            };
+            let Some((back_row, back_col)) = map
+                .cells_in(other)
+                .find(|&(r, c)| solution[r][c] == solution[row][col])
+            else {
+                continue;
+            };
+
+            map.regions[row][col] = other;
+            map.regions[back_row][back_col] = region;
+            if !map.is_connected(region) || !map.is_connected(other) {
+                map.regions[row][col] = region;
+                map.regions[back_row][back_col] = other;
+            }
+        }
+        map
+    }
+
+    /// A layout from raw region indices, as read from a share code.
+    /// Check it with `is_valid_for` before use.
+    pub(crate) fn from_rows(regions: Vec<Vec<usize>>) -> Self {
+        Self { regions }
+    }
+
+    /// Number of rows (and regions) on the board.
+    pub fn size(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// The region containing (row, col).
+    pub fn region_of(&self, row: usize, col: usize) -> usize {
+        self.regions[row][col]
+    }
+
+    /// Every cell in a region, in reading order.
+    pub fn cells_in(&self, region: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let size = self.size();
+        (0..size)
+            .flat_map(move |row| (0..size).map(move |col| (row, col)))
+            .filter(move |&(row, col)| self.regions[row][col] == region)
+    }
+
+    /// Whether this is the standard box layout for the board size.
+    pub fn is_boxes(&self, grid_size: GridSize) -> bool {
+        self.size() == grid_size.dimension()
+            && self.regions.iter().enumerate().all(|(row, regions)| {
+                regions.iter().enumerate().all(|(col, &region)| region == grid_size.box_index(row, col))
+            })
+    }
+
+    /// Whether the map fits a board size: every region has one cell per row
+    /// of the board and is a single connected piece. Used to vet loaded saves.
+    pub fn is_valid_for(&self, grid_size: GridSize) -> bool {
+        let size = grid_size.dimension();
+        if self.regions.len() != size || self.regions.iter().any(|row| row.len() != size) {
+            return false;
+        }
+        let mut counts = vec![0; size];
+        for &region in self.regions.iter().flatten() {
+            if region >= size {
+                return false;
+            }
+            counts[region] += 1;
+        }
+        counts.iter().all(|&count| count == size) && (0..size).all(|region| self.is_connected(region))
+    }
+
+    /// Whether every cell of a region can reach the others through orthogonal steps.
+    fn is_connected(&self, region: usize) -> bool {
+        let size = self.size();
+        let Some(start) = self.cells_in(region).next() else {
+            return true;
+        };
+
+        let mut seen = vec![vec![false; size]; size];
+        seen[start.0][start.1] = true;
+        let mut queue = VecDeque::from([start]);
+        let mut reached = 0;
+        while let Some((row, col)) = queue.pop_front() {
+            reached += 1;
+            for (r, c) in neighbours(row, col, size) {
+                if !seen[r][c] && self.regions[r][c] == region {
+                    seen[r][c] = true;
+                    queue.push_back((r, c));
+                }
+            }
+        }
+        reached == self.cells_in(region).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BoardState;
+
+    #[test]
+    fn test_jigsaw_regions_are_valid() {
+        for grid_size in GridSize::all() {
+            let boxes = RegionMap::boxes(grid_size);
+            assert!(boxes.is_valid_for(grid_size));
+            assert!(boxes.is_boxes(grid_size));
+
+            let mut board = BoardState::with_size(grid_size);
+            assert!(board.fill_board());
+            let solution: Vec<Vec<usize>> =
+                board.cells.iter().map(|row| row.iter().map(|c| c.unwrap()).collect()).collect();
+
+            board.regions = RegionMap::jigsaw(&solution, grid_size, &mut rand::thread_rng());
+            assert!(board.regions.is_valid_for(grid_size), "{:?} jigsaw layout is broken", grid_size);
+            // Tiny boards sometimes have no trade to make; bigger ones always reshape
+            if grid_size.dimension() >= 9 {
+                assert!(!board.regions.is_boxes(grid_size), "{:?} jigsaw layout never changed", grid_size);
+            }
+            assert!(board.get_conflicts().is_empty(), "The solution must still fit the new regions");
+        }
+
+        let mut broken = RegionMap::boxes(GridSize::Mini);
+        broken.regions[0][0] = 3; // Region 3 gains a stray, disconnected cell
+        assert!(!broken.is_valid_for(GridSize::Mini));
+    }
+}
@@ -0,0 +1,303 @@
+//! Solve recordings for watching a game back.
+//!
+//! A `Replay` keeps the board as it was when the puzzle started and every
+//! change made to it since, in order, as a stream of moves. Any point in the
+//! solve can be rebuilt by applying moves forward or undoing them backward
+//! from wherever the viewer currently is, so scrubbing never replays the
+//! whole game from scratch.
+//!
+//! A `SavedReplay` is the same recording in a form that can be written to
+//! disk, so a record-setting solve can be kept and watched again later.
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::{BoardState, CellType, GridSize, Move, SavedMove, Solution};
+
+/// What a step in a replay was, so key moments can be marked on a timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayEventKind {
+    /// An ordinary placement, change, or erasure by the player.
+    Move,
+    /// A cat placed by a hint.
+    Hint,
+    /// A cat that doesn't match the solution.
+    Mistake,
+}
+
+/// One change to the board during the solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayStep {
+    pub game_move: SavedMove,
+    pub kind: ReplayEventKind,
+}
+
+/// The recording of the current puzzle's solve.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct Replay {
+    /// The board before the first step.
+    start: BoardState,
+    /// The board after the last step, to spot what changed next.
+    end: BoardState,
+    pub steps: Vec<ReplayStep>,
+}
+
+impl Replay {
+    /// Start a fresh recording from a board.
+    pub fn new(board: &BoardState) -> Self {
+        Self {
+            start: board.clone(),
+            end: board.clone(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Number of steps recorded.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Whether nothing has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// The board as it was before the first step.
+    pub fn start(&self) -> &BoardState {
+        &self.start
+    }
+
+    /// Bring the recording up to date with the board, adding a step for
+    /// every cell that changed since the last call. `hinted` is the cell a
+    /// hint just filled, if any. A board with different givens is a new
+    /// puzzle, so the recording starts over from it.
+    pub fn track(&mut self, board: &BoardState, solution: &Solution, hinted: Option<(usize, usize)>) {
+        let same_puzzle = board.grid_size == self.end.grid_size
+            && (0..board.size()).all(|row| {
+                (0..board.size()).all(|col| board.is_given_cell(row, col) == self.end.is_given_cell(row, col))
+            });
+        if !same_puzzle {
+            *self = Self::new(board);
+            return;
+        }
+
+        for row in 0..board.size() {
+            for col in 0..board.size() {
+                let (old_value, new_value) = (self.end.cells[row][col], board.cells[row][col]);
+                if old_value == new_value {
+                    continue;
+                }
+                let correct = solution.cells.get(row).and_then(|cells| cells.get(col)).copied();
+                let kind = if hinted == Some((row, col)) {
+                    ReplayEventKind::Hint
+                } else if new_value.is_some() && correct.is_some() && new_value != correct {
+                    ReplayEventKind::Mistake
+                } else {
+                    ReplayEventKind::Move
+                };
+                self.steps.push(ReplayStep {
+                    game_move: SavedMove { row, col, old_value, new_value },
+                    kind,
+                });
+            }
+        }
+        self.end = board.clone();
+    }
+
+    /// Take back the last step if it's `game_move`, as when the first click
+    /// of a double-click is replaced by the cat the double-click fills in, so
+    /// the fill is recorded as one step from before the click. Returns
+    /// whether the step was taken back.
+    pub fn take_back(&mut self, game_move: &Move) -> bool {
+        if self.steps.last().is_none_or(|step| step.game_move != SavedMove::from(game_move)) {
+            return false;
+        }
+        self.steps.pop();
+        self.end.undo_move(game_move);
+        true
+    }
+
+    /// Move a board that shows the solve after `from` steps to show it after
+    /// `to` steps instead, applying or undoing the moves in between.
+    /// Positions past the end are clamped. Returns the new position.
+    pub fn seek(&self, board: &mut BoardState, from: usize, to: usize) -> usize {
+        let (from, to) = (from.min(self.len()), to.min(self.len()));
+        if to > from {
+            for step in &self.steps[from..to] {
+                board.apply_move(&step.game_move.to_move());
+            }
+        } else {
+            for step in self.steps[to..from].iter().rev() {
+                board.undo_move(&step.game_move.to_move());
+            }
+        }
+        to
+    }
+
+    /// How many cats were placed that don't match the solution.
+    pub fn mistakes(&self) -> usize {
+        self.steps.iter().filter(|step| step.kind == ReplayEventKind::Mistake).count()
+    }
+
+    /// The steps worth marking on a timeline (hints and mistakes), with their positions.
+    pub fn key_events(&self) -> impl Iterator<Item = (usize, ReplayEventKind)> + '_ {
+        self.steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| step.kind != ReplayEventKind::Move)
+            .map(|(index, step)| (index, step.kind))
+    }
+
+    /// The recording in a form that can be saved.
+    pub fn to_saved(&self) -> SavedReplay {
+        SavedReplay {
+            grid_size: self.start.grid_size,
+            cells: self.start.cells.clone(),
+            cell_types: self.start.cell_types.clone(),
+            steps: self.steps.clone(),
+        }
+    }
+}
+
+/// A replay as stored alongside a leaderboard record. Only the starting
+/// cats are kept; cages and regions don't change during a solve and the
+/// board is all a viewer needs to show.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedReplay {
+    pub grid_size: GridSize,
+    pub cells: Vec<Vec<Option<usize>>>,
+    pub cell_types: Vec<Vec<Option<CellType>>>,
+    pub steps: Vec<ReplayStep>,
+}
+
+impl SavedReplay {
+    /// Rebuild the recording. Steps that don't fit the board (from a damaged
+    /// file) are dropped rather than replayed.
+    pub fn restore(&self) -> Replay {
+        let mut start = BoardState::with_size(self.grid_size);
+        let size = start.size();
+        if is_square(&self.cells, size) && is_square(&self.cell_types, size) {
+            start.cells = self.cells.clone();
+            start.cell_types = self.cell_types.clone();
+        }
+
+        let mut replay = Replay::new(&start);
+        replay.steps = self
+            .steps
+            .iter()
+            .filter(|step| step.game_move.row < size && step.game_move.col < size)
+            .copied()
+            .collect();
+        let mut end = start;
+        replay.seek(&mut end, 0, replay.len());
+        replay.end = end;
+        replay
+    }
+}
+
+/// Whether a grid has `size` rows of `size` cells.
+fn is_square<T>(grid: &[Vec<T>], size: usize) -> bool {
+    grid.len() == size && grid.iter().all(|row| row.len() == size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PresetKind, PuzzleSettings};
+
+    #[test]
+    fn test_replay_scrubbing() {
+        let mut board = BoardState::new();
+        let solution = board
+            .generate_puzzle_with_settings(&PuzzleSettings::from_preset(PresetKind::CozyKitten))
+            .unwrap();
+        let mut replay = Replay::new(&board);
+
+        let empty: Vec<(usize, usize)> = (0..9)
+            .flat_map(|row| (0..9).map(move |col| (row, col)))
+            .filter(|&(row, col)| board.cells[row][col].is_none())
+            .take(3)
+            .collect();
+        let (wrong_row, wrong_col) = empty[1];
+        let wrong = (solution.cells[wrong_row][wrong_col] + 1) % 9;
+
+        // A correct move, a mistake, the mistake fixed, then a hint
+        let (row, col) = empty[0];
+        board.set_cell(row, col, Some(solution.cells[row][col]));
+        replay.track(&board, &solution, None);
+        board.set_cell(wrong_row, wrong_col, Some(wrong));
+        replay.track(&board, &solution, None);
+        board.set_cell(wrong_row, wrong_col, None);
+        replay.track(&board, &solution, None);
+        let (row, col) = empty[2];
+        board.set_cell(row, col, Some(solution.cells[row][col]));
+        replay.track(&board, &solution, Some((row, col)));
+
+        assert_eq!(replay.len(), 4);
+        let marks: Vec<(usize, ReplayEventKind)> = replay.key_events().collect();
+        assert_eq!(marks, vec![(1, ReplayEventKind::Mistake), (3, ReplayEventKind::Hint)]);
+        assert_eq!(replay.mistakes(), 1);
+
+        // Scrub back to the mistake, then all the way to each end
+        let mut view = board.clone();
+        let position = replay.seek(&mut view, replay.len(), 2);
+        assert_eq!(position, 2);
+        assert_eq!(view.cells[wrong_row][wrong_col], Some(wrong));
+        assert_eq!(view.cells[row][col], None);
+        replay.seek(&mut view, 2, 0);
+        assert_eq!(view.cells, replay.start().cells);
+        assert_eq!(replay.seek(&mut view, 0, usize::MAX), 4);
+        assert_eq!(view.cells, board.cells);
+
+        // A step replaced straight away (by a double-click's fill) is taken
+        // back, so the replacement is one step from before it
+        let first_click = board.set_cell(wrong_row, wrong_col, Some(wrong)).unwrap();
+        replay.track(&board, &solution, None);
+        assert!(replay.take_back(&first_click));
+        assert!(!replay.take_back(&first_click), "Only the last step can be taken back");
+        board.set_cell(wrong_row, wrong_col, Some(solution.cells[wrong_row][wrong_col]));
+        replay.track(&board, &solution, None);
+        assert_eq!(replay.len(), 5);
+        let filled = replay.steps[4].game_move;
+        assert_eq!((filled.old_value, filled.new_value), (None, Some(solution.cells[wrong_row][wrong_col])));
+
+        // A new puzzle starts a new recording
+        let mut other = BoardState::new();
+        other.generate_puzzle_with_settings(&PuzzleSettings::from_preset(PresetKind::CuriousCat)).expect("Puzzle should generate");
+        replay.track(&other, &solution, None);
+        assert!(replay.is_empty());
+    }
+
+    #[test]
+    fn test_saved_replay_round_trip() {
+        let mut board = BoardState::new();
+        let solution = board
+            .generate_puzzle_with_settings(&PuzzleSettings::from_preset(PresetKind::CozyKitten))
+            .unwrap();
+        let mut replay = Replay::new(&board);
+        let empty: Vec<(usize, usize)> = (0..9)
+            .flat_map(|row| (0..9).map(move |col| (row, col)))
+            .filter(|&(row, col)| board.cells[row][col].is_none())
+            .take(2)
+            .collect();
+        for (index, &(row, col)) in empty.iter().enumerate() {
+            board.set_cell(row, col, Some(solution.cells[row][col]));
+            replay.track(&board, &solution, (index == 1).then_some((row, col)));
+        }
+
+        let json = serde_json::to_string(&replay.to_saved()).unwrap();
+        let restored = serde_json::from_str::<SavedReplay>(&json).unwrap().restore();
+        assert_eq!(restored.steps, replay.steps);
+        assert_eq!(restored.start().cells, replay.start().cells);
+        let mut view = restored.start().clone();
+        restored.seek(&mut view, 0, restored.len());
+        assert_eq!(view.cells, board.cells);
+
+        // Steps off the board are dropped
+        let mut damaged = replay.to_saved();
+        damaged.steps[0].game_move.row = 40;
+        assert_eq!(damaged.restore().len(), 1);
+    }
+}
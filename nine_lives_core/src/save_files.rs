@@ -0,0 +1,325 @@
+//! Where save files live, and how they're written.
+//!
+//! Saves are read and written through a [`StorageBackend`], picked by target
+//! with [`default_storage`]: files on the desktop, the browser's
+//! `localStorage` in the web build (where there's no file system to write to).
+//!
+//! On the desktop, saves go in the platform's data directory: `~/.local/share/nine_lives` on
+//! Linux (or wherever `XDG_DATA_HOME` points), `~/Library/Application
+//! Support/nine_lives` on macOS, and `%APPDATA%\nine_lives` on Windows. Saves
+//! from before that, in `~/.nine_lives`, are moved over the first time.
+//!
+//! Files are written to a temporary file first and then renamed over the
+//! old one, so a crash mid-write never leaves half a save behind. The file
+//! being replaced is kept alongside as `<name>.bak`, to fall back on if the
+//! new one turns out to be damaged. The web build keeps the same backup under
+//! its own key.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Name of the game's folder inside the platform's data directory
+const SAVE_DIRECTORY_NAME: &str = "nine_lives";
+/// Where saves lived before they moved to the platform's data directory
+const LEGACY_SAVE_DIRECTORY_NAME: &str = ".nine_lives";
+
+// --- Backends ---
+
+/// Somewhere saves can be kept, by name (like `nine_lives_data.json`).
+pub trait StorageBackend {
+    /// The contents saved under `key`, or None if there aren't any or they
+    /// can't be read.
+    fn read(&self, key: &str) -> Option<String>;
+
+    /// Save `contents` under `key`, keeping whatever was there before under
+    /// [`backup_key`] in case the new save turns out to be damaged.
+    fn write(&self, key: &str, contents: &str) -> std::io::Result<()>;
+}
+
+/// Where a save's previous contents are kept, like `nine_lives_data.json.bak`.
+pub fn backup_key(key: &str) -> String {
+    format!("{}.bak", key)
+}
+
+/// Saves as files in a folder, replaced atomically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStorage {
+    pub dir: PathBuf,
+}
+
+impl FileStorage {
+    /// Files in the standard save directory.
+    pub fn new() -> Self {
+        Self { dir: get_save_directory() }
+    }
+}
+
+impl Default for FileStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StorageBackend for FileStorage {
+    fn read(&self, key: &str) -> Option<String> {
+        fs::read_to_string(self.dir.join(key)).ok()
+    }
+
+    fn write(&self, key: &str, contents: &str) -> std::io::Result<()> {
+        write_atomically(&self.dir.join(key), contents)
+    }
+}
+
+/// Saves in the browser's `localStorage`, which keeps them between visits.
+/// Keys are prefixed with `nine_lives/` so they don't clash with anything
+/// else on the page's origin.
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalStorage;
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    fn item_name(key: &str) -> String {
+        format!("nine_lives/{}", key)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl StorageBackend for LocalStorage {
+    fn read(&self, key: &str) -> Option<String> {
+        Self::storage()?.get_item(&Self::item_name(key)).ok().flatten()
+    }
+
+    fn write(&self, key: &str, contents: &str) -> std::io::Result<()> {
+        let storage = Self::storage().ok_or_else(|| std::io::Error::other("localStorage isn't available"))?;
+        let set = |name: &str, value: &str| {
+            storage
+                .set_item(name, value)
+                .map_err(|e| std::io::Error::other(format!("localStorage refused the save: {:?}", e)))
+        };
+        if let Ok(Some(previous)) = storage.get_item(&Self::item_name(key)) {
+            set(&Self::item_name(&backup_key(key)), &previous)?;
+        }
+        set(&Self::item_name(key), contents)
+    }
+}
+
+/// The storage saves use on this target: files on the desktop,
+/// `localStorage` on the web.
+pub fn default_storage() -> Box<dyn StorageBackend> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        Box::new(LocalStorage)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Box::new(FileStorage::new())
+    }
+}
+
+// --- Files ---
+
+/// Get the standard save directory for the game, moving saves over from
+/// the old `~/.nine_lives` folder if they're still there.
+pub(crate) fn get_save_directory() -> PathBuf {
+    let Some(data_dir) = dirs::data_dir() else {
+        // Fallback to current directory if there's no data directory
+        return PathBuf::from(LEGACY_SAVE_DIRECTORY_NAME);
+    };
+    let save_dir = data_dir.join(SAVE_DIRECTORY_NAME);
+    match dirs::home_dir().map(|home| home.join(LEGACY_SAVE_DIRECTORY_NAME)) {
+        Some(legacy_dir) => migrate_save_directory(&legacy_dir, &save_dir),
+        None => save_dir,
+    }
+}
+
+/// Move the old save folder to its new place, unless there's already
+/// something there. Returns the folder to use: the new one, or the old one
+/// if it couldn't be moved.
+fn migrate_save_directory(legacy_dir: &Path, save_dir: &Path) -> PathBuf {
+    if save_dir.exists() || !legacy_dir.is_dir() {
+        return save_dir.to_path_buf();
+    }
+    let moved = save_dir.parent().is_none_or(|parent| fs::create_dir_all(parent).is_ok())
+        && fs::rename(legacy_dir, save_dir).is_ok();
+    if moved {
+        info!("📁 Moved saves from {:?} to {:?}", legacy_dir, save_dir);
+        save_dir.to_path_buf()
+    } else {
+        warn!("⚠️ Couldn't move saves from {:?}; still using them there", legacy_dir);
+        legacy_dir.to_path_buf()
+    }
+}
+
+/// The backup kept of a save file, like `nine_lives_data.json.bak`.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// Replace a file's contents all at once: write a temporary file, flush it
+/// to disk, keep the current file as the backup, and rename the new one
+/// into place.
+fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut file = fs::File::create(&temp_path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    if path.exists() {
+        fs::copy(path, backup_path(path))?;
+    }
+    fs::rename(&temp_path, path)
+}
+
+/// Read a save, passing its contents to `parse`, and fall back to its
+/// backup if the save is missing, unreadable, or won't parse.
+pub(crate) fn read_with_backup<T, E: std::fmt::Display>(
+    storage: &dyn StorageBackend,
+    key: &str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Option<T> {
+    for candidate in [key.to_string(), backup_key(key)] {
+        let Some(contents) = storage.read(&candidate) else {
+            continue;
+        };
+        match parse(&contents) {
+            Ok(value) => {
+                if candidate != key {
+                    info!("🩹 {} was damaged; recovered from its backup", key);
+                }
+                return Some(value);
+            }
+            Err(e) => warn!("⚠️ Failed to parse {}: {}", candidate, e),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh, empty folder for one test.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nine_lives_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_atomic_writes_keep_a_backup() {
+        let dir = scratch_dir("atomic");
+        let storage = FileStorage { dir: dir.clone() };
+        let path = dir.join("data.json");
+        storage.write("data.json", "1").unwrap();
+        assert!(!backup_path(&path).exists(), "Nothing to back up the first time");
+        storage.write("data.json", "2").unwrap();
+        assert_eq!(storage.read("data.json").as_deref(), Some("2"));
+        assert_eq!(storage.read(&backup_key("data.json")).as_deref(), Some("1"));
+        assert!(!dir.join("data.json.tmp").exists());
+
+        // A damaged save falls back to the backup
+        let parse = |text: &str| text.trim().parse::<u32>();
+        assert_eq!(read_with_backup(&storage, "data.json", parse), Some(2));
+        fs::write(&path, "garbage").unwrap();
+        assert_eq!(read_with_backup(&storage, "data.json", parse), Some(1));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read_with_backup(&storage, "data.json", parse), Some(1));
+        fs::remove_file(backup_path(&path)).unwrap();
+        assert_eq!(read_with_backup(&storage, "data.json", parse), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Saves kept in memory, standing in for the browser's storage.
+    #[derive(Default)]
+    struct MemoryStorage(std::cell::RefCell<std::collections::HashMap<String, String>>);
+
+    impl StorageBackend for MemoryStorage {
+        fn read(&self, key: &str) -> Option<String> {
+            self.0.borrow().get(key).cloned()
+        }
+
+        fn write(&self, key: &str, contents: &str) -> std::io::Result<()> {
+            let mut items = self.0.borrow_mut();
+            if let Some(previous) = items.insert(key.to_string(), contents.to_string()) {
+                items.insert(backup_key(key), previous);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_persistent_data_uses_any_backend() {
+        let storage = MemoryStorage::default();
+        assert_eq!(crate::PersistentData::load_from(&storage).statistics.games_completed, 0);
+
+        let mut data = crate::PersistentData::default();
+        data.statistics.games_completed = 3;
+        data.save_to(&storage).unwrap();
+        data.statistics.games_completed = 4;
+        data.save_to(&storage).unwrap();
+        assert_eq!(crate::PersistentData::load_from(&storage).statistics.games_completed, 4);
+
+        // A damaged save falls back to the one before it
+        storage.0.borrow_mut().insert(crate::PersistentData::SAVE_KEY.to_string(), "{".to_string());
+        assert_eq!(crate::PersistentData::load_from(&storage).statistics.games_completed, 3);
+    }
+
+    #[test]
+    fn test_profiles_keep_their_own_saves() {
+        let storage = MemoryStorage::default();
+        let mut roster = crate::ProfileRoster::load_from(&storage);
+        let mut data = roster.load_active_data_from(&storage);
+        data.statistics.games_completed = 2;
+        data.save_to(&storage).unwrap();
+        assert!(storage.read(crate::PersistentData::SAVE_KEY).is_some(), "The first profile keeps the old save");
+
+        roster.active = roster.add("Whiskers").unwrap();
+        roster.save_to(&storage).unwrap();
+        let mut data = roster.load_active_data_from(&storage);
+        assert_eq!(data.statistics.games_completed, 0);
+        data.statistics.games_completed = 5;
+        data.save_to(&storage).unwrap();
+
+        let mut roster = crate::ProfileRoster::load_from(&storage);
+        assert_eq!(roster.active_profile().unwrap().name, "Whiskers");
+        assert_eq!(roster.load_active_data_from(&storage).statistics.games_completed, 5);
+        roster.active = 0;
+        assert_eq!(roster.load_active_data_from(&storage).statistics.games_completed, 2);
+    }
+
+    #[test]
+    fn test_legacy_save_directory_moves() {
+        let dir = scratch_dir("migrate");
+        let legacy_dir = dir.join(".nine_lives");
+        let save_dir = dir.join("data").join("nine_lives");
+        fs::create_dir_all(&legacy_dir).unwrap();
+        fs::write(legacy_dir.join("nine_lives_data.json"), "{}").unwrap();
+
+        assert_eq!(migrate_save_directory(&legacy_dir, &save_dir), save_dir);
+        assert!(save_dir.join("nine_lives_data.json").exists());
+        assert!(!legacy_dir.exists());
+
+        // Once moved (or with nothing to move), the new folder is used as is
+        fs::create_dir_all(&legacy_dir).unwrap();
+        assert_eq!(migrate_save_directory(&legacy_dir, &save_dir), save_dir);
+        assert!(legacy_dir.exists(), "An old folder is left alone once the new one exists");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
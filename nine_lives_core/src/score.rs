@@ -0,0 +1,172 @@
+//! Scoring: points for solving well, taken away for hints and mistakes.
+//!
+//! The rules are fixed, so the same play always scores the same:
+//! - Every cell filled with its right cat earns `PLACEMENT_POINTS`, once per
+//!   cell, however often it's emptied and filled again.
+//! - A right cat placed within `SPEED_BONUS_SECONDS` of the one before earns
+//!   `SPEED_BONUS` on top.
+//! - Completing a house (row, column, box, or window) that no hint helped
+//!   with earns `CLEAN_HOUSE_BONUS`, once per house.
+//! - Every hint that reveals a cat costs `HINT_PENALTY`, and every wrong cat
+//!   placed costs `MISTAKE_PENALTY`.
+//!
+//! Only cats that settle in their cell are scored (see `GameEvent::Placed`),
+//! so the cats clicked past on the way to the right one cost nothing.
+//!
+//! The score never drops below zero. It's saved with an unfinished game, so
+//! continuing one picks the score up where it was.
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::{HouseKind, Solution};
+
+/// Points for each cell filled with its right cat.
+pub const PLACEMENT_POINTS: u32 = 10;
+/// Bonus for a right cat placed soon after the one before.
+pub const SPEED_BONUS: u32 = 5;
+/// How soon after the last right cat the next must come for the speed bonus.
+pub const SPEED_BONUS_SECONDS: u64 = 15;
+/// Bonus for completing a house no hint helped with.
+pub const CLEAN_HOUSE_BONUS: u32 = 50;
+/// Cost of each hint that reveals a cat.
+pub const HINT_PENALTY: u32 = 30;
+/// Cost of each wrong cat placed.
+pub const MISTAKE_PENALTY: u32 = 20;
+
+/// Keeps the score for the puzzle being played.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct ScoreTracker {
+    /// Cells filled with their right cat
+    pub placements: u32,
+    /// Right cats that earned the speed bonus
+    pub speed_bonuses: u32,
+    /// Houses completed without a hint
+    pub clean_houses: u32,
+    /// Hints that revealed a cat
+    pub hints: u32,
+    /// Wrong cats placed
+    pub mistakes: u32,
+    /// Cells that have already scored (or been given away by a hint)
+    scored: BTreeSet<(usize, usize)>,
+    /// Cells a hint filled in
+    hinted: BTreeSet<(usize, usize)>,
+    /// Houses that have already earned their bonus
+    completed_houses: Vec<(HouseKind, usize)>,
+    /// When the last right cat was placed, in game time
+    last_placement: Option<Duration>,
+}
+
+impl ScoreTracker {
+    /// Score a cat the player placed, `elapsed` into the game.
+    pub fn record_placement(&mut self, solution: &Solution, row: usize, col: usize, value: usize, elapsed: Duration) {
+        if solution.cells.get(row).and_then(|cells| cells.get(col)) != Some(&value) {
+            self.mistakes += 1;
+            return;
+        }
+        if !self.scored.insert((row, col)) {
+            return;
+        }
+        self.placements += 1;
+        if self
+            .last_placement
+            .is_some_and(|last| elapsed.saturating_sub(last) <= Duration::from_secs(SPEED_BONUS_SECONDS))
+        {
+            self.speed_bonuses += 1;
+        }
+        self.last_placement = Some(elapsed);
+    }
+
+    /// Charge for a hint that filled in a cell. The cell scores nothing
+    /// afterwards, and its houses earn no bonus.
+    pub fn record_hint(&mut self, row: usize, col: usize) {
+        self.hints += 1;
+        self.scored.insert((row, col));
+        self.hinted.insert((row, col));
+    }
+
+    /// Score a completed house, given its cells.
+    pub fn record_house(&mut self, kind: HouseKind, index: usize, cells: &[(usize, usize)]) {
+        if self.completed_houses.contains(&(kind, index)) {
+            return;
+        }
+        self.completed_houses.push((kind, index));
+        if !cells.iter().any(|cell| self.hinted.contains(cell)) {
+            self.clean_houses += 1;
+        }
+    }
+
+    /// Points earned before the penalties.
+    pub fn earned(&self) -> u32 {
+        self.placements * PLACEMENT_POINTS + self.speed_bonuses * SPEED_BONUS + self.clean_houses * CLEAN_HOUSE_BONUS
+    }
+
+    /// Points lost to hints and mistakes.
+    pub fn penalties(&self) -> u32 {
+        self.hints * HINT_PENALTY + self.mistakes * MISTAKE_PENALTY
+    }
+
+    /// The score so far.
+    pub fn score(&self) -> u32 {
+        self.earned().saturating_sub(self.penalties())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution() -> Solution {
+        Solution { cells: (0..9).map(|row| (0..9).map(|col| (row * 3 + row / 3 + col) % 9).collect()).collect() }
+    }
+
+    #[test]
+    fn test_placements_and_speed() {
+        let solution = solution();
+        let mut score = ScoreTracker::default();
+        let right = solution.cells[0][0];
+        score.record_placement(&solution, 0, 0, right, Duration::from_secs(5));
+        assert_eq!(score.score(), PLACEMENT_POINTS, "The first cat has nothing to be quick after");
+
+        score.record_placement(&solution, 0, 1, solution.cells[0][1], Duration::from_secs(20));
+        assert_eq!(score.speed_bonuses, 1);
+        score.record_placement(&solution, 0, 2, solution.cells[0][2], Duration::from_secs(60));
+        assert_eq!((score.placements, score.speed_bonuses), (3, 1));
+
+        // Filling a cell again earns nothing more
+        score.record_placement(&solution, 0, 0, right, Duration::from_secs(61));
+        assert_eq!(score.score(), 3 * PLACEMENT_POINTS + SPEED_BONUS);
+
+        // A wrong cat costs points
+        score.record_placement(&solution, 1, 0, (solution.cells[1][0] + 1) % 9, Duration::from_secs(62));
+        assert_eq!(score.mistakes, 1);
+        assert_eq!(score.score(), 3 * PLACEMENT_POINTS + SPEED_BONUS - MISTAKE_PENALTY);
+    }
+
+    #[test]
+    fn test_houses_and_hints() {
+        let row: Vec<(usize, usize)> = (0..9).map(|col| (0, col)).collect();
+        let column: Vec<(usize, usize)> = (0..9).map(|row| (row, 0)).collect();
+        let mut score = ScoreTracker::default();
+        score.record_house(HouseKind::Row, 0, &row);
+        score.record_house(HouseKind::Row, 0, &row);
+        assert_eq!(score.clean_houses, 1, "A house earns its bonus once");
+
+        score.record_hint(4, 0);
+        score.record_house(HouseKind::Column, 0, &column);
+        assert_eq!(score.clean_houses, 1, "A hinted house earns no bonus");
+        assert_eq!(score.score(), CLEAN_HOUSE_BONUS - HINT_PENALTY);
+
+        // The hinted cell scores nothing, and the score stops at zero
+        score.record_placement(&solution(), 4, 0, solution().cells[4][0], Duration::ZERO);
+        assert_eq!(score.placements, 0);
+        score.record_hint(5, 5);
+        score.record_hint(6, 6);
+        assert_eq!(score.score(), 0);
+    }
+}
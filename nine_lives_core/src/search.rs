@@ -0,0 +1,459 @@
+//! Bitmask backtracking search shared by the solver and the generator.
+//!
+//! Each row, column, and box (or jigsaw region) keeps a bitmask of the cats already placed in
+//! it, so a cell's candidates are a couple of bit operations away. The search
+//! always branches on the most constrained choice (MRV): either the empty cell
+//! with the fewest candidates, or the cat with the fewest places left in some
+//! house. Hidden singles fall out of the second case for free, which keeps
+//! even 16x16 boards fast to fill and count.
+//!
+//! Hyper windows are simply extra houses with their own bitmasks.
+//!
+//! Killer cages are tracked alongside the houses: a cat is only a candidate
+//! if it isn't already in the cell's cage and the cage's remaining total can
+//! still be made from the cats left over.
+//!
+//! The cats themselves live in a `Grid` and the layout is shared, so a search
+//! grid is cheap to copy: the generator and the solver snapshot it rather
+//! than cloning the whole board.
+
+use std::rc::Rc;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::{BoardState, Grid, MAX_DIMENSION};
+
+/// Bitmask of the cats that could go in (row, col) without clashing with
+/// the rest of its row, column, box, window, or cage. The cell's own value is
+/// ignored, and unlike `SearchGrid` this works on boards that already hold
+/// mistakes, so it's safe to call mid-game.
+pub(crate) fn legal_mask(board: &BoardState, row: usize, col: usize) -> u32 {
+    let size = board.size();
+    let window = board
+        .window_index(row, col)
+        .into_iter()
+        .flat_map(|window| board.windows[window].iter().map(|&(r, c)| ((r, c), board.cells[r][c])));
+    let peers = board.row(row).chain(board.col(col)).chain(board.box_(board.regions.region_of(row, col))).chain(window);
+
+    let mut used = 0u32;
+    for (cell, cat) in peers {
+        if cell != (row, col)
+            && let Some(value) = cat
+        {
+            used |= 1 << value;
+        }
+    }
+
+    let mut mask = ((1u32 << size) - 1) & !used;
+    if !board.cages.is_empty() {
+        for value in 0..size {
+            if mask & (1 << value) != 0 && !board.cage_allows(row, col, value) {
+                mask &= !(1 << value);
+            }
+        }
+    }
+    mask
+}
+
+/// The parts of a board a search never changes, shared by every copy of a
+/// `SearchGrid`.
+#[derive(Debug)]
+struct SearchLayout {
+    /// Which box (or jigsaw region) each cell belongs to.
+    box_of: Vec<usize>,
+    /// Which Hyper window (if any) each cell belongs to.
+    window_of: Vec<Option<usize>>,
+    /// Which Killer cage (if any) each cell belongs to.
+    cage_of: Vec<Option<usize>>,
+    /// Cell indices of every row, column, box, and window.
+    houses: Vec<Vec<usize>>,
+}
+
+/// A board laid out for fast backtracking.
+#[derive(Debug, Clone)]
+pub(crate) struct SearchGrid {
+    layout: Rc<SearchLayout>,
+    cells: Grid,
+    rows: [u32; MAX_DIMENSION],
+    cols: [u32; MAX_DIMENSION],
+    boxes: [u32; MAX_DIMENSION],
+    /// Cats in each Hyper window.
+    windows: Vec<u32>,
+    /// Running totals for each Killer cage.
+    cages: Vec<CageTrack>,
+    /// How many more search nodes may be visited before giving up.
+    nodes_left: usize,
+}
+
+/// Running totals for one Killer cage.
+#[derive(Debug, Clone)]
+struct CageTrack {
+    sum: usize,
+    cells: usize,
+    used: u32,
+    total: usize,
+    filled: usize,
+}
+
+impl SearchGrid {
+    /// Build a search grid from a board.
+    /// Returns None if the board's filled cells already clash with each other.
+    pub(crate) fn from_board(board: &BoardState) -> Option<Self> {
+        let size = board.size();
+        let mut layout = SearchLayout {
+            box_of: (0..size * size).map(|i| board.regions.region_of(i / size, i % size)).collect(),
+            window_of: vec![None; size * size],
+            cage_of: vec![None; size * size],
+            houses: board
+                .houses()
+                .map(|(_, cells)| cells.into_iter().map(|(row, col)| row * size + col).collect())
+                .collect(),
+        };
+        for (window_index, window) in board.windows.iter().enumerate() {
+            for &(row, col) in window {
+                layout.window_of[row * size + col] = Some(window_index);
+            }
+        }
+        for (cage_index, cage) in board.cages.iter().enumerate() {
+            for &(row, col) in &cage.cells {
+                layout.cage_of[row * size + col] = Some(cage_index);
+            }
+        }
+
+        let mut grid = Self {
+            layout: Rc::new(layout),
+            cells: Grid::new(board.grid_size),
+            rows: [0; MAX_DIMENSION],
+            cols: [0; MAX_DIMENSION],
+            boxes: [0; MAX_DIMENSION],
+            windows: vec![0; board.windows.len()],
+            cages: board
+                .cages
+                .iter()
+                .map(|cage| CageTrack { sum: cage.sum, cells: cage.cells.len(), used: 0, total: 0, filled: 0 })
+                .collect(),
+            nodes_left: usize::MAX,
+        };
+
+        for row in 0..size {
+            for col in 0..size {
+                if let Some(value) = board.cells[row][col] {
+                    let index = row * size + col;
+                    if value >= size || grid.candidates(index) & (1 << value) == 0 {
+                        return None;
+                    }
+                    grid.place(index, value);
+                }
+            }
+        }
+        Some(grid)
+    }
+
+    /// Copy every cell back onto the board (cell types are left untouched).
+    pub(crate) fn write_to(&self, board: &mut BoardState) {
+        for ((row, col), value) in self.cells.cells() {
+            board.cells[row][col] = value;
+        }
+    }
+
+    /// The cats as they stand.
+    pub(crate) fn grid(&self) -> Grid {
+        self.cells
+    }
+
+    /// Empty a cell, returning the cat it held.
+    pub(crate) fn take(&mut self, row: usize, col: usize) -> Option<usize> {
+        let value = self.cells.get(row, col)?;
+        self.unplace(row * self.size() + col, value);
+        Some(value)
+    }
+
+    /// Put a cat back in a cell emptied with `take`.
+    pub(crate) fn put_back(&mut self, row: usize, col: usize, value: usize) {
+        self.place(row * self.size() + col, value);
+    }
+
+    /// Count solutions, stopping once `limit` have been found.
+    pub(crate) fn count_solutions(&mut self, limit: usize) -> usize {
+        let mut count = 0;
+        self.search(&mut |_| {
+            count += 1;
+            count >= limit
+        }, None::<&mut rand::rngs::ThreadRng>);
+        count
+    }
+
+    /// Solve in place. Returns false if there is no solution.
+    pub(crate) fn solve(&mut self) -> bool {
+        self.search(&mut |_| true, None::<&mut rand::rngs::ThreadRng>)
+    }
+
+    /// Fill every empty cell, trying cats in random order for variety.
+    /// Gives up (returning false) after visiting `node_budget` search nodes.
+    pub(crate) fn fill_random<R: Rng>(&mut self, rng: &mut R, node_budget: usize) -> bool {
+        self.nodes_left = node_budget;
+        self.search(&mut |_| true, Some(rng))
+    }
+
+    /// Whether the grid can still be completed with `value` placed at (row, col).
+    /// Gives up with None after visiting `node_budget` search nodes, since a
+    /// few searches on large boards are far slower than the rest.
+    pub(crate) fn solvable_with(&self, row: usize, col: usize, value: usize, node_budget: usize) -> Option<bool> {
+        let index = row * self.size() + col;
+        if self.cells.get(row, col).is_some() || self.candidates(index) & (1 << value) == 0 {
+            return Some(false);
+        }
+        let mut grid = self.clone();
+        grid.place(index, value);
+        grid.nodes_left = node_budget;
+        let solved = grid.solve();
+        if solved || grid.nodes_left > 0 { Some(solved) } else { None }
+    }
+
+    fn size(&self) -> usize {
+        self.cells.size()
+    }
+
+    /// The cat in a cell, by index.
+    fn cell(&self, index: usize) -> Option<usize> {
+        let size = self.size();
+        self.cells.get(index / size, index % size)
+    }
+
+    /// Bitmask of the cats that could go in a cell.
+    fn candidates(&self, index: usize) -> u32 {
+        let mask = self.house_candidates(index);
+        match self.layout.cage_of[index] {
+            Some(cage) if mask != 0 => mask & self.cage_candidates(cage),
+            _ => mask,
+        }
+    }
+
+    /// Bitmask of the cats not yet in a cell's row, column, box, or window.
+    fn house_candidates(&self, index: usize) -> u32 {
+        let size = self.size();
+        let full = (1u32 << size) - 1;
+        let (row, col) = (index / size, index % size);
+        let window = self.layout.window_of[index].map_or(0, |window| self.windows[window]);
+        full & !(self.rows[row] | self.cols[col] | self.boxes[self.layout.box_of[index]] | window)
+    }
+
+    /// Bitmask of the cats that could still go in an empty cell of a cage:
+    /// unused in the cage, and leaving a total the other empty cells can make.
+    fn cage_candidates(&self, cage: usize) -> u32 {
+        let size = self.size();
+        let cage = &self.cages[cage];
+        if cage.filled == cage.cells {
+            return 0;
+        }
+        let free = ((1u32 << size) - 1) & !cage.used;
+        let remaining_sum = cage.sum.saturating_sub(cage.total);
+        let others = cage.cells - cage.filled - 1; // Empty cells besides this one
+
+        let mut mask = 0;
+        for value in (0..size).filter(|v| free & (1 << v) != 0) {
+            let number = value + 1;
+            if others == 0 {
+                if number == remaining_sum {
+                    mask |= 1 << value;
+                }
+                continue;
+            }
+            if number >= remaining_sum {
+                continue;
+            }
+
+            // The other cells need `others` distinct cats from what's left;
+            // their total must fall between the smallest and largest picks.
+            let rest = free & !(1 << value);
+            if (rest.count_ones() as usize) < others {
+                continue;
+            }
+            let smallest: usize = (0..size).filter(|v| rest & (1 << v) != 0).take(others).map(|v| v + 1).sum();
+            let largest: usize = (0..size).rev().filter(|v| rest & (1 << v) != 0).take(others).map(|v| v + 1).sum();
+            if (smallest..=largest).contains(&(remaining_sum - number)) {
+                mask |= 1 << value;
+            }
+        }
+        mask
+    }
+
+    fn place(&mut self, index: usize, value: usize) {
+        let size = self.size();
+        let box_index = self.layout.box_of[index];
+        self.cells.set(index / size, index % size, Some(value));
+        self.rows[index / size] |= 1 << value;
+        self.cols[index % size] |= 1 << value;
+        self.boxes[box_index] |= 1 << value;
+        if let Some(window) = self.layout.window_of[index] {
+            self.windows[window] |= 1 << value;
+        }
+        if let Some(cage) = self.layout.cage_of[index] {
+            let cage = &mut self.cages[cage];
+            cage.used |= 1 << value;
+            cage.total += value + 1;
+            cage.filled += 1;
+        }
+    }
+
+    fn unplace(&mut self, index: usize, value: usize) {
+        let size = self.size();
+        let box_index = self.layout.box_of[index];
+        self.cells.set(index / size, index % size, None);
+        self.rows[index / size] &= !(1 << value);
+        self.cols[index % size] &= !(1 << value);
+        self.boxes[box_index] &= !(1 << value);
+        if let Some(window) = self.layout.window_of[index] {
+            self.windows[window] &= !(1 << value);
+        }
+        if let Some(cage) = self.layout.cage_of[index] {
+            let cage = &mut self.cages[cage];
+            cage.used &= !(1 << value);
+            cage.total -= value + 1;
+            cage.filled -= 1;
+        }
+    }
+
+    /// The most constrained choice left, as the (cell, cat) placements to try.
+    /// None if the grid is full; an empty list means a dead end.
+    fn most_constrained_choice(&self) -> Option<Vec<(usize, usize)>> {
+        let size = self.size();
+        // Each cage's candidates are shared by all of its cells, so work them out once
+        let cage_masks: Vec<u32> = (0..self.cages.len()).map(|cage| self.cage_candidates(cage)).collect();
+        let masks: Vec<u32> = (0..size * size)
+            .map(|i| match (self.cell(i), self.layout.cage_of[i]) {
+                (Some(_), _) => 0,
+                (None, Some(cage)) => self.house_candidates(i) & cage_masks[cage],
+                (None, None) => self.house_candidates(i),
+            })
+            .collect();
+
+        // Fewest candidates for a single cell
+        let (index, mask) = (0..size * size)
+            .filter(|&i| self.cell(i).is_none())
+            .map(|i| (i, masks[i]))
+            .min_by_key(|(_, mask)| mask.count_ones())?;
+        let mut best: Vec<(usize, usize)> = (0..size).filter(|v| mask & (1 << v) != 0).map(|v| (index, v)).collect();
+
+        // Fewest places for a missing cat in a single house
+        for house in &self.layout.houses {
+            if best.len() <= 1 {
+                break; // Can't do better than a forced move (or a dead end)
+            }
+
+            let mut placed = 0u32;
+            let mut places = [0usize; u32::BITS as usize];
+            for &i in house {
+                match self.cell(i) {
+                    Some(value) => placed |= 1 << value,
+                    None => {
+                        let mut mask = masks[i];
+                        while mask != 0 {
+                            places[mask.trailing_zeros() as usize] += 1;
+                            mask &= mask - 1;
+                        }
+                    }
+                }
+            }
+
+            if let Some(value) = (0..size)
+                .filter(|v| placed & (1 << v) == 0)
+                .min_by_key(|&v| places[v])
+                && places[value] < best.len()
+            {
+                best = house
+                    .iter()
+                    .filter(|&&i| masks[i] & (1 << value) != 0)
+                    .map(|&i| (i, value))
+                    .collect();
+            }
+        }
+        Some(best)
+    }
+
+    /// Depth-first search. `on_solution` is called for each complete grid and
+    /// returns true to stop searching; the grid is left solved in that case.
+    /// Running out of node budget also stops the search (returning false).
+    fn search<R: Rng>(&mut self, on_solution: &mut dyn FnMut(&Self) -> bool, mut rng: Option<&mut R>) -> bool {
+        if self.nodes_left == 0 {
+            return false;
+        }
+        self.nodes_left -= 1;
+
+        let Some(mut choices) = self.most_constrained_choice() else {
+            return on_solution(self);
+        };
+
+        if let Some(rng) = rng.as_deref_mut() {
+            choices.shuffle(rng);
+        }
+
+        for (index, value) in choices {
+            self.place(index, value);
+            if self.search(on_solution, rng.as_deref_mut()) {
+                return true;
+            }
+            self.unplace(index, value);
+            if self.nodes_left == 0 {
+                break;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridSize;
+
+    #[test]
+    fn test_fill_and_count_mega_board() {
+        let mut board = BoardState::with_size(GridSize::Mega);
+        let mut grid = SearchGrid::from_board(&board).expect("Empty board is consistent");
+        assert!(grid.fill_random(&mut rand::thread_rng(), usize::MAX));
+        grid.write_to(&mut board);
+        assert!(board.is_complete(), "Filled 16x16 board should be a valid solution");
+
+        // A complete grid has exactly one solution; blanking one cell keeps it unique
+        board.cells[5][5] = None;
+        let mut grid = SearchGrid::from_board(&board).unwrap();
+        assert_eq!(grid.clone().count_solutions(2), 1);
+
+        // Cats taken out and put back leave the grid as it was; copies search on their own
+        let value = grid.take(0, 0).expect("Full cell");
+        let blanked = grid.grid();
+        assert_eq!(grid.clone().count_solutions(2), 1);
+        assert_eq!(grid.grid(), blanked, "Searching a copy leaves the original alone");
+        grid.put_back(0, 0, value);
+        assert_eq!(grid.count_solutions(2), 1);
+
+        // Clashing givens are rejected up front
+        let mut clash = BoardState::new();
+        clash.cells[0][0] = Some(3);
+        clash.cells[0][8] = Some(3);
+        assert!(SearchGrid::from_board(&clash).is_none());
+    }
+
+    #[test]
+    fn test_cages_constrain_the_search() {
+        // With half the cells blanked, solving must still honour every cage
+        let mut board = BoardState::new();
+        assert!(board.fill_board());
+        let solution: Vec<Vec<usize>> = board.cells.iter().map(|row| row.iter().map(|c| c.unwrap()).collect()).collect();
+        board.cages = crate::generate_cages(&solution, GridSize::Classic, &mut rand::thread_rng());
+        for (row, cells) in board.cells.iter_mut().enumerate() {
+            for (col, cell) in cells.iter_mut().enumerate() {
+                if (row + col) % 2 == 0 {
+                    *cell = None;
+                }
+            }
+        }
+
+        let mut grid = SearchGrid::from_board(&board).unwrap();
+        assert!(grid.solve());
+        grid.write_to(&mut board);
+        assert!(board.is_complete(), "Solved Killer grid should satisfy rows, columns, boxes, and cages");
+    }
+}
@@ -0,0 +1,286 @@
+//! Generator integrity checks.
+//!
+//! Generates a batch of puzzles for every preset and checks each one: it must
+//! generate at all, have exactly one solution, match its stored solution, keep
+//! its givens inside the preset's range, grade within the preset's range (see
+//! `PuzzleSettings::grade_range`), and the whole batch must finish inside
+//! a wall-clock budget. Used by the integration tests and the `self-test`
+//! command as the gate for solver and generator changes.
+
+use std::time::{Duration, Instant};
+
+use crate::{BoardState, Difficulty, GridSize, PresetKind, PuzzleSettings, Variant, rate_difficulty, validate_unique_solution};
+
+/// What to generate and how long it may take.
+#[derive(Debug, Clone)]
+pub struct SelfTestConfig {
+    pub puzzles_per_preset: usize,
+    /// Wall-clock budget for each preset's batch.
+    pub time_budget_per_preset: Duration,
+    pub grid_size: GridSize,
+    pub variant: Variant,
+}
+
+impl Default for SelfTestConfig {
+    fn default() -> Self {
+        Self {
+            puzzles_per_preset: 5,
+            time_budget_per_preset: Duration::from_secs(30),
+            grid_size: GridSize::Classic,
+            variant: Variant::Classic,
+        }
+    }
+}
+
+impl SelfTestConfig {
+    /// One config for every board size and variant that go together, each
+    /// checking `puzzles_per_preset` puzzles per preset.
+    pub fn every_board(puzzles_per_preset: usize) -> Vec<SelfTestConfig> {
+        Variant::all()
+            .into_iter()
+            .flat_map(|variant| {
+                GridSize::all()
+                    .into_iter()
+                    .filter(move |&grid_size| variant.supports(grid_size))
+                    .map(move |grid_size| SelfTestConfig { puzzles_per_preset, grid_size, variant, ..Default::default() })
+            })
+            .collect()
+    }
+}
+
+/// A single problem found by the self-test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelfTestIssue {
+    GenerationFailed,
+    NotUnique,
+    HasConflicts,
+    /// The stored solution doesn't agree with the givens or isn't a valid grid.
+    SolutionMismatch,
+    GivensOutOfRange { givens: usize, range: (usize, usize) },
+    /// `rate_difficulty` graded the puzzle outside the preset's grade range.
+    GradeOutOfRange { expected: (Difficulty, Difficulty), actual: Difficulty },
+    /// The batch ran past its budget; the remaining puzzles were skipped.
+    OverBudget { elapsed: Duration, budget: Duration },
+}
+
+impl SelfTestIssue {
+    pub fn description(&self) -> String {
+        match self {
+            SelfTestIssue::GenerationFailed => "generator gave up".to_string(),
+            SelfTestIssue::NotUnique => "puzzle has more than one solution".to_string(),
+            SelfTestIssue::HasConflicts => "givens clash with each other".to_string(),
+            SelfTestIssue::SolutionMismatch => "stored solution doesn't solve the puzzle".to_string(),
+            SelfTestIssue::GivensOutOfRange { givens, range } => {
+                format!("{} givens, expected {}-{}", givens, range.0, range.1)
+            }
+            SelfTestIssue::GradeOutOfRange { expected, actual } => {
+                format!("grades {}, expected {}-{}", actual.name(), expected.0.name(), expected.1.name())
+            }
+            SelfTestIssue::OverBudget { elapsed, budget } => {
+                format!("took {:.1?}, over the {:.1?} budget", elapsed, budget)
+            }
+        }
+    }
+}
+
+/// A failed check, with enough context to reproduce it.
+#[derive(Debug, Clone)]
+pub struct SelfTestFailure {
+    pub puzzle_index: usize,
+    pub issue: SelfTestIssue,
+    /// The generated puzzle, if there was one.
+    pub puzzle: Option<String>,
+}
+
+/// Results for one preset's batch.
+#[derive(Debug, Clone)]
+pub struct PresetSelfTest {
+    pub preset: PresetKind,
+    pub generated: usize,
+    pub elapsed: Duration,
+    pub failures: Vec<SelfTestFailure>,
+}
+
+/// Results for every preset.
+#[derive(Debug, Clone, Default)]
+pub struct SelfTestReport {
+    pub presets: Vec<PresetSelfTest>,
+}
+
+impl SelfTestReport {
+    /// Whether every check passed.
+    pub fn passed(&self) -> bool {
+        self.presets.iter().all(|preset| preset.failures.is_empty())
+    }
+
+    /// A human-readable report, one line per preset plus one per failure.
+    pub fn summary(&self) -> String {
+        let mut lines = Vec::new();
+        for result in &self.presets {
+            let status = if result.failures.is_empty() { "✅" } else { "❌" };
+            lines.push(format!(
+                "{} {}: {} puzzles in {:.2?}",
+                status,
+                result.preset.display_name(),
+                result.generated,
+                result.elapsed
+            ));
+            for failure in &result.failures {
+                lines.push(format!(
+                    "   puzzle #{}: {}{}",
+                    failure.puzzle_index + 1,
+                    failure.issue.description(),
+                    failure.puzzle.as_ref().map(|p| format!(" [{}]", p)).unwrap_or_default()
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Run the self-test for every preset.
+pub fn run_self_test(config: &SelfTestConfig) -> SelfTestReport {
+    SelfTestReport {
+        presets: PresetKind::all()
+            .into_iter()
+            .map(|preset| self_test_preset(preset, config))
+            .collect(),
+    }
+}
+
+fn self_test_preset(preset: PresetKind, config: &SelfTestConfig) -> PresetSelfTest {
+    let settings = PuzzleSettings::from_preset(preset)
+        .with_grid_size(config.grid_size)
+        .with_variant(config.variant);
+    let started = Instant::now();
+    let mut result = PresetSelfTest {
+        preset,
+        generated: 0,
+        elapsed: Duration::ZERO,
+        failures: Vec::new(),
+    };
+
+    for puzzle_index in 0..config.puzzles_per_preset {
+        let elapsed = started.elapsed();
+        if elapsed > config.time_budget_per_preset {
+            result.failures.push(SelfTestFailure {
+                puzzle_index,
+                issue: SelfTestIssue::OverBudget {
+                    elapsed,
+                    budget: config.time_budget_per_preset,
+                },
+                puzzle: None,
+            });
+            break;
+        }
+
+        let mut board = BoardState::with_size(config.grid_size);
+        let Ok(solution) = board.generate_puzzle_with_settings(&settings) else {
+            result.failures.push(SelfTestFailure {
+                puzzle_index,
+                issue: SelfTestIssue::GenerationFailed,
+                puzzle: None,
+            });
+            continue;
+        };
+        result.generated += 1;
+
+        for issue in check_puzzle(&board, &solution.cells, &settings) {
+            result.failures.push(SelfTestFailure {
+                puzzle_index,
+                issue,
+                puzzle: Some(board.puzzle_string()),
+            });
+        }
+    }
+
+    result.elapsed = started.elapsed();
+    if result.elapsed > config.time_budget_per_preset
+        && !result.failures.iter().any(|f| matches!(f.issue, SelfTestIssue::OverBudget { .. }))
+    {
+        result.failures.push(SelfTestFailure {
+            puzzle_index: config.puzzles_per_preset.saturating_sub(1),
+            issue: SelfTestIssue::OverBudget {
+                elapsed: result.elapsed,
+                budget: config.time_budget_per_preset,
+            },
+            puzzle: None,
+        });
+    }
+    result
+}
+
+/// Check one generated puzzle against its stored solution and the clue and
+/// grade ranges of the settings it was generated from.
+fn check_puzzle(board: &BoardState, solution: &[Vec<usize>], settings: &PuzzleSettings) -> Vec<SelfTestIssue> {
+    let givens_range = settings.givens_range;
+    let mut issues = Vec::new();
+
+    if !board.get_conflicts().is_empty() {
+        issues.push(SelfTestIssue::HasConflicts);
+    }
+
+    let givens = board.cells.iter().flatten().filter(|c| c.is_some()).count();
+    if givens < givens_range.0 || givens > givens_range.1 {
+        issues.push(SelfTestIssue::GivensOutOfRange { givens, range: givens_range });
+    }
+
+    let mut solved = board.clone();
+    for (row, values) in solution.iter().enumerate() {
+        for (col, &value) in values.iter().enumerate() {
+            if board.cells[row][col].is_some_and(|given| given != value) {
+                issues.push(SelfTestIssue::SolutionMismatch);
+                return issues;
+            }
+            solved.cells[row][col] = Some(value);
+        }
+    }
+    if !solved.is_complete() {
+        issues.push(SelfTestIssue::SolutionMismatch);
+    }
+
+    if !validate_unique_solution(board) {
+        issues.push(SelfTestIssue::NotUnique);
+        return issues;
+    }
+
+    if let Some(expected) = settings.grade_range() {
+        let actual = rate_difficulty(board).difficulty();
+        if actual < expected.0 || actual > expected.1 {
+            issues.push(SelfTestIssue::GradeOutOfRange { expected, actual });
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_board_skips_unsupported_sizes() {
+        let configs = SelfTestConfig::every_board(2);
+        assert!(configs.iter().all(|config| config.variant.supports(config.grid_size) && config.puzzles_per_preset == 2));
+        assert!(configs.iter().any(|config| config.grid_size == GridSize::Classic && config.variant == Variant::Classic));
+    }
+
+    #[test]
+    fn test_check_puzzle_flags_grade_out_of_range() {
+        // A Night Prowler puzzle that needs guessing
+        let prowler = PuzzleSettings { seed: Some(1), ..PuzzleSettings::from_preset(PresetKind::NightProwler) };
+        let mut board = BoardState::new();
+        let solution = board.generate_puzzle_with_settings(&prowler).expect("Puzzle should generate");
+        assert_eq!(rate_difficulty(&board).difficulty(), Difficulty::Expert);
+        assert!(check_puzzle(&board, &solution.cells, &prowler).is_empty());
+
+        // Passed off as a Cozy Kitten, it's out of range
+        let kitten = PuzzleSettings { givens_range: prowler.givens_range, ..PuzzleSettings::from_preset(PresetKind::CozyKitten) };
+        assert_eq!(
+            check_puzzle(&board, &solution.cells, &kitten),
+            vec![SelfTestIssue::GradeOutOfRange {
+                expected: (Difficulty::Easy, Difficulty::Medium),
+                actual: Difficulty::Expert,
+            }]
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! Share codes: a puzzle packed into a short string friends can type or paste.
+//!
+//! A code holds the board size, variant, and difficulty, then the givens
+//! (one "is there a cat" bit per cell plus the cat itself), and finally
+//! whatever else the variant needs to be solvable: the region of every cell
+//! on Jigsaw boards, or the cage sums and cage of every cell on Killer boards.
+//! Hyper windows follow from the board size, so they aren't stored. The bits
+//! are written out in Crockford base32 with a trailing checksum character to
+//! catch typos.
+
+use crate::{BoardState, CellType, Cage, Difficulty, GridSize, PresetKind, PuzzleSettings, RegionMap, Variant, hyper_windows};
+
+/// Base32 alphabet: no I, L, O, or U, so codes are hard to misread.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Format version written at the start of every code.
+const FORMAT_VERSION: usize = 0;
+const VERSION_BITS: usize = 3;
+/// Bits for the cage count (a 16x16 board has at most 256 cages).
+const CAGE_COUNT_BITS: usize = 9;
+/// Bits for a cage sum (the largest possible is 1 + 2 + ... + 16 = 136).
+const CAGE_SUM_BITS: usize = 8;
+
+/// A puzzle read back from a share code.
+#[derive(Debug, Clone)]
+pub struct SharedPuzzle {
+    /// The puzzle, with only its givens filled in.
+    pub board: BoardState,
+    pub variant: Variant,
+    pub difficulty: Difficulty,
+}
+
+impl SharedPuzzle {
+    /// Settings to play the shared puzzle with: the preset matching its
+    /// difficulty, adjusted to its board size and variant.
+    pub fn settings(&self) -> PuzzleSettings {
+        let preset = match self.difficulty {
+            Difficulty::Easy => PresetKind::CozyKitten,
+            Difficulty::Medium => PresetKind::CuriousCat,
+            Difficulty::Hard => PresetKind::StreetwiseStray,
+            Difficulty::Expert => PresetKind::NightProwler,
+        };
+        PuzzleSettings::from_preset(preset)
+            .with_grid_size(self.board.grid_size)
+            .with_variant(self.variant)
+    }
+}
+
+impl BoardState {
+    /// Pack this puzzle's givens (player entries are left out), along with the
+    /// variant and difficulty from its settings, into a share code.
+    pub fn to_share_code(&self, settings: &PuzzleSettings) -> String {
+        let size = self.size();
+        let value_bits = bits_for(size);
+        let mut bits = BitWriter::default();
+
+        bits.push(FORMAT_VERSION, VERSION_BITS);
+        bits.push(index_of(&GridSize::all(), &self.grid_size), 2);
+        bits.push(index_of(&Variant::all(), &settings.variant), 2);
+        bits.push(index_of(&Difficulty::all(), &settings.difficulty), 2);
+
+        for row in 0..size {
+            for col in 0..size {
+                match self.cells[row][col].filter(|_| self.is_given_cell(row, col)) {
+                    Some(value) => {
+                        bits.push(1, 1);
+                        bits.push(value, value_bits);
+                    }
+                    None => bits.push(0, 1),
+                }
+            }
+        }
+
+        match settings.variant {
+            Variant::Jigsaw => {
+                for row in 0..size {
+                    for col in 0..size {
+                        bits.push(self.regions.region_of(row, col), value_bits);
+                    }
+                }
+            }
+            Variant::Killer => {
+                bits.push(self.cages.len(), CAGE_COUNT_BITS);
+                for cage in &self.cages {
+                    bits.push(cage.sum, CAGE_SUM_BITS);
+                }
+                let cage_bits = bits_for(self.cages.len());
+                for cage in self.cage_map().iter().flatten() {
+                    bits.push(cage.unwrap_or(0), cage_bits);
+                }
+            }
+            Variant::Classic | Variant::Hyper => {}
+        }
+
+        bits.finish()
+    }
+
+    /// Unpack a share code. Letters may be in either case, and spaces or
+    /// dashes are ignored. Returns `None` for codes that are mistyped, from a
+    /// newer version of the game, or describe a board that breaks the rules;
+    /// whether the puzzle has a unique solution is left to the caller.
+    pub fn from_share_code(code: &str) -> Option<SharedPuzzle> {
+        let mut bits = BitReader::new(code)?;
+        if bits.read(VERSION_BITS)? != FORMAT_VERSION {
+            return None;
+        }
+        let grid_size = *GridSize::all().get(bits.read(2)?)?;
+        let variant = *Variant::all().get(bits.read(2)?)?;
+        let difficulty = *Difficulty::all().get(bits.read(2)?)?;
+        if !variant.supports(grid_size) {
+            return None;
+        }
+
+        let mut board = BoardState::with_size(grid_size);
+        let size = board.size();
+        let value_bits = bits_for(size);
+        for row in 0..size {
+            for col in 0..size {
+                if bits.read(1)? == 1 {
+                    let value = bits.read(value_bits)?;
+                    if value >= size {
+                        return None;
+                    }
+                    board.cells[row][col] = Some(value);
+                    board.cell_types[row][col] = Some(CellType::Given);
+                }
+            }
+        }
+
+        match variant {
+            Variant::Jigsaw => {
+                let mut regions = vec![vec![0; size]; size];
+                for region in regions.iter_mut().flatten() {
+                    *region = bits.read(value_bits)?;
+                }
+                board.regions = RegionMap::from_rows(regions);
+                if !board.regions.is_valid_for(grid_size) {
+                    return None;
+                }
+            }
+            Variant::Killer => {
+                let cage_count = bits.read(CAGE_COUNT_BITS)?;
+                let mut cages = Vec::with_capacity(cage_count);
+                for _ in 0..cage_count {
+                    cages.push(Cage {
+                        cells: Vec::new(),
+                        sum: bits.read(CAGE_SUM_BITS)?,
+                    });
+                }
+                let cage_bits = bits_for(cage_count);
+                for row in 0..size {
+                    for col in 0..size {
+                        cages.get_mut(bits.read(cage_bits)?)?.cells.push((row, col));
+                    }
+                }
+                if cages.iter().any(|cage| cage.cells.is_empty() || cage.sum == 0) {
+                    return None;
+                }
+                board.cages = cages;
+            }
+            Variant::Hyper => board.windows = hyper_windows(grid_size),
+            Variant::Classic => {}
+        }
+
+        board.get_conflicts().is_empty().then_some(SharedPuzzle {
+            board,
+            variant,
+            difficulty,
+        })
+    }
+}
+
+/// Bits needed to store any index below `count`.
+fn bits_for(count: usize) -> usize {
+    (usize::BITS - count.saturating_sub(1).leading_zeros()) as usize
+}
+
+/// Position of an option in its `all()` list.
+fn index_of<T: PartialEq>(all: &[T], item: &T) -> usize {
+    all.iter().position(|other| other == item).unwrap_or(0)
+}
+
+/// Collects bits, most significant first, and spells them out in base32.
+#[derive(Default)]
+struct BitWriter {
+    bits: Vec<bool>,
+}
+
+impl BitWriter {
+    /// Append the lowest `width` bits of `value`.
+    fn push(&mut self, value: usize, width: usize) {
+        self.bits.extend((0..width).rev().map(|bit| (value >> bit) & 1 == 1));
+    }
+
+    /// The code: base32 symbols (the last one zero-padded), then a checksum.
+    fn finish(self) -> String {
+        let symbols: Vec<usize> = self
+            .bits
+            .chunks(5)
+            .map(|chunk| chunk.iter().enumerate().fold(0, |acc, (i, &bit)| acc | ((bit as usize) << (4 - i))))
+            .collect();
+        let checksum = checksum(&symbols);
+        symbols.iter().chain([&checksum]).map(|&symbol| ALPHABET[symbol] as char).collect()
+    }
+}
+
+/// Reads bits back out of a base32 code.
+struct BitReader {
+    bits: Vec<bool>,
+    position: usize,
+}
+
+impl BitReader {
+    /// Decode the symbols and check the checksum.
+    fn new(code: &str) -> Option<Self> {
+        let mut symbols = code
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .map(|c| ALPHABET.iter().position(|&symbol| symbol as char == c.to_ascii_uppercase()))
+            .collect::<Option<Vec<usize>>>()?;
+        let expected = symbols.pop()?;
+        if checksum(&symbols) != expected {
+            return None;
+        }
+        Some(Self {
+            bits: symbols.iter().flat_map(|symbol| (0..5).rev().map(move |bit| (symbol >> bit) & 1 == 1)).collect(),
+            position: 0,
+        })
+    }
+
+    /// Read the next `width` bits as a number, or `None` if the code ran out.
+    fn read(&mut self, width: usize) -> Option<usize> {
+        let bits = self.bits.get(self.position..self.position + width)?;
+        self.position += width;
+        Some(bits.iter().fold(0, |acc, &bit| (acc << 1) | bit as usize))
+    }
+}
+
+/// Checksum symbol: a position-weighted sum, so swapped characters are caught too.
+fn checksum(symbols: &[usize]) -> usize {
+    symbols.iter().enumerate().map(|(i, &symbol)| (i + 1) * symbol).sum::<usize>() % 31
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Generate a puzzle, share it, and check the code brings back the same puzzle.
+    fn round_trip(grid_size: GridSize, variant: Variant, difficulty: Difficulty) {
+        let settings = PuzzleSettings::from_preset(PresetKind::CozyKitten)
+            .with_grid_size(grid_size)
+            .with_variant(variant);
+        let settings = PuzzleSettings { difficulty, ..settings };
+        let mut board = BoardState::with_size(grid_size);
+        let solution = board.generate_puzzle_with_settings(&settings).expect("Puzzle should generate");
+
+        // A player entry must not leak into the code
+        let (row, col) = (0..board.size() * board.size())
+            .map(|i| (i / board.size(), i % board.size()))
+            .find(|&(row, col)| board.cells[row][col].is_none())
+            .expect("Puzzle should have an empty cell");
+        board.set_cell(row, col, Some(solution.cells[row][col]));
+
+        let code = board.to_share_code(&settings);
+        let shared = BoardState::from_share_code(&code.to_lowercase()).expect("Code should decode");
+        assert_eq!(shared.variant, variant);
+        assert_eq!(shared.difficulty, difficulty);
+        assert_eq!(shared.board.cells[row][col], None, "Player entries aren't shared");
+
+        board.cells[row][col] = None;
+        board.cell_types[row][col] = None;
+        assert_eq!(shared.board.cells, board.cells);
+        assert_eq!(shared.board.cell_types, board.cell_types);
+        assert_eq!(shared.board.regions, board.regions);
+        assert_eq!(shared.board.windows, board.windows);
+        assert_eq!(shared.board.cage_map(), board.cage_map());
+        let sums = |board: &BoardState| board.cages.iter().map(|cage| cage.sum).collect::<Vec<_>>();
+        assert_eq!(sums(&shared.board), sums(&board));
+    }
+
+    #[test]
+    fn test_share_code_round_trip() {
+        round_trip(GridSize::Classic, Variant::Classic, Difficulty::Hard);
+        round_trip(GridSize::Small, Variant::Killer, Difficulty::Medium);
+        round_trip(GridSize::Classic, Variant::Jigsaw, Difficulty::Easy);
+        round_trip(GridSize::Mini, Variant::Hyper, Difficulty::Expert);
+    }
+
+    #[test]
+    fn test_share_code_rejects_bad_codes() {
+        let settings = PuzzleSettings::default();
+        let mut board = BoardState::new();
+        board.generate_puzzle_with_settings(&settings).expect("Puzzle should generate");
+        let code = board.to_share_code(&settings);
+        assert!(code.len() < 60, "A classic code should stay short, got {} characters", code.len());
+
+        // Spaces and dashes are fine
+        let spaced: String = code.chars().flat_map(|c| [c, ' ']).collect();
+        assert!(BoardState::from_share_code(&spaced).is_some());
+
+        // A typo breaks the checksum
+        let mut typo: Vec<char> = code.chars().collect();
+        typo[3] = if typo[3] == 'A' { 'B' } else { 'A' };
+        assert!(BoardState::from_share_code(&typo.into_iter().collect::<String>()).is_none());
+
+        // Truncated codes and junk are rejected
+        assert!(BoardState::from_share_code(&code[..code.len() / 2]).is_none());
+        assert!(BoardState::from_share_code("").is_none());
+        assert!(BoardState::from_share_code("not a code!").is_none());
+    }
+}
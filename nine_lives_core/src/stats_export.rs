@@ -0,0 +1,191 @@
+//! Exporting play history, for players who chart their progress outside the
+//! game.
+//!
+//! `GameStatistics::export` writes every recorded game (see `play_history`),
+//! oldest first, as CSV (one row per game, with a header) or as a JSON
+//! array. Each game has its date, preset, time, hints, mistakes, and score.
+//! Where the file goes is up to the player; without a choice it goes into
+//! the `exports` folder of the save directory.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CompletedGameRecord, GameStatistics, NineLivesError, format_unix_date};
+
+/// File format used when exporting play history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum StatsExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl StatsExportFormat {
+    /// Get all formats in the order they're offered.
+    pub fn all() -> [StatsExportFormat; 2] {
+        [StatsExportFormat::Csv, StatsExportFormat::Json]
+    }
+
+    /// Get the display name for this format.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            StatsExportFormat::Csv => "CSV",
+            StatsExportFormat::Json => "JSON",
+        }
+    }
+
+    /// File extension for this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            StatsExportFormat::Csv => "csv",
+            StatsExportFormat::Json => "json",
+        }
+    }
+
+    /// The file name an export is offered under.
+    pub fn file_name(&self) -> String {
+        format!("nine_lives_stats.{}", self.extension())
+    }
+}
+
+/// One game in an exported play history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedGame {
+    /// When the puzzle was finished, like "2025-10-09 08:53 UTC"
+    pub date: String,
+    pub preset: String,
+    pub seconds: u64,
+    pub hints_used: u32,
+    pub mistakes: u32,
+    pub score: u32,
+}
+
+/// Quote a CSV field if it needs it (RFC 4180).
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl GameStatistics {
+    /// Every recorded game, oldest first: the game log (see `game_records`),
+    /// after any leaderboard times from before the log was started.
+    pub fn play_history(&self) -> Vec<ExportedGame> {
+        let log_start = self.game_records.front().map_or(u64::MAX, |record| record.date);
+        let mut earlier: Vec<CompletedGameRecord> = self
+            .leaderboard
+            .entries
+            .iter()
+            .flat_map(|(&preset, entries)| {
+                entries.iter().map(move |entry| CompletedGameRecord {
+                    date: entry.completed_at,
+                    preset,
+                    duration: entry.seconds,
+                    hints_used: entry.hints_used,
+                    mistakes: entry.mistakes,
+                    score: entry.score,
+                })
+            })
+            .filter(|record| record.date < log_start)
+            .collect();
+        earlier.sort_by_key(|record| record.date);
+        earlier
+            .iter()
+            .chain(&self.game_records)
+            .map(|record| ExportedGame {
+                date: format_unix_date(record.date),
+                preset: record.preset.display_name().to_string(),
+                seconds: record.duration,
+                hints_used: record.hints_used,
+                mistakes: record.mistakes,
+                score: record.score,
+            })
+            .collect()
+    }
+
+    /// The play history (see `play_history`) in the given format.
+    pub fn export(&self, format: StatsExportFormat) -> String {
+        let games = self.play_history();
+        match format {
+            StatsExportFormat::Csv => {
+                let mut out = String::from("date,preset,seconds,hints_used,mistakes,score\n");
+                for game in &games {
+                    out.push_str(&format!(
+                        "{},{},{},{},{},{}\n",
+                        csv_field(&game.date),
+                        csv_field(&game.preset),
+                        game.seconds,
+                        game.hints_used,
+                        game.mistakes,
+                        game.score
+                    ));
+                }
+                out
+            }
+            StatsExportFormat::Json => serde_json::to_string_pretty(&games).unwrap_or_else(|_| "[]".to_string()),
+        }
+    }
+
+    /// Write the play history to `path`, or, when none is given, into the
+    /// exports folder of the save directory. Returns the path written.
+    pub fn export_to_file(
+        &self,
+        format: StatsExportFormat,
+        path: Option<PathBuf>,
+    ) -> Result<PathBuf, NineLivesError> {
+        let path = match path {
+            Some(path) => path,
+            None => {
+                let exports_dir = crate::get_save_directory().join("exports");
+                std::fs::create_dir_all(&exports_dir)?;
+                exports_dir.join(format.file_name())
+            }
+        };
+        std::fs::write(&path, self.export(format))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LeaderboardEntry, PresetKind};
+
+    #[test]
+    fn test_export_play_history() {
+        let mut statistics = GameStatistics::default();
+        let game = |seconds, completed_at| LeaderboardEntry { seconds, completed_at, hints_used: 1, mistakes: 2, score: 300 };
+        statistics.leaderboard.record(PresetKind::CuriousCat, game(200, 1_760_000_000));
+        statistics.leaderboard.record(PresetKind::CuriousCat, game(150, 1_760_100_000));
+        statistics.leaderboard.record(PresetKind::StreetwiseStray, game(400, 1_760_050_000));
+
+        // Oldest first, across presets
+        let history = statistics.play_history();
+        assert_eq!(history.iter().map(|game| game.seconds).collect::<Vec<_>>(), vec![200, 400, 150]);
+
+        let csv = statistics.export(StatsExportFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "date,preset,seconds,hints_used,mistakes,score");
+        assert_eq!(lines[1], format!("2025-10-09 08:53 UTC,{},200,1,2,300", PresetKind::CuriousCat.display_name()));
+
+        let json: Vec<ExportedGame> = serde_json::from_str(&statistics.export(StatsExportFormat::Json)).unwrap();
+        assert_eq!(json, history);
+
+        // Once the game log starts, it takes over from the leaderboards
+        statistics.record_game(CompletedGameRecord {
+            date: 1_760_060_000,
+            preset: PresetKind::StreetwiseStray,
+            duration: 400,
+            hints_used: 1,
+            mistakes: 2,
+            score: 300,
+        });
+        let seconds: Vec<u64> = statistics.play_history().iter().map(|game| game.seconds).collect();
+        assert_eq!(seconds, vec![200, 400, 400]);
+        assert_eq!(csv_field("a \"b\", c"), "\"a \"\"b\"\", c\"");
+    }
+}
@@ -0,0 +1,117 @@
+//! Streaks: right cats placed one after another without a mistake.
+//!
+//! A `StreakTracker` counts the run of right cats in the puzzle being played.
+//! A wrong cat ends it; hints, undos, and erasing leave it be. Each cell adds
+//! to it once, so a right cat erased and placed again doesn't count twice,
+//! and only cats that settle in their cell count (see `GameEvent::Placed`),
+//! so clicking past the wrong cats on the way to the right one is free. At certain
+//! lengths (3, 5, 10, and every 5 after) the streak reaches a milestone the
+//! game cheers, louder the longer it gets. The longest streak ever is kept in
+//! `GameStatistics::best_placement_streak`. The streak going is saved with an
+//! unfinished game, so continuing one doesn't break it.
+
+use std::collections::BTreeSet;
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+/// A streak length worth cheering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreakMilestone {
+    /// Right cats in a row
+    pub count: u32,
+    /// How big a cheer, from 1 to `StreakMilestone::MAX_LEVEL`
+    pub level: u8,
+}
+
+impl StreakMilestone {
+    /// The biggest cheer.
+    pub const MAX_LEVEL: u8 = 3;
+
+    /// The milestone a streak of `count` reaches, if any.
+    pub fn for_count(count: u32) -> Option<StreakMilestone> {
+        let level = match count {
+            3 => 1,
+            5 => 2,
+            count if count >= 10 && count % 5 == 0 => Self::MAX_LEVEL,
+            _ => return None,
+        };
+        Some(StreakMilestone { count, level })
+    }
+
+    /// The cheer, like "3 purr-fect in a row!".
+    pub fn message(&self) -> String {
+        match self.level {
+            1 => format!("{} purr-fect in a row!", self.count),
+            2 => format!("{} in a row, you're on a roll! 🐾", self.count),
+            _ => format!("{} in a row! Cat-tastic! 🔥", self.count),
+        }
+    }
+}
+
+/// Counts the right cats placed in a row in the puzzle being played.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct StreakTracker {
+    /// The streak going now
+    pub current: u32,
+    /// The longest streak in this puzzle
+    pub longest: u32,
+    /// Cells whose right cat has already counted (older saves have none)
+    #[serde(default)]
+    counted: BTreeSet<(usize, usize)>,
+}
+
+impl StreakTracker {
+    /// Count a cat the player placed at (`row`, `col`). Returns the
+    /// milestone it reaches, if any.
+    pub fn record(&mut self, row: usize, col: usize, correct: bool) -> Option<StreakMilestone> {
+        if !correct {
+            self.current = 0;
+            return None;
+        }
+        if !self.counted.insert((row, col)) {
+            return None;
+        }
+        self.current += 1;
+        self.longest = self.longest.max(self.current);
+        StreakMilestone::for_count(self.current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streak_milestones() {
+        let mut streak = StreakTracker::default();
+        let reached: Vec<u32> =
+            (0..20).filter_map(|col| streak.record(0, col, true)).map(|milestone| milestone.count).collect();
+        assert_eq!(reached, [3, 5, 10, 15, 20]);
+        assert_eq!(streak.record(1, 0, false), None);
+        assert_eq!((streak.current, streak.longest), (0, 20));
+
+        // A new streak starts from nothing, and cheers again from the start
+        assert_eq!(streak.record(1, 0, true), None);
+        streak.record(1, 1, true);
+        let milestone = streak.record(1, 2, true).unwrap();
+        assert_eq!(milestone.message(), "3 purr-fect in a row!");
+        assert_eq!(StreakMilestone::for_count(5).unwrap().level, 2);
+        assert_eq!(StreakMilestone::for_count(25).unwrap().level, StreakMilestone::MAX_LEVEL);
+    }
+
+    #[test]
+    fn test_each_cell_counts_once() {
+        let mut streak = StreakTracker::default();
+        streak.record(0, 0, true);
+        streak.record(0, 1, true);
+
+        // Erasing a right cat and placing it again earns nothing more
+        assert_eq!(streak.record(0, 1, true), None);
+        assert_eq!(streak.record(0, 1, true), None);
+        assert_eq!(streak.current, 2);
+        assert!(streak.record(0, 2, true).is_some(), "A new cell still reaches the milestone");
+    }
+}
@@ -0,0 +1,320 @@
+//! Session summaries for players who keep a solving journal.
+//!
+//! After a win the game can write a small Markdown or HTML report of the
+//! solve (the puzzle, the time taken, and a snapshot of the player's stats)
+//! into the `journal` folder of the save directory.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BoardState, GameStatistics, GridSize, NineLivesError, PuzzleSettings};
+
+/// File format used when exporting session summaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SummaryExport {
+    /// Don't export summaries.
+    #[default]
+    Off,
+    Markdown,
+    Html,
+}
+
+impl SummaryExport {
+    /// Cycle to the next export option (wraps around).
+    pub fn next(&self) -> Self {
+        match self {
+            SummaryExport::Off => SummaryExport::Markdown,
+            SummaryExport::Markdown => SummaryExport::Html,
+            SummaryExport::Html => SummaryExport::Off,
+        }
+    }
+
+    /// Get the display name for this option.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SummaryExport::Off => "Off",
+            SummaryExport::Markdown => "Markdown",
+            SummaryExport::Html => "HTML",
+        }
+    }
+
+    /// File extension for this format, if exporting is enabled.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            SummaryExport::Off => None,
+            SummaryExport::Markdown => Some("md"),
+            SummaryExport::Html => Some("html"),
+        }
+    }
+}
+
+/// Everything worth recording about one solved puzzle.
+#[derive(Debug, Clone)]
+pub struct SessionSummary {
+    pub difficulty: String,
+    pub grid_size: GridSize,
+    /// The puzzle as it was dealt: givens only.
+    pub givens: Vec<Vec<Option<usize>>>,
+    pub elapsed_seconds: u64,
+    pub move_count: usize,
+    pub hints_used: u32,
+    pub completed_at: u64, // Unix timestamp
+    pub games_completed: u32,
+    pub best_time_seconds: Option<u64>,
+    pub win_streak: u32,
+}
+
+impl SessionSummary {
+    /// Build a summary from the finished board and the (already updated) statistics.
+    pub fn new(
+        board: &BoardState,
+        settings: &PuzzleSettings,
+        statistics: &GameStatistics,
+        elapsed_seconds: u64,
+        move_count: usize,
+        hints_used: u32,
+    ) -> Self {
+        let givens = (0..board.size())
+            .map(|row| {
+                (0..board.size())
+                    .map(|col| board.cells[row][col].filter(|_| board.is_given_cell(row, col)))
+                    .collect()
+            })
+            .collect();
+        let difficulty = settings.difficulty.name();
+
+        Self {
+            difficulty: difficulty.to_string(),
+            grid_size: board.grid_size,
+            givens,
+            elapsed_seconds,
+            move_count,
+            hints_used,
+            completed_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            games_completed: statistics.games_completed,
+            best_time_seconds: statistics.best_time_for(difficulty),
+            win_streak: statistics.current_win_streak,
+        }
+    }
+
+    /// The puzzle as a single line of characters ('.' for empty cells),
+    /// the usual interchange format for sharing Sudoku puzzles.
+    pub fn puzzle_string(&self) -> String {
+        self.givens.iter().flatten().map(|cell| crate::puzzle_char(*cell)).collect()
+    }
+
+    /// Render the summary as Markdown.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("# 🐱 Nine Lives — {} solved\n\n", self.difficulty));
+        out.push_str(&format!("*Completed {}*\n\n", format_unix_date(self.completed_at)));
+        out.push_str("## Puzzle\n\n```\n");
+        let (box_rows, box_cols) = (self.grid_size.box_rows(), self.grid_size.box_cols());
+        let boxes_across = self.grid_size.dimension() / box_cols;
+        // Each box is "a b c" wide, padded by one space on either side of a '|'
+        let separator = (0..boxes_across)
+            .map(|i| "-".repeat(box_cols * 2 - 1 + usize::from(i > 0) + usize::from(i + 1 < boxes_across)))
+            .collect::<Vec<_>>()
+            .join("+");
+        for (row, cells) in self.givens.iter().enumerate() {
+            if row > 0 && row % box_rows == 0 {
+                out.push_str(&separator);
+                out.push('\n');
+            }
+            let line: Vec<String> = cells
+                .chunks(box_cols)
+                .map(|chunk| {
+                    chunk
+                        .iter()
+                        .map(|cell| cell.map_or(".".to_string(), |v| (v + 1).to_string()))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect();
+            out.push_str(&line.join(" | "));
+            out.push('\n');
+        }
+        out.push_str("```\n\n");
+        out.push_str(&format!("Puzzle string: `{}`\n\n", self.puzzle_string()));
+        out.push_str("## This solve\n\n");
+        for (label, value) in self.solve_rows() {
+            out.push_str(&format!("- **{}**: {}\n", label, value));
+        }
+        out.push_str("\n## Your stats\n\n");
+        for (label, value) in self.stats_rows() {
+            out.push_str(&format!("- **{}**: {}\n", label, value));
+        }
+        out
+    }
+
+    /// Render the summary as a standalone HTML page.
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!("<title>Nine Lives — {} solved</title>\n", self.difficulty));
+        out.push_str("<style>table.puzzle{border-collapse:collapse}table.puzzle td{width:2em;height:2em;text-align:center;border:1px solid #999}table.puzzle td.box-right{border-right:2px solid #333}table.puzzle tr.box-bottom td{border-bottom:2px solid #333}</style>\n");
+        out.push_str("</head>\n<body>\n");
+        out.push_str(&format!("<h1>🐱 Nine Lives — {} solved</h1>\n", self.difficulty));
+        out.push_str(&format!("<p><em>Completed {}</em></p>\n", format_unix_date(self.completed_at)));
+        out.push_str("<h2>Puzzle</h2>\n<table class=\"puzzle\">\n");
+        let (box_rows, box_cols) = (self.grid_size.box_rows(), self.grid_size.box_cols());
+        let size = self.grid_size.dimension();
+        for (row, cells) in self.givens.iter().enumerate() {
+            if row % box_rows == box_rows - 1 && row + 1 < size {
+                out.push_str("<tr class=\"box-bottom\">");
+            } else {
+                out.push_str("<tr>");
+            }
+            for (col, cell) in cells.iter().enumerate() {
+                let class = if col % box_cols == box_cols - 1 && col + 1 < size { " class=\"box-right\"" } else { "" };
+                let value = cell.map_or(String::new(), |v| (v + 1).to_string());
+                out.push_str(&format!("<td{}>{}</td>", class, value));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+        out.push_str(&format!("<p>Puzzle string: <code>{}</code></p>\n", self.puzzle_string()));
+        out.push_str("<h2>This solve</h2>\n<ul>\n");
+        for (label, value) in self.solve_rows() {
+            out.push_str(&format!("<li><strong>{}</strong>: {}</li>\n", label, value));
+        }
+        out.push_str("</ul>\n<h2>Your stats</h2>\n<ul>\n");
+        for (label, value) in self.stats_rows() {
+            out.push_str(&format!("<li><strong>{}</strong>: {}</li>\n", label, value));
+        }
+        out.push_str("</ul>\n</body>\n</html>\n");
+        out
+    }
+
+    /// Render the summary in the given format (None when exporting is off).
+    pub fn render(&self, format: SummaryExport) -> Option<String> {
+        match format {
+            SummaryExport::Off => None,
+            SummaryExport::Markdown => Some(self.to_markdown()),
+            SummaryExport::Html => Some(self.to_html()),
+        }
+    }
+
+    /// Write the summary into the journal folder of the save directory.
+    /// Returns the path written, or None when exporting is off.
+    pub fn export(&self, format: SummaryExport) -> Result<Option<std::path::PathBuf>, NineLivesError> {
+        let (Some(contents), Some(extension)) = (self.render(format), format.extension()) else {
+            return Ok(None);
+        };
+
+        let journal_dir = crate::get_save_directory().join("journal");
+        std::fs::create_dir_all(&journal_dir)?;
+
+        let file_name = format!(
+            "nine_lives_{}_{}.{}",
+            self.difficulty.to_lowercase(),
+            self.completed_at,
+            extension
+        );
+        let path = journal_dir.join(file_name);
+        std::fs::write(&path, contents)?;
+        Ok(Some(path))
+    }
+
+    fn solve_rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Difficulty", self.difficulty.clone()),
+            ("Time", format_seconds(self.elapsed_seconds)),
+            ("Moves", self.move_count.to_string()),
+            ("Hints used", self.hints_used.to_string()),
+        ]
+    }
+
+    fn stats_rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Games completed", self.games_completed.to_string()),
+            (
+                "Best time at this difficulty",
+                self.best_time_seconds.map(format_seconds).unwrap_or_else(|| "--:--".to_string()),
+            ),
+            ("Current win streak", self.win_streak.to_string()),
+        ]
+    }
+}
+
+/// Format seconds as MM:SS.
+fn format_seconds(total_seconds: u64) -> String {
+    format!("{:02}:{:02}", total_seconds / 60, total_seconds % 60)
+}
+
+/// Format a Unix timestamp as "YYYY-MM-DD HH:MM UTC".
+pub fn format_unix_date(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds_of_day = timestamp % 86_400;
+
+    // Civil-from-days conversion (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        (seconds_of_day % 3600) / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CellType, GRID_SIZE};
+
+    fn sample_summary() -> SessionSummary {
+        let mut board = BoardState::new();
+        board.cells[0][0] = Some(4);
+        board.cell_types[0][0] = Some(CellType::Given);
+        board.cells[0][1] = Some(2);
+        board.cell_types[0][1] = Some(CellType::Player);
+
+        let mut summary = SessionSummary::new(
+            &board,
+            &PuzzleSettings::default(),
+            &GameStatistics::default(),
+            125,
+            40,
+            1,
+        );
+        summary.completed_at = 1_760_000_000;
+        summary
+    }
+
+    #[test]
+    fn test_summary_only_records_givens() {
+        let summary = sample_summary();
+        let puzzle = summary.puzzle_string();
+        assert_eq!(puzzle.len(), GRID_SIZE * GRID_SIZE);
+        assert!(puzzle.starts_with("5.."), "Player entries are not part of the puzzle: {}", puzzle);
+    }
+
+    #[test]
+    fn test_summary_formats() {
+        let summary = sample_summary();
+        let markdown = summary.to_markdown();
+        assert!(markdown.contains("# 🐱 Nine Lives — Easy solved"));
+        assert!(markdown.contains("- **Time**: 02:05"));
+        assert!(markdown.contains("2025-10-09 08:53 UTC"));
+        assert!(markdown.contains("\n------+-------+------\n"));
+
+        let html = summary.to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<li><strong>Moves</strong>: 40</li>"));
+
+        assert!(summary.render(SummaryExport::Off).is_none());
+    }
+}
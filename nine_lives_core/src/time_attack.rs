@@ -0,0 +1,151 @@
+//! Time attack: solve as many puzzles as possible before the clock runs out.
+//!
+//! A run starts with `TIME_ATTACK_SECONDS` on the clock and a stream of
+//! generated puzzles: Cozy Kitten ones to warm up, then Curious Cat ones.
+//! Each solve scores points by its difficulty and the next puzzle follows
+//! straight away. When the clock runs out the run is over, and its score goes
+//! into `GameStatistics::time_attack`.
+
+use std::time::Duration;
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::{Difficulty, PresetKind};
+
+/// How long a run lasts, in seconds.
+pub const TIME_ATTACK_SECONDS: u64 = 5 * 60;
+
+/// How many Easy puzzles a run starts with before moving on to Medium ones.
+pub const TIME_ATTACK_WARM_UP: u32 = 3;
+
+/// A time attack run in progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct TimeAttack {
+    /// Time left on the clock
+    pub remaining: Duration,
+    /// Puzzles solved so far
+    pub solved: u32,
+    pub score: u32,
+}
+
+impl Default for TimeAttack {
+    fn default() -> Self {
+        Self {
+            remaining: Duration::from_secs(TIME_ATTACK_SECONDS),
+            solved: 0,
+            score: 0,
+        }
+    }
+}
+
+impl TimeAttack {
+    /// Points for solving a puzzle of the given difficulty.
+    pub fn points_for(difficulty: Difficulty) -> u32 {
+        match difficulty {
+            Difficulty::Easy => 100,
+            Difficulty::Medium => 250,
+            Difficulty::Hard => 500,
+            Difficulty::Expert => 1000,
+        }
+    }
+
+    /// The preset the next puzzle is generated with.
+    pub fn next_preset(&self) -> PresetKind {
+        if self.solved < TIME_ATTACK_WARM_UP {
+            PresetKind::CozyKitten
+        } else {
+            PresetKind::CuriousCat
+        }
+    }
+
+    /// Count a solved puzzle, returning the points it scored. Nothing
+    /// counts once the clock has run out.
+    pub fn record_solve(&mut self, difficulty: Difficulty) -> u32 {
+        if self.is_over() {
+            return 0;
+        }
+        let points = Self::points_for(difficulty);
+        self.solved += 1;
+        self.score += points;
+        points
+    }
+
+    /// Run the clock down. Returns whether this ran it out.
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        if self.is_over() {
+            return false;
+        }
+        self.remaining = self.remaining.saturating_sub(delta);
+        self.is_over()
+    }
+
+    /// Whether the clock has run out.
+    pub fn is_over(&self) -> bool {
+        self.remaining.is_zero()
+    }
+}
+
+/// Time attack results, kept with the rest of the statistics.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeAttackRecord {
+    /// Runs played to the end of the clock
+    pub runs: u32,
+    pub best_score: u32,
+    /// Puzzles solved in the best-scoring run
+    pub best_solved: u32,
+    /// Puzzles solved across every run
+    pub puzzles_solved: u32,
+}
+
+impl TimeAttackRecord {
+    /// Record a finished run. Returns whether it set a new best score.
+    pub fn record(&mut self, run: &TimeAttack) -> bool {
+        self.runs += 1;
+        self.puzzles_solved += run.solved;
+        let best = run.score > self.best_score;
+        if best {
+            self.best_score = run.score;
+            self.best_solved = run.solved;
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_attack_run() {
+        let mut run = TimeAttack::default();
+        assert_eq!(run.next_preset(), PresetKind::CozyKitten);
+        for _ in 0..TIME_ATTACK_WARM_UP {
+            assert_eq!(run.record_solve(Difficulty::Easy), 100);
+        }
+        assert_eq!(run.next_preset(), PresetKind::CuriousCat);
+        assert_eq!(run.record_solve(Difficulty::Medium), 250);
+        assert_eq!((run.solved, run.score), (4, 550));
+
+        assert!(!run.tick(Duration::from_secs(TIME_ATTACK_SECONDS - 1)));
+        assert!(run.tick(Duration::from_secs(5)), "The clock runs out");
+        assert!(!run.tick(Duration::from_secs(1)), "It only runs out once");
+        assert_eq!(run.record_solve(Difficulty::Medium), 0, "Too late to count");
+        assert_eq!(run.score, 550);
+    }
+
+    #[test]
+    fn test_time_attack_record_keeps_best() {
+        let mut record = TimeAttackRecord::default();
+        let good = TimeAttack { remaining: Duration::ZERO, solved: 4, score: 550 };
+        let worse = TimeAttack { solved: 2, score: 200, ..good.clone() };
+        assert!(record.record(&good));
+        assert!(!record.record(&worse));
+        assert_eq!(
+            record,
+            TimeAttackRecord { runs: 2, best_score: 550, best_solved: 4, puzzles_solved: 6 }
+        );
+    }
+}
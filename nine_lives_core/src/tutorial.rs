@@ -0,0 +1,182 @@
+//! A guided first puzzle for new players.
+//!
+//! The tutorial walks through a small 4×4 puzzle one step at a time. Some
+//! steps just explain a rule and wait for the player to move on; the others
+//! point at a cell and wait for the right cat to go there. While a step is
+//! waiting for a cat, only its cell can be changed. Clearing or undoing a cat
+//! the tutorial asked for takes it back to that step.
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::Resource;
+
+use crate::{BoardState, CellCommand, Solution};
+
+/// The tutorial puzzle, as a puzzle string (see `BoardState::from_puzzle_string`)
+pub const TUTORIAL_PUZZLE: &str = "123. .412 21.3 4.21";
+
+/// The tutorial puzzle's solution
+pub const TUTORIAL_SOLUTION: &str = "1234 3412 2143 4321";
+
+/// One step of the tutorial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TutorialStep {
+    /// Name of the step; the UI looks its prompt up as `tutorial.<name>`
+    pub name: &'static str,
+    /// The cell the step points at, if any
+    pub cell: Option<(usize, usize)>,
+    /// The cat that has to go in the cell to move on. None for steps that
+    /// only explain something.
+    pub cat: Option<usize>,
+}
+
+impl TutorialStep {
+    /// Whether the step's cat is in its cell.
+    fn is_done(&self, board: &BoardState) -> bool {
+        match (self.cell, self.cat) {
+            (Some((row, col)), Some(cat)) => board.cells[row][col] == Some(cat),
+            _ => false,
+        }
+    }
+}
+
+/// The tutorial's steps, in order.
+pub const TUTORIAL_STEPS: [TutorialStep; 7] = [
+    TutorialStep { name: "welcome", cell: None, cat: None },
+    TutorialStep { name: "row", cell: Some((0, 3)), cat: None },
+    TutorialStep { name: "fill_row", cell: Some((0, 3)), cat: Some(3) },
+    TutorialStep { name: "column", cell: Some((1, 0)), cat: Some(2) },
+    TutorialStep { name: "box", cell: Some((2, 2)), cat: Some(3) },
+    TutorialStep { name: "conflict", cell: Some((3, 1)), cat: None },
+    TutorialStep { name: "last", cell: Some((3, 1)), cat: Some(2) },
+];
+
+/// A tutorial in progress.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct Tutorial {
+    /// Index of the current step in `TUTORIAL_STEPS`
+    pub step: usize,
+}
+
+impl Tutorial {
+    /// The tutorial's board and solution.
+    pub fn puzzle() -> (BoardState, Solution) {
+        let board = BoardState::from_puzzle_string(TUTORIAL_PUZZLE).expect("Tutorial puzzle should read");
+        let solved = BoardState::from_puzzle_string(TUTORIAL_SOLUTION).expect("Tutorial solution should read");
+        let solution = Solution::from_board(&solved).expect("Tutorial solution should be complete");
+        (board, solution)
+    }
+
+    /// The step the tutorial is on, or None once it's finished.
+    pub fn current(&self) -> Option<&'static TutorialStep> {
+        TUTORIAL_STEPS.get(self.step)
+    }
+
+    /// Whether every step is done.
+    pub fn is_finished(&self) -> bool {
+        self.current().is_none()
+    }
+
+    /// Whether the current step only explains something, so the player
+    /// moves on with Next.
+    pub fn waits_for_next(&self) -> bool {
+        self.current().is_some_and(|step| step.cat.is_none())
+    }
+
+    /// Move on from a step that only explains something.
+    pub fn next(&mut self) {
+        if self.waits_for_next() {
+            self.step += 1;
+        }
+    }
+
+    /// Catch up with the board: go back to the first step whose cat has been
+    /// taken away again, then past any steps whose cat is already in place.
+    pub fn sync(&mut self, board: &BoardState) {
+        let steps = &TUTORIAL_STEPS[..self.step.min(TUTORIAL_STEPS.len())];
+        if let Some(undone) = steps.iter().position(|step| step.cat.is_some() && !step.is_done(board)) {
+            self.step = undone;
+        }
+        while self.current().is_some_and(|step| step.is_done(board)) {
+            self.step += 1;
+        }
+    }
+
+    /// Whether the player may change a cell: only the current step's cell
+    /// while it waits for a cat, none while a step explains something, and
+    /// any once the tutorial is finished.
+    pub fn allows_cell(&self, row: usize, col: usize) -> bool {
+        match self.current() {
+            Some(step) => step.cat.is_some() && step.cell == Some((row, col)),
+            None => true,
+        }
+    }
+
+    /// Whether the player may carry out a command from the input model.
+    /// Commands that don't change a cell are always allowed.
+    pub fn allows(&self, command: &CellCommand) -> bool {
+        match *command {
+            CellCommand::Cycle { row, col }
+            | CellCommand::Set { row, col, .. }
+            | CellCommand::Erase { row, col }
+            | CellCommand::ToggleNote { row, col, .. } => self.allows_cell(row, col),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridSize;
+
+    #[test]
+    fn test_tutorial() {
+        let (mut board, solution) = Tutorial::puzzle();
+        assert_eq!(board.grid_size, GridSize::Mini);
+        assert!(board.get_conflicts().is_empty());
+        for step in &TUTORIAL_STEPS {
+            if let (Some((row, col)), Some(cat)) = (step.cell, step.cat) {
+                assert_eq!(board.cells[row][col], None, "Step {} should point at an empty cell", step.name);
+                assert_eq!(solution.cells[row][col], cat, "Step {} should ask for the right cat", step.name);
+            }
+        }
+
+        // Explaining steps wait for Next and lock the board
+        let mut tutorial = Tutorial::default();
+        assert!(tutorial.waits_for_next());
+        assert!(!tutorial.allows_cell(0, 3));
+        tutorial.next();
+        tutorial.next();
+        assert!(!tutorial.waits_for_next());
+        assert!(tutorial.allows(&CellCommand::Set { row: 0, col: 3, value: Some(0) }));
+        assert!(!tutorial.allows(&CellCommand::Erase { row: 1, col: 0 }));
+        assert!(tutorial.allows(&CellCommand::Pick(Some(1))));
+
+        // A wrong cat doesn't move the tutorial on, the right one does
+        board.set_cell(0, 3, Some(0));
+        tutorial.sync(&board);
+        assert_eq!(tutorial.current().unwrap().name, "fill_row");
+        board.set_cell(0, 3, Some(3));
+        tutorial.sync(&board);
+        assert_eq!(tutorial.current().unwrap().name, "column");
+
+        // Taking the cat away again goes back to its step
+        board.clear_cell(0, 3);
+        tutorial.sync(&board);
+        assert_eq!(tutorial.current().unwrap().name, "fill_row");
+
+        // Cats already in place (from a hint, say) are skipped over
+        for (row, col) in [(0, 3), (1, 0), (2, 2)] {
+            board.set_cell(row, col, Some(solution.cells[row][col]));
+        }
+        tutorial.sync(&board);
+        assert_eq!(tutorial.current().unwrap().name, "conflict");
+        tutorial.next();
+        board.set_cell(3, 1, Some(2));
+        tutorial.sync(&board);
+        assert!(tutorial.is_finished());
+        assert!(board.is_complete());
+        assert!(tutorial.allows_cell(1, 1));
+    }
+}
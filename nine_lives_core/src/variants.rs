@@ -0,0 +1,322 @@
+//! Puzzle variants that add extra rules on top of classic Sudoku.
+//!
+//! Jigsaw Sudoku swaps the rectangular boxes for irregular regions (see
+//! `RegionMap`); every other rule is unchanged.
+//!
+//! Hyper Sudoku (also known as Windoku) adds shaded windows: extra box-shaped
+//! houses sitting one cell in from the boxes, four of them on a 9x9 board.
+//! Each window holds every cat exactly once, like a box.
+//!
+//! Killer Sudoku splits the board into cages: groups of cells whose cats must
+//! add up to the cage's target sum, with no cat repeated inside a cage. The
+//! usual row, column, and box rules still apply, so Killer puzzles need far
+//! fewer givens (often none at all).
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::{BoardState, GridSize};
+
+/// The rule set a puzzle is played with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Variant {
+    /// Plain Sudoku: rows, columns, and boxes.
+    #[default]
+    Classic,
+    /// Killer Sudoku: classic rules plus summed cages.
+    Killer,
+    /// Jigsaw Sudoku: irregular regions instead of boxes.
+    Jigsaw,
+    /// Hyper Sudoku: classic rules plus shaded windows.
+    Hyper,
+}
+
+impl Variant {
+    /// Get all variants in display order.
+    pub fn all() -> [Variant; 4] {
+        [Variant::Classic, Variant::Killer, Variant::Jigsaw, Variant::Hyper]
+    }
+
+    /// Cycle to the next variant (wraps around).
+    pub fn next(&self) -> Self {
+        match self {
+            Variant::Classic => Variant::Killer,
+            Variant::Killer => Variant::Jigsaw,
+            Variant::Jigsaw => Variant::Hyper,
+            Variant::Hyper => Variant::Classic,
+        }
+    }
+
+    /// Whether puzzles of this variant can be generated on a board size.
+    /// Filling a 16x16 board around nine Hyper windows is too slow to do on
+    /// demand, so Hyper stops at the classic board.
+    pub fn supports(&self, grid_size: GridSize) -> bool {
+        !(*self == Variant::Hyper && grid_size == GridSize::Mega)
+    }
+
+    /// Get the display name for this variant.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Variant::Classic => "Classic",
+            Variant::Killer => "Killer",
+            Variant::Jigsaw => "Jigsaw",
+            Variant::Hyper => "Hyper",
+        }
+    }
+}
+
+/// A Killer Sudoku cage: its cats must add up to `sum` without repeating.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cage {
+    /// The cells in this cage as (row, col).
+    pub cells: Vec<(usize, usize)>,
+    /// Target total of the cat numbers (1-based, so cat 1 counts as 1).
+    pub sum: usize,
+}
+
+impl Cage {
+    /// Whether (row, col) belongs to this cage.
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        self.cells.contains(&(row, col))
+    }
+
+    /// The cell the cage's sum is printed in: its top-left cell.
+    pub fn anchor(&self) -> (usize, usize) {
+        self.cells.iter().copied().min().unwrap_or((0, 0))
+    }
+}
+
+/// Cage sizes to aim for when carving up a solved grid; mostly two to four
+/// cells, which keeps sums readable and the puzzle well constrained.
+const CAGE_SIZES: [usize; 8] = [2, 2, 3, 3, 3, 4, 4, 5];
+
+/// Largest cage a leftover single cell may be folded into.
+const MAX_CAGE_SIZE: usize = 6;
+
+/// Split a solved grid into random cages of connected cells, with no cat
+/// repeated inside a cage. Every cell ends up in exactly one cage.
+pub fn generate_cages<R: Rng>(solution: &[Vec<usize>], grid_size: GridSize, rng: &mut R) -> Vec<Cage> {
+    let size = grid_size.dimension();
+    let mut owner: Vec<Vec<Option<usize>>> = vec![vec![None; size]; size];
+    let mut cages: Vec<Vec<(usize, usize)>> = Vec::new();
+
+    let mut starts: Vec<(usize, usize)> = (0..size).flat_map(|row| (0..size).map(move |col| (row, col))).collect();
+    starts.shuffle(rng);
+
+    for start in starts {
+        if owner[start.0][start.1].is_some() {
+            continue;
+        }
+
+        let target = *CAGE_SIZES.choose(rng).unwrap_or(&3);
+        let index = cages.len();
+        let mut cells = vec![start];
+        owner[start.0][start.1] = Some(index);
+
+        while cells.len() < target {
+            let mut frontier: Vec<(usize, usize)> = cells
+                .iter()
+                .flat_map(|&(row, col)| neighbours(row, col, size))
+                .filter(|&(row, col)| {
+                    owner[row][col].is_none() && !cells.iter().any(|&(r, c)| solution[r][c] == solution[row][col])
+                })
+                .collect();
+            frontier.sort();
+            frontier.dedup();
+            let Some(&next) = frontier.choose(rng) else {
+                break; // Boxed in
+            };
+            owner[next.0][next.1] = Some(index);
+            cells.push(next);
+        }
+        cages.push(cells);
+    }
+
+    // Fold single-cell cages into a neighbouring cage where the cat fits,
+    // since a one-cell cage just gives its cat away.
+    for index in 0..cages.len() {
+        if cages[index].len() != 1 {
+            continue;
+        }
+        let (row, col) = cages[index][0];
+        let target = neighbours(row, col, size)
+            .filter_map(|(r, c)| owner[r][c])
+            .filter(|&other| other != index && !cages[other].is_empty() && cages[other].len() < MAX_CAGE_SIZE)
+            .find(|&other| !cages[other].iter().any(|&(r, c)| solution[r][c] == solution[row][col]));
+        if let Some(other) = target {
+            cages[index].clear();
+            cages[other].push((row, col));
+            owner[row][col] = Some(other);
+        }
+    }
+
+    cages
+        .into_iter()
+        .filter(|cells| !cells.is_empty())
+        .map(|mut cells| {
+            cells.sort();
+            let sum = cells.iter().map(|&(row, col)| solution[row][col] + 1).sum();
+            Cage { cells, sum }
+        })
+        .collect()
+}
+
+/// The shaded windows of a Hyper board: box-sized blocks starting one cell
+/// in from the top-left corner, with one row or column of gap between them.
+/// Each window is listed as its cells in reading order.
+pub fn hyper_windows(grid_size: GridSize) -> Vec<Vec<(usize, usize)>> {
+    let size = grid_size.dimension();
+    let (box_rows, box_cols) = (grid_size.box_rows(), grid_size.box_cols());
+    let row_starts: Vec<usize> = (1..size).step_by(box_rows + 1).filter(|&r| r + box_rows <= size).collect();
+    let col_starts: Vec<usize> = (1..size).step_by(box_cols + 1).filter(|&c| c + box_cols <= size).collect();
+
+    row_starts
+        .iter()
+        .flat_map(|&top| col_starts.iter().map(move |&left| (top, left)))
+        .map(|(top, left)| {
+            (top..top + box_rows)
+                .flat_map(|row| (left..left + box_cols).map(move |col| (row, col)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Orthogonal neighbours of a cell that are on the board.
+pub(crate) fn neighbours(row: usize, col: usize, size: usize) -> impl Iterator<Item = (usize, usize)> {
+    [
+        (row.wrapping_sub(1), col),
+        (row + 1, col),
+        (row, col.wrapping_sub(1)),
+        (row, col + 1),
+    ]
+    .into_iter()
+    .filter(move |&(r, c)| r < size && c < size)
+}
+
+impl BoardState {
+    /// Index of the Hyper window containing (row, col), if any.
+    pub fn window_index(&self, row: usize, col: usize) -> Option<usize> {
+        self.windows.iter().position(|window| window.contains(&(row, col)))
+    }
+
+    /// Check the window rule for placing `value` at (row, col): the cat must
+    /// not already be elsewhere in the cell's window.
+    pub(crate) fn window_allows(&self, row: usize, col: usize, value: usize) -> bool {
+        self.window_index(row, col).is_none_or(|index| {
+            self.windows[index]
+                .iter()
+                .all(|&(r, c)| (r, c) == (row, col) || self.cells[r][c] != Some(value))
+        })
+    }
+
+    /// Index of the cage containing (row, col), if the board has cages.
+    pub fn cage_index(&self, row: usize, col: usize) -> Option<usize> {
+        self.cages.iter().position(|cage| cage.contains(row, col))
+    }
+
+    /// Map every cell to the index of its cage, indexed as `[row][col]`.
+    pub fn cage_map(&self) -> Vec<Vec<Option<usize>>> {
+        let mut map = vec![vec![None; self.size()]; self.size()];
+        for (index, cage) in self.cages.iter().enumerate() {
+            for &(row, col) in &cage.cells {
+                map[row][col] = Some(index);
+            }
+        }
+        map
+    }
+
+    /// Check the cage rules for placing `value` at (row, col): the cat must
+    /// not already be in the cage, and the cage total must still be reachable
+    /// (exactly the target once every cell is filled).
+    pub(crate) fn cage_allows(&self, row: usize, col: usize, value: usize) -> bool {
+        let Some(cage) = self.cages.iter().find(|cage| cage.contains(row, col)) else {
+            return true;
+        };
+
+        let mut total = value + 1;
+        let mut filled = 1;
+        for &(r, c) in &cage.cells {
+            if (r, c) == (row, col) {
+                continue;
+            }
+            if let Some(other) = self.cells[r][c] {
+                if other == value {
+                    return false;
+                }
+                total += other + 1;
+                filled += 1;
+            }
+        }
+
+        // Every empty cell still needs at least a 1
+        let empty = cage.cells.len() - filled;
+        if empty == 0 { total == cage.sum } else { total + empty <= cage.sum }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_cages_cover_the_board() {
+        let mut board = BoardState::new();
+        assert!(board.fill_board());
+        let solution: Vec<Vec<usize>> = board.cells.iter().map(|row| row.iter().map(|c| c.unwrap()).collect()).collect();
+
+        let cages = generate_cages(&solution, GridSize::Classic, &mut rand::thread_rng());
+        let covered: usize = cages.iter().map(|cage| cage.cells.len()).sum();
+        assert_eq!(covered, 81, "Every cell should be in exactly one cage");
+        assert_eq!(cages.iter().map(|cage| cage.sum).sum::<usize>(), 9 * 45);
+
+        for cage in &cages {
+            let mut values: Vec<usize> = cage.cells.iter().map(|&(r, c)| solution[r][c]).collect();
+            values.sort();
+            values.dedup();
+            assert_eq!(values.len(), cage.cells.len(), "Cats must not repeat inside a cage");
+        }
+    }
+
+    #[test]
+    fn test_cage_rules() {
+        let mut board = BoardState::new();
+        board.cages = vec![Cage { cells: vec![(0, 0), (0, 1), (1, 0)], sum: 6 }];
+
+        assert!(board.cage_allows(0, 0, 0)); // 1, leaving 5 for two cells
+        assert!(!board.cage_allows(0, 0, 4), "5 leaves only 1 for two cells");
+
+        board.cells[0][0] = Some(0); // 1
+        assert!(!board.cage_allows(0, 1, 0), "Cats can't repeat inside a cage");
+        board.cells[0][1] = Some(1); // 2
+        assert!(board.cage_allows(1, 0, 2), "1 + 2 + 3 = 6");
+        assert!(!board.cage_allows(1, 0, 3), "The last cell must hit the sum exactly");
+
+        board.cells[1][0] = Some(3);
+        assert_eq!(board.get_conflicts().len(), 3, "A wrong total flags the whole cage");
+        assert_eq!(board.cage_index(1, 0), Some(0));
+        assert_eq!(board.cage_index(5, 5), None);
+    }
+
+    #[test]
+    fn test_hyper_windows() {
+        let windows = hyper_windows(GridSize::Classic);
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[0].first(), Some(&(1, 1)));
+        assert_eq!(windows[3].last(), Some(&(7, 7)));
+        assert!(windows.iter().all(|window| window.len() == 9));
+        assert_eq!(hyper_windows(GridSize::Mini).len(), 1);
+        assert_eq!(hyper_windows(GridSize::Small).len(), 2);
+        assert_eq!(hyper_windows(GridSize::Mega).len(), 9);
+        assert!(!Variant::Hyper.supports(GridSize::Mega));
+
+        let mut board = BoardState::new();
+        board.windows = windows;
+        board.cells[1][1] = Some(4);
+        assert!(!board.window_allows(3, 3, 4), "Cats can't repeat inside a window");
+        assert!(board.window_allows(4, 4, 4), "(4, 4) sits between the windows");
+        assert!(board.get_conflicts().is_empty());
+        board.cells[3][3] = Some(4);
+        assert_eq!(board.get_conflicts(), vec![(1, 1), (3, 3)]);
+    }
+}
@@ -0,0 +1,83 @@
+//! Generator integrity tests.
+//!
+//! Every preset must produce solvable, unique puzzles with givens and grade in range,
+//! within a wall-clock budget. Run these before merging solver or generator
+//! changes (the release-mode `nine_lives self-test` command runs the same checks).
+
+use std::time::Duration;
+
+use nine_lives_core::{GridSize, SelfTestConfig, Variant, run_self_test};
+
+fn assert_self_test_passes(config: SelfTestConfig) {
+    let report = run_self_test(&config);
+    println!("{}", report.summary());
+    assert!(report.passed(), "Self-test failed:\n{}", report.summary());
+}
+
+#[test]
+fn test_every_classic_preset_generates_valid_puzzles() {
+    assert_self_test_passes(SelfTestConfig {
+        puzzles_per_preset: 3,
+        time_budget_per_preset: Duration::from_secs(60),
+        grid_size: GridSize::Classic,
+        variant: Variant::Classic,
+    });
+}
+
+#[test]
+fn test_every_small_board_preset_generates_valid_puzzles() {
+    for grid_size in [GridSize::Mini, GridSize::Small] {
+        assert_self_test_passes(SelfTestConfig {
+            puzzles_per_preset: 3,
+            time_budget_per_preset: Duration::from_secs(10),
+            grid_size,
+            variant: Variant::Classic,
+        });
+    }
+}
+
+#[test]
+fn test_every_mega_preset_generates_valid_puzzles() {
+    assert_self_test_passes(SelfTestConfig {
+        puzzles_per_preset: 1,
+        time_budget_per_preset: Duration::from_secs(120),
+        grid_size: GridSize::Mega,
+        variant: Variant::Classic,
+    });
+}
+
+#[test]
+fn test_every_killer_preset_generates_valid_puzzles() {
+    for grid_size in [GridSize::Mini, GridSize::Small, GridSize::Classic] {
+        assert_self_test_passes(SelfTestConfig {
+            puzzles_per_preset: 3,
+            time_budget_per_preset: Duration::from_secs(60),
+            grid_size,
+            variant: Variant::Killer,
+        });
+    }
+}
+
+#[test]
+fn test_every_jigsaw_preset_generates_valid_puzzles() {
+    for grid_size in GridSize::all() {
+        assert_self_test_passes(SelfTestConfig {
+            puzzles_per_preset: if grid_size == GridSize::Mega { 1 } else { 3 },
+            time_budget_per_preset: Duration::from_secs(60),
+            grid_size,
+            variant: Variant::Jigsaw,
+        });
+    }
+}
+
+#[test]
+fn test_every_hyper_preset_generates_valid_puzzles() {
+    for grid_size in [GridSize::Mini, GridSize::Small, GridSize::Classic] {
+        assert_self_test_passes(SelfTestConfig {
+            puzzles_per_preset: 3,
+            time_budget_per_preset: Duration::from_secs(60),
+            grid_size,
+            variant: Variant::Hyper,
+        });
+    }
+}
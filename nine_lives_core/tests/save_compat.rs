@@ -0,0 +1,115 @@
+//! Save file compatibility tests.
+//!
+//! `tests/fixtures/saves` holds a sample save for every save format the game
+//! has shipped, named `v<format>_<description>.json`. Each one must still load
+//! through `PersistentData::from_json` (and its migrations) with the player's
+//! settings, statistics, and game in progress intact. When the save format
+//! changes, add a fixture for the new version here rather than updating the
+//! old ones.
+
+use std::path::PathBuf;
+
+use nine_lives_core::{
+    BoardState, CellType, Difficulty, GivenStyle, GlyphSet, GridSize, HintVerbosity, NineLivesError, PersistentData,
+    PresetKind, SAVE_FORMAT_VERSION, Variant,
+};
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/saves")
+}
+
+fn load_fixture(name: &str) -> PersistentData {
+    let contents = std::fs::read_to_string(fixtures_dir().join(name)).expect("Fixture should be readable");
+    PersistentData::from_json(&contents).unwrap_or_else(|e| panic!("{} should load: {}", name, e))
+}
+
+#[test]
+fn test_every_fixture_loads_and_round_trips() {
+    let mut versions = Vec::new();
+    for entry in std::fs::read_dir(fixtures_dir()).expect("Fixtures directory should exist") {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        let version: u64 = name
+            .strip_prefix('v')
+            .and_then(|rest| rest.split('_').next())
+            .and_then(|version| version.parse().ok())
+            .unwrap_or_else(|| panic!("{} should be named v<format>_<description>.json", name));
+        versions.push(version);
+
+        // Loading, saving, and loading again keeps everything
+        let data = load_fixture(&name);
+        let resaved = data.to_json().expect("Should serialize");
+        assert!(resaved.contains(&format!("\"format_version\": {}", SAVE_FORMAT_VERSION)));
+        let reloaded = PersistentData::from_json(&resaved).expect("Resaved data should load");
+        assert_eq!(reloaded.to_json().unwrap(), resaved, "{} should survive a round trip", name);
+    }
+
+    // Every format version from the first to the current one has a fixture
+    for version in 1..=SAVE_FORMAT_VERSION {
+        assert!(versions.contains(&version), "No save fixture for format version {}", version);
+    }
+}
+
+#[test]
+fn test_damaged_save_is_a_load_error() {
+    for damaged in ["{\"user_settings\": ", "not a save", "{\"statistics\": 7}"] {
+        let error = PersistentData::from_json(damaged).expect_err("A damaged save shouldn't load");
+        assert!(matches!(error, NineLivesError::LoadError { .. }), "{:?} should be a load error", error);
+        assert!(error.to_string().starts_with("couldn't load"));
+    }
+}
+
+#[test]
+fn test_v1_release_save_keeps_classic_game() {
+    let data = load_fixture("v1_2.0.0_classic.json");
+
+    assert_eq!(data.user_settings.last_preset, PresetKind::StreetwiseStray);
+    assert_eq!(data.user_settings.volume, 0.55);
+    assert!(!data.user_settings.auto_save_enabled);
+    // Settings that came later start at their defaults
+    assert_eq!(data.user_settings.glyph_set, GlyphSet::default());
+    assert!(data.user_settings.double_click_fill);
+
+    assert_eq!(data.statistics.games_completed, 5);
+    assert_eq!(data.statistics.completed_for("Hard"), 2);
+    assert_eq!(data.statistics.fastest_completion_seconds, Some(412));
+    assert_eq!(data.statistics.games_abandoned, 0);
+
+    let save = data.current_save.expect("The game in progress should survive");
+    assert_eq!(save.grid_size, GridSize::Classic);
+    assert_eq!(save.settings.difficulty, Difficulty::Hard);
+    assert_eq!(save.settings.variant, Variant::Classic);
+    assert!(save.history.is_empty());
+
+    let mut board = BoardState::new();
+    board.restore_from_save(&save);
+    assert_eq!(board.cells[0][0], Some(4));
+    assert_eq!(board.cell_types[0][2], Some(CellType::Player));
+    assert_eq!(save.restore_session().move_count, 3);
+    assert_eq!(save.restore_hints().hints_remaining, 1);
+}
+
+#[test]
+fn test_killer_saves_keep_cages_and_history() {
+    for name in ["v1_killer_mini.json", "v2_killer_mini.json"] {
+        let data = load_fixture(name);
+
+        assert_eq!(data.user_settings.hint_verbosity, HintVerbosity::Teaching, "{}", name);
+        assert_eq!(data.user_settings.given_style, GivenStyle::Badge, "{}", name);
+        assert!(!data.user_settings.double_click_fill, "{}", name);
+        assert_eq!(data.statistics.best_win_streak, 2, "{}", name);
+
+        let save = data.current_save.expect("The game in progress should survive");
+        assert_eq!((save.grid_size, save.settings.variant), (GridSize::Mini, Variant::Killer), "{}", name);
+        assert_eq!(save.cages.len(), 6, "{}", name);
+
+        let mut board = BoardState::new();
+        board.restore_from_save(&save);
+        assert_eq!(board.size(), 4);
+        assert_eq!(board.cells[0][0], Some(3));
+
+        // One move made, and one undone that can still be redone
+        let history = save.restore_history();
+        assert!(history.can_undo() && history.can_redo(), "{}", name);
+    }
+}
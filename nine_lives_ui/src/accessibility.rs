@@ -0,0 +1,43 @@
+//! Screen reader support for Nine Lives Cat Sudoku.
+//!
+//! Bevy hands every button to the platform's accessibility API (through
+//! AccessKit), labelled with its text. That's enough for the other buttons,
+//! but a cell's text is cat art, so each cell is labelled with a description
+//! of it instead ("Cell R4C5 selected, candidates 2 3 7", see
+//! `nine_lives_core::describe_cell`) and described further by its row. The
+//! keyboard and gamepad focus (see `FocusManager`) is passed on as the
+//! platform's focus, so screen readers follow it through the focus order.
+
+use bevy::a11y::AccessibilityNode;
+use bevy::input_focus::InputFocus;
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, describe_cell, describe_row};
+
+use crate::{Cell, FocusManager, SelectedCell};
+
+// --- Systems ---
+
+/// System that labels each cell for screen readers, whenever the board or
+/// the selection changes (and once Bevy has made the cells accessible).
+pub fn describe_cells_for_screen_readers(
+    board: Res<BoardState>,
+    selected: Res<SelectedCell>,
+    added: Query<(), (With<Cell>, Added<AccessibilityNode>)>,
+    mut cell_query: Query<(&Cell, &mut AccessibilityNode)>,
+) {
+    if !board.is_changed() && !selected.is_changed() && added.is_empty() {
+        return;
+    }
+    for (cell, mut node) in &mut cell_query {
+        let is_selected = selected.cell == Some((cell.row, cell.col));
+        node.set_label(describe_cell(&board, cell.row, cell.col, is_selected));
+        node.set_description(describe_row(&board, cell.row));
+    }
+}
+
+/// System that passes the keyboard and gamepad focus on to screen readers.
+pub fn sync_input_focus(focus: Res<FocusManager>, mut input_focus: ResMut<InputFocus>) {
+    if input_focus.0 != focus.focused {
+        input_focus.0 = focus.focused;
+    }
+}
@@ -0,0 +1,96 @@
+//! The game window for Nine Lives Cat Sudoku: its icon and title.
+//!
+//! The window wears the same cat paw as the pointer (see `paw_pixels`), drawn
+//! once the window exists. While a puzzle is on screen the title names the
+//! preset it's closest to and the clock ("Nine Lives — Night Prowler —
+//! 12:34"), so it reads well in a taskbar or a stream's window capture.
+//! Streamers who'd rather not show the clock can take it out of the title on
+//! the settings screen.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy::winit::WinitWindows;
+use nine_lives_core::{GameSession, PersistentData, PresetKind, PuzzleSettings, UserSettings};
+use winit::window::Icon;
+
+use crate::{AppState, PAW_COLOR, format_duration_secs, paw_pixels};
+
+/// The window title away from a puzzle
+pub const MENU_TITLE: &str = "Nine Lives: Cat Sudoku";
+/// Width and height of the window icon, in pixels
+const ICON_SIZE: u32 = 64;
+
+/// The window title for the current screen: the preset and (when the
+/// settings allow it) the clock during a puzzle, the game's name elsewhere.
+pub fn window_title(state: &AppState, puzzle: &PuzzleSettings, settings: &UserSettings, elapsed_secs: u64) -> String {
+    if *state != AppState::Ready {
+        return MENU_TITLE.to_string();
+    }
+    let preset = PresetKind::for_difficulty(puzzle.difficulty).name();
+    if settings.title_clock {
+        format!("Nine Lives — {} — {}", preset, format_duration_secs(elapsed_secs))
+    } else {
+        format!("Nine Lives — {}", preset)
+    }
+}
+
+// --- Systems ---
+
+/// System that gives the window its paw icon. Tries each frame until the
+/// window has been created, then stops.
+pub fn set_window_icon(
+    mut done: Local<bool>,
+    windows: Option<NonSend<WinitWindows>>,
+    window_query: Query<Entity, With<PrimaryWindow>>,
+) {
+    if *done {
+        return;
+    }
+    let (Some(windows), Ok(entity)) = (windows, window_query.single()) else {
+        return;
+    };
+    let Some(window) = windows.get_window(entity) else {
+        return;
+    };
+    match Icon::from_rgba(paw_pixels(ICON_SIZE, PAW_COLOR), ICON_SIZE, ICON_SIZE) {
+        Ok(icon) => window.set_window_icon(Some(icon)),
+        Err(e) => warn!("🐾 Couldn't make the window icon: {}", e),
+    }
+    *done = true;
+}
+
+/// System that keeps the window title in step with the screen, the clock,
+/// and the title clock setting.
+pub fn update_window_title(
+    state: Res<State<AppState>>,
+    puzzle: Res<PuzzleSettings>,
+    persistent_data: Res<PersistentData>,
+    session: Option<Res<GameSession>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.single_mut() else {
+        return;
+    };
+    let elapsed = session.map_or(0, |session| session.current_elapsed().as_secs());
+    let title = window_title(state.get(), &puzzle, &persistent_data.user_settings, elapsed);
+    // Only touch the window when the title actually changes
+    if window.title != title {
+        window.title = title;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_title() {
+        let puzzle = PuzzleSettings::from_preset(PresetKind::NightProwler);
+        let mut settings = UserSettings::default();
+        assert_eq!(window_title(&AppState::Customization, &puzzle, &settings, 754), MENU_TITLE);
+        assert_eq!(window_title(&AppState::Ready, &puzzle, &settings, 754), "Nine Lives — Night Prowler — 12:34");
+
+        settings.title_clock = false;
+        assert_eq!(window_title(&AppState::Ready, &puzzle, &settings, 754), "Nine Lives — Night Prowler");
+    }
+}
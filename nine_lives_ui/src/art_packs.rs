@@ -0,0 +1,229 @@
+//! Cat art packs for Nine Lives Cat Sudoku.
+//!
+//! The built-in ASCII cats can be swapped for a pack loaded at startup from
+//! `assets/art_packs/`. A pack is a `.cats` text file: an optional
+//! `name: ...` line, then one piece of art per cat, separated by lines
+//! holding only `---`. A piece can be multi-line ASCII art or a single emoji.
+//! Cats past the end of a pack are drawn as compact faces, the same as on
+//! boards with more cats than the built-in art has (see `CellDetail`).
+//!
+//! The chosen pack is saved in the user's settings by its file name, and is
+//! picked on the settings screen.
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, LoadedFolder};
+use bevy::prelude::*;
+use nine_lives_core::PersistentData;
+
+use crate::{CatEmojis, classic_cat_art};
+
+/// Folder, under the assets folder, that art packs are loaded from
+pub const ART_PACK_FOLDER: &str = "art_packs";
+/// Line separating one cat's art from the next in a pack file
+const PIECE_SEPARATOR: &str = "---";
+/// Display name of the built-in cats
+const CLASSIC_PACK_NAME: &str = "Classic";
+
+// --- Assets ---
+
+/// A set of cat art loaded from a `.cats` file.
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, Eq)]
+pub struct ArtPack {
+    /// The pack's file name without its extension, as saved in the settings
+    pub id: String,
+    pub name: String,
+    /// One piece of art per cat, first cat first
+    pub pieces: Vec<String>,
+}
+
+impl ArtPack {
+    /// Read a pack file. Packs without a `name:` line are named after their
+    /// file. Trailing spaces are trimmed and blank lines around each piece
+    /// dropped; a pack with no art at all is rejected.
+    pub fn parse(id: &str, text: &str) -> Option<ArtPack> {
+        let mut name = id.to_string();
+        let mut pieces = Vec::new();
+        let mut piece: Vec<&str> = Vec::new();
+        let mut finish = |piece: &mut Vec<&str>| {
+            let text = piece.join("\n").trim_matches('\n').to_string();
+            if !text.trim().is_empty() {
+                pieces.push(text);
+            }
+            piece.clear();
+        };
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim_end();
+            if index == 0
+                && let Some(pack_name) = line.strip_prefix("name:")
+            {
+                name = pack_name.trim().to_string();
+            } else if line == PIECE_SEPARATOR {
+                finish(&mut piece);
+            } else {
+                piece.push(line);
+            }
+        }
+        finish(&mut piece);
+
+        (!pieces.is_empty()).then_some(ArtPack { id: id.to_string(), name, pieces })
+    }
+}
+
+/// Asset loader for `.cats` art pack files.
+#[derive(Default)]
+pub struct ArtPackLoader;
+
+impl AssetLoader for ArtPackLoader {
+    type Asset = ArtPack;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<ArtPack, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = String::from_utf8(bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let id = load_context
+            .path()
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        ArtPack::parse(&id, &text)
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "art pack has no art"))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["cats"]
+    }
+}
+
+// --- Resources ---
+
+/// Resource holding the art packs found in the art pack folder.
+#[derive(Resource, Debug, Default)]
+pub struct ArtPackLibrary {
+    /// The folder being loaded, until it has been read
+    folder: Option<Handle<LoadedFolder>>,
+    /// Every pack that loaded, sorted by file name
+    pub packs: Vec<ArtPack>,
+}
+
+impl ArtPackLibrary {
+    /// A pack by its file name.
+    pub fn get(&self, id: &str) -> Option<&ArtPack> {
+        self.packs.iter().find(|pack| pack.id == id)
+    }
+
+    /// The packs a player can choose from, by file name: the built-in cats
+    /// first, then every loaded pack.
+    pub fn choices(&self) -> Vec<Option<String>> {
+        std::iter::once(None)
+            .chain(self.packs.iter().map(|pack| Some(pack.id.clone())))
+            .collect()
+    }
+
+    /// The name to show for a choice. A saved pack that isn't (or isn't yet)
+    /// loaded is shown by its file name.
+    pub fn display_name(&self, choice: Option<&str>) -> String {
+        match choice {
+            None => CLASSIC_PACK_NAME.to_string(),
+            Some(id) => self.get(id).map_or_else(|| id.to_string(), |pack| pack.name.clone()),
+        }
+    }
+
+    /// The art for a choice, falling back to the built-in cats when the
+    /// pack isn't loaded.
+    pub fn art(&self, choice: Option<&str>) -> Vec<String> {
+        choice
+            .and_then(|id| self.get(id))
+            .map_or_else(classic_cat_art, |pack| pack.pieces.clone())
+    }
+}
+
+// --- Systems ---
+
+/// System that starts loading the art pack folder.
+pub fn load_art_packs(asset_server: Res<AssetServer>, mut library: ResMut<ArtPackLibrary>) {
+    library.folder = Some(asset_server.load_folder(ART_PACK_FOLDER));
+}
+
+/// System that collects the art packs once their folder has loaded.
+pub fn collect_art_packs(
+    mut events: EventReader<AssetEvent<LoadedFolder>>,
+    folders: Res<Assets<LoadedFolder>>,
+    packs: Res<Assets<ArtPack>>,
+    mut library: ResMut<ArtPackLibrary>,
+) {
+    for event in events.read() {
+        let AssetEvent::LoadedWithDependencies { id } = event else {
+            continue;
+        };
+        if library.folder.as_ref().is_none_or(|folder| folder.id() != *id) {
+            continue;
+        }
+        let Some(folder) = folders.get(*id) else {
+            continue;
+        };
+
+        let mut loaded: Vec<ArtPack> = folder
+            .handles
+            .iter()
+            .filter_map(|handle| handle.id().try_typed::<ArtPack>().ok().and_then(|id| packs.get(id)))
+            .cloned()
+            .collect();
+        loaded.sort_by(|a, b| a.id.cmp(&b.id));
+        info!("🎨 Found {} cat art pack(s)", loaded.len());
+        library.packs = loaded;
+        library.folder = None;
+    }
+}
+
+/// System that draws cats with the chosen art pack.
+pub fn apply_art_pack(
+    library: Res<ArtPackLibrary>,
+    persistent_data: Res<PersistentData>,
+    mut cat_emojis: ResMut<CatEmojis>,
+) {
+    let art = library.art(persistent_data.user_settings.art_pack.as_deref());
+    if cat_emojis.emojis != art {
+        cat_emojis.emojis = art;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_art_pack_parsing() {
+        let pack = ArtPack::parse("tiny", "name: Tiny Cats\n =^.^= \n  1\n---\n\n =o.o=\n  2\n---\n").unwrap();
+        assert_eq!(pack.name, "Tiny Cats");
+        assert_eq!(pack.pieces, vec![" =^.^=\n  1".to_string(), " =o.o=\n  2".to_string()]);
+
+        // One-line emoji packs without a name are named after their file
+        let pack = ArtPack::parse("emoji", "🐱\n---\n😺\n---\n😸").unwrap();
+        assert_eq!(pack.name, "emoji");
+        assert_eq!(pack.pieces.len(), 3);
+
+        assert_eq!(ArtPack::parse("empty", "name: Nothing\n---\n\n"), None);
+    }
+
+    #[test]
+    fn test_art_pack_choices() {
+        let mut library = ArtPackLibrary::default();
+        assert_eq!(library.choices(), vec![None]);
+        assert_eq!(library.art(Some("emoji")), classic_cat_art(), "Unloaded packs fall back to the built-in cats");
+        assert_eq!(library.display_name(Some("emoji")), "emoji");
+
+        library.packs.push(ArtPack::parse("emoji", "name: Emoji Cats\n🐱\n---\n😺").unwrap());
+        assert_eq!(library.choices(), vec![None, Some("emoji".to_string())]);
+        assert_eq!(library.display_name(None), "Classic");
+        assert_eq!(library.display_name(Some("emoji")), "Emoji Cats");
+        assert_eq!(library.art(Some("emoji")), vec!["🐱".to_string(), "😺".to_string()]);
+    }
+}
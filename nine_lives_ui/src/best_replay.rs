@@ -0,0 +1,377 @@
+//! Record replays on the statistics screen for Nine Lives Cat Sudoku.
+//!
+//! Each preset whose record was set with a replay gets a "Watch" button under
+//! its leaderboard. Pressing it opens a small board below the leaderboards
+//! that plays the record-setting solve back one move at a time. Playback can
+//! be paused and stepped either way, and the viewer closes when the player
+//! leaves the screen.
+
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, GlyphSet, PersistentData, PresetKind, Replay};
+
+use crate::Focusable;
+
+/// Seconds between moves while a record replay plays
+const PLAYBACK_STEP_SECS: f32 = 0.3;
+
+// --- Components & Resources ---
+
+/// Component for the button that watches a preset's record replay.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchBestReplayButton(pub PresetKind);
+
+/// Component to tag the panel showing the record replay being watched.
+#[derive(Component)]
+pub struct BestReplayPanel;
+
+/// Component to tag the title above the record replay's board.
+#[derive(Component)]
+pub struct BestReplayTitle;
+
+/// Component to tag the text drawing the record replay's board.
+#[derive(Component)]
+pub struct BestReplayBoardText;
+
+/// Component to tag the "Move 12/40" text under the record replay's board.
+#[derive(Component)]
+pub struct BestReplayPositionText;
+
+/// The buttons under the record replay's board.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BestReplayControl {
+    Back,
+    PlayPause,
+    Forward,
+    Close,
+}
+
+impl BestReplayControl {
+    /// Get all controls in the order they're laid out.
+    pub fn all() -> [BestReplayControl; 4] {
+        [
+            BestReplayControl::Back,
+            BestReplayControl::PlayPause,
+            BestReplayControl::Forward,
+            BestReplayControl::Close,
+        ]
+    }
+
+    /// Get the button label for this control.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BestReplayControl::Back => "◀",
+            BestReplayControl::PlayPause => "⏯",
+            BestReplayControl::Forward => "▶",
+            BestReplayControl::Close => "✖ Close",
+        }
+    }
+}
+
+/// Resource present while a record replay is being watched.
+#[derive(Resource)]
+pub struct BestReplayViewer {
+    pub preset: PresetKind,
+    pub replay: Replay,
+    /// The board after `position` moves
+    pub board: BoardState,
+    /// How many recorded moves the board currently shows
+    pub position: usize,
+    pub playing: bool,
+    pub timer: Timer,
+}
+
+impl BestReplayViewer {
+    /// Start watching a replay from its first move.
+    pub fn new(preset: PresetKind, replay: Replay) -> Self {
+        Self {
+            preset,
+            board: replay.start().clone(),
+            replay,
+            position: 0,
+            playing: true,
+            timer: Timer::from_seconds(PLAYBACK_STEP_SECS, TimerMode::Repeating),
+        }
+    }
+
+    /// Move the board to show the solve after `target` moves.
+    pub fn seek(&mut self, target: usize) {
+        self.position = self.replay.seek(&mut self.board, self.position, target);
+    }
+
+    /// Whether the board shows the end of the solve.
+    pub fn at_end(&self) -> bool {
+        self.position >= self.replay.len()
+    }
+}
+
+/// Draw a board as lines of glyphs, with a dot for each empty cell.
+pub fn board_text(board: &BoardState, glyphs: GlyphSet) -> String {
+    board
+        .cells
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.map_or_else(|| "·".to_string(), |value| glyphs.glyph(value)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// --- Layout ---
+
+/// Spawn the "Watch" button under a preset's leaderboard.
+pub fn spawn_watch_best_replay_button(column: &mut ChildSpawnerCommands, preset: PresetKind, index: usize) {
+    column
+        .spawn((
+            Button,
+            WatchBestReplayButton(preset),
+            Focusable::WatchBestReplay(index),
+            Node {
+                width: Val::Px(120.0),
+                height: Val::Px(26.0),
+                margin: UiRect::top(Val::Px(4.0)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            BorderColor(Color::srgb(0.5, 0.5, 0.8)),
+        ))
+        .with_children(|button_parent| {
+            button_parent.spawn((
+                Text::new("▶ Watch record"),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Spawn the (initially hidden) record replay panel.
+pub fn spawn_best_replay_panel(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            BestReplayPanel,
+            Node {
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(6.0),
+                margin: UiRect::top(Val::Px(12.0)),
+                ..default()
+            },
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                BestReplayTitle,
+            ));
+            panel.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(JustifyText::Center),
+                BestReplayBoardText,
+            ));
+            panel.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.85, 0.85, 0.95)),
+                BestReplayPositionText,
+            ));
+
+            panel
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(8.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    for (index, control) in BestReplayControl::all().into_iter().enumerate() {
+                        row.spawn((
+                            Button,
+                            control,
+                            Focusable::BestReplayControl(index),
+                            Node {
+                                min_width: Val::Px(40.0),
+                                height: Val::Px(28.0),
+                                padding: UiRect::horizontal(Val::Px(6.0)),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                Text::new(control.display_name()),
+                                TextFont {
+                                    font_size: 12.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+                    }
+                });
+        });
+}
+
+// --- Systems ---
+
+/// System that starts watching a preset's record replay.
+pub fn handle_watch_best_replay_buttons(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &WatchBestReplayButton), Changed<Interaction>>,
+    data: Res<PersistentData>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let Some(saved) = data.statistics.leaderboard.best_replay(button.0) {
+            let replay = saved.restore();
+            info!("🎬 Watching the {} record ({} moves)", button.0.display_name(), replay.len());
+            commands.insert_resource(BestReplayViewer::new(button.0, replay));
+        }
+    }
+}
+
+/// System that handles the record replay's buttons, and the left and right
+/// arrow keys for stepping through it. Stepping pauses playback.
+pub fn handle_best_replay_controls(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &BestReplayControl), Changed<Interaction>>,
+    input: Res<ButtonInput<KeyCode>>,
+    mut viewer: ResMut<BestReplayViewer>,
+) {
+    let mut pressed: Vec<BestReplayControl> = interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, control)| *control)
+        .collect();
+    if input.just_pressed(KeyCode::ArrowLeft) {
+        pressed.push(BestReplayControl::Back);
+    }
+    if input.just_pressed(KeyCode::ArrowRight) {
+        pressed.push(BestReplayControl::Forward);
+    }
+
+    for control in pressed {
+        match control {
+            BestReplayControl::Back => {
+                viewer.playing = false;
+                let target = viewer.position.saturating_sub(1);
+                viewer.seek(target);
+            }
+            BestReplayControl::Forward => {
+                viewer.playing = false;
+                let target = viewer.position + 1;
+                viewer.seek(target);
+            }
+            BestReplayControl::PlayPause => {
+                // Playing from the end starts over
+                if !viewer.playing && viewer.at_end() {
+                    viewer.seek(0);
+                }
+                viewer.playing = !viewer.playing;
+            }
+            BestReplayControl::Close => commands.remove_resource::<BestReplayViewer>(),
+        }
+    }
+}
+
+/// System that plays the record replay forward, stopping at the end.
+pub fn play_best_replay(time: Res<Time>, mut viewer: ResMut<BestReplayViewer>) {
+    if !viewer.playing {
+        return;
+    }
+    if viewer.timer.tick(time.delta()).just_finished() {
+        let target = viewer.position + 1;
+        viewer.seek(target);
+        if viewer.at_end() {
+            viewer.playing = false;
+        }
+    }
+}
+
+/// System that shows the record replay panel while one is being watched,
+/// drawing its board and position.
+#[allow(clippy::type_complexity)]
+pub fn update_best_replay_panel(
+    viewer: Option<Res<BestReplayViewer>>,
+    data: Res<PersistentData>,
+    mut panel_query: Query<&mut Node, With<BestReplayPanel>>,
+    mut title_query: Query<&mut Text, With<BestReplayTitle>>,
+    mut board_query: Query<&mut Text, (With<BestReplayBoardText>, Without<BestReplayTitle>)>,
+    mut position_query: Query<
+        &mut Text,
+        (With<BestReplayPositionText>, Without<BestReplayTitle>, Without<BestReplayBoardText>),
+    >,
+) {
+    for mut node in &mut panel_query {
+        node.display = if viewer.is_some() { Display::Flex } else { Display::None };
+    }
+    let Some(viewer) = viewer else {
+        return;
+    };
+
+    for mut text in &mut title_query {
+        text.0 = format!("🎬 {} record", viewer.preset.display_name());
+    }
+    for mut text in &mut board_query {
+        text.0 = board_text(&viewer.board, data.user_settings.glyph_set);
+    }
+    for mut text in &mut position_query {
+        text.0 = format!("Move {}/{}", viewer.position, viewer.replay.len());
+    }
+}
+
+/// System that stops watching when leaving the statistics screen.
+pub fn end_best_replay_viewer(mut commands: Commands) {
+    commands.remove_resource::<BestReplayViewer>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nine_lives_core::{GridSize, ReplayEventKind, ReplayStep, SavedMove};
+
+    #[test]
+    fn test_best_replay_viewer_steps() {
+        let board = BoardState::with_size(GridSize::Mini);
+        let mut replay = Replay::new(&board);
+        for (col, value) in [(0, 1), (1, 2)] {
+            replay.steps.push(ReplayStep {
+                game_move: SavedMove { row: 0, col, old_value: None, new_value: Some(value) },
+                kind: ReplayEventKind::Move,
+            });
+        }
+
+        let mut viewer = BestReplayViewer::new(PresetKind::CozyKitten, replay);
+        assert_eq!(board_text(&viewer.board, GlyphSet::Arabic).lines().next(), Some("· · · ·"));
+        viewer.seek(5);
+        assert!(viewer.at_end());
+        assert_eq!(board_text(&viewer.board, GlyphSet::Arabic).lines().next(), Some("2 3 · ·"));
+        viewer.seek(1);
+        assert_eq!(board_text(&viewer.board, GlyphSet::Arabic).lines().next(), Some("2 · · ·"));
+    }
+}
@@ -0,0 +1,141 @@
+//! Killer cage rendering for Nine Lives Cat Sudoku.
+//!
+//! Every cell carries an inset outline node. Its borders are drawn only on
+//! the sides where the neighbouring cell belongs to a different cage, so the
+//! outlines of all the cells in a cage join up into one shape. The cage's
+//! sum is printed in the top-left corner of its top-left cell on screen.
+
+use bevy::prelude::*;
+use nine_lives_core::BoardState;
+
+use crate::{BoardOrientation, Cell, GridSlot};
+
+/// Color of the cage outlines
+const CAGE_OUTLINE_COLOR: Color = Color::srgb(0.3, 0.3, 0.4);
+/// Color of the cage sum labels
+const CAGE_SUM_COLOR: Color = Color::srgb(0.15, 0.15, 0.25);
+/// Gap between a cell's edge and its cage outline
+const CAGE_INSET: f32 = 3.0;
+/// How far a joined side reaches past the cell (its 1px border plus half the
+/// 2px grid gap), so neighbouring outlines in the same cage meet up
+const CAGE_JOIN_OVERHANG: f32 = -2.0;
+
+// --- Components ---
+
+/// Component to tag the cage outline drawn inside a cell.
+#[derive(Component)]
+pub struct CageOutline;
+
+/// Component to tag the text showing a cage's sum.
+#[derive(Component)]
+pub struct CageSumLabel;
+
+// --- Layout ---
+
+/// Spawn the (initially invisible) cage outline and sum label inside a cell.
+/// Must come after the cell's cat text, which other systems expect as the first child.
+pub fn spawn_cage_outline(cell_parent: &mut ChildSpawnerCommands) {
+    cell_parent
+        .spawn((
+            CageOutline,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(CAGE_INSET),
+                right: Val::Px(CAGE_INSET),
+                top: Val::Px(CAGE_INSET),
+                bottom: Val::Px(CAGE_INSET),
+                border: UiRect::ZERO,
+                ..default()
+            },
+            BorderColor(CAGE_OUTLINE_COLOR),
+        ))
+        .with_children(|outline| {
+            outline.spawn((
+                CageSumLabel,
+                Text::new(""),
+                TextFont {
+                    font_size: 10.0,
+                    ..default()
+                },
+                TextColor(CAGE_SUM_COLOR),
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(1.0),
+                    top: Val::Px(0.0),
+                    ..default()
+                },
+            ));
+        });
+}
+
+// --- Systems ---
+
+/// System that draws the cage outlines and sums for the current board.
+/// Works in on-screen slots so the outlines stay joined up when the board is
+/// rotated; classic boards (no cages) get no outlines at all.
+pub fn update_cage_outlines(
+    board: Res<BoardState>,
+    orientation: Res<BoardOrientation>,
+    cell_query: Query<(&Cell, &GridSlot, &Children)>,
+    mut outline_query: Query<(&mut Node, &Children), With<CageOutline>>,
+    mut label_query: Query<&mut Text, With<CageSumLabel>>,
+) {
+    let size = board.size();
+    let cage_map = board.cage_map();
+
+    // The slot each cage's sum is printed in
+    let anchors: Vec<(usize, usize)> = board
+        .cages
+        .iter()
+        .map(|cage| {
+            cage.cells
+                .iter()
+                .map(|&(row, col)| orientation.board_to_slot(row, col, size))
+                .min()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    for (cell, slot, children) in &cell_query {
+        let cage = cage_map[cell.row][cell.col];
+
+        // A side is an edge of the cage wherever the neighbouring slot is off
+        // the board or in another cage; other sides stretch to meet the neighbour
+        let is_edge = |row_offset: isize, col_offset: isize| {
+            let (row, col) = (slot.row as isize + row_offset, slot.col as isize + col_offset);
+            if row < 0 || col < 0 || row >= size as isize || col >= size as isize {
+                return true;
+            }
+            let (board_row, board_col) = orientation.slot_to_board(row as usize, col as usize, size);
+            cage_map[board_row][board_col] != cage
+        };
+        let sides = [is_edge(0, -1), is_edge(0, 1), is_edge(-1, 0), is_edge(1, 0)];
+        let width = |edge: bool| if cage.is_some() && edge { Val::Px(1.0) } else { Val::ZERO };
+        let offset = |edge: bool| Val::Px(if edge { CAGE_INSET } else { CAGE_JOIN_OVERHANG });
+        let border = UiRect::new(width(sides[0]), width(sides[1]), width(sides[2]), width(sides[3]));
+
+        let label = cage
+            .filter(|&index| anchors[index] == (slot.row, slot.col))
+            .map(|index| board.cages[index].sum.to_string())
+            .unwrap_or_default();
+
+        for child in children.iter() {
+            if let Ok((mut node, outline_children)) = outline_query.get_mut(child) {
+                if node.border != border {
+                    node.border = border;
+                    node.left = offset(sides[0]);
+                    node.right = offset(sides[1]);
+                    node.top = offset(sides[2]);
+                    node.bottom = offset(sides[3]);
+                }
+                for label_entity in outline_children.iter() {
+                    if let Ok(mut text) = label_query.get_mut(label_entity)
+                        && text.0 != label
+                    {
+                        text.0 = label.clone();
+                    }
+                }
+            }
+        }
+    }
+}
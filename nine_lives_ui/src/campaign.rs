@@ -0,0 +1,375 @@
+//! Campaign screen for Nine Lives Cat Sudoku.
+//!
+//! Reached with the 🗺️ Campaign button on the customization screen. Lists
+//! the campaign's chapters (see `nine_lives_core::campaign`) with a button for
+//! each puzzle showing the best stars earned on it; puzzles not yet unlocked
+//! show a padlock and can't be picked. Picking one generates its fixed puzzle
+//! and goes to the game screen, where solving it announces the stars earned
+//! alongside the share code.
+
+use bevy::prelude::*;
+use nine_lives_core::{
+    BoardState, CHAPTERS, CampaignLevel, CampaignProgress, GameHistory, GameSession, HintSystem, PersistentData, Solution,
+    star_string,
+};
+
+use crate::{AppState, ErrorToast, Focusable, LastGeneration};
+
+/// Background of an unlocked puzzle's button
+const LEVEL_BG: Color = Color::srgb(0.25, 0.3, 0.5);
+/// Background of an unlocked puzzle's button while hovered
+const LEVEL_HOVER_BG: Color = Color::srgb(0.3, 0.38, 0.62);
+/// Background of a puzzle that's still locked
+const LOCKED_BG: Color = Color::srgb(0.18, 0.18, 0.22);
+/// Color of the stars
+const STAR_COLOR: Color = Color::srgb(1.0, 0.85, 0.4);
+
+// --- Components & Resources ---
+
+/// Component to tag the campaign screen root for cleanup.
+#[derive(Component)]
+pub struct CampaignScreenRoot;
+
+/// Component to tag the "Campaign" button on the customization screen.
+#[derive(Component)]
+pub struct CampaignButton;
+
+/// Component to tag the "Back" button on the campaign screen.
+#[derive(Component)]
+pub struct CampaignBackButton;
+
+/// Component for an unlocked puzzle's button on the campaign screen.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CampaignLevelButton(pub CampaignLevel);
+
+/// Component to tag the line announcing the stars a campaign solve earned.
+#[derive(Component)]
+pub struct CampaignResultDisplay;
+
+/// The stars the last solved campaign puzzle earned.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CampaignResult {
+    /// The puzzle solved, if it was a campaign puzzle
+    pub level: Option<CampaignLevel>,
+    pub stars: u8,
+    /// Whether the stars beat the puzzle's previous best
+    pub improved: bool,
+}
+
+impl CampaignResult {
+    /// The announcement for the game screen, if a campaign puzzle was solved.
+    pub fn message(&self) -> Option<String> {
+        let level = self.level?;
+        Some(format!(
+            "🗺️ Campaign {} solved: {}{}",
+            level.label(),
+            star_string(self.stars),
+            if self.improved { " (new best!)" } else { "" }
+        ))
+    }
+}
+
+/// A puzzle button's label: its number and best stars, or a padlock while
+/// it's locked.
+pub fn campaign_level_label(progress: &CampaignProgress, level: CampaignLevel) -> String {
+    if progress.is_unlocked(level) {
+        format!("{}\n{}", level.label(), star_string(progress.stars(level)))
+    } else {
+        "🔒".to_string()
+    }
+}
+
+// --- Systems ---
+
+/// System that creates the campaign screen UI.
+pub fn setup_campaign_screen(mut commands: Commands, data: Res<PersistentData>) {
+    let progress = &data.campaign;
+    let max_stars = CampaignLevel::all().len() as u32 * nine_lives_core::MAX_STARS as u32;
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+            CampaignScreenRoot,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new("🗺️ Cat Campaign"),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::new(format!("★ {}/{}", progress.total_stars(), max_stars)),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(STAR_COLOR),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+            ));
+
+            let mut index = 0;
+            for (chapter_index, chapter) in CHAPTERS.iter().enumerate() {
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(4.0),
+                        margin: UiRect::top(Val::Px(6.0)),
+                        ..default()
+                    })
+                    .with_children(|section| {
+                        section.spawn((
+                            Text::new(format!("Chapter {}: {}", chapter_index + 1, chapter.name)),
+                            TextFont {
+                                font_size: 16.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                        ));
+                        section.spawn((
+                            Text::new(chapter.blurb),
+                            TextFont {
+                                font_size: 11.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.7, 0.7, 0.8)),
+                        ));
+                        section
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Row,
+                                column_gap: Val::Px(8.0),
+                                ..default()
+                            })
+                            .with_children(|row| {
+                                for puzzle in 0..chapter.seeds.len() {
+                                    let level = CampaignLevel { chapter: chapter_index, puzzle };
+                                    spawn_campaign_level_button(row, progress, level, index);
+                                    index += 1;
+                                }
+                            });
+                    });
+            }
+
+            // Back button
+            parent
+                .spawn((
+                    Button,
+                    CampaignBackButton,
+                    Focusable::CampaignBack,
+                    Node {
+                        width: Val::Px(160.0),
+                        height: Val::Px(45.0),
+                        margin: UiRect::top(Val::Px(20.0)),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                    BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        Text::new("⬅ Back"),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+/// Spawn one puzzle's button. Locked puzzles get a plain box instead, so
+/// they can't be clicked or focused.
+fn spawn_campaign_level_button(
+    row: &mut ChildSpawnerCommands,
+    progress: &CampaignProgress,
+    level: CampaignLevel,
+    index: usize,
+) {
+    let node = Node {
+        width: Val::Px(64.0),
+        height: Val::Px(48.0),
+        align_items: AlignItems::Center,
+        justify_content: JustifyContent::Center,
+        border: UiRect::all(Val::Px(2.0)),
+        ..default()
+    };
+    let label = (
+        Text::new(campaign_level_label(progress, level)),
+        TextFont {
+            font_size: 13.0,
+            ..default()
+        },
+        TextColor(if progress.stars(level) > 0 { STAR_COLOR } else { Color::WHITE }),
+        TextLayout::new_with_justify(JustifyText::Center),
+    );
+    if progress.is_unlocked(level) {
+        row.spawn((
+            Button,
+            CampaignLevelButton(level),
+            Focusable::CampaignLevel(index),
+            node,
+            BackgroundColor(LEVEL_BG),
+            BorderColor(Color::srgb(0.45, 0.5, 0.8)),
+        ))
+        .with_child(label);
+    } else {
+        row.spawn((node, BackgroundColor(LOCKED_BG), BorderColor(Color::srgb(0.3, 0.3, 0.35))))
+            .with_child(label);
+    }
+}
+
+/// System to clean up the campaign screen when exiting that state.
+pub fn cleanup_campaign_screen(mut commands: Commands, query: Query<Entity, With<CampaignScreenRoot>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// System that opens the campaign screen from the customization screen.
+pub fn handle_campaign_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<CampaignButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::Campaign);
+        }
+    }
+}
+
+/// System that returns from the campaign screen to the customization screen.
+pub fn handle_campaign_back_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<CampaignBackButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::Customization);
+        }
+    }
+}
+
+/// Everything needed to start a campaign puzzle.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct CampaignStart<'w, 's> {
+    commands: Commands<'w, 's>,
+    app_state: ResMut<'w, NextState<AppState>>,
+    board: ResMut<'w, BoardState>,
+    session: ResMut<'w, GameSession>,
+    history: ResMut<'w, GameHistory>,
+    solution: ResMut<'w, Solution>,
+    hint_system: ResMut<'w, HintSystem>,
+    last_generation: ResMut<'w, LastGeneration>,
+    toasts: EventWriter<'w, ErrorToast>,
+}
+
+impl CampaignStart<'_, '_> {
+    /// Generate a campaign puzzle and go to the game screen, like the Start
+    /// Game button does for a random one.
+    fn start(&mut self, level: CampaignLevel) {
+        let settings = level.settings();
+        let (new_solution, report) = match self.board.generate_puzzle_with_report(&settings) {
+            Ok(generated) => generated,
+            Err(e) => {
+                self.toasts.write(ErrorToast::new(&format!("Campaign puzzle {}", level.label()), &e));
+                return;
+            }
+        };
+        info!("🗺️ Starting campaign puzzle {}: {}", level.label(), settings.description());
+        *self.solution = new_solution;
+        self.session.reset();
+        self.history.clear();
+        self.hint_system.reset(settings.max_hints);
+        *self.last_generation = LastGeneration { report: Some(report), pre_generated: false };
+        self.commands.insert_resource(settings);
+        self.app_state.set(AppState::Ready);
+    }
+}
+
+/// System that starts the puzzle whose button is pressed.
+pub fn handle_campaign_level_buttons(
+    interaction_query: Query<(&Interaction, &CampaignLevelButton), Changed<Interaction>>,
+    mut start: CampaignStart,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            start.start(button.0);
+        }
+    }
+}
+
+/// System to handle puzzle button hover effects.
+#[allow(clippy::type_complexity)]
+pub fn update_campaign_level_colors(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), (With<CampaignLevelButton>, Changed<Interaction>)>,
+) {
+    for (interaction, mut bg_color) in &mut button_query {
+        bg_color.0 = match interaction {
+            Interaction::Pressed | Interaction::Hovered => LEVEL_HOVER_BG,
+            Interaction::None => LEVEL_BG,
+        };
+    }
+}
+
+/// Spawn the line announcing a campaign solve's stars (empty until one).
+pub fn spawn_campaign_result_display(parent: &mut ChildSpawnerCommands) {
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(STAR_COLOR),
+        CampaignResultDisplay,
+    ));
+}
+
+/// System that announces a campaign solve's stars on the game screen.
+pub fn update_campaign_result_display(
+    result: Res<CampaignResult>,
+    mut display_query: Query<&mut Text, With<CampaignResultDisplay>>,
+) {
+    for mut text in &mut display_query {
+        text.0 = result.message().unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_campaign_labels() {
+        let mut progress = CampaignProgress::default();
+        let first = CampaignLevel { chapter: 0, puzzle: 0 };
+        let second = CampaignLevel { chapter: 0, puzzle: 1 };
+        assert_eq!(campaign_level_label(&progress, first), "1-1\n☆☆☆");
+        assert_eq!(campaign_level_label(&progress, second), "🔒");
+        progress.record(first, 2);
+        assert_eq!(campaign_level_label(&progress, first), "1-1\n★★☆");
+        assert_eq!(campaign_level_label(&progress, second), "1-2\n☆☆☆");
+
+        assert_eq!(CampaignResult::default().message(), None);
+        let result = CampaignResult { level: Some(second), stars: 3, improved: true };
+        assert_eq!(result.message().unwrap(), "🗺️ Campaign 1-2 solved: ★★★ (new best!)");
+    }
+}
@@ -0,0 +1,284 @@
+//! Cell backgrounds for Nine Lives Cat Sudoku.
+//!
+//! A cell's background is built up in layers, bottom to top, and composed in
+//! one system (`update_cell_colors`) that nothing else competes with:
+//!
+//! 1. **Base**: the box shading (or a single color on jigsaw boards), with
+//!    Hyper windows tinted by the theme's accent color.
+//! 2. **State**: what the cell is: a shaded given, an error, part of a solved
+//!    board, or its heat on the mistake heatmap.
+//! 3. **Selection**: the cells sharing a row, column, or box with the
+//!    selected cell (and the selected cell itself) lean toward the selection
+//!    blue, as strongly as the settings ask.
+//! 4. **Hover**: the hovered cell brightens, and the cells sharing a row,
+//!    column, or box with it are shaded when that highlighting is turned on.
+//!
+//! Each layer tints the one below it rather than replacing it, so a hovered
+//! conflict still reads as a conflict. A new kind of highlight is a new
+//! `CellState`, `SelectionTint`, or `HoverTint`, not another system writing
+//! `BackgroundColor`.
+
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, ConflictTracker, GameMode, GameState, HighlightIntensity, PersistentData, Solution};
+use std::collections::HashSet;
+
+use crate::{Cell, HeatmapOverlay, SelectedCell, Theme, heat_color};
+
+/// How strongly Hyper window cells are tinted with the theme's accent color
+const WINDOW_SHADE: f32 = 0.25;
+/// Background of a cell with a conflict or mistake
+const ERROR_COLOR: Color = Color::srgb(1.0, 0.7, 0.7);
+/// Background of every cell once the puzzle is solved
+const SOLVED_COLOR: Color = Color::srgb(0.6, 0.9, 0.6);
+/// How much darker shaded givens are than their base (in linear space)
+const GIVEN_SHADE: f32 = 0.7;
+/// How much brighter the hovered cell is than its state color (in linear space)
+const HOVER_BRIGHTEN: f32 = 1.2;
+/// How much lighter the hovered cell's row, column, and box are (in linear space)
+const HOUSE_LIFT: f32 = 0.05;
+/// The color the selected cell's row, column, and box lean toward (the
+/// selected cell's border is the same blue)
+const SELECTION_COLOR: Color = Color::srgb(0.2, 0.5, 0.9);
+
+// --- Layers ---
+
+/// The state layer: what the cell is, as far as its color goes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CellState {
+    /// An ordinary cell: just the base color.
+    Open,
+    /// A given, shaded darker (when the given style asks for it).
+    Given,
+    /// A conflict, or a cat that doesn't match the solution.
+    Error,
+    /// Part of a solved board.
+    Solved,
+    /// Part of a solved board under the mistake heatmap, with its heat (0.0 to 1.0).
+    Heat(f32),
+}
+
+/// The selection layer: how far the cell leans toward the selection blue
+/// (0.0 to 1.0) for sharing a house with the selected cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelectionTint {
+    None,
+    House(f32),
+}
+
+impl SelectionTint {
+    /// The tint for a cell in the selected cell's houses, at the intensity
+    /// picked in the settings.
+    pub fn for_intensity(intensity: HighlightIntensity) -> Self {
+        match intensity {
+            HighlightIntensity::Off => SelectionTint::None,
+            HighlightIntensity::Subtle => SelectionTint::House(0.12),
+            HighlightIntensity::Medium => SelectionTint::House(0.22),
+            HighlightIntensity::Strong => SelectionTint::House(0.35),
+        }
+    }
+}
+
+/// The hover layer: how the hovered cell affects this one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoverTint {
+    None,
+    /// Shares a row, column, or box with the hovered cell.
+    House,
+    /// Is the hovered cell.
+    Cell,
+}
+
+/// The layers a cell's background is composed from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellLayers {
+    pub base: Color,
+    pub state: CellState,
+    pub selection: SelectionTint,
+    pub hover: HoverTint,
+}
+
+impl CellLayers {
+    /// The cell's background: the base, tinted by its state, then by the
+    /// selection, then by hover.
+    pub fn compose(&self) -> Color {
+        let color = match self.state {
+            CellState::Open => self.base,
+            CellState::Given => scale_linear(self.base, GIVEN_SHADE),
+            CellState::Error => ERROR_COLOR,
+            CellState::Solved => SOLVED_COLOR,
+            CellState::Heat(heat) => heat_color(self.base, heat),
+        };
+        let color = match self.selection {
+            SelectionTint::None => color,
+            SelectionTint::House(amount) => color.mix(&SELECTION_COLOR, amount),
+        };
+        match self.hover {
+            HoverTint::None => color,
+            HoverTint::House => {
+                let [r, g, b, a] = color.to_linear().to_f32_array();
+                Color::linear_rgba(r + HOUSE_LIFT, g + HOUSE_LIFT, b + HOUSE_LIFT, a)
+            }
+            HoverTint::Cell => scale_linear(color, HOVER_BRIGHTEN),
+        }
+    }
+}
+
+/// Scale a color's channels in linear space (leaving alpha alone).
+fn scale_linear(color: Color, factor: f32) -> Color {
+    let [r, g, b, a] = color.to_linear().to_f32_array();
+    Color::linear_rgba(r * factor, g * factor, b * factor, a)
+}
+
+/// The base layer for a cell, from its position and the theme.
+/// Creates a visual distinction between the sudoku boxes (jigsaw regions are
+/// outlined instead, so those boards use a single color). Cells inside a
+/// Hyper window are shaded with the theme's accent color.
+fn base_color(row: usize, col: usize, board: &BoardState, theme: &Theme) -> Color {
+    let base = if !board.regions.is_boxes(board.grid_size) {
+        theme.primary_color
+    } else {
+        let box_row = row / board.grid_size.box_rows();
+        let box_col = col / board.grid_size.box_cols();
+
+        // Alternate colors for the boxes to make them visually distinct
+        if (box_row + box_col).is_multiple_of(2) {
+            theme.primary_color
+        } else {
+            theme.secondary_color
+        }
+    };
+
+    if board.window_index(row, col).is_some() {
+        base.mix(&theme.accent_color, WINDOW_SHADE)
+    } else {
+        base
+    }
+}
+
+// --- Systems ---
+
+/// A system to update cell colors from their layers (see the module docs).
+///
+/// This provides visual feedback by:
+/// - Highlighting conflicting cells in red (and, if the error policy asks for
+///   it, cats that don't match the solution)
+/// - Highlighting the entire board in green when completed (or showing the
+///   mistake heatmap over it)
+/// - Shading the selected cell's row, column, and box for as long as it's
+///   selected, as strongly as picked in the settings
+/// - Brightening the hovered cell, and shading its row, column, and box
+///   when that highlighting is turned on
+/// - Using themed colors for normal cells
+#[allow(clippy::too_many_arguments)]
+pub fn update_cell_colors(
+    board: Res<BoardState>,
+    game_state: Res<GameState>,
+    theme: Res<Theme>,
+    persistent_data: Res<PersistentData>,
+    game_mode: Res<GameMode>,
+    solution: Res<Solution>,
+    heatmap: Res<HeatmapOverlay>,
+    tracker: Res<ConflictTracker>,
+    selected: Res<SelectedCell>,
+    mut cell_query: Query<(&Cell, &Interaction, &mut BackgroundColor)>,
+) {
+    let settings = &persistent_data.user_settings;
+    let shade_givens = settings.given_style.shades_background();
+    let error_policy = game_mode.error_policy(settings.error_policy);
+    let mut error_set: HashSet<(usize, usize)> = HashSet::new();
+    if error_policy.shows_conflicts() {
+        error_set.extend(tracker.conflicts());
+    }
+    if error_policy.shows_mistakes() {
+        for (row, cells) in board.cells.iter().enumerate() {
+            for (col, value) in cells.iter().enumerate() {
+                if value.is_some_and(|value| solution.cells.get(row).and_then(|r| r.get(col)) != Some(&value)) {
+                    error_set.insert((row, col));
+                }
+            }
+        }
+    }
+    let is_complete = matches!(*game_state, GameState::Won);
+    let hovered = cell_query
+        .iter()
+        .find(|(_, interaction, _)| **interaction == Interaction::Hovered)
+        .map(|(cell, _, _)| (cell.row, cell.col));
+
+    let shares_house = |(row, col): (usize, usize), cell: &Cell| {
+        row == cell.row || col == cell.col || board.regions.region_of(row, col) == board.regions.region_of(cell.row, cell.col)
+    };
+
+    for (cell, _, mut bg_color) in &mut cell_query {
+        let is_given = board.is_given_cell(cell.row, cell.col);
+        let state = if is_complete && heatmap.shown {
+            // Redder where more mistakes have been made, across games
+            CellState::Heat(persistent_data.statistics.cell_stats.heat(board.grid_size, cell.row, cell.col))
+        } else if is_complete {
+            CellState::Solved
+        } else if error_set.contains(&(cell.row, cell.col)) {
+            CellState::Error
+        } else if shade_givens && is_given {
+            CellState::Given
+        } else {
+            CellState::Open
+        };
+        // A solved board doesn't react to the selection or hovering, and
+        // givens (which can't be changed) only get their house's shading
+        let selection = match selected.cell {
+            Some(at) if !is_complete && shares_house(at, cell) => SelectionTint::for_intensity(settings.selection_highlight),
+            _ => SelectionTint::None,
+        };
+        let hover = match hovered {
+            _ if is_complete => HoverTint::None,
+            Some(at) if at == (cell.row, cell.col) && !is_given => HoverTint::Cell,
+            Some(at) if settings.highlight_houses && shares_house(at, cell) => HoverTint::House,
+            _ => HoverTint::None,
+        };
+        let layers = CellLayers { base: base_color(cell.row, cell.col, &board, &theme), state, selection, hover };
+        let color = layers.compose();
+        if bg_color.0 != color {
+            bg_color.0 = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: Color = Color::srgb(0.5, 0.5, 0.6);
+
+    fn layers(state: CellState, hover: HoverTint) -> Color {
+        CellLayers { base: BASE, state, selection: SelectionTint::None, hover }.compose()
+    }
+
+    #[test]
+    fn test_hover_tints_rather_than_replaces_the_state() {
+        let error = layers(CellState::Error, HoverTint::None).to_srgba();
+        let hovered_error = layers(CellState::Error, HoverTint::Cell).to_srgba();
+        assert!(hovered_error.green > error.green, "The hovered cell is brighter");
+        assert!(hovered_error.red > hovered_error.green, "...and still reads as an error");
+
+        let given = layers(CellState::Given, HoverTint::None).to_srgba();
+        let given_in_house = layers(CellState::Given, HoverTint::House).to_srgba();
+        let open = layers(CellState::Open, HoverTint::None).to_srgba();
+        assert!(given.blue < given_in_house.blue && given_in_house.blue < open.blue);
+    }
+
+    #[test]
+    fn test_selection_tint_follows_the_intensity() {
+        let blue = |intensity| {
+            let selection = SelectionTint::for_intensity(intensity);
+            CellLayers { base: BASE, state: CellState::Open, selection, hover: HoverTint::None }.compose().to_srgba().blue
+        };
+        assert_eq!(SelectionTint::for_intensity(HighlightIntensity::Off), SelectionTint::None);
+        let [off, subtle, medium, strong] = HighlightIntensity::all().map(blue);
+        assert!(off < subtle && subtle < medium && medium < strong);
+    }
+
+    #[test]
+    fn test_open_cell_without_hover_is_its_base() {
+        assert_eq!(layers(CellState::Open, HoverTint::None), BASE);
+        assert_eq!(layers(CellState::Solved, HoverTint::None), SOLVED_COLOR);
+    }
+}
@@ -0,0 +1,119 @@
+//! How much of each cat the board's cells draw, for Nine Lives Cat Sudoku.
+//!
+//! Cats are drawn as multi-line ASCII art when the cells have room for it,
+//! as a small kitty face over the cat's glyph when they don't, and as the bare
+//! glyph when even that won't fit. The choice is made from the cells' actual
+//! size on screen, so it follows window resizes and layout changes, and is
+//! made for the whole board at once so cells never mix styles.
+
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, GlyphSet, PersistentData};
+
+use crate::{CatEmojis, Cell, compact_cat, estimated_text_size};
+
+/// Font size of the cats drawn in cells
+pub(crate) const CELL_FONT_SIZE: f32 = 8.0;
+/// Room around a cell's cat left for the cell border and a little breathing space
+const CELL_CONTENT_MARGIN: f32 = 4.0;
+
+// --- Resources ---
+
+/// Resource holding how much of each cat the board's cells have room for.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellDetail {
+    /// The full ASCII art
+    #[default]
+    Art,
+    /// A small kitty face over the cat's glyph
+    Compact,
+    /// Just the cat's glyph
+    Glyph,
+}
+
+impl CellDetail {
+    /// The most detailed style in which every one of `cats` cats fits in a
+    /// cell of `cell_size` logical pixels. Art is only used if there's a
+    /// piece for every cat.
+    pub fn for_cells(cell_size: Vec2, cats: usize, cat_emojis: &CatEmojis, glyphs: GlyphSet) -> Self {
+        let room = cell_size - Vec2::splat(CELL_CONTENT_MARGIN);
+        let fits = |text: String| estimated_text_size(&text, CELL_FONT_SIZE).cmple(room).all();
+        if cat_emojis.emojis.len() >= cats
+            && (0..cats).all(|value| fits(glyphs.localize_art(&cat_emojis.emojis[value], value)))
+        {
+            CellDetail::Art
+        } else if (0..cats).all(|value| fits(compact_cat(value, glyphs))) {
+            CellDetail::Compact
+        } else {
+            CellDetail::Glyph
+        }
+    }
+
+    /// The text for a cell holding the given cat.
+    pub fn cat_text(&self, value: usize, cat_emojis: &CatEmojis, glyphs: GlyphSet) -> String {
+        match (self, cat_emojis.emojis.get(value)) {
+            (CellDetail::Art, Some(art)) => glyphs.localize_art(art, value),
+            (CellDetail::Art | CellDetail::Compact, _) => compact_cat(value, glyphs),
+            (CellDetail::Glyph, _) => glyphs.glyph(value),
+        }
+    }
+}
+
+// --- Systems ---
+
+/// System that picks the cell detail from the smallest cell on the board,
+/// whenever the cells are laid out afresh or the cat art changes. Cells that
+/// haven't been laid out yet are skipped.
+pub fn update_cell_detail(
+    board: Res<BoardState>,
+    cat_emojis: Res<CatEmojis>,
+    persistent_data: Res<PersistentData>,
+    cell_query: Query<&ComputedNode, With<Cell>>,
+    changed_query: Query<(), (With<Cell>, Changed<ComputedNode>)>,
+    mut detail: ResMut<CellDetail>,
+) {
+    if changed_query.is_empty() && !persistent_data.is_changed() && !cat_emojis.is_changed() {
+        return;
+    }
+    let Some(cell_size) = cell_query
+        .iter()
+        .map(|node| node.size() * node.inverse_scale_factor())
+        .filter(|size| *size != Vec2::ZERO)
+        .reduce(Vec2::min)
+    else {
+        return;
+    };
+
+    let new_detail = CellDetail::for_cells(cell_size, board.size(), &cat_emojis, persistent_data.user_settings.glyph_set);
+    if *detail != new_detail {
+        *detail = new_detail;
+        info!("🔍 Cells now show {:?} cats", new_detail);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BoardOrientation;
+
+    #[test]
+    fn test_cell_detail_follows_cell_size() {
+        let mut app = App::new();
+        app.add_systems(Startup, crate::setup_cat_emojis);
+        app.update();
+        let cat_emojis = app.world().resource::<CatEmojis>();
+        let glyphs = GlyphSet::Arabic;
+        let cell = |size: usize| Vec2::from(BoardOrientation::Landscape.cell_size(size, 1.0));
+
+        // Classic cells fit the art; Mega boards have more cats than art pieces
+        assert_eq!(CellDetail::for_cells(cell(9), 9, cat_emojis, glyphs), CellDetail::Art);
+        assert_eq!(CellDetail::for_cells(cell(16), 16, cat_emojis, glyphs), CellDetail::Compact);
+
+        // Shrunken cells step down to the compact face, then the bare glyph
+        assert_eq!(CellDetail::for_cells(cell(9) * 0.5, 9, cat_emojis, glyphs), CellDetail::Compact);
+        assert_eq!(CellDetail::for_cells(Vec2::splat(16.0), 9, cat_emojis, glyphs), CellDetail::Glyph);
+        assert_eq!(CellDetail::Glyph.cat_text(4, cat_emojis, glyphs), "5");
+
+        // Art is never asked for a cat without a piece
+        assert_eq!(CellDetail::Art.cat_text(12, cat_emojis, glyphs), compact_cat(12, glyphs));
+    }
+}
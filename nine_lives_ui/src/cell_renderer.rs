@@ -0,0 +1,164 @@
+//! Cell renderers for Nine Lives Cat Sudoku.
+//!
+//! A `CellRenderer` decides what a cell holding a cat shows: some text, an
+//! image, or both. `update_cell_text` draws every cell through whichever
+//! renderer the player picked in settings, so the two styles share the
+//! layout, coloring, and candidate marks:
+//!
+//! - `AsciiRenderer` draws the cat as text, in as much detail as the cells
+//!   have room for (see `CellDetail`).
+//! - `SpriteRenderer` draws a picture of each cat, loaded from
+//!   `assets/sprites/cat_<number>.png`. Cats whose picture is missing (or
+//!   still loading) are drawn as text instead.
+
+use bevy::prelude::*;
+use nine_lives_core::{GlyphSet, GridSize};
+
+use crate::{CatEmojis, CellDetail};
+
+/// Folder, under the assets folder, that cat sprites are loaded from
+pub const CAT_SPRITE_FOLDER: &str = "sprites";
+/// Tint for sprites of cats placed by the player, so they stand apart from givens
+const PLAYER_SPRITE_TINT: Color = Color::srgb(0.75, 0.85, 1.0);
+/// How much of its cell a sprite fills, as a percentage
+const SPRITE_SIZE_PERCENT: f32 = 80.0;
+
+// --- Renderers ---
+
+/// Decides what a cell holding a cat shows.
+pub trait CellRenderer {
+    /// The text drawn in a cell holding `value`.
+    fn cat_text(&self, value: usize) -> String;
+
+    /// The image drawn in a cell holding `value`, if the cat is drawn as one.
+    fn cat_image(&self, _value: usize) -> Option<Handle<Image>> {
+        None
+    }
+}
+
+/// Draws cats as ASCII art, compact faces, or glyphs.
+#[derive(Clone, Copy)]
+pub struct AsciiRenderer<'a> {
+    pub detail: CellDetail,
+    pub cat_emojis: &'a CatEmojis,
+    pub glyphs: GlyphSet,
+}
+
+impl CellRenderer for AsciiRenderer<'_> {
+    fn cat_text(&self, value: usize) -> String {
+        self.detail.cat_text(value, self.cat_emojis, self.glyphs)
+    }
+}
+
+/// Draws cats as sprites, falling back to text for cats without one.
+pub struct SpriteRenderer<'a> {
+    pub sprites: &'a CatSprites,
+    pub images: &'a Assets<Image>,
+    pub fallback: AsciiRenderer<'a>,
+}
+
+impl SpriteRenderer<'_> {
+    /// The cat's sprite, if it has loaded.
+    fn sprite(&self, value: usize) -> Option<&Handle<Image>> {
+        self.sprites.images.get(value).filter(|handle| self.images.contains(*handle))
+    }
+}
+
+impl CellRenderer for SpriteRenderer<'_> {
+    fn cat_text(&self, value: usize) -> String {
+        match self.sprite(value) {
+            Some(_) => String::new(),
+            None => self.fallback.cat_text(value),
+        }
+    }
+
+    fn cat_image(&self, value: usize) -> Option<Handle<Image>> {
+        self.sprite(value).cloned()
+    }
+}
+
+// --- Components & Resources ---
+
+/// Component to tag the image node in each cell that shows a cat's sprite.
+#[derive(Component)]
+pub struct CellSprite;
+
+/// Resource holding the sprite of each cat, first cat first.
+#[derive(Resource, Debug, Default)]
+pub struct CatSprites {
+    pub images: Vec<Handle<Image>>,
+}
+
+/// The asset path of a cat's sprite.
+pub fn cat_sprite_path(value: usize) -> String {
+    format!("{}/cat_{}.png", CAT_SPRITE_FOLDER, value + 1)
+}
+
+/// Spawn the (initially hidden) sprite node inside a cell.
+pub fn spawn_cell_sprite(cell_parent: &mut ChildSpawnerCommands) {
+    let inset = Val::Percent((100.0 - SPRITE_SIZE_PERCENT) / 2.0);
+    cell_parent.spawn((
+        CellSprite,
+        ImageNode::default(),
+        Node {
+            display: Display::None,
+            position_type: PositionType::Absolute,
+            left: inset,
+            top: inset,
+            width: Val::Percent(SPRITE_SIZE_PERCENT),
+            height: Val::Percent(SPRITE_SIZE_PERCENT),
+            ..default()
+        },
+    ));
+}
+
+/// Show a cell's sprite (or hide it, for `None`), tinting sprites of cats
+/// the player placed.
+pub fn set_cell_sprite(image_node: &mut ImageNode, node: &mut Node, image: Option<Handle<Image>>, given: bool) {
+    let display = if image.is_some() { Display::Flex } else { Display::None };
+    if node.display != display {
+        node.display = display;
+    }
+    if let Some(image) = image {
+        let tint = if given { Color::WHITE } else { PLAYER_SPRITE_TINT };
+        if image_node.image != image || image_node.color != tint {
+            image_node.image = image;
+            image_node.color = tint;
+        }
+    }
+}
+
+// --- Systems ---
+
+/// System that starts loading a sprite for every cat the largest board uses.
+pub fn load_cat_sprites(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let images = (0..GridSize::Mega.dimension())
+        .map(|value| asset_server.load(cat_sprite_path(value)))
+        .collect();
+    commands.insert_resource(CatSprites { images });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sprite_renderer_falls_back_to_text() {
+        let cat_emojis = CatEmojis { emojis: crate::classic_cat_art() };
+        let ascii = AsciiRenderer { detail: CellDetail::Glyph, cat_emojis: &cat_emojis, glyphs: GlyphSet::Arabic };
+        assert_eq!(ascii.cat_text(2), "3");
+        assert_eq!(ascii.cat_image(2), None);
+
+        // Only the first cat's sprite has loaded
+        let mut images = Assets::<Image>::default();
+        let loaded = images.add(Image::default());
+        let sprites = CatSprites { images: vec![loaded.clone(), Handle::default()] };
+        let renderer = SpriteRenderer { sprites: &sprites, images: &images, fallback: ascii };
+        assert_eq!(renderer.cat_text(0), "");
+        assert_eq!(renderer.cat_image(0), Some(loaded));
+        assert_eq!(renderer.cat_text(1), "2");
+        assert_eq!(renderer.cat_image(1), None);
+        assert_eq!(renderer.cat_text(9), "10", "Cats past the end of the sprites are drawn as text");
+        assert_eq!(cat_sprite_path(9), "sprites/cat_10.png");
+    }
+}
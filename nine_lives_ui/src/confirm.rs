@@ -0,0 +1,296 @@
+//! Confirmation dialog for Nine Lives Cat Sudoku.
+//!
+//! Buttons that throw away the player's work (Clear Board, Restart Puzzle,
+//! Show Solution, New Game mid-puzzle, and discarding the saved game) don't
+//! act straight away. They ask for a `ConfirmAction`, a dialog pops up over
+//! the screen, and only pressing its confirm button sends the
+//! `ConfirmedAction` event the action is carried out on. Some actions offer
+//! a second way forward as well (New Game can start over with the same
+//! settings instead of choosing new ones), which gets its own button. Cancel,
+//! or Escape, backs out; while the dialog is open, keyboard and gamepad focus
+//! stay on its buttons.
+//!
+//! Any screen can use the dialog by spawning it with `spawn_confirm_dialog`
+//! and running `handle_confirm_dialog_buttons` and `update_confirm_dialog`
+//! in its state.
+
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::{Focusable, TextFit};
+
+// --- Components & Resources ---
+
+/// An action that needs the player's go-ahead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmAction {
+    ClearBoard,
+    RestartPuzzle,
+    ShowSolution,
+    NewGame,
+    SameSettings,
+    LeavePuzzle,
+    DeleteSave,
+}
+
+impl ConfirmAction {
+    /// The question the dialog asks.
+    pub fn message(&self) -> &'static str {
+        match self {
+            ConfirmAction::ClearBoard => "Clear every cat you've placed? The starting cats stay.",
+            ConfirmAction::RestartPuzzle => "Restart this puzzle? Your cats, timer, and moves start over.",
+            ConfirmAction::ShowSolution => "Give up and show the solution? This puzzle will count as abandoned.",
+            ConfirmAction::NewGame => {
+                "Play again with the same settings, or choose new ones? A puzzle you've started counts as abandoned."
+            }
+            ConfirmAction::SameSettings => "Start over with a new puzzle like this one?",
+            ConfirmAction::LeavePuzzle => "Leave this puzzle for a new one? It will count as abandoned.",
+            ConfirmAction::DeleteSave => "Discard your unfinished game? It can't be continued afterwards.",
+        }
+    }
+
+    /// Label for the confirm button.
+    pub fn confirm_label(&self) -> &'static str {
+        match self {
+            ConfirmAction::ClearBoard => "Clear",
+            ConfirmAction::RestartPuzzle => "Restart",
+            ConfirmAction::ShowSolution => "Show",
+            ConfirmAction::NewGame => "New Settings",
+            ConfirmAction::SameSettings => "Same Settings",
+            ConfirmAction::LeavePuzzle => "Leave",
+            ConfirmAction::DeleteSave => "Discard",
+        }
+    }
+
+    /// The other action the dialog offers alongside this one, if any.
+    pub fn alternative(&self) -> Option<ConfirmAction> {
+        match self {
+            ConfirmAction::NewGame => Some(ConfirmAction::SameSettings),
+            _ => None,
+        }
+    }
+}
+
+/// Resource holding the action waiting on the dialog, if any.
+#[derive(Resource, Debug, Default)]
+pub struct PendingConfirmation {
+    pub action: Option<ConfirmAction>,
+}
+
+/// Event sent when the player confirms an action.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmedAction(pub ConfirmAction);
+
+/// Component to tag the dialog's full-screen overlay.
+#[derive(Component)]
+pub struct ConfirmDialog;
+
+/// Component to tag the dialog's question text.
+#[derive(Component)]
+pub struct ConfirmDialogText;
+
+/// Component to tag the dialog's confirm button.
+#[derive(Component)]
+pub struct ConfirmYesButton;
+
+/// Component to tag the dialog's button for the alternative action (hidden
+/// unless the action has one).
+#[derive(Component)]
+pub struct ConfirmAltButton;
+
+/// Component to tag the dialog's cancel button.
+#[derive(Component)]
+pub struct ConfirmCancelButton;
+
+// --- Layout ---
+
+/// Spawn the (initially hidden) confirmation dialog on a screen.
+/// The overlay blocks clicks on everything underneath while it's shown.
+pub fn spawn_confirm_dialog(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            ConfirmDialog,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            FocusPolicy::Block,
+            GlobalZIndex(10),
+        ))
+        .with_children(|overlay| {
+            overlay
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(16.0),
+                        padding: UiRect::all(Val::Px(20.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        max_width: Val::Px(420.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.15, 0.15, 0.2)),
+                    BorderColor(Color::srgb(0.5, 0.5, 0.6)),
+                ))
+                .with_children(|dialog| {
+                    dialog.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        TextFit::new(320.0, 3, 16.0),
+                        ConfirmDialogText,
+                    ));
+
+                    dialog
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(16.0),
+                            ..default()
+                        })
+                        .with_children(|buttons| {
+                            buttons
+                                .spawn((
+                                    Button,
+                                    ConfirmAltButton,
+                                    Focusable::ConfirmAlt,
+                                    Node {
+                                        display: Display::None,
+                                        width: Val::Px(110.0),
+                                        height: Val::Px(36.0),
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::Center,
+                                        border: UiRect::all(Val::Px(2.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(Color::srgb(0.25, 0.45, 0.3)),
+                                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                                ))
+                                .with_children(|button_parent| {
+                                    button_parent.spawn((
+                                        Text::new(""),
+                                        TextFont {
+                                            font_size: 14.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::WHITE),
+                                    ));
+                                });
+
+                            for (label, confirm) in [("Clear", true), ("Cancel", false)] {
+                                let mut button = buttons.spawn((
+                                    Button,
+                                    Node {
+                                        width: Val::Px(110.0),
+                                        height: Val::Px(36.0),
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::Center,
+                                        border: UiRect::all(Val::Px(2.0)),
+                                        ..default()
+                                    },
+                                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                                ));
+                                if confirm {
+                                    button.insert((
+                                        ConfirmYesButton,
+                                        Focusable::ConfirmYes,
+                                        BackgroundColor(Color::srgb(0.6, 0.3, 0.3)),
+                                    ));
+                                } else {
+                                    button.insert((
+                                        ConfirmCancelButton,
+                                        Focusable::ConfirmCancel,
+                                        BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                                    ));
+                                }
+                                button.with_children(|button_parent| {
+                                    button_parent.spawn((
+                                        Text::new(label),
+                                        TextFont {
+                                            font_size: 14.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::WHITE),
+                                    ));
+                                });
+                            }
+                        });
+                });
+        });
+}
+
+// --- Systems ---
+
+/// System that shows the dialog while an action is waiting on it, with the
+/// action's question and confirm label, and the alternative's button when
+/// there is one.
+pub fn update_confirm_dialog(
+    pending: Res<PendingConfirmation>,
+    mut dialog_query: Query<&mut Node, (With<ConfirmDialog>, Without<ConfirmAltButton>)>,
+    mut text_query: Query<&mut Text, With<ConfirmDialogText>>,
+    yes_query: Query<&Children, With<ConfirmYesButton>>,
+    mut alt_query: Query<(&mut Node, &Children), With<ConfirmAltButton>>,
+    mut label_query: Query<&mut Text, Without<ConfirmDialogText>>,
+) {
+    for mut node in &mut dialog_query {
+        node.display = if pending.action.is_some() { Display::Flex } else { Display::None };
+    }
+    let Some(action) = pending.action else {
+        return;
+    };
+    for mut text in &mut text_query {
+        text.0 = action.message().to_string();
+    }
+    for children in &yes_query {
+        for child in children.iter() {
+            if let Ok(mut label) = label_query.get_mut(child) {
+                label.0 = action.confirm_label().to_string();
+            }
+        }
+    }
+    for (mut node, children) in &mut alt_query {
+        node.display = if action.alternative().is_some() { Display::Flex } else { Display::None };
+        if let Some(alternative) = action.alternative() {
+            for child in children.iter() {
+                if let Ok(mut label) = label_query.get_mut(child) {
+                    label.0 = alternative.confirm_label().to_string();
+                }
+            }
+        }
+    }
+}
+
+/// System that handles the dialog's buttons (and Escape, which cancels).
+pub fn handle_confirm_dialog_buttons(
+    yes_query: Query<&Interaction, (Changed<Interaction>, With<ConfirmYesButton>)>,
+    alt_query: Query<&Interaction, (Changed<Interaction>, With<ConfirmAltButton>)>,
+    cancel_query: Query<&Interaction, (Changed<Interaction>, With<ConfirmCancelButton>)>,
+    input: Res<ButtonInput<KeyCode>>,
+    mut pending: ResMut<PendingConfirmation>,
+    mut confirmed: EventWriter<ConfirmedAction>,
+) {
+    let Some(action) = pending.action else {
+        return;
+    };
+    if yes_query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        confirmed.write(ConfirmedAction(action));
+        pending.action = None;
+    } else if let Some(alternative) = action.alternative()
+        && alt_query.iter().any(|interaction| *interaction == Interaction::Pressed)
+    {
+        confirmed.write(ConfirmedAction(alternative));
+        pending.action = None;
+    } else if cancel_query.iter().any(|interaction| *interaction == Interaction::Pressed)
+        || input.just_pressed(KeyCode::Escape)
+    {
+        pending.action = None;
+    }
+}
@@ -0,0 +1,179 @@
+//! Conflict feedback for Nine Lives Cat Sudoku.
+//!
+//! Beyond the steady red tint, a cat placed where it clashes with another in
+//! its row, column, or box gives a brief shake and a red flash around its
+//! cell. How strongly is set by the error policy (see
+//! `ErrorPolicy::flash_intensity`); with errors hidden, nothing happens.
+//!
+//! Both read the clashes from the `ConflictTracker`, which follows the board
+//! a move at a time rather than rescanning it.
+
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, ConflictTracker, GameMode, PersistentData};
+
+use crate::{Cell, FocusManager, ReplayViewer};
+
+/// How long a conflict flash lasts, in seconds
+const CONFLICT_FLASH_SECONDS: f32 = 0.45;
+/// How far a cell shakes to either side at full intensity, in pixels
+const CONFLICT_SHAKE_PX: f32 = 5.0;
+/// Shakes per second
+const CONFLICT_SHAKE_HZ: f32 = 18.0;
+/// Color of the flash around a clashing cell
+const CONFLICT_FLASH_COLOR: Color = Color::srgb(0.95, 0.2, 0.2);
+/// Width of the flash around a clashing cell at full intensity
+const CONFLICT_FLASH_WIDTH: f32 = 3.0;
+
+// --- Components ---
+
+/// Component on a cell whose newly placed cat clashes, while it shakes and
+/// flashes. Removed (along with its outline) once the timer runs out.
+#[derive(Component)]
+pub struct ConflictFlash {
+    pub timer: Timer,
+    /// How strongly it shakes and flashes, from 0 to 1
+    pub intensity: f32,
+}
+
+impl ConflictFlash {
+    /// How far the cell is pushed sideways `elapsed` seconds in, shaking
+    /// less and less until it settles.
+    pub fn offset(&self, elapsed: f32) -> f32 {
+        let remaining = (1.0 - elapsed / CONFLICT_FLASH_SECONDS).clamp(0.0, 1.0);
+        (elapsed * CONFLICT_SHAKE_HZ * std::f32::consts::TAU).sin() * CONFLICT_SHAKE_PX * self.intensity * remaining
+    }
+
+    /// How opaque the flash is `elapsed` seconds in, fading from full.
+    pub fn alpha(&self, elapsed: f32) -> f32 {
+        (1.0 - elapsed / CONFLICT_FLASH_SECONDS).clamp(0.0, 1.0) * self.intensity
+    }
+}
+
+/// The cells where `board` has a player's cat that `previous` didn't, and
+/// that cat clashes with another (going by `tracker`, synced with `board`).
+pub fn newly_conflicting(
+    previous: &BoardState,
+    board: &BoardState,
+    tracker: &ConflictTracker,
+) -> Vec<(usize, usize)> {
+    if previous.grid_size != board.grid_size {
+        return Vec::new();
+    }
+    tracker
+        .conflicts()
+        .into_iter()
+        .filter(|&(row, col)| {
+            !board.is_given_cell(row, col)
+                && board.cells[row][col].is_some()
+                && board.cells[row][col] != previous.cells[row][col]
+        })
+        .collect()
+}
+
+// --- Systems ---
+
+/// System that brings the conflict tracker up to date with the board.
+pub fn sync_conflict_tracker(board: Res<BoardState>, mut tracker: ResMut<ConflictTracker>) {
+    tracker.sync(&board);
+}
+
+/// System that starts a flash on every cell whose newly placed cat clashes.
+/// The board a replay is showing is skipped.
+#[allow(clippy::too_many_arguments)]
+pub fn start_conflict_flash(
+    mut commands: Commands,
+    board: Res<BoardState>,
+    tracker: Res<ConflictTracker>,
+    persistent_data: Res<PersistentData>,
+    game_mode: Res<GameMode>,
+    viewer: Option<Res<ReplayViewer>>,
+    mut previous: Local<BoardState>,
+    cell_query: Query<(Entity, &Cell)>,
+) {
+    let clashes = newly_conflicting(&previous, &board, &tracker);
+    *previous = board.clone();
+    let intensity = game_mode.error_policy(persistent_data.user_settings.error_policy).flash_intensity();
+    if viewer.is_some() || clashes.is_empty() || intensity <= 0.0 {
+        return;
+    }
+
+    for (entity, cell) in &cell_query {
+        if clashes.contains(&(cell.row, cell.col)) {
+            commands.entity(entity).insert((
+                ConflictFlash {
+                    timer: Timer::from_seconds(CONFLICT_FLASH_SECONDS, TimerMode::Once),
+                    intensity,
+                },
+                Outline::new(Val::Px(CONFLICT_FLASH_WIDTH * intensity), Val::ZERO, CONFLICT_FLASH_COLOR),
+            ));
+        }
+    }
+}
+
+/// System that shakes and fades flashing cells, settling them back in place
+/// when they're done. A focused cell keeps its focus outline meanwhile.
+pub fn animate_conflict_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    focus: Res<FocusManager>,
+    mut flash_query: Query<(Entity, &mut ConflictFlash, &mut Node, &mut Outline)>,
+) {
+    for (entity, mut flash, mut node, mut outline) in &mut flash_query {
+        flash.timer.tick(time.delta());
+        if flash.timer.finished() {
+            node.left = Val::Auto;
+            commands.entity(entity).remove::<(ConflictFlash, Outline)>();
+            continue;
+        }
+
+        let elapsed = flash.timer.elapsed_secs();
+        node.left = Val::Px(flash.offset(elapsed));
+        if focus.focused != Some(entity) {
+            let color = CONFLICT_FLASH_COLOR.with_alpha(flash.alpha(elapsed));
+            *outline = Outline::new(Val::Px(CONFLICT_FLASH_WIDTH * flash.intensity), Val::ZERO, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nine_lives_core::{CellType, ErrorPolicy};
+
+    #[test]
+    fn test_newly_conflicting() {
+        let mut board = BoardState::new();
+        board.cells[0][0] = Some(3);
+        board.cell_types[0][0] = Some(CellType::Given);
+        let previous = board.clone();
+
+        // A clashing cat placed in the same row flashes; the given it clashes with doesn't
+        board.set_cell(0, 5, Some(3));
+        let mut tracker = ConflictTracker::new(&board);
+        assert_eq!(newly_conflicting(&previous, &board, &tracker), vec![(0, 5)]);
+        // Nor does it flash again once it's already there
+        assert!(newly_conflicting(&board, &board, &tracker).is_empty());
+        // A cat that fits is fine
+        let previous = board.clone();
+        board.set_cell(4, 4, Some(7));
+        tracker.sync(&board);
+        assert!(newly_conflicting(&previous, &board, &tracker).is_empty());
+    }
+
+    #[test]
+    fn test_conflict_flash_settles() {
+        let flash = ConflictFlash {
+            timer: Timer::from_seconds(CONFLICT_FLASH_SECONDS, TimerMode::Once),
+            intensity: ErrorPolicy::ShowMistakes.flash_intensity(),
+        };
+        let peak = 0.25 / CONFLICT_SHAKE_HZ;
+        assert!((flash.offset(peak) - CONFLICT_SHAKE_PX * (1.0 - peak / CONFLICT_FLASH_SECONDS)).abs() < 1e-3);
+        assert_eq!(flash.offset(CONFLICT_FLASH_SECONDS), 0.0);
+        assert_eq!(flash.alpha(0.0), 1.0);
+        assert_eq!(flash.alpha(CONFLICT_FLASH_SECONDS), 0.0);
+
+        // Gentler policies shake less
+        let gentle = ConflictFlash { timer: flash.timer.clone(), intensity: ErrorPolicy::ShowConflicts.flash_intensity() };
+        assert!(gentle.offset(peak).abs() < flash.offset(peak).abs());
+    }
+}
@@ -0,0 +1,241 @@
+//! Continuing an unfinished game for Nine Lives Cat Sudoku.
+//!
+//! The game in progress is autosaved into `PersistentData::current_save`
+//! (every so often, and when the app closes). While there is one, the
+//! customization screen shows a "Continue" button above Start Game that picks
+//! the puzzle back up: board, solution, settings, clock, hints, and undo
+//! history. The 🗑 button next to it throws the saved game away, once the
+//! player confirms.
+
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, GameHistory, GameSession, HintSystem, PersistentData, SaveGame, Solution};
+
+use crate::{
+    AppState, ConfirmAction, ConfirmedAction, ErrorToast, Focusable, LastGeneration, PendingConfirmation,
+    format_duration_secs,
+};
+
+/// Background of the Continue button
+const CONTINUE_BG: Color = Color::srgb(0.2, 0.45, 0.7);
+/// Background of the Continue button while hovered
+const CONTINUE_HOVER_BG: Color = Color::srgb(0.25, 0.55, 0.85);
+/// Background of the Continue button while pressed
+const CONTINUE_PRESSED_BG: Color = Color::srgb(0.15, 0.35, 0.55);
+/// Background of the discard button
+const DISCARD_BG: Color = Color::srgb(0.5, 0.25, 0.25);
+
+// --- Components ---
+
+/// Component for the button that continues the saved game.
+#[derive(Component)]
+pub struct ContinueButton;
+
+/// Component for the button that discards the saved game.
+#[derive(Component)]
+pub struct DiscardSaveButton;
+
+/// Component to tag the row holding the Continue and discard buttons.
+#[derive(Component)]
+pub struct ContinueRow;
+
+/// The Continue button's label, like "▶ Continue (Expert, 12:34)".
+pub fn continue_label(save: &SaveGame) -> String {
+    format!(
+        "▶ Continue ({}, {})",
+        save.settings.difficulty.name(),
+        format_duration_secs(save.elapsed_seconds)
+    )
+}
+
+// --- Layout ---
+
+/// Spawn the Continue button for a saved game, with the discard button
+/// beside it.
+pub fn spawn_continue_button(parent: &mut ChildSpawnerCommands, save: &SaveGame) {
+    parent
+        .spawn((
+            ContinueRow,
+            Node {
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(8.0),
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ))
+        .with_children(|row| {
+            row.spawn((
+                Button,
+                ContinueButton,
+                Focusable::ContinueGame,
+                Node {
+                    width: Val::Px(260.0),
+                    height: Val::Px(44.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    border: UiRect::all(Val::Px(3.0)),
+                    ..default()
+                },
+                BackgroundColor(CONTINUE_BG),
+                BorderColor(Color::srgb(0.3, 0.6, 0.9)),
+            ))
+            .with_children(|button_parent| {
+                button_parent.spawn((
+                    Text::new(continue_label(save)),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            });
+
+            row.spawn((
+                Button,
+                DiscardSaveButton,
+                Focusable::DiscardSave,
+                Node {
+                    width: Val::Px(44.0),
+                    height: Val::Px(44.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    border: UiRect::all(Val::Px(3.0)),
+                    ..default()
+                },
+                BackgroundColor(DISCARD_BG),
+                BorderColor(Color::srgb(0.7, 0.35, 0.35)),
+            ))
+            .with_children(|button_parent| {
+                button_parent.spawn((
+                    Text::new("🗑"),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            });
+        });
+}
+
+// --- Systems ---
+
+/// Everything needed to pick a saved game back up.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct ContinueGameStart<'w, 's> {
+    commands: Commands<'w, 's>,
+    app_state: ResMut<'w, NextState<AppState>>,
+    board: ResMut<'w, BoardState>,
+    session: ResMut<'w, GameSession>,
+    history: ResMut<'w, GameHistory>,
+    solution: ResMut<'w, Solution>,
+    hint_system: ResMut<'w, HintSystem>,
+    last_generation: ResMut<'w, LastGeneration>,
+    toasts: EventWriter<'w, ErrorToast>,
+}
+
+impl ContinueGameStart<'_, '_> {
+    /// Restore a saved game and go to the game screen, like the Start Game
+    /// button does for a new one. A saved board that doesn't hold together
+    /// (see `BoardState::check_invariants`) is left alone.
+    fn resume(&mut self, save: &SaveGame) {
+        let mut board = BoardState::default();
+        board.restore_from_save(save);
+        if let Err(violations) = board.check_invariants() {
+            let problem = violations[0].description();
+            self.toasts.write(ErrorToast(format!("⚠️ Saved game is damaged, not continuing: {}", problem)));
+            return;
+        }
+        info!("💾 Continuing saved game: {}", save.settings.description());
+        *self.board = board;
+        *self.solution = save.restore_solution();
+        *self.session = save.restore_session();
+        *self.history = save.restore_history();
+        *self.hint_system = save.restore_hints();
+        *self.last_generation = LastGeneration::default();
+        self.commands.insert_resource(save.restore_lives());
+        self.commands.insert_resource(save.restore_score());
+        self.commands.insert_resource(save.restore_streak());
+        self.commands.insert_resource(save.settings.clone());
+        self.app_state.set(AppState::Ready);
+    }
+}
+
+/// System that continues the saved game when its button is pressed.
+pub fn handle_continue_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ContinueButton>)>,
+    persistent_data: Res<PersistentData>,
+    mut start: ContinueGameStart,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed
+            && let Some(save) = &persistent_data.current_save
+        {
+            start.resume(save);
+        }
+    }
+}
+
+/// System that asks before discarding the saved game when the discard
+/// button is pressed.
+pub fn handle_discard_save_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<DiscardSaveButton>)>,
+    mut pending: ResMut<PendingConfirmation>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            pending.action = Some(ConfirmAction::DeleteSave);
+        }
+    }
+}
+
+/// System that discards the saved game once the player confirms, and takes
+/// the Continue button away with it.
+pub fn discard_saved_game(
+    mut commands: Commands,
+    mut confirmed: EventReader<ConfirmedAction>,
+    mut persistent_data: ResMut<PersistentData>,
+    row_query: Query<Entity, With<ContinueRow>>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for ConfirmedAction(action) in confirmed.read() {
+        if *action != ConfirmAction::DeleteSave {
+            continue;
+        }
+        persistent_data.current_save = None;
+        if let Err(e) = persistent_data.save() {
+            toasts.write(ErrorToast::new("Saved game", &e));
+        }
+        for row in &row_query {
+            commands.entity(row).despawn();
+        }
+        info!("🗑 Discarded saved game");
+    }
+}
+
+/// System to handle Continue button hover effects.
+#[allow(clippy::type_complexity)]
+pub fn update_continue_button_colors(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), (With<ContinueButton>, Changed<Interaction>)>,
+) {
+    for (interaction, mut bg_color) in &mut button_query {
+        bg_color.0 = match interaction {
+            Interaction::Pressed => CONTINUE_PRESSED_BG,
+            Interaction::Hovered => CONTINUE_HOVER_BG,
+            Interaction::None => CONTINUE_BG,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nine_lives_core::{PresetKind, PuzzleSettings};
+
+    #[test]
+    fn test_continue_label() {
+        let board = BoardState::new();
+        let settings = PuzzleSettings::from_preset(PresetKind::NightProwler);
+        let save = board.create_save_game(&Solution::new(), &settings, 754, 12, 0);
+        assert_eq!(continue_label(&save), "▶ Continue (Expert, 12:34)");
+    }
+}
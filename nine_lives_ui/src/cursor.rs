@@ -0,0 +1,186 @@
+//! Paw pointer for Nine Lives Cat Sudoku.
+//!
+//! Over the game window the mouse pointer is a cat paw: a solid one
+//! everywhere you can click, including the cells you can fill in, and a
+//! faded one over the starting cats (givens), which can't be changed. The
+//! paws are drawn in code at startup (see `paw_pixels`), so there's no image
+//! file to ship. Players who'd rather have their system's pointer can turn
+//! the paw off on the settings screen.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::window::{PrimaryWindow, SystemCursorIcon};
+use bevy::winit::cursor::{CursorIcon, CustomCursor, CustomCursorImage};
+use nine_lives_core::{BoardState, PersistentData};
+
+use crate::Cell;
+
+/// Width and height of the paw pointer, in pixels
+const PAW_SIZE: u32 = 32;
+/// Paw color for the solid pointer (RGBA)
+pub const PAW_COLOR: [u8; 4] = [70, 45, 35, 255];
+/// Paw color for the faded pointer over givens (RGBA)
+const FADED_PAW_COLOR: [u8; 4] = [120, 120, 120, 150];
+/// Outline around either paw, so it shows on dark and light backgrounds (RGBA)
+const OUTLINE_COLOR: [u8; 4] = [255, 255, 255, 255];
+/// Width of the outline, as a fraction of the paw's size
+const OUTLINE_WIDTH: f32 = 0.05;
+
+/// The paw's pads, as ellipses in a unit square: center and radii. The big
+/// pad comes first, then the four toe beans.
+const PADS: [((f32, f32), (f32, f32)); 5] = [
+    ((0.5, 0.68), (0.25, 0.2)),
+    ((0.2, 0.4), (0.1, 0.12)),
+    ((0.39, 0.22), (0.1, 0.12)),
+    ((0.61, 0.22), (0.1, 0.12)),
+    ((0.8, 0.4), (0.1, 0.12)),
+];
+
+// --- Resources ---
+
+/// Resource holding the paw pointer images.
+#[derive(Resource, Debug, Clone)]
+pub struct PawCursors {
+    pub solid: Handle<Image>,
+    pub faded: Handle<Image>,
+}
+
+/// Which pointer to show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerKind {
+    /// The system's own pointer (the paw is turned off).
+    System,
+    /// The solid paw.
+    Paw,
+    /// The faded paw, over a given.
+    FadedPaw,
+}
+
+impl PointerKind {
+    /// The pointer for the current hover: the faded paw over a given cell,
+    /// the solid paw anywhere else, or the system's when the paw is off.
+    pub fn for_hover(paw_enabled: bool, over_given: bool) -> Self {
+        match (paw_enabled, over_given) {
+            (false, _) => PointerKind::System,
+            (true, true) => PointerKind::FadedPaw,
+            (true, false) => PointerKind::Paw,
+        }
+    }
+}
+
+/// RGBA pixels for a square paw `size` pixels across in the given color,
+/// with a white outline, on a transparent background. The window icon is
+/// drawn with it too (see `app_window`).
+pub fn paw_pixels(size: u32, color: [u8; 4]) -> Vec<u8> {
+    // How far inside a pad a point is: below 1.0 is inside
+    let pad_distance = |x: f32, y: f32| {
+        PADS.iter()
+            .map(|&((cx, cy), (rx, ry))| (((x - cx) / rx).powi(2) + ((y - cy) / ry).powi(2)).sqrt())
+            .fold(f32::INFINITY, f32::min)
+    };
+    let mut pixels = Vec::with_capacity((size * size * 4) as usize);
+    for row in 0..size {
+        for col in 0..size {
+            let x = (col as f32 + 0.5) / size as f32;
+            let y = (row as f32 + 0.5) / size as f32;
+            let inside = pad_distance(x, y) < 1.0;
+            // Grow every pad by the outline width (relative to its size) for the outline
+            let in_outline = PADS.iter().any(|&((cx, cy), (rx, ry))| {
+                ((x - cx) / (rx + OUTLINE_WIDTH)).powi(2) + ((y - cy) / (ry + OUTLINE_WIDTH)).powi(2) < 1.0
+            });
+            pixels.extend_from_slice(&if inside {
+                color
+            } else if in_outline {
+                OUTLINE_COLOR
+            } else {
+                [0, 0, 0, 0]
+            });
+        }
+    }
+    pixels
+}
+
+/// A paw image in the given color, kept on the CPU for the window to read.
+fn paw_image(color: [u8; 4]) -> Image {
+    Image::new(
+        Extent3d { width: PAW_SIZE, height: PAW_SIZE, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        paw_pixels(PAW_SIZE, color),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD,
+    )
+}
+
+/// The window's cursor for a pointer kind. The paw's hotspot is its middle.
+fn cursor_icon(kind: PointerKind, paws: &PawCursors) -> CursorIcon {
+    let handle = match kind {
+        PointerKind::System => return CursorIcon::System(SystemCursorIcon::Default),
+        PointerKind::Paw => paws.solid.clone(),
+        PointerKind::FadedPaw => paws.faded.clone(),
+    };
+    let middle = (PAW_SIZE / 2) as u16;
+    CursorIcon::Custom(CustomCursor::Image(CustomCursorImage {
+        handle,
+        hotspot: (middle, middle),
+        ..default()
+    }))
+}
+
+// --- Systems ---
+
+/// System that draws the paw pointers at startup.
+pub fn setup_paw_cursors(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.insert_resource(PawCursors {
+        solid: images.add(paw_image(PAW_COLOR)),
+        faded: images.add(paw_image(FADED_PAW_COLOR)),
+    });
+}
+
+/// System that keeps the window's pointer in step with what's under it and
+/// the paw setting.
+pub fn update_cursor(
+    mut commands: Commands,
+    persistent_data: Res<PersistentData>,
+    paws: Res<PawCursors>,
+    board: Res<BoardState>,
+    cell_query: Query<(&Cell, &Interaction)>,
+    window_query: Query<(Entity, Option<&CursorIcon>), With<PrimaryWindow>>,
+) {
+    let Ok((window, current)) = window_query.single() else {
+        return;
+    };
+    let over_given = cell_query
+        .iter()
+        .any(|(cell, interaction)| *interaction == Interaction::Hovered && board.is_given_cell(cell.row, cell.col));
+    let kind = PointerKind::for_hover(persistent_data.user_settings.paw_cursor, over_given);
+    let icon = cursor_icon(kind, &paws);
+    if current != Some(&icon) {
+        commands.entity(window).insert(icon);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paw_pixels() {
+        let pixels = paw_pixels(PAW_SIZE, PAW_COLOR);
+        assert_eq!(pixels.len(), (PAW_SIZE * PAW_SIZE * 4) as usize);
+        let at = |col: u32, row: u32| {
+            let index = ((row * PAW_SIZE + col) * 4) as usize;
+            [pixels[index], pixels[index + 1], pixels[index + 2], pixels[index + 3]]
+        };
+        assert_eq!(at(16, 22), PAW_COLOR, "The big pad is filled in");
+        assert_eq!(at(0, 0)[3], 0, "The corners are see-through");
+        assert_eq!(at(16, 12)[3], 0, "There's a gap between the toes and the pad");
+    }
+
+    #[test]
+    fn test_pointer_kind() {
+        assert_eq!(PointerKind::for_hover(true, false), PointerKind::Paw);
+        assert_eq!(PointerKind::for_hover(true, true), PointerKind::FadedPaw);
+        assert_eq!(PointerKind::for_hover(false, true), PointerKind::System);
+    }
+}
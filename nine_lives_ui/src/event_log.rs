@@ -0,0 +1,201 @@
+//! Debug event log panel for Nine Lives Cat Sudoku.
+//!
+//! While debug mode is on (⌘D / Ctrl+D), a panel in the corner of the game
+//! screen lists the most recent game events, newest first, with timestamps.
+//! Buttons along the top show or hide each category, and the list scrolls
+//! with the mouse wheel.
+
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use nine_lives_core::{DebugMode, EventCategory, GameEventLog};
+
+use crate::Focusable;
+
+/// Width of the event log panel
+const PANEL_WIDTH: f32 = 300.0;
+/// Height of the scrolling list of events
+const LIST_HEIGHT: f32 = 240.0;
+/// Pixels scrolled per line of mouse wheel movement (see `scroll_list_system`)
+pub(crate) const SCROLL_LINE_HEIGHT: f32 = 16.0;
+
+// --- Components & Resources ---
+
+/// Component to tag the debug event log panel.
+#[derive(Component)]
+pub struct EventLogPanel;
+
+/// Component for the buttons that show or hide a category of events.
+#[derive(Component)]
+pub struct EventLogFilterButton(pub EventCategory);
+
+/// Component to tag the scrolling list of events.
+#[derive(Component)]
+pub struct EventLogList;
+
+/// Component to tag the text holding the event lines.
+#[derive(Component)]
+pub struct EventLogText;
+
+/// Resource holding the event categories the log panel shows.
+#[derive(Resource)]
+pub struct EventLogFilter {
+    pub shown: Vec<EventCategory>,
+}
+
+impl Default for EventLogFilter {
+    fn default() -> Self {
+        Self {
+            shown: EventCategory::all().to_vec(),
+        }
+    }
+}
+
+impl EventLogFilter {
+    /// Show the category if it's hidden, or hide it if it's shown.
+    pub fn toggle(&mut self, category: EventCategory) {
+        if let Some(index) = self.shown.iter().position(|&shown| shown == category) {
+            self.shown.remove(index);
+        } else {
+            self.shown.push(category);
+        }
+    }
+}
+
+/// Background of a filter button, brighter while its category is shown.
+fn filter_button_color(shown: bool) -> Color {
+    if shown { Color::srgb(0.3, 0.45, 0.3) } else { Color::srgb(0.2, 0.2, 0.2) }
+}
+
+// --- Layout ---
+
+/// Spawn the (initially hidden) debug event log panel on the game screen.
+pub fn spawn_event_log_panel(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            EventLogPanel,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                width: Val::Px(PANEL_WIDTH),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.9)),
+            BorderColor(Color::srgb(0.4, 0.4, 0.5)),
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new("🐛 Event log"),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            panel
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(4.0),
+                    ..default()
+                })
+                .with_children(|filters| {
+                    for (index, category) in EventCategory::all().into_iter().enumerate() {
+                        filters
+                            .spawn((
+                                Button,
+                                EventLogFilterButton(category),
+                                Focusable::EventLogFilter(index),
+                                Node {
+                                    padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                                    border: UiRect::all(Val::Px(1.0)),
+                                    ..default()
+                                },
+                                BackgroundColor(filter_button_color(true)),
+                                BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                            ))
+                            .with_children(|button_parent| {
+                                button_parent.spawn((
+                                    Text::new(category.display_name()),
+                                    TextFont {
+                                        font_size: 11.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                ));
+                            });
+                    }
+                });
+
+            panel
+                .spawn((
+                    EventLogList,
+                    RelativeCursorPosition::default(),
+                    Node {
+                        height: Val::Px(LIST_HEIGHT),
+                        flex_direction: FlexDirection::Column,
+                        overflow: Overflow::scroll_y(),
+                        ..default()
+                    },
+                    ScrollPosition::default(),
+                ))
+                .with_children(|list| {
+                    list.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 11.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.8, 0.9, 0.8)),
+                        EventLogText,
+                    ));
+                });
+        });
+}
+
+// --- Systems ---
+
+/// System that shows the event log panel only while debug mode is on.
+pub fn update_event_log_visibility(
+    debug_mode: Res<DebugMode>,
+    mut panel_query: Query<&mut Node, With<EventLogPanel>>,
+) {
+    for mut node in &mut panel_query {
+        node.display = if debug_mode.enabled { Display::Flex } else { Display::None };
+    }
+}
+
+/// System that shows or hides a category of events when its button is pressed.
+pub fn handle_event_log_filter_buttons(
+    mut interaction_query: Query<(&Interaction, &EventLogFilterButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut filter: ResMut<EventLogFilter>,
+) {
+    for (interaction, button, mut background) in &mut interaction_query {
+        if *interaction == Interaction::Pressed {
+            filter.toggle(button.0);
+            *background = BackgroundColor(filter_button_color(filter.shown.contains(&button.0)));
+        }
+    }
+}
+
+/// System that lists the logged events in the shown categories, newest first.
+pub fn update_event_log_text(
+    log: Res<GameEventLog>,
+    filter: Res<EventLogFilter>,
+    mut text_query: Query<&mut Text, With<EventLogText>>,
+) {
+    let lines: Vec<String> = log.filtered(&filter.shown).map(|entry| entry.line()).collect();
+    let text = if lines.is_empty() {
+        "No events yet".to_string()
+    } else {
+        lines.into_iter().rev().collect::<Vec<_>>().join("\n")
+    };
+    for mut display in &mut text_query {
+        display.0 = text.clone();
+    }
+}
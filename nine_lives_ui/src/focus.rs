@@ -0,0 +1,377 @@
+//! Keyboard and gamepad focus for Nine Lives Cat Sudoku.
+//!
+//! Every button carries a `Focusable` naming its place in the focus order,
+//! which runs top to bottom through each screen. Tab and Shift+Tab (or the
+//! gamepad's shoulder buttons) move focus through the visible buttons in that
+//! order, and Enter or Space (or the gamepad's South button) presses the
+//! focused one. The board's cells share a single stop: once focus is on the
+//! board, the arrow keys (or the d-pad) move it between cells. The focused
+//! button is drawn with an outline.
+//!
+//! While the confirmation dialog or game-over overlay is open, focus stays
+//! on its buttons.
+//!
+//! Both devices drive the same `FocusManager`, so switching between them
+//! mid-game keeps the focus where it was.
+
+use bevy::prelude::*;
+use nine_lives_core::BoardState;
+
+use crate::{
+    CheckMark, ConflictFlash, GridSlot, HintedCell, HouseFlash, ProfileNameEntry, ReplayViewer, ShareCodeEntry, ShortcutCapture,
+    UnitHighlight,
+};
+
+/// Color of the focus outline
+const FOCUS_OUTLINE_COLOR: Color = Color::srgb(0.3, 0.7, 1.0);
+/// Width of the focus outline
+const FOCUS_OUTLINE_WIDTH: f32 = 3.0;
+/// Gap between a focused button and its outline
+const FOCUS_OUTLINE_OFFSET: f32 = 2.0;
+
+// --- Components & Resources ---
+
+/// Component giving a button its place in the focus order. Buttons are
+/// visited in the order the variants are listed; the board's cells all share
+/// `Board` and count as one stop.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Focusable {
+    // Customization screen
+    Profiles,
+    Preset(usize),
+    Variant,
+    GridSize,
+    GlyphSet,
+    GivenStyle,
+    DoubleClickFill,
+    NineLives,
+    PauseWhenAway,
+    GameMode,
+    ContinueGame,
+    DiscardSave,
+    StartGame,
+    Campaign,
+    PuzzlePacks,
+    TimeAttack,
+    Tutorial,
+    TrainingGrounds,
+    Statistics,
+    Settings,
+    EnterShareCode,
+    // Game screen
+    TutorialNext,
+    TutorialSkip,
+    Board,
+    NumberPad(usize),
+    Eraser,
+    Notes,
+    AssistMode,
+    InputModel,
+    SidePanelToggle,
+    NewGame,
+    Restart,
+    Clear,
+    ShowSolution,
+    Candidates,
+    Undo,
+    Redo,
+    UndoToCorrect,
+    Hint,
+    Check,
+    HintVerbosity,
+    HistoryPanel,
+    HistoryEntry(usize),
+    CopyShareCode,
+    Heatmap,
+    WatchReplay,
+    ReplayDone,
+    EventLogFilter(usize),
+    SolverStep,
+    SolverRestart,
+    ConfirmAlt,
+    ConfirmYes,
+    ConfirmCancel,
+    TryAgain,
+    GameOverNewGame,
+    GaveUpTryAgain,
+    GaveUpNewGame,
+    TimeAttackAgain,
+    TimeAttackNewGame,
+    // Statistics screen
+    WatchBestReplay(usize),
+    BestReplayControl(usize),
+    SummaryExport,
+    StatsExport(usize),
+    StatisticsBack,
+    // Settings screen
+    SettingStep(usize),
+    Shortcut(usize),
+    SettingsBack,
+    // Campaign screen
+    CampaignLevel(usize),
+    CampaignBack,
+    // Packs screen
+    PackSelect(usize),
+    PackPuzzle(usize),
+    PacksBack,
+    // Training Grounds screen
+    Practice(usize),
+    TrainingBack,
+    // Profile screen
+    ProfileSelect(usize),
+    NewProfile,
+    ProfilesBack,
+}
+
+impl Focusable {
+    /// Whether the button belongs to a dialog or overlay that keeps focus
+    /// to itself while it's open.
+    pub fn is_modal(&self) -> bool {
+        matches!(
+            self,
+            Focusable::ConfirmAlt
+                | Focusable::ConfirmYes
+                | Focusable::ConfirmCancel
+                | Focusable::TryAgain
+                | Focusable::GameOverNewGame
+                | Focusable::GaveUpTryAgain
+                | Focusable::GaveUpNewGame
+                | Focusable::TimeAttackAgain
+                | Focusable::TimeAttackNewGame
+        )
+    }
+}
+
+/// A request to move focus or press the focused button, from either device.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusAction {
+    /// Move to the next stop in the focus order
+    Next,
+    /// Move to the previous stop in the focus order
+    Previous,
+    /// Move between the board's cells, by on-screen rows and columns
+    Move { rows: isize, cols: isize },
+    /// Press the focused button
+    Activate,
+}
+
+/// Resource tracking which button has keyboard and gamepad focus.
+#[derive(Resource, Debug, Default)]
+pub struct FocusManager {
+    /// The focused button, if any
+    pub focused: Option<Entity>,
+    /// The cell focused last, so tabbing back onto the board returns to it
+    board_cell: Option<Entity>,
+    /// A button pressed from the keyboard or gamepad, released on the next frame
+    pressed: Option<Entity>,
+}
+
+impl FocusManager {
+    /// Move focus one stop forwards or backwards through `stops`, wrapping at
+    /// either end. `stops` holds every visible focusable button, sorted into
+    /// focus order; buttons sharing a `Focusable` make up a single stop.
+    pub fn step(&mut self, stops: &[(Focusable, Entity)], forward: bool) -> Option<Entity> {
+        let mut groups: Vec<&[(Focusable, Entity)]> = stops.chunk_by(|a, b| a.0 == b.0).collect();
+        if groups.is_empty() {
+            self.focused = None;
+            return None;
+        }
+
+        let current = self
+            .focused
+            .and_then(|focused| groups.iter().position(|group| group.iter().any(|&(_, entity)| entity == focused)));
+        let index = match (current, forward) {
+            (Some(index), true) => (index + 1) % groups.len(),
+            (Some(index), false) => (index + groups.len() - 1) % groups.len(),
+            (None, true) => 0,
+            (None, false) => groups.len() - 1,
+        };
+        let group = groups.swap_remove(index);
+        let target = self
+            .board_cell
+            .filter(|cell| group.iter().any(|&(_, entity)| entity == *cell))
+            .unwrap_or(group[0].1);
+        self.focus(target, group[0].0);
+        Some(target)
+    }
+
+    /// Focus the given button.
+    pub fn focus(&mut self, entity: Entity, focusable: Focusable) {
+        self.focused = Some(entity);
+        if focusable == Focusable::Board {
+            self.board_cell = Some(entity);
+        }
+    }
+}
+
+/// Whether a button is on screen (not hidden, and not inside a hidden panel).
+fn is_shown(node: &ComputedNode, visibility: &InheritedVisibility) -> bool {
+    visibility.get() && node.size() != Vec2::ZERO
+}
+
+// --- Systems ---
+
+/// System that turns Tab, Shift+Tab, the arrow keys, Enter, and Space into
+/// focus actions. Stays out of the way while a share code or profile name
+/// is being typed, or a shortcut is waiting for its new key.
+pub fn keyboard_focus_input(
+    input: Res<ButtonInput<KeyCode>>,
+    share_code_entry: Res<ShareCodeEntry>,
+    profile_name_entry: Res<ProfileNameEntry>,
+    shortcut_capture: Res<ShortcutCapture>,
+    mut actions: EventWriter<FocusAction>,
+) {
+    if share_code_entry.active || profile_name_entry.active || shortcut_capture.action.is_some() {
+        return;
+    }
+    let shift_pressed = input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight);
+    if input.just_pressed(KeyCode::Tab) {
+        actions.write(if shift_pressed { FocusAction::Previous } else { FocusAction::Next });
+    }
+    for (key, rows, cols) in [
+        (KeyCode::ArrowUp, -1, 0),
+        (KeyCode::ArrowDown, 1, 0),
+        (KeyCode::ArrowLeft, 0, -1),
+        (KeyCode::ArrowRight, 0, 1),
+    ] {
+        if input.just_pressed(key) {
+            actions.write(FocusAction::Move { rows, cols });
+        }
+    }
+    if input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter, KeyCode::Space]) {
+        actions.write(FocusAction::Activate);
+    }
+}
+
+/// System that turns the shoulder buttons, d-pad, and South button of any
+/// connected gamepad into focus actions.
+pub fn gamepad_focus_input(gamepads: Query<&Gamepad>, mut actions: EventWriter<FocusAction>) {
+    for gamepad in &gamepads {
+        if gamepad.just_pressed(GamepadButton::RightTrigger) {
+            actions.write(FocusAction::Next);
+        }
+        if gamepad.just_pressed(GamepadButton::LeftTrigger) {
+            actions.write(FocusAction::Previous);
+        }
+        for (button, rows, cols) in [
+            (GamepadButton::DPadUp, -1, 0),
+            (GamepadButton::DPadDown, 1, 0),
+            (GamepadButton::DPadLeft, 0, -1),
+            (GamepadButton::DPadRight, 0, 1),
+        ] {
+            if gamepad.just_pressed(button) {
+                actions.write(FocusAction::Move { rows, cols });
+            }
+        }
+        if gamepad.just_pressed(GamepadButton::South) {
+            actions.write(FocusAction::Activate);
+        }
+    }
+}
+
+/// System that carries out focus actions. Off the board, the arrow keys and
+/// d-pad step through the focus order like Tab does; on the board they move
+/// between cells (unless a replay is being scrubbed with them). Pressing a
+/// button sets its `Interaction` to `Pressed` for one frame, so it goes
+/// through the same handlers as a click.
+pub fn apply_focus_actions(
+    mut actions: EventReader<FocusAction>,
+    mut focus: ResMut<FocusManager>,
+    focusable_query: Query<(Entity, &Focusable, &ComputedNode, &InheritedVisibility, Option<&GridSlot>)>,
+    mut interaction_query: Query<&mut Interaction>,
+    board: Option<Res<BoardState>>,
+    replay_viewer: Option<Res<ReplayViewer>>,
+) {
+    // Release last frame's keyboard or gamepad press
+    if let Some(pressed) = focus.pressed.take()
+        && let Ok(mut interaction) = interaction_query.get_mut(pressed)
+        && *interaction == Interaction::Pressed
+    {
+        *interaction = Interaction::None;
+    }
+
+    let mut stops: Vec<(Focusable, Entity, Option<GridSlot>)> = focusable_query
+        .iter()
+        .filter(|(_, _, node, visibility, _)| is_shown(node, visibility))
+        .map(|(entity, &focusable, _, _, slot)| (focusable, entity, slot.copied()))
+        .collect();
+    // An open dialog or overlay keeps focus to itself
+    if stops.iter().any(|(focusable, _, _)| focusable.is_modal()) {
+        stops.retain(|(focusable, _, _)| focusable.is_modal());
+    }
+    stops.sort_by_key(|&(focusable, entity, slot)| (focusable, slot.map(|slot| (slot.row, slot.col)), entity));
+    let order: Vec<(Focusable, Entity)> = stops.iter().map(|&(focusable, entity, _)| (focusable, entity)).collect();
+    if focus.focused.is_some_and(|focused| !order.iter().any(|&(_, entity)| entity == focused)) {
+        focus.focused = None;
+    }
+
+    for action in actions.read() {
+        let focused_slot = focus
+            .focused
+            .and_then(|focused| stops.iter().find(|&&(_, entity, _)| entity == focused))
+            .and_then(|&(_, _, slot)| slot);
+        match *action {
+            FocusAction::Next => {
+                focus.step(&order, true);
+            }
+            FocusAction::Previous => {
+                focus.step(&order, false);
+            }
+            FocusAction::Move { rows, cols } => match focused_slot {
+                Some(slot) => {
+                    if replay_viewer.is_some() {
+                        continue;
+                    }
+                    let size = board.as_ref().map_or(0, |board| board.size()) as isize;
+                    let row = (slot.row as isize + rows).clamp(0, size - 1) as usize;
+                    let col = (slot.col as isize + cols).clamp(0, size - 1) as usize;
+                    if let Some(&(_, entity, _)) =
+                        stops.iter().find(|&&(_, _, slot)| slot.is_some_and(|slot| (slot.row, slot.col) == (row, col)))
+                    {
+                        focus.focus(entity, Focusable::Board);
+                    }
+                }
+                // Arrows beside the replay timeline scrub the replay instead
+                None if replay_viewer.is_some() => {}
+                None => {
+                    focus.step(&order, rows + cols > 0);
+                }
+            },
+            FocusAction::Activate => {
+                if let Some(focused) = focus.focused
+                    && let Ok(mut interaction) = interaction_query.get_mut(focused)
+                {
+                    *interaction = Interaction::Pressed;
+                    focus.pressed = Some(focused);
+                }
+            }
+        }
+    }
+}
+
+/// System that draws the focus outline around the focused button, and takes
+/// it off a button once focus moves on. Cells under a hint nudge or pulse, or
+/// a conflict or house completion flash, keep their outline; it gets its own
+/// color back once focus leaves them.
+#[allow(clippy::type_complexity)]
+pub fn update_focus_outline(
+    mut commands: Commands,
+    focus: Res<FocusManager>,
+    outlined_query: Query<
+        (Entity, &Outline),
+        (Without<UnitHighlight>, Without<HintedCell>, Without<ConflictFlash>, Without<HouseFlash>, Without<CheckMark>),
+    >,
+    all_outlines: Query<&Outline>,
+) {
+    let focus_outline = Outline::new(Val::Px(FOCUS_OUTLINE_WIDTH), Val::Px(FOCUS_OUTLINE_OFFSET), FOCUS_OUTLINE_COLOR);
+    for (entity, outline) in &outlined_query {
+        if *outline == focus_outline && Some(entity) != focus.focused {
+            commands.entity(entity).remove::<Outline>();
+        }
+    }
+    if let Some(focused) = focus.focused
+        && !all_outlines.get(focused).is_ok_and(|outline| *outline == focus_outline)
+    {
+        commands.entity(focused).try_insert(focus_outline);
+    }
+}
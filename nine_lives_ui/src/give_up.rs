@@ -0,0 +1,337 @@
+//! Giving up on a puzzle for Nine Lives Cat Sudoku.
+//!
+//! The 🐾 Show Solution button asks first (see `ConfirmAction::ShowSolution`),
+//! then counts the puzzle as abandoned and hands it a `SolutionReveal`: the
+//! controller fills the cells still to solve (empty, or holding the wrong
+//! cat) from the solution one by one, and the game ends as `GameState::GaveUp`
+//! rather than won. Once every cell is filled, the overlay swaps its "showing
+//! the solution" line for a look back at how far the player got, with the
+//! choice to try the puzzle again or start a new one.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use nine_lives_core::{BoardState, Solution};
+
+use crate::{Focusable, NewGameButton, TryAgainButton, format_duration_secs};
+
+/// Seconds between revealed cells
+const REVEAL_INTERVAL_SECONDS: f32 = 0.06;
+
+// --- Components & Resources ---
+
+/// How far the player had got when they gave up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GiveUpSummary {
+    /// The player's cats that matched the solution
+    pub right: usize,
+    /// The player's cats that didn't
+    pub wrong: usize,
+    /// Cells still empty
+    pub empty: usize,
+    /// Time played, in seconds
+    pub seconds: u64,
+    /// Hints used
+    pub hints_used: usize,
+}
+
+impl GiveUpSummary {
+    /// The lines the overlay shows once the solution is in.
+    pub fn lines(&self) -> Vec<String> {
+        let placed = self.right + self.wrong;
+        vec![
+            format!(
+                "You placed {} of the {} cats the puzzle needed ({} right).",
+                placed,
+                placed + self.empty,
+                self.right
+            ),
+            format!(
+                "Time played: {}  •  Hints used: {}",
+                format_duration_secs(self.seconds),
+                self.hints_used
+            ),
+            if self.wrong == 0 {
+                "Everything you placed was right. Same puzzle, another go?".to_string()
+            } else {
+                "Compare the board with where you went astray, then have another go.".to_string()
+            },
+        ]
+    }
+}
+
+/// Resource present while a given-up puzzle's solution is shown. Holds the
+/// cells still to fill, in reading order, and the summary for the overlay.
+#[derive(Resource, Debug)]
+pub struct SolutionReveal {
+    pub remaining: VecDeque<(usize, usize)>,
+    pub timer: Timer,
+    pub summary: GiveUpSummary,
+}
+
+impl SolutionReveal {
+    /// Start revealing `solution` over `board`: every empty cell and every
+    /// player cat that doesn't match gets filled in.
+    pub fn new(board: &BoardState, solution: &Solution, seconds: u64, hints_used: usize) -> Self {
+        let wrong = board.diff_against(solution);
+        let mut remaining = VecDeque::new();
+        let mut summary = GiveUpSummary { seconds, hints_used, ..default() };
+        for row in 0..board.size() {
+            for col in 0..board.size() {
+                if board.is_given_cell(row, col) {
+                    continue;
+                }
+                match board.cells[row][col] {
+                    None => summary.empty += 1,
+                    Some(_) if wrong.contains(&(row, col)) => summary.wrong += 1,
+                    Some(_) => {
+                        summary.right += 1;
+                        continue;
+                    }
+                }
+                remaining.push_back((row, col));
+            }
+        }
+        Self {
+            remaining,
+            timer: Timer::from_seconds(REVEAL_INTERVAL_SECONDS, TimerMode::Repeating),
+            summary,
+        }
+    }
+
+    /// Whether every cell has been filled in.
+    pub fn is_done(&self) -> bool {
+        self.remaining.is_empty()
+    }
+}
+
+/// Component for the button that gives up and shows the solution.
+#[derive(Component)]
+pub struct ShowSolutionButton;
+
+/// Component to tag the give-up overlay.
+#[derive(Component)]
+pub struct GaveUpOverlay;
+
+/// Component to tag the overlay's summary text.
+#[derive(Component)]
+pub struct GaveUpSummaryText;
+
+// --- Layout ---
+
+/// Spawn the Show Solution button.
+pub fn spawn_show_solution_button(parent: &mut ChildSpawnerCommands, normal: Color, border: Color, text: Color) {
+    parent
+        .spawn((
+            Button,
+            ShowSolutionButton,
+            Focusable::ShowSolution,
+            Node {
+                width: Val::Px(130.0),
+                height: Val::Px(40.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(normal),
+            BorderColor(border),
+        ))
+        .with_children(|button_parent| {
+            button_parent.spawn((
+                Text::new("🐾 Show Solution"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(text),
+            ));
+        });
+}
+
+/// Spawn the (initially hidden) give-up overlay on the game screen. It sits
+/// along the bottom of the screen so the board stays in view as the solution
+/// fills in, but still blocks clicks on everything underneath.
+pub fn spawn_gave_up_overlay(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            GaveUpOverlay,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::FlexEnd,
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::bottom(Val::Px(24.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.05, 0.35)),
+            FocusPolicy::Block,
+            GlobalZIndex(10),
+        ))
+        .with_children(|overlay| {
+            overlay
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(12.0),
+                        padding: UiRect::all(Val::Px(20.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        max_width: Val::Px(560.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.12, 0.13, 0.2)),
+                    BorderColor(Color::srgb(0.45, 0.5, 0.7)),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::new("🐾 The solution"),
+                        TextFont {
+                            font_size: 24.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.75, 0.8, 1.0)),
+                    ));
+                    panel.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        GaveUpSummaryText,
+                    ));
+
+                    panel
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(16.0),
+                            ..default()
+                        })
+                        .with_children(|buttons| {
+                            // Same handlers as the game-over overlay's buttons
+                            buttons
+                                .spawn((
+                                    Button,
+                                    TryAgainButton,
+                                    Focusable::GaveUpTryAgain,
+                                    Node {
+                                        width: Val::Px(120.0),
+                                        height: Val::Px(40.0),
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::Center,
+                                        border: UiRect::all(Val::Px(2.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(Color::srgb(0.2, 0.5, 0.5)),
+                                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                                ))
+                                .with_children(|button_parent| {
+                                    button_parent.spawn((
+                                        Text::new("Try Again"),
+                                        TextFont {
+                                            font_size: 14.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::WHITE),
+                                    ));
+                                });
+
+                            buttons
+                                .spawn((
+                                    Button,
+                                    NewGameButton,
+                                    Focusable::GaveUpNewGame,
+                                    Node {
+                                        width: Val::Px(120.0),
+                                        height: Val::Px(40.0),
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::Center,
+                                        border: UiRect::all(Val::Px(2.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(Color::srgb(0.2, 0.6, 0.2)),
+                                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                                ))
+                                .with_children(|button_parent| {
+                                    button_parent.spawn((
+                                        Text::new("New Game"),
+                                        TextFont {
+                                            font_size: 14.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::WHITE),
+                                    ));
+                                });
+                        });
+                });
+        });
+}
+
+// --- Systems ---
+
+/// System that shows the give-up overlay while a solution is shown, with
+/// the summary once it's all filled in.
+pub fn update_gave_up_overlay(
+    reveal: Option<Res<SolutionReveal>>,
+    mut overlay_query: Query<&mut Node, With<GaveUpOverlay>>,
+    mut text_query: Query<&mut Text, With<GaveUpSummaryText>>,
+) {
+    let display = if reveal.is_some() { Display::Flex } else { Display::None };
+    for mut node in &mut overlay_query {
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    let Some(reveal) = reveal.filter(|reveal| reveal.is_changed()) else {
+        return;
+    };
+    let summary = if reveal.is_done() {
+        reveal.summary.lines().join("\n")
+    } else {
+        "Filling in the rest of the cats…".to_string()
+    };
+    for mut text in &mut text_query {
+        text.0.clone_from(&summary);
+    }
+}
+
+/// System that puts the solution away when leaving the game screen.
+pub fn end_solution_reveal(mut commands: Commands) {
+    commands.remove_resource::<SolutionReveal>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nine_lives_core::CellType;
+
+    #[test]
+    fn test_solution_reveal() {
+        let mut board = BoardState::new();
+        let solution = Solution { cells: vec![vec![0; 9]; 9] };
+        board.cells[0][0] = Some(0);
+        board.cell_types[0][0] = Some(CellType::Given);
+        board.cells[0][1] = Some(0);
+        board.cell_types[0][1] = Some(CellType::Player);
+        board.cells[0][2] = Some(3);
+        board.cell_types[0][2] = Some(CellType::Player);
+
+        let reveal = SolutionReveal::new(&board, &solution, 754, 2);
+        // The wrong cat gets replaced; the given and the right cat stay put
+        assert_eq!(reveal.remaining.front(), Some(&(0, 2)));
+        assert_eq!(reveal.remaining.len(), 81 - 2);
+        assert_eq!(
+            reveal.summary,
+            GiveUpSummary { right: 1, wrong: 1, empty: 78, seconds: 754, hints_used: 2 }
+        );
+        assert_eq!(reveal.summary.lines()[0], "You placed 2 of the 80 cats the puzzle needed (1 right).");
+        assert_eq!(reveal.summary.lines()[1], "Time played: 12:34  •  Hints used: 2");
+        assert!(!reveal.is_done());
+    }
+}
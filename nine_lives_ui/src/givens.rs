@@ -0,0 +1,74 @@
+//! Given-cell styling for Nine Lives Cat Sudoku.
+//!
+//! Players pick how the starting cats stand out from their own: a darker
+//! background, a bold glyph, a corner badge, or all three. The background and
+//! glyph are handled by the cell color and text systems; this module owns the
+//! corner badge.
+
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, PersistentData};
+
+use crate::Cell;
+
+/// Color of the corner badge on given cells
+const GIVEN_BADGE_COLOR: Color = Color::srgb(0.25, 0.2, 0.15);
+/// Side length of the corner badge
+const GIVEN_BADGE_SIZE: f32 = 6.0;
+/// How far the bold glyph's shadow copy is nudged sideways
+pub(crate) const BOLD_GLYPH_OFFSET: Vec2 = Vec2::new(0.6, 0.0);
+
+// --- Components ---
+
+/// Component to tag the corner badge drawn on given cells.
+#[derive(Component)]
+pub struct GivenBadge;
+
+// --- Layout ---
+
+/// Spawn the (initially hidden) given badge inside a cell.
+/// Must come after the cell's cat text, which other systems expect as the first child.
+pub fn spawn_given_badge(cell_parent: &mut ChildSpawnerCommands) {
+    cell_parent.spawn((
+        GivenBadge,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(2.0),
+            right: Val::Px(2.0),
+            width: Val::Px(GIVEN_BADGE_SIZE),
+            height: Val::Px(GIVEN_BADGE_SIZE),
+            ..default()
+        },
+        BackgroundColor(GIVEN_BADGE_COLOR),
+        BorderRadius::all(Val::Px(GIVEN_BADGE_SIZE / 2.0)),
+        Visibility::Hidden,
+    ));
+}
+
+// --- Systems ---
+
+/// System that shows the corner badge on given cells when the chosen given
+/// style calls for one.
+pub fn update_given_badges(
+    board: Res<BoardState>,
+    persistent_data: Res<PersistentData>,
+    cell_query: Query<(&Cell, &Children)>,
+    mut badge_query: Query<&mut Visibility, With<GivenBadge>>,
+) {
+    let shows_badge = persistent_data.user_settings.given_style.shows_badge();
+
+    for (cell, children) in &cell_query {
+        let visibility = if shows_badge && board.is_given_cell(cell.row, cell.col) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        for child in children.iter() {
+            if let Ok(mut badge) = badge_query.get_mut(child)
+                && *badge != visibility
+            {
+                *badge = visibility;
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Mistake heatmap for Nine Lives Cat Sudoku.
+//!
+//! Once a puzzle is solved, "🔥 Mistake heatmap" under the board tints every
+//! cell by how many mistakes the player has made there across their solved
+//! games (see `nine_lives_core::CellStats`): the more mistakes, the redder.
+//! A line under the button names the box with the most. The choice sticks for
+//! later wins until it's turned off again.
+
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, PersistentData};
+
+use crate::Focusable;
+
+/// Color of the hottest cell on the heatmap
+const HOTTEST_COLOR: [f32; 3] = [1.0, 0.35, 0.2];
+
+// --- Components & Resources ---
+
+/// Component to tag the button that shows or hides the heatmap.
+#[derive(Component)]
+pub struct HeatmapButton;
+
+/// Component to tag the line naming the box with the most mistakes.
+#[derive(Component)]
+pub struct HeatmapSummaryDisplay;
+
+/// Resource noting whether the heatmap is laid over a solved board.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HeatmapOverlay {
+    pub shown: bool,
+}
+
+/// Label for the heatmap button.
+fn heatmap_button_label(overlay: HeatmapOverlay) -> &'static str {
+    if overlay.shown { "🔥 Hide heatmap" } else { "🔥 Mistake heatmap" }
+}
+
+/// A cell's color on the heatmap: its own color, blended toward red by its
+/// heat (0.0 to 1.0).
+pub fn heat_color(base: Color, heat: f32) -> Color {
+    let base = base.to_srgba();
+    let [r, g, b] = HOTTEST_COLOR;
+    let heat = heat.clamp(0.0, 1.0);
+    Color::srgb(
+        base.red + (r - base.red) * heat,
+        base.green + (g - base.green) * heat,
+        base.blue + (b - base.blue) * heat,
+    )
+}
+
+// --- Layout ---
+
+/// Spawn the heatmap button and its summary line as children of `parent`.
+pub fn spawn_heatmap_toggle(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Button,
+            HeatmapButton,
+            Focusable::Heatmap,
+            Node {
+                width: Val::Px(180.0),
+                height: Val::Px(35.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.5, 0.25, 0.2)),
+            BorderColor(Color::srgb(0.8, 0.4, 0.3)),
+        ))
+        .with_children(|button_parent| {
+            button_parent.spawn((
+                Text::new(heatmap_button_label(HeatmapOverlay::default())),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.8, 0.7)),
+        HeatmapSummaryDisplay,
+    ));
+}
+
+// --- Systems ---
+
+/// System that shows or hides the heatmap when its button is pressed.
+pub fn handle_heatmap_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<HeatmapButton>)>,
+    mut overlay: ResMut<HeatmapOverlay>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            overlay.shown = !overlay.shown;
+        }
+    }
+}
+
+/// System that labels the heatmap button and, with the heatmap shown, names
+/// the box with the most mistakes.
+pub fn update_heatmap_labels(
+    overlay: Res<HeatmapOverlay>,
+    board: Res<BoardState>,
+    persistent_data: Res<PersistentData>,
+    button_query: Query<&Children, With<HeatmapButton>>,
+    mut text_query: Query<&mut Text, Without<HeatmapSummaryDisplay>>,
+    mut summary_query: Query<&mut Text, With<HeatmapSummaryDisplay>>,
+) {
+    for children in &button_query {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = heatmap_button_label(*overlay).to_string();
+                break;
+            }
+        }
+    }
+
+    let summary = if !overlay.shown {
+        String::new()
+    } else {
+        match persistent_data.statistics.cell_stats.worst_box(board.grid_size) {
+            Some((index, mistakes)) => format!(
+                "Most mistakes in box {} of {}: {} across your games",
+                index + 1,
+                board.size(),
+                mistakes
+            ),
+            None => "No mistakes on this size of board yet 🐾".to_string(),
+        }
+    };
+    for mut text in &mut summary_query {
+        text.0 = summary.clone();
+    }
+}
@@ -0,0 +1,166 @@
+//! Hint nudge highlighting for Nine Lives Cat Sudoku.
+//!
+//! The first press of the Hint button doesn't place anything; it points at
+//! the row, column, or box holding the next deduction. Those cells get a
+//! bright outline that fades away over a few seconds.
+//!
+//! When a hint does place a cat, that cell's outline pulses for a moment and
+//! then fades out, so it's easy to see what the hint changed.
+
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, HintTier, LastHint};
+
+use crate::{Cell, FocusManager};
+
+/// How long a nudge highlight lasts, in seconds
+const UNIT_HIGHLIGHT_SECONDS: f32 = 3.0;
+/// Color of the nudge highlight outline
+const UNIT_HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 0.75, 0.2);
+/// Width of the nudge highlight outline
+const UNIT_HIGHLIGHT_WIDTH: f32 = 2.0;
+/// How long a hinted cell pulses before fading, in seconds
+const HINT_PULSE_SECONDS: f32 = 2.4;
+/// How long a hinted cell's pulse takes to fade out, in seconds
+const HINT_FADE_SECONDS: f32 = 0.8;
+/// Seconds per pulse of a hinted cell
+const HINT_PULSE_PERIOD: f32 = 0.6;
+/// Color of a hinted cell's pulsing outline
+const HINT_PULSE_COLOR: Color = Color::srgb(0.3, 0.9, 0.5);
+/// Width of a hinted cell's pulsing outline
+const HINT_PULSE_WIDTH: f32 = 3.0;
+
+// --- Components ---
+
+/// Component on a cell that's part of the house a hint nudge points at.
+/// Removed (along with its outline) once the timer runs out.
+#[derive(Component)]
+pub struct UnitHighlight {
+    pub timer: Timer,
+}
+
+/// Component on the cell a hint just filled, while its outline pulses.
+/// Removed (along with its outline) once the timer runs out.
+#[derive(Component)]
+pub struct HintedCell {
+    pub timer: Timer,
+}
+
+/// How opaque a hinted cell's outline is `elapsed` seconds after the hint:
+/// pulsing between faint and full, then fading out to nothing.
+pub fn hint_pulse_alpha(elapsed: f32) -> f32 {
+    let pulse = 0.6 + 0.4 * (elapsed / HINT_PULSE_PERIOD * std::f32::consts::TAU).cos();
+    let fade = ((elapsed - HINT_PULSE_SECONDS) / HINT_FADE_SECONDS).clamp(0.0, 1.0);
+    pulse * (1.0 - fade)
+}
+
+// --- Systems ---
+
+/// System that outlines the cells of the nudged house whenever a new hint
+/// nudge arrives, and clears any older highlight.
+pub fn start_unit_highlight(
+    mut commands: Commands,
+    last_hint: Res<LastHint>,
+    board: Res<BoardState>,
+    cell_query: Query<(Entity, &Cell)>,
+    highlighted_query: Query<Entity, With<UnitHighlight>>,
+) {
+    for entity in &highlighted_query {
+        commands.entity(entity).remove::<(UnitHighlight, Outline)>();
+    }
+
+    let Some(explanation) = last_hint.explanation.filter(|_| last_hint.tier == HintTier::Nudge) else {
+        return;
+    };
+    let focus = explanation.focus_cells(&board);
+    for (entity, cell) in &cell_query {
+        if focus.contains(&(cell.row, cell.col)) {
+            commands.entity(entity).insert((
+                UnitHighlight {
+                    timer: Timer::from_seconds(UNIT_HIGHLIGHT_SECONDS, TimerMode::Once),
+                },
+                Outline::new(Val::Px(UNIT_HIGHLIGHT_WIDTH), Val::ZERO, UNIT_HIGHLIGHT_COLOR),
+            ));
+        }
+    }
+}
+
+/// System that starts the pulse on the cell a hint just filled. Any earlier
+/// pulse stops, so only the latest hint pulses.
+pub fn start_hint_pulse(
+    mut commands: Commands,
+    last_hint: Res<LastHint>,
+    cell_query: Query<(Entity, &Cell)>,
+    hinted_query: Query<Entity, With<HintedCell>>,
+) {
+    let Some(explanation) = last_hint.explanation.filter(|_| last_hint.tier == HintTier::Reveal) else {
+        return;
+    };
+    for entity in &hinted_query {
+        commands.entity(entity).remove::<(HintedCell, Outline)>();
+    }
+    for (entity, cell) in &cell_query {
+        if (cell.row, cell.col) == (explanation.row, explanation.col) {
+            commands.entity(entity).insert((
+                HintedCell {
+                    timer: Timer::from_seconds(HINT_PULSE_SECONDS + HINT_FADE_SECONDS, TimerMode::Once),
+                },
+                Outline::new(Val::Px(HINT_PULSE_WIDTH), Val::ZERO, HINT_PULSE_COLOR),
+            ));
+        }
+    }
+}
+
+/// System that pulses the hinted cell's outline, fading it out and removing
+/// it when it's done. A focused cell keeps its focus outline meanwhile.
+pub fn pulse_hinted_cells(
+    mut commands: Commands,
+    time: Res<Time>,
+    focus: Res<FocusManager>,
+    mut hinted_query: Query<(Entity, &mut HintedCell, &mut Outline)>,
+) {
+    for (entity, mut hinted, mut outline) in &mut hinted_query {
+        hinted.timer.tick(time.delta());
+        if hinted.timer.finished() {
+            commands.entity(entity).remove::<(HintedCell, Outline)>();
+        } else if focus.focused != Some(entity) {
+            let color = HINT_PULSE_COLOR.with_alpha(hint_pulse_alpha(hinted.timer.elapsed_secs()));
+            *outline = Outline::new(Val::Px(HINT_PULSE_WIDTH), Val::ZERO, color);
+        }
+    }
+}
+
+/// System that fades the nudge highlight out and removes it when it's done.
+/// A focused cell keeps its focus outline while the nudge fades underneath.
+pub fn fade_unit_highlight(
+    mut commands: Commands,
+    time: Res<Time>,
+    focus: Res<FocusManager>,
+    mut highlight_query: Query<(Entity, &mut UnitHighlight, &mut Outline)>,
+) {
+    for (entity, mut highlight, mut outline) in &mut highlight_query {
+        highlight.timer.tick(time.delta());
+        if highlight.timer.finished() {
+            commands.entity(entity).remove::<(UnitHighlight, Outline)>();
+        } else if focus.focused != Some(entity) {
+            let color = UNIT_HIGHLIGHT_COLOR.with_alpha(1.0 - highlight.timer.fraction());
+            *outline = Outline::new(Val::Px(UNIT_HIGHLIGHT_WIDTH), Val::ZERO, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hint_pulse_fades_out() {
+        // Full at the start of each pulse, faint halfway through
+        assert_eq!(hint_pulse_alpha(0.0), 1.0);
+        assert!((hint_pulse_alpha(HINT_PULSE_PERIOD / 2.0) - 0.2).abs() < 1e-4);
+        assert!((hint_pulse_alpha(HINT_PULSE_PERIOD) - 1.0).abs() < 1e-4);
+
+        // Then gone once the fade is over
+        assert!(hint_pulse_alpha(HINT_PULSE_SECONDS + HINT_FADE_SECONDS / 2.0) < 0.6);
+        assert!(hint_pulse_alpha(HINT_PULSE_SECONDS + HINT_FADE_SECONDS) < 1e-4);
+    }
+}
@@ -0,0 +1,264 @@
+//! Move history panel for Nine Lives Cat Sudoku.
+//!
+//! The 📜 History button in the game screen's side panel opens a list of
+//! every move in the undo history, like "Move 12: cat 5 at R3C7",
+//! with the time into the game it was made. Clicking a move undoes or redoes
+//! everything needed to get back to just after it ("Start" goes back to the
+//! puzzle as it began). Moves past the current point, which Redo would bring
+//! back, are dimmed.
+
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use nine_lives_core::{GameHistory, GameSession, Move};
+
+use crate::{ChangedButtons, Focusable, format_duration_secs};
+
+/// Height of the scrolling list of moves
+const LIST_HEIGHT: f32 = 300.0;
+/// Pixels scrolled per line of mouse wheel movement (see `scroll_list_system`)
+pub(crate) const SCROLL_LINE_HEIGHT: f32 = 22.0;
+/// Background of the entry for where the game is now
+const CURRENT_ENTRY_BG: Color = Color::srgb(0.25, 0.4, 0.6);
+/// Background of every other entry
+const ENTRY_BG: Color = Color::srgba(0.0, 0.0, 0.0, 0.0);
+/// Background of an entry while hovered
+const ENTRY_HOVER_BG: Color = Color::srgb(0.2, 0.25, 0.35);
+
+// --- Components & Resources ---
+
+/// Component for the button that opens and closes the history panel.
+#[derive(Component)]
+pub struct HistoryPanelButton;
+
+/// Component to tag the history panel.
+#[derive(Component)]
+pub struct HistoryPanel;
+
+/// Component to tag the "Move 5/10" line at the top of the panel.
+#[derive(Component)]
+pub struct HistoryPositionText;
+
+/// Component to tag the scrolling list of moves.
+#[derive(Component)]
+pub struct HistoryList;
+
+/// Component for an entry in the history list. Holds the history position
+/// clicking it jumps to: the number of moves made, so 0 is the start.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryEntryButton(pub usize);
+
+/// Resource recording whether the history panel is open.
+#[derive(Resource, Default)]
+pub struct HistoryPanelState {
+    pub open: bool,
+}
+
+/// Label for the panel button.
+fn history_button_label(open: bool) -> &'static str {
+    if open { "📜 Hide History" } else { "📜 History" }
+}
+
+/// An entry's label, like "Move 12: cat 5 at R3C7  03:05". The time is how
+/// far into the game the move was made.
+pub fn history_entry_label(number: usize, game_move: &Move, game_started: std::time::SystemTime) -> String {
+    let into_game = game_move.timestamp.duration_since(game_started).unwrap_or_default();
+    format!(
+        "Move {}: {}  {}",
+        number,
+        game_move.describe(),
+        format_duration_secs(into_game.as_secs())
+    )
+}
+
+// --- Layout ---
+
+/// Spawn the button that opens the history panel.
+pub fn spawn_history_panel_button(parent: &mut ChildSpawnerCommands, normal: Color, border: Color, text: Color) {
+    parent
+        .spawn((
+            Button,
+            HistoryPanelButton,
+            Focusable::HistoryPanel,
+            Node {
+                width: Val::Px(120.0),
+                height: Val::Px(35.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(normal),
+            BorderColor(border),
+        ))
+        .with_children(|button_parent| {
+            button_parent.spawn((
+                Text::new(history_button_label(false)),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(text),
+            ));
+        });
+}
+
+/// Spawn the (initially hidden) history panel, in the side panel.
+pub fn spawn_history_panel(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            HistoryPanel,
+            Node {
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.9)),
+            BorderColor(Color::srgb(0.4, 0.4, 0.5)),
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new("📜 Move 0/0"),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                HistoryPositionText,
+            ));
+
+            panel.spawn((
+                HistoryList,
+                RelativeCursorPosition::default(),
+                Node {
+                    max_height: Val::Px(LIST_HEIGHT),
+                    flex_direction: FlexDirection::Column,
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+                ScrollPosition::default(),
+            ));
+        });
+}
+
+/// Spawn one entry of the history list.
+fn spawn_history_entry(list: &mut ChildSpawnerCommands, position: usize, label: String, current: bool, undone: bool) {
+    list.spawn((
+        Button,
+        HistoryEntryButton(position),
+        Focusable::HistoryEntry(position),
+        Node {
+            padding: UiRect::axes(Val::Px(6.0), Val::Px(3.0)),
+            ..default()
+        },
+        BackgroundColor(if current { CURRENT_ENTRY_BG } else { ENTRY_BG }),
+    ))
+    .with_children(|entry| {
+        entry.spawn((
+            Text::new(label),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(if undone { Color::srgb(0.5, 0.5, 0.55) } else { Color::srgb(0.9, 0.9, 0.9) }),
+        ));
+    });
+}
+
+// --- Systems ---
+
+/// System that opens or closes the history panel when its button is pressed.
+pub fn handle_history_panel_button(
+    interaction_query: ChangedButtons<HistoryPanelButton>,
+    mut text_query: Query<&mut Text>,
+    mut panel_state: ResMut<HistoryPanelState>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            panel_state.open = !panel_state.open;
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(child) {
+                    text.0 = history_button_label(panel_state.open).to_string();
+                }
+            }
+        }
+    }
+}
+
+/// System that shows the history panel only while it's open.
+pub fn update_history_panel_visibility(
+    panel_state: Res<HistoryPanelState>,
+    mut panel_query: Query<&mut Node, With<HistoryPanel>>,
+) {
+    for mut node in &mut panel_query {
+        node.display = if panel_state.open { Display::Flex } else { Display::None };
+    }
+}
+
+/// System that lists the history's moves, marking where the game is now.
+pub fn update_history_list(
+    mut commands: Commands,
+    history: Res<GameHistory>,
+    session: Res<GameSession>,
+    list_query: Query<Entity, With<HistoryList>>,
+    mut position_query: Query<&mut Text, With<HistoryPositionText>>,
+) {
+    let (current, total) = history.position_info();
+    for mut text in &mut position_query {
+        text.0 = format!("📜 Move {}/{}", current, total);
+    }
+
+    let game_started = std::time::SystemTime::now() - session.started_at.elapsed();
+    for list in &list_query {
+        commands.entity(list).despawn_related::<Children>();
+        commands.entity(list).with_children(|list| {
+            spawn_history_entry(list, 0, "Start".to_string(), current == 0, false);
+            for (index, game_move) in history.moves.iter().enumerate() {
+                let position = index + 1;
+                let label = history_entry_label(position, game_move, game_started);
+                spawn_history_entry(list, position, label, position == current, position > current);
+            }
+        });
+    }
+}
+
+/// System to handle history entry hover effects.
+pub fn update_history_entry_colors(
+    history: Res<GameHistory>,
+    mut entry_query: Query<(&Interaction, &HistoryEntryButton, &mut BackgroundColor), Changed<Interaction>>,
+) {
+    let (current, _) = history.position_info();
+    for (interaction, entry, mut bg_color) in &mut entry_query {
+        bg_color.0 = match interaction {
+            Interaction::Hovered | Interaction::Pressed => ENTRY_HOVER_BG,
+            Interaction::None if entry.0 == current => CURRENT_ENTRY_BG,
+            Interaction::None => ENTRY_BG,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_history_entry_label() {
+        let started = SystemTime::now();
+        let game_move = Move {
+            row: 2,
+            col: 6,
+            old_value: None,
+            new_value: Some(4),
+            timestamp: started + Duration::from_secs(185),
+            cleared_notes: Vec::new(),
+        };
+        assert_eq!(history_entry_label(12, &game_move, started), "Move 12: cat 5 at R3C7  03:05");
+
+        // Moves from before the clock started (like a resumed game's) show 00:00
+        let earlier = Move { new_value: None, timestamp: started - Duration::from_secs(60), ..game_move };
+        assert_eq!(history_entry_label(3, &earlier, started), "Move 3: cleared R3C7  00:00");
+    }
+}
@@ -0,0 +1,81 @@
+//! House completion flash for Nine Lives Cat Sudoku.
+//!
+//! When a move fills a row, column, box, or window with no cat repeated, the
+//! controller sends a `HouseCompleted` event and the house's cells get a
+//! golden outline that fades out over a moment.
+
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, HouseKind};
+
+use crate::{Cell, FocusManager};
+
+/// How long a completed house flashes, in seconds
+const HOUSE_FLASH_SECONDS: f32 = 0.8;
+/// Color of a completed house's flash
+const HOUSE_FLASH_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+/// Width of a completed house's flash
+const HOUSE_FLASH_WIDTH: f32 = 2.0;
+
+// --- Events & Components ---
+
+/// Event sent when a move completes a house.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HouseCompleted {
+    pub kind: HouseKind,
+    pub index: usize,
+}
+
+/// Component on a cell of a just-completed house, while it flashes.
+/// Removed (along with its outline) once the timer runs out.
+#[derive(Component)]
+pub struct HouseFlash {
+    pub timer: Timer,
+}
+
+// --- Systems ---
+
+/// System that starts the flash on the cells of every house just completed.
+pub fn start_house_flash(
+    mut commands: Commands,
+    mut events: EventReader<HouseCompleted>,
+    board: Res<BoardState>,
+    cell_query: Query<(Entity, &Cell)>,
+) {
+    let cells: Vec<(usize, usize)> = events
+        .read()
+        .flat_map(|event| event.kind.cells_at(&board, event.index))
+        .collect();
+    if cells.is_empty() {
+        return;
+    }
+
+    for (entity, cell) in &cell_query {
+        if cells.contains(&(cell.row, cell.col)) {
+            commands.entity(entity).insert((
+                HouseFlash {
+                    timer: Timer::from_seconds(HOUSE_FLASH_SECONDS, TimerMode::Once),
+                },
+                Outline::new(Val::Px(HOUSE_FLASH_WIDTH), Val::ZERO, HOUSE_FLASH_COLOR),
+            ));
+        }
+    }
+}
+
+/// System that fades the flash out and removes it when it's done. A focused
+/// cell keeps its focus outline meanwhile.
+pub fn fade_house_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    focus: Res<FocusManager>,
+    mut flash_query: Query<(Entity, &mut HouseFlash, &mut Outline)>,
+) {
+    for (entity, mut flash, mut outline) in &mut flash_query {
+        flash.timer.tick(time.delta());
+        if flash.timer.finished() {
+            commands.entity(entity).remove::<(HouseFlash, Outline)>();
+        } else if focus.focused != Some(entity) {
+            let color = HOUSE_FLASH_COLOR.with_alpha(1.0 - flash.timer.fraction());
+            *outline = Outline::new(Val::Px(HOUSE_FLASH_WIDTH), Val::ZERO, color);
+        }
+    }
+}
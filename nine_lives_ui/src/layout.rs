@@ -0,0 +1,284 @@
+//! Game screen layout for Nine Lives Cat Sudoku.
+//!
+//! The game screen is split in two. The main area on the left holds the
+//! board with the number pad under it; the side panel on the right holds the
+//! stats (clock, moves, score), the controls, and the move history. Between
+//! them runs a narrow strip with a button that collapses the panel, giving
+//! the board the whole window, and a handle that drags the panel wider or
+//! narrower. The board is scaled to whatever room the panel leaves (see
+//! `GridScale`).
+//!
+//! New controls belong in the side panel rather than under the board.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::Focusable;
+
+/// Width of the strip between the main area and the side panel
+pub const RAIL_WIDTH: f32 = 28.0;
+/// Width of the side panel when the game starts
+const DEFAULT_PANEL_WIDTH: f32 = 300.0;
+/// Narrowest and widest the side panel can be dragged
+const PANEL_WIDTH_RANGE: (f32, f32) = (220.0, 480.0);
+/// Background of the side panel
+const PANEL_BG: Color = Color::srgba(0.05, 0.05, 0.08, 0.6);
+/// Background of the strip between the main area and the side panel
+const RAIL_BG: Color = Color::srgba(0.1, 0.1, 0.14, 0.8);
+/// The drag handle, at rest and while hovered or dragged
+const HANDLE_COLOR: Color = Color::srgb(0.3, 0.3, 0.38);
+const HANDLE_ACTIVE_COLOR: Color = Color::srgb(0.45, 0.6, 0.85);
+
+// --- Components & Resources ---
+
+/// Component to tag the main area of the game screen (the board and number pad).
+#[derive(Component)]
+pub struct GameMainArea;
+
+/// Component to tag the side panel.
+#[derive(Component)]
+pub struct SidePanel;
+
+/// Component for the button that collapses and expands the side panel.
+#[derive(Component)]
+pub struct SidePanelToggle;
+
+/// Component for the handle that drags the side panel wider or narrower.
+#[derive(Component)]
+pub struct SplitHandle;
+
+/// Resource recording whether the side panel is open, and how wide it is
+/// (in interface pixels). Kept between games.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct SidePanelState {
+    pub open: bool,
+    pub width: f32,
+}
+
+impl Default for SidePanelState {
+    fn default() -> Self {
+        Self { open: true, width: DEFAULT_PANEL_WIDTH }
+    }
+}
+
+impl SidePanelState {
+    /// The room the panel and the strip beside it take from the board, in
+    /// interface pixels.
+    pub fn occupied_width(&self) -> f32 {
+        if self.open { RAIL_WIDTH + self.width } else { RAIL_WIDTH }
+    }
+
+    /// The panel width for the drag handle held at `cursor_x`, in a window
+    /// `window_width` wide (both in window pixels) with the interface drawn
+    /// at `ui_scale`. The handle sits just left of the panel.
+    pub fn width_for_cursor(window_width: f32, cursor_x: f32, ui_scale: f32) -> f32 {
+        let width = (window_width - cursor_x) / ui_scale - RAIL_WIDTH / 2.0;
+        width.clamp(PANEL_WIDTH_RANGE.0, PANEL_WIDTH_RANGE.1)
+    }
+}
+
+/// Label for the toggle: points the way the panel will go.
+fn toggle_label(open: bool) -> &'static str {
+    if open { "▶" } else { "◀" }
+}
+
+// --- Layout ---
+
+/// The node for the main area: takes whatever room the side panel leaves,
+/// with its contents centered.
+pub fn main_area_node() -> Node {
+    Node {
+        flex_grow: 1.0,
+        height: Val::Percent(100.0),
+        align_items: AlignItems::Center,
+        justify_content: JustifyContent::Center,
+        flex_direction: FlexDirection::Column,
+        ..default()
+    }
+}
+
+/// Spawn the strip between the main area and the side panel, holding the
+/// panel's toggle and drag handle.
+pub fn spawn_panel_rail(parent: &mut ChildSpawnerCommands, state: &SidePanelState) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(RAIL_WIDTH),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::vertical(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(RAIL_BG),
+        ))
+        .with_children(|rail| {
+            rail.spawn((
+                Button,
+                SidePanelToggle,
+                Focusable::SidePanelToggle,
+                Node {
+                    width: Val::Px(RAIL_WIDTH - 6.0),
+                    height: Val::Px(40.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.2, 0.28)),
+                BorderColor(Color::srgb(0.4, 0.4, 0.5)),
+            ))
+            .with_children(|button| {
+                button.spawn((
+                    Text::new(toggle_label(state.open)),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            });
+
+            // Drag handle (only while the panel is open)
+            rail.spawn((
+                Button,
+                SplitHandle,
+                Node {
+                    display: if state.open { Display::Flex } else { Display::None },
+                    width: Val::Px(6.0),
+                    flex_grow: 1.0,
+                    ..default()
+                },
+                BackgroundColor(HANDLE_COLOR),
+            ));
+        });
+}
+
+/// Spawn the side panel, filled in by `contents`.
+pub fn spawn_side_panel(
+    parent: &mut ChildSpawnerCommands,
+    state: &SidePanelState,
+    contents: impl FnOnce(&mut ChildSpawnerCommands),
+) {
+    parent
+        .spawn((
+            SidePanel,
+            Node {
+                display: if state.open { Display::Flex } else { Display::None },
+                width: Val::Px(state.width),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                overflow: Overflow::clip_y(),
+                ..default()
+            },
+            BackgroundColor(PANEL_BG),
+        ))
+        .with_children(contents);
+}
+
+/// Spawn a section heading in the side panel.
+pub fn spawn_panel_heading(panel: &mut ChildSpawnerCommands, heading: impl Bundle) {
+    panel.spawn((
+        heading,
+        TextFont {
+            font_size: 15.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.75, 0.8, 0.95)),
+        Node {
+            margin: UiRect::top(Val::Px(6.0)),
+            ..default()
+        },
+    ));
+}
+
+// --- Systems ---
+
+/// System that collapses or expands the side panel when its toggle is pressed.
+pub fn handle_side_panel_toggle(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SidePanelToggle>)>,
+    mut side_panel: ResMut<SidePanelState>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            side_panel.open = !side_panel.open;
+            debug!("📐 Side panel {}", if side_panel.open { "opened" } else { "collapsed" });
+        }
+    }
+}
+
+/// System that resizes the side panel while its handle is dragged, and
+/// lights the handle up while it's hovered or held.
+pub fn drag_split_handle(
+    mut handle_query: Query<(&Interaction, &mut BackgroundColor), With<SplitHandle>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    mut side_panel: ResMut<SidePanelState>,
+) {
+    let mut dragging = false;
+    for (interaction, mut bg_color) in &mut handle_query {
+        let color = if *interaction == Interaction::None { HANDLE_COLOR } else { HANDLE_ACTIVE_COLOR };
+        if bg_color.0 != color {
+            bg_color.0 = color;
+        }
+        dragging |= *interaction == Interaction::Pressed;
+    }
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    if let (true, Some(cursor)) = (dragging, window.cursor_position()) {
+        let width = SidePanelState::width_for_cursor(window.width(), cursor.x, ui_scale.0);
+        // Only report a change when the width actually moves, so the board isn't refitted every frame
+        if side_panel.width != width {
+            side_panel.width = width;
+        }
+    }
+}
+
+/// System that shows, hides, and sizes the side panel to match its state.
+pub fn apply_side_panel_layout(
+    side_panel: Res<SidePanelState>,
+    mut panel_query: Query<&mut Node, (With<SidePanel>, Without<SplitHandle>)>,
+    mut handle_query: Query<&mut Node, (With<SplitHandle>, Without<SidePanel>)>,
+    toggle_query: Query<&Children, With<SidePanelToggle>>,
+    mut text_query: Query<&mut Text>,
+) {
+    let display = if side_panel.open { Display::Flex } else { Display::None };
+    for mut node in &mut panel_query {
+        node.display = display;
+        node.width = Val::Px(side_panel.width);
+    }
+    for mut node in &mut handle_query {
+        node.display = display;
+    }
+    for children in &toggle_query {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = toggle_label(side_panel.open).to_string();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_side_panel_width() {
+        let mut state = SidePanelState::default();
+        assert_eq!(state.occupied_width(), RAIL_WIDTH + DEFAULT_PANEL_WIDTH);
+        state.open = false;
+        assert_eq!(state.occupied_width(), RAIL_WIDTH, "A collapsed panel leaves just the strip");
+
+        // Dragging left widens the panel, at any interface scale, within its range
+        let at = |x| SidePanelState::width_for_cursor(1000.0, x, 1.0);
+        assert!(at(600.0) > at(700.0));
+        assert_eq!(SidePanelState::width_for_cursor(2000.0, 1400.0, 2.0), at(700.0));
+        assert_eq!(at(0.0), PANEL_WIDTH_RANGE.1);
+        assert_eq!(at(990.0), PANEL_WIDTH_RANGE.0);
+    }
+}
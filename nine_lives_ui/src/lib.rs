@@ -26,11 +26,108 @@
 //! updated, fixing the highlighting sync issue.
 
 use bevy::prelude::*;
-use nine_lives_core::{BoardState, GRID_SIZE, GameState, GameSession, HintSystem, DebugMode, PresetKind, PuzzleSettings, Solution, GameHistory};
-use std::collections::HashSet;
+use nine_lives_core::{BoardState, CatRendering, ConflictTracker, GenerationReport, GivenStyle, GlyphSet, GridSize, GameState, GameSession, HintSystem, DebugMode, PresetKind, PuzzleSettings, PuzzlePool, Solution, GameHistory, HintTier, LastHint, Lives, PersistentData, ThemeKind, Variant, GameEventLog, GameMode, ProfileRoster, ScoreTracker, StreakTracker, ShortcutAction, Tutorial};
+
+mod accessibility;
+mod app_window;
+mod art_packs;
+mod best_replay;
+mod campaign;
+mod cages;
+mod cell_colors;
+mod cell_renderer;
+mod cell_content;
+mod confirm;
+mod conflict_flash;
+mod continue_game;
+mod cursor;
+mod event_log;
+mod focus;
+mod give_up;
+mod givens;
+mod heatmap;
+mod history_panel;
+mod house_flash;
+mod hint_highlight;
+mod layout;
+mod lives;
+mod locale;
+mod music;
+mod number_pad;
+mod packs;
+mod profiles;
+mod puzzle_swap;
+mod progress_check;
+mod regions;
+mod replay;
+mod scaling;
+mod scroll;
+mod settings;
+mod share;
+mod shortcuts;
+mod solver_steps;
+mod statistics;
+mod streak;
+mod text_fit;
+mod time_attack;
+mod toasts;
+mod touch;
+mod training;
+mod tutorial;
+pub use accessibility::*;
+pub use app_window::*;
+pub use art_packs::*;
+pub use best_replay::*;
+pub use campaign::*;
+pub use cages::*;
+pub use cell_colors::*;
+pub use cell_renderer::*;
+pub use cell_content::*;
+pub use confirm::*;
+pub use conflict_flash::*;
+pub use continue_game::*;
+pub use cursor::*;
+pub use event_log::*;
+pub use focus::*;
+pub use give_up::*;
+pub use givens::*;
+pub use heatmap::*;
+pub use history_panel::*;
+pub use house_flash::*;
+pub use hint_highlight::*;
+pub use layout::*;
+pub use lives::*;
+pub use locale::*;
+pub use music::*;
+pub use number_pad::*;
+pub use packs::*;
+pub use profiles::*;
+pub use puzzle_swap::*;
+pub use progress_check::*;
+pub use regions::*;
+pub use replay::*;
+pub use scaling::*;
+pub use scroll::*;
+pub use settings::*;
+pub use share::*;
+pub use shortcuts::*;
+pub use solver_steps::*;
+pub use statistics::*;
+pub use streak::*;
+pub use text_fit::*;
+pub use time_attack::*;
+pub use toasts::*;
+pub use touch::*;
+pub use training::*;
+pub use tutorial::*;
 
 // --- UI Components ---
 
+/// The buttons of one kind whose interaction just changed, with their
+/// children (for the button's label).
+pub type ChangedButtons<'w, 's, B> =
+    Query<'w, 's, (&'static Interaction, &'static Children), (Changed<Interaction>, With<B>)>;
+
 /// A component to tag a UI entity as a grid cell, storing its position.
 #[derive(Component)]
 pub struct Cell {
@@ -42,6 +139,10 @@ pub struct Cell {
 #[derive(Component)]
 pub struct ClearButton;
 
+/// A component to tag the "Restart Puzzle" button entity.
+#[derive(Component)]
+pub struct RestartButton;
+
 /// A component to tag the "New Game" button entity.
 #[derive(Component)]
 pub struct NewGameButton;
@@ -54,6 +155,10 @@ pub struct TimerDisplay;
 #[derive(Component)]
 pub struct MoveCounterDisplay;
 
+/// A component to tag the score display.
+#[derive(Component)]
+pub struct ScoreDisplay;
+
 /// A component to tag the undo button.
 #[derive(Component)]
 pub struct UndoButton;
@@ -62,6 +167,11 @@ pub struct UndoButton;
 #[derive(Component)]
 pub struct RedoButton;
 
+/// A component to tag the button that undoes back to the last move where
+/// every cat placed was right.
+#[derive(Component)]
+pub struct UndoToCorrectButton;
+
 /// A component to tag the hint button.
 #[derive(Component)]
 pub struct HintButton;
@@ -70,11 +180,17 @@ pub struct HintButton;
 #[derive(Component)]
 pub struct DebugStatusDisplay;
 
-/// Component to mark a cell as currently hinted (for pulsing animation).
+/// A component to tag the text that explains the most recent hint.
 #[derive(Component)]
-pub struct HintedCell {
-    pub timer: Timer,
-}
+pub struct HintExplanationDisplay;
+
+/// A component to tag the button that toggles auto-candidate mode.
+#[derive(Component)]
+pub struct CandidatesButton;
+
+/// A component to tag the button that cycles hint explanation verbosity.
+#[derive(Component)]
+pub struct HintVerbosityButton;
 
 /// Component to tag preset selection buttons.
 #[derive(Component)]
@@ -98,12 +214,97 @@ pub struct CustomizationScreenRoot;
 #[derive(Component)]
 pub struct GameScreenRoot;
 
+/// Component to tag the sudoku grid container node.
+#[derive(Component)]
+pub struct GridContainer;
+
+/// The on-screen slot a cell occupies in the grid layout.
+/// Unlike `Cell`, this never changes when the board is rotated.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridSlot {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Component to tag the Start Game button's label.
+#[derive(Component)]
+pub struct StartGameLabel;
+
+/// Component to tag the line describing how the puzzle was generated.
+#[derive(Component)]
+pub struct GenerationInfoDisplay;
+
+/// Resource describing how the puzzle being played was generated, for the
+/// info line on the game screen. Shared puzzles have no report.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct LastGeneration {
+    pub report: Option<GenerationReport>,
+    /// Whether the puzzle came out of the pre-generated pool
+    pub pre_generated: bool,
+}
+
+impl LastGeneration {
+    /// The info line, like "Puzzle generated in 340 ms, 2 attempts, 24 givens, grade: Expert".
+    pub fn info_line(&self) -> String {
+        match &self.report {
+            Some(report) if self.pre_generated => format!("Puzzle {} (ahead of time)", report.summary()),
+            Some(report) => format!("Puzzle {}", report.summary()),
+            None => String::new(),
+        }
+    }
+}
+
 /// Resource to track the currently selected preset on the customization screen.
 #[derive(Resource, Clone, Debug, PartialEq, Eq)]
 pub struct SelectedPreset {
     pub preset: PresetKind,
 }
 
+/// Resource to track the board size chosen on the customization screen.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct SelectedGridSize {
+    pub grid_size: GridSize,
+}
+
+/// Component to tag the button that cycles the board size on the customization screen.
+#[derive(Component)]
+pub struct GridSizeButton;
+
+/// Resource to track the rule set (preset family) chosen on the customization screen.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct SelectedVariant {
+    pub variant: Variant,
+}
+
+/// Component to tag the button that cycles the rule set on the customization screen.
+#[derive(Component)]
+pub struct VariantButton;
+
+/// Component to tag the button that cycles the glyph set used for cat numbers.
+#[derive(Component)]
+pub struct GlyphSetButton;
+
+/// Component to tag the button that cycles how given cells are styled.
+#[derive(Component)]
+pub struct GivenStyleButton;
+
+/// Component to tag the button that turns double-click filling on and off.
+#[derive(Component)]
+pub struct DoubleClickFillButton;
+
+/// Component to tag the button that turns nine lives on and off.
+#[derive(Component)]
+pub struct NineLivesButton;
+
+/// Component to tag the button that turns pausing while away on and off.
+#[derive(Component)]
+pub struct PauseWhenAwayButton;
+
+/// Component to tag the button that turns Zen mode on and off.
+#[derive(Component)]
+pub struct GameModeButton;
+
+
 // --- UI Resources ---
 
 /// A Bevy resource that holds the ASCII art for the cats.
@@ -113,9 +314,41 @@ pub struct CatEmojis {
     pub emojis: Vec<String>,
 }
 
+/// Colors for one kind of game-screen button.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ButtonColors {
+    pub normal: Color,
+    pub hovered: Color,
+    pub pressed: Color,
+    pub border: Color,
+}
+
+impl ButtonColors {
+    /// Button colors from a base color, lightened on hover and darkened when pressed.
+    pub fn shaded(normal: Color, border: Color) -> Self {
+        Self {
+            normal,
+            hovered: normal.lighter(0.1),
+            pressed: normal.darker(0.1),
+            border,
+        }
+    }
+
+    /// The background for a button in the given interaction state.
+    pub fn background(&self, interaction: Interaction) -> Color {
+        match interaction {
+            Interaction::Pressed => self.pressed,
+            Interaction::Hovered => self.hovered,
+            Interaction::None => self.normal,
+        }
+    }
+}
+
 /// Theme system for visual customization.
+/// Picked in the settings (see `ThemeKind`) and swapped in by `sync_theme`.
 #[derive(Resource, Clone, Debug)]
 pub struct Theme {
+    pub kind: ThemeKind,
     pub name: String,
     pub primary_color: Color,
     pub secondary_color: Color,
@@ -123,6 +356,17 @@ pub struct Theme {
     pub text_color: Color,
     pub grid_background: Color,
     pub cell_highlight_color: Color,
+    pub cell_border_color: Color,
+    pub given_cell_border_color: Color,
+    pub button_text_color: Color,
+    pub new_game_button: ButtonColors,
+    pub restart_button: ButtonColors,
+    pub clear_button: ButtonColors,
+    pub undo_button: ButtonColors,
+    pub redo_button: ButtonColors,
+    pub hint_button: ButtonColors,
+    /// Colors for the on/off and cycling buttons (candidates, hint detail)
+    pub toggle_button: ButtonColors,
 }
 
 impl Default for Theme {
@@ -132,8 +376,18 @@ impl Default for Theme {
 }
 
 impl Theme {
+    /// The theme for the given choice.
+    pub fn for_kind(kind: ThemeKind) -> Self {
+        match kind {
+            ThemeKind::Classic => Self::classic(),
+            ThemeKind::Dark => Self::dark(),
+            ThemeKind::HighContrast => Self::high_contrast(),
+        }
+    }
+
     pub fn classic() -> Self {
         Self {
+            kind: ThemeKind::Classic,
             name: "Classic".to_string(),
             primary_color: Color::srgb(0.9, 0.9, 0.9),
             secondary_color: Color::srgb(0.8, 0.8, 0.8),
@@ -141,11 +395,57 @@ impl Theme {
             text_color: Color::WHITE,
             grid_background: Color::srgb(0.2, 0.2, 0.2),
             cell_highlight_color: Color::srgb(0.3, 0.7, 1.0),
+            cell_border_color: Color::srgb(0.4, 0.4, 0.4),
+            given_cell_border_color: Color::srgb(0.3, 0.3, 0.3),
+            button_text_color: Color::WHITE,
+            new_game_button: ButtonColors {
+                normal: Color::srgb(0.3, 0.6, 0.3),
+                hovered: Color::srgb(0.4, 0.7, 0.4),
+                pressed: Color::srgb(0.2, 0.4, 0.2),
+                border: Color::srgb(0.4, 0.8, 0.4),
+            },
+            restart_button: ButtonColors {
+                normal: Color::srgb(0.2, 0.5, 0.55),
+                hovered: Color::srgb(0.3, 0.6, 0.65),
+                pressed: Color::srgb(0.15, 0.35, 0.4),
+                border: Color::srgb(0.3, 0.7, 0.75),
+            },
+            clear_button: ButtonColors {
+                normal: Color::srgb(0.6, 0.3, 0.3),
+                hovered: Color::srgb(0.7, 0.4, 0.4),
+                pressed: Color::srgb(0.4, 0.2, 0.2),
+                border: Color::srgb(0.8, 0.4, 0.4),
+            },
+            undo_button: ButtonColors {
+                normal: Color::srgb(0.3, 0.3, 0.6),
+                hovered: Color::srgb(0.4, 0.4, 0.7),
+                pressed: Color::srgb(0.2, 0.2, 0.4),
+                border: Color::srgb(0.4, 0.4, 0.8),
+            },
+            redo_button: ButtonColors {
+                normal: Color::srgb(0.6, 0.3, 0.6),
+                hovered: Color::srgb(0.7, 0.4, 0.7),
+                pressed: Color::srgb(0.4, 0.2, 0.4),
+                border: Color::srgb(0.8, 0.4, 0.8),
+            },
+            hint_button: ButtonColors {
+                normal: Color::srgb(0.7, 0.4, 0.1),
+                hovered: Color::srgb(0.8, 0.5, 0.2),
+                pressed: Color::srgb(0.5, 0.3, 0.1),
+                border: Color::srgb(0.9, 0.6, 0.3),
+            },
+            toggle_button: ButtonColors {
+                normal: Color::srgb(0.3, 0.3, 0.3),
+                hovered: Color::srgb(0.4, 0.4, 0.4),
+                pressed: Color::srgb(0.2, 0.2, 0.2),
+                border: Color::srgb(0.5, 0.5, 0.5),
+            },
         }
     }
 
     pub fn dark() -> Self {
         Self {
+            kind: ThemeKind::Dark,
             name: "Dark".to_string(),
             primary_color: Color::srgb(0.3, 0.3, 0.3),
             secondary_color: Color::srgb(0.2, 0.2, 0.2),
@@ -153,11 +453,22 @@ impl Theme {
             text_color: Color::srgb(0.9, 0.9, 0.9),
             grid_background: Color::srgb(0.1, 0.1, 0.1),
             cell_highlight_color: Color::srgb(0.6, 0.3, 0.1),
+            cell_border_color: Color::srgb(0.15, 0.15, 0.15),
+            given_cell_border_color: Color::srgb(0.05, 0.05, 0.05),
+            button_text_color: Color::srgb(0.9, 0.9, 0.9),
+            new_game_button: ButtonColors::shaded(Color::srgb(0.2, 0.35, 0.2), Color::srgb(0.3, 0.5, 0.3)),
+            restart_button: ButtonColors::shaded(Color::srgb(0.15, 0.3, 0.33), Color::srgb(0.25, 0.45, 0.5)),
+            clear_button: ButtonColors::shaded(Color::srgb(0.4, 0.2, 0.2), Color::srgb(0.55, 0.3, 0.3)),
+            undo_button: ButtonColors::shaded(Color::srgb(0.2, 0.2, 0.4), Color::srgb(0.3, 0.3, 0.55)),
+            redo_button: ButtonColors::shaded(Color::srgb(0.35, 0.2, 0.35), Color::srgb(0.5, 0.3, 0.5)),
+            hint_button: ButtonColors::shaded(Color::srgb(0.45, 0.25, 0.05), Color::srgb(0.6, 0.4, 0.2)),
+            toggle_button: ButtonColors::shaded(Color::srgb(0.2, 0.2, 0.2), Color::srgb(0.35, 0.35, 0.35)),
         }
     }
 
     pub fn high_contrast() -> Self {
         Self {
+            kind: ThemeKind::HighContrast,
             name: "High Contrast".to_string(),
             primary_color: Color::srgb(1.0, 1.0, 1.0),
             secondary_color: Color::srgb(0.8, 0.8, 0.8),
@@ -165,6 +476,82 @@ impl Theme {
             text_color: Color::BLACK,
             grid_background: Color::BLACK,
             cell_highlight_color: Color::srgb(0.0, 0.5, 1.0),
+            cell_border_color: Color::BLACK,
+            given_cell_border_color: Color::BLACK,
+            button_text_color: Color::WHITE,
+            new_game_button: ButtonColors::shaded(Color::srgb(0.0, 0.35, 0.0), Color::srgb(0.0, 1.0, 0.0)),
+            restart_button: ButtonColors::shaded(Color::srgb(0.0, 0.3, 0.35), Color::srgb(0.0, 1.0, 1.0)),
+            clear_button: ButtonColors::shaded(Color::srgb(0.45, 0.0, 0.0), Color::srgb(1.0, 0.3, 0.3)),
+            undo_button: ButtonColors::shaded(Color::srgb(0.0, 0.0, 0.5), Color::srgb(0.4, 0.6, 1.0)),
+            redo_button: ButtonColors::shaded(Color::srgb(0.4, 0.0, 0.4), Color::srgb(1.0, 0.4, 1.0)),
+            hint_button: ButtonColors::shaded(Color::srgb(0.5, 0.25, 0.0), Color::srgb(1.0, 0.7, 0.0)),
+            toggle_button: ButtonColors::shaded(Color::BLACK, Color::WHITE),
+        }
+    }
+
+    /// Border color for a cell that isn't hovered or selected.
+    pub fn cell_border(&self, given: bool) -> Color {
+        if given { self.given_cell_border_color } else { self.cell_border_color }
+    }
+}
+
+/// How the board is laid out on screen.
+///
+/// Portrait rotates the board 90° clockwise so that tall windows (tablets,
+/// phones in the WASM build) get cells that are taller than they are wide.
+/// `Cell` components always hold board coordinates, so input stays correct
+/// regardless of orientation.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BoardOrientation {
+    #[default]
+    Landscape,
+    Portrait,
+}
+
+impl BoardOrientation {
+    /// Pick the orientation that best fits a window of the given size.
+    pub fn for_window(width: f32, height: f32) -> Self {
+        if height > width {
+            Self::Portrait
+        } else {
+            Self::Landscape
+        }
+    }
+
+    /// Map an on-screen slot to the board cell it displays on a board with `size` rows.
+    pub fn slot_to_board(&self, slot_row: usize, slot_col: usize, size: usize) -> (usize, usize) {
+        match self {
+            Self::Landscape => (slot_row, slot_col),
+            // Rotated clockwise: the left column of the board becomes the top row.
+            Self::Portrait => (size - 1 - slot_col, slot_row),
+        }
+    }
+
+    /// Map a board cell to the on-screen slot that displays it on a board with `size` rows.
+    pub fn board_to_slot(&self, row: usize, col: usize, size: usize) -> (usize, usize) {
+        match self {
+            Self::Landscape => (row, col),
+            Self::Portrait => (col, size - 1 - row),
+        }
+    }
+
+    /// Cell size in pixels (width, height) for this orientation on a board
+    /// with `size` rows, drawn at `scale` (see `GridScale`). Smaller boards get
+    /// bigger cells so the grid keeps its footprint.
+    pub fn cell_size(&self, size: usize, scale: f32) -> (f32, f32) {
+        let (long, short) = (675.0 * scale / size as f32, 585.0 * scale / size as f32);
+        match self {
+            Self::Landscape => (long, short),
+            Self::Portrait => (short, long),
+        }
+    }
+
+    /// Grid container size in pixels (width, height) for this orientation,
+    /// drawn at `scale` (see `GridScale`).
+    pub fn grid_size(&self, scale: f32) -> (f32, f32) {
+        match self {
+            Self::Landscape => (720.0 * scale, 630.0 * scale),
+            Self::Portrait => (630.0 * scale, 720.0 * scale),
         }
     }
 }
@@ -173,12 +560,25 @@ impl Theme {
 
 /// Defines the different states of the application flow.
 /// Loading -> Customization -> Ready (gameplay)
+/// Loading -> Profiles -> Customization (with more than one profile)
+/// Customization <-> Profiles
+/// Customization <-> Statistics
+/// Customization <-> Settings
+/// Customization <-> Campaign -> Ready
+/// Customization <-> TrainingGrounds -> Ready
+/// Customization <-> Packs -> Ready
 #[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
 pub enum AppState {
     #[default]
     Loading,
     Customization,
     Ready,
+    Statistics,
+    Settings,
+    Campaign,
+    Packs,
+    Profiles,
+    TrainingGrounds,
 }
 
 // --- Color Constants for Preset Buttons ---
@@ -202,18 +602,33 @@ const PRESET_PRESSED_BORDER: Color = Color::srgb(0.4, 0.7, 0.4);
 
 // --- Helper Functions ---
 
-/// Returns the background color for a cell based on its position and theme
-/// Creates a visual distinction between the 3x3 sudoku boxes
-fn get_cell_background_color(row: usize, col: usize, theme: &Theme) -> Color {
-    let box_row = row / 3;
-    let box_col = col / 3;
+/// Render a cell's candidates as a small block of glyphs shaped like one of
+/// the board's boxes (3x3 on the classic board), with a dot in place of each
+/// eliminated value.
+pub fn format_candidates(candidates: &[usize], grid_size: GridSize, glyphs: GlyphSet) -> String {
+    let (lines, per_line) = (grid_size.box_rows(), grid_size.box_cols());
+    (0..lines)
+        .map(|line| {
+            (0..per_line)
+                .map(|i| {
+                    let value = line * per_line + i;
+                    if candidates.contains(&value) {
+                        glyphs.glyph(value)
+                    } else {
+                        "·".to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
-    // Alternate colors for the 3x3 boxes to make them visually distinct
-    if (box_row + box_col) % 2 == 0 {
-        theme.primary_color
-    } else {
-        theme.secondary_color
-    }
+/// A compact kitty icon for cells too small for the full ASCII art (and for
+/// boards with more cats than there are art pieces).
+pub fn compact_cat(value: usize, glyphs: GlyphSet) -> String {
+    format!("=^.^=\n{}", glyphs.glyph(value))
 }
 
 // --- UI Systems ---
@@ -224,9 +639,14 @@ pub fn setup_theme(mut commands: Commands) {
 }
 
 /// A system that loads the cat ASCII art into the `CatEmojis` resource.
-/// Now using the user's new detailed multi-line ASCII kitten designs!
+/// The built-in cats are used until an art pack is chosen (see `art_packs`).
 pub fn setup_cat_emojis(mut commands: Commands) {
-    let emojis = vec![
+    commands.insert_resource(CatEmojis { emojis: classic_cat_art() });
+}
+
+/// The built-in cats: the user's detailed multi-line ASCII kitten designs.
+pub fn classic_cat_art() -> Vec<String> {
+    vec![
         r"   /\_/\  
   ( o.o ) 
   >  ^  < 
@@ -280,181 +700,161 @@ pub fn setup_cat_emojis(mut commands: Commands) {
   /  |  \ 
  <__*__*__>"
             .to_string(),
-    ];
-    commands.insert_resource(CatEmojis { emojis });
+    ]
 }
 
 /// A system to update the text in the cells when the board state changes. This is the "View".
+/// Cats are drawn by the renderer picked in settings (see `CellRenderer`): as text in as much
+/// detail as the cells have room for (see `CellDetail`), or as sprites.
+/// In auto-candidate mode, empty cells show their remaining candidates as small digits.
+#[allow(clippy::too_many_arguments)]
 pub fn update_cell_text(
     board: Res<BoardState>,
     cat_emojis: Res<CatEmojis>,
+    persistent_data: Res<PersistentData>,
+    detail: Res<CellDetail>,
+    sprites: Res<CatSprites>,
+    images: Res<Assets<Image>>,
     cell_query: Query<(&Cell, &Children)>,
-    mut text_query: Query<(&mut Text, &mut TextColor)>,
+    mut text_query: Query<(&mut Text, &mut TextColor, &mut TextShadow)>,
+    mut sprite_query: Query<(&mut ImageNode, &mut Node), With<CellSprite>>,
 ) {
+    let glyphs = persistent_data.user_settings.glyph_set;
+    let ascii = AsciiRenderer { detail: *detail, cat_emojis: &cat_emojis, glyphs };
+    let sprite_renderer = SpriteRenderer { sprites: &sprites, images: &images, fallback: ascii };
+    let renderer: &dyn CellRenderer = match persistent_data.user_settings.cat_rendering {
+        CatRendering::Ascii => &ascii,
+        CatRendering::Sprites => &sprite_renderer,
+    };
+    let bold_givens = persistent_data.user_settings.given_style.bolds_glyph();
+    let candidates = persistent_data
+        .user_settings
+        .auto_candidates
+        .then(|| board.compute_candidates());
+
     for (cell, children) in &cell_query {
         // Get the first child of the cell, which should be the Text entity.
-        if let Some(text_entity) = children.iter().next() {
-            if let Ok((mut text, mut color)) = text_query.get_mut(text_entity) {
-                let new_text_value = match board.cells[cell.row][cell.col] {
-                    Some(idx) => cat_emojis.emojis[idx].clone(),
-                    None => " ".to_string(), // Empty cells are just blank.
-                };
-
-                // Only update the text if it has actually changed.
-                if text.0 != new_text_value {
-                    text.0 = new_text_value;
+        if let Some(text_entity) = children.iter().next()
+            && let Ok((mut text, mut color, mut shadow)) = text_query.get_mut(text_entity)
+        {
+            let new_text_value = match (board.cells[cell.row][cell.col], &candidates) {
+                (Some(idx), _) => renderer.cat_text(idx),
+                (None, Some(candidates)) => format_candidates(&candidates[cell.row][cell.col], board.grid_size, glyphs),
+                (None, None) => {
+                    // The player's own notes, or else blank
+                    let notes = board.notes_at(cell.row, cell.col);
+                    if notes.is_empty() {
+                        " ".to_string()
+                    } else {
+                        format_candidates(&notes, board.grid_size, glyphs)
+                    }
                 }
+            };
 
-                // Style: Given numbers are much darker and bolder, player numbers are bright blue
-                if board.is_given_cell(cell.row, cell.col) {
-                    // Very dark, almost black text for givens (permanent puzzle numbers)
-                    color.0 = Color::srgb(0.0, 0.0, 0.0);
-                } else {
-                    // Bright blue for player entries (clearly different)
-                    color.0 = Color::srgb(0.1, 0.3, 0.8);
-                }
+            // Only update the text if it has actually changed.
+            if text.0 != new_text_value {
+                text.0 = new_text_value;
+            }
+
+            // Style: Given numbers are much darker and bolder, player numbers are bright blue
+            if board.cells[cell.row][cell.col].is_none() {
+                // Soft grey for candidate pencil marks
+                color.0 = Color::srgb(0.45, 0.45, 0.5);
+            } else if board.is_given_cell(cell.row, cell.col) {
+                // Very dark, almost black text for givens (permanent puzzle numbers)
+                color.0 = Color::srgb(0.0, 0.0, 0.0);
+            } else {
+                // Bright blue for player entries (clearly different)
+                color.0 = Color::srgb(0.1, 0.3, 0.8);
+            }
+
+            // Bold givens: a copy of the glyph drawn a fraction of a pixel to the side
+            let bold = bold_givens && board.is_given_cell(cell.row, cell.col);
+            let (offset, shadow_color) = if bold { (BOLD_GLYPH_OFFSET, color.0) } else { (Vec2::ZERO, Color::NONE) };
+            if shadow.offset != offset || shadow.color != shadow_color {
+                *shadow = TextShadow { offset, color: shadow_color };
             }
         }
-    }
-}
 
-/// A system to update cell colors based on Sudoku validation.
-///
-/// This provides visual feedback by:
-/// - Highlighting conflicting cells in red
-/// - Highlighting the entire board in green when completed
-/// - Using themed colors for normal cells
-pub fn update_cell_colors(
-    board: Res<BoardState>,
-    game_state: Res<GameState>,
-    theme: Res<Theme>,
-    mut cell_query: Query<(&Cell, &mut BackgroundColor)>,
-) {
-    let conflicts = board.get_conflicts();
-    let conflict_set: HashSet<(usize, usize)> = conflicts.into_iter().collect();
-    let is_complete = matches!(*game_state, GameState::Won);
-
-    for (cell, mut bg_color) in &mut cell_query {
-        let base_color = get_cell_background_color(cell.row, cell.col, &theme);
-
-        if is_complete {
-            // Green tint for completion - celebrate!
-            *bg_color = BackgroundColor(Color::srgb(0.6, 0.9, 0.6));
-        } else if conflict_set.contains(&(cell.row, cell.col)) {
-            // Red tint for conflicts - show mistakes
-            *bg_color = BackgroundColor(Color::srgb(1.0, 0.7, 0.7));
-        } else if board.is_given_cell(cell.row, cell.col) {
-            // Slightly darker/more solid background for given cells (permanent puzzle numbers)
-            // Convert to linear space, darken, then back to sRGB
-            let [r, g, b, a] = base_color.to_linear().to_f32_array();
-            let darker_base = Color::linear_rgba(
-                r * 0.7, // Make significantly darker (30% of original)
-                g * 0.7,
-                b * 0.7,
-                a,
-            );
-            *bg_color = BackgroundColor(darker_base);
-        } else {
-            // Normal alternating colors for player-fillable cells
-            *bg_color = BackgroundColor(base_color);
+        let image = board.cells[cell.row][cell.col].and_then(|idx| renderer.cat_image(idx));
+        for child in children.iter() {
+            if let Ok((mut image_node, mut node)) = sprite_query.get_mut(child) {
+                set_cell_sprite(&mut image_node, &mut node, image.clone(), board.is_given_cell(cell.row, cell.col));
+            }
         }
     }
 }
 
 /// System to add hover effects to buttons for better user feedback.
+/// Colors come from the theme, and are all redone when the theme changes.
+#[allow(clippy::type_complexity)]
 pub fn update_button_colors(
-    mut new_game_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (With<NewGameButton>, Changed<Interaction>),
-    >,
-    mut clear_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (
-            With<ClearButton>,
-            Changed<Interaction>,
-            Without<NewGameButton>,
-        ),
-    >,
-    mut undo_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (
-            With<UndoButton>,
-            Changed<Interaction>,
-            Without<NewGameButton>,
-            Without<ClearButton>,
-        ),
-    >,
-    mut redo_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (
-            With<RedoButton>,
-            Changed<Interaction>,
-            Without<NewGameButton>,
-            Without<ClearButton>,
-            Without<UndoButton>,
-        ),
-    >,
-    mut hint_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (
-            With<HintButton>,
-            Changed<Interaction>,
-            Without<NewGameButton>,
-            Without<ClearButton>,
-            Without<UndoButton>,
-            Without<RedoButton>,
-        ),
-    >,
+    theme: Res<Theme>,
+    mut button_query: Query<(
+        Ref<Interaction>,
+        &mut BackgroundColor,
+        &mut BorderColor,
+        &Children,
+        AnyOf<(
+            &NewGameButton,
+            &RestartButton,
+            &ClearButton,
+            &UndoButton,
+            &RedoButton,
+            &HintButton,
+            &CandidatesButton,
+            &HintVerbosityButton,
+            &UndoToCorrectButton,
+        )>,
+    )>,
+    mut text_query: Query<&mut TextColor>,
 ) {
-    // Handle New Game button (green theme)
-    for (interaction, mut bg_color) in &mut new_game_query {
-        match interaction {
-            Interaction::Pressed => bg_color.0 = Color::srgb(0.2, 0.4, 0.2),
-            Interaction::Hovered => bg_color.0 = Color::srgb(0.4, 0.7, 0.4),
-            Interaction::None => bg_color.0 = Color::srgb(0.3, 0.6, 0.3),
-        }
-    }
-
-    // Handle Clear button (red theme)
-    for (interaction, mut bg_color) in &mut clear_query {
-        match interaction {
-            Interaction::Pressed => bg_color.0 = Color::srgb(0.4, 0.2, 0.2),
-            Interaction::Hovered => bg_color.0 = Color::srgb(0.7, 0.4, 0.4),
-            Interaction::None => bg_color.0 = Color::srgb(0.6, 0.3, 0.3),
+    for (interaction, mut bg_color, mut border_color, children, kind) in &mut button_query {
+        if !interaction.is_changed() && !theme.is_changed() {
+            continue;
         }
-    }
-
-    // Handle Undo button (blue theme)
-    for (interaction, mut bg_color) in &mut undo_query {
-        match interaction {
-            Interaction::Pressed => bg_color.0 = Color::srgb(0.2, 0.2, 0.4),
-            Interaction::Hovered => bg_color.0 = Color::srgb(0.4, 0.4, 0.7),
-            Interaction::None => bg_color.0 = Color::srgb(0.3, 0.3, 0.6),
+        let colors = match kind {
+            (Some(_), ..) => theme.new_game_button,
+            (_, Some(_), ..) => theme.restart_button,
+            (_, _, Some(_), ..) => theme.clear_button,
+            (_, _, _, Some(_), ..) | (.., Some(_)) => theme.undo_button,
+            (_, _, _, _, Some(_), ..) => theme.redo_button,
+            (_, _, _, _, _, Some(_), ..) => theme.hint_button,
+            _ => theme.toggle_button,
+        };
+        bg_color.0 = colors.background(*interaction);
+
+        if theme.is_changed() {
+            border_color.0 = colors.border;
+            for child in children.iter() {
+                if let Ok(mut text_color) = text_query.get_mut(child) {
+                    text_color.0 = theme.button_text_color;
+                }
+            }
         }
     }
+}
 
-    // Handle Redo button (purple theme)
-    for (interaction, mut bg_color) in &mut redo_query {
-        match interaction {
-            Interaction::Pressed => bg_color.0 = Color::srgb(0.4, 0.2, 0.4),
-            Interaction::Hovered => bg_color.0 = Color::srgb(0.7, 0.4, 0.7),
-            Interaction::None => bg_color.0 = Color::srgb(0.6, 0.3, 0.6),
-        }
+/// System that recolors the grid and the cell borders when the theme
+/// changes (cell backgrounds are handled by `update_cell_colors`).
+pub fn apply_theme_to_grid(
+    theme: Res<Theme>,
+    board: Res<BoardState>,
+    mut grid_query: Query<&mut BackgroundColor, With<GridContainer>>,
+    mut cell_query: Query<(&Cell, &mut BorderColor)>,
+) {
+    for mut bg_color in &mut grid_query {
+        bg_color.0 = theme.grid_background;
     }
-
-    // Handle Hint button (orange theme)
-    for (interaction, mut bg_color) in &mut hint_query {
-        match interaction {
-            Interaction::Pressed => bg_color.0 = Color::srgb(0.5, 0.3, 0.1),
-            Interaction::Hovered => bg_color.0 = Color::srgb(0.8, 0.5, 0.2),
-            Interaction::None => bg_color.0 = Color::srgb(0.7, 0.4, 0.1),
-        }
+    for (cell, mut border_color) in &mut cell_query {
+        border_color.0 = theme.cell_border(board.is_given_cell(cell.row, cell.col));
     }
 }
 
 /// System to update the timer display with current elapsed time.
 pub fn update_timer_display(
     session: Res<GameSession>,
+    locale: Res<Locale>,
     mut timer_query: Query<&mut Text, With<TimerDisplay>>,
 ) {
     if session.is_changed() {
@@ -462,19 +862,49 @@ pub fn update_timer_display(
             let elapsed = session.current_elapsed();
             let minutes = elapsed.as_secs() / 60;
             let seconds = elapsed.as_secs() % 60;
-            text.0 = format!("Time: {:02}:{:02}", minutes, seconds);
+            let paused = if session.is_paused { " ⏸" } else { "" };
+            let time = format!("{:02}:{:02}{}", minutes, seconds, paused);
+            text.0 = locale.format("game.time", &[("time", &time)]);
         }
     }
 }
 
+/// System to update the score display.
+pub fn update_score_display(
+    score: Res<ScoreTracker>,
+    locale: Res<Locale>,
+    mut score_query: Query<&mut Text, With<ScoreDisplay>>,
+) {
+    for mut text in &mut score_query {
+        text.0 = locale.format("game.score", &[("score", &score.score().to_string())]);
+    }
+}
+
+/// System to update the line on how the puzzle was generated, for when a
+/// new puzzle starts without leaving the game screen (or is on its way).
+pub fn update_generation_info_display(
+    last_generation: Res<LastGeneration>,
+    generation: Option<Res<PuzzleGeneration>>,
+    mut info_query: Query<&mut Text, With<GenerationInfoDisplay>>,
+) {
+    for mut text in &mut info_query {
+        text.0 = if generation.is_some() {
+            "⏳ Generating a new puzzle...".to_string()
+        } else {
+            last_generation.info_line()
+        };
+    }
+}
+
 /// System to update the move counter display.
 pub fn update_move_counter_display(
     session: Res<GameSession>,
+    locale: Res<Locale>,
     mut counter_query: Query<&mut Text, With<MoveCounterDisplay>>,
 ) {
     if session.is_changed() {
         for mut text in &mut counter_query {
-            text.0 = format!("Moves: {}", session.move_count);
+            text.0 = locale.format("game.moves", &[("count", &session.move_count.to_string())]);
         }
     }
 }
@@ -483,6 +913,7 @@ pub fn update_move_counter_display(
 pub fn update_hint_button_text(
     hint_system: Res<HintSystem>,
     debug_mode: Res<DebugMode>,
+    locale: Res<Locale>,
     hint_query: Query<&Children, With<HintButton>>,
     mut text_query: Query<&mut Text>,
 ) {
@@ -492,7 +923,11 @@ pub fn update_hint_button_text(
             // Find the text child of the hint button
             for child in children.iter() {
                 if let Ok(mut text) = text_query.get_mut(child) {
-                    text.0 = hint_system.get_hint_button_text(&debug_mode);
+                    text.0 = if debug_mode.unlimited_hints {
+                        locale.get("game.hint_debug")
+                    } else {
+                        locale.format("game.hints_left", &[("count", &hint_system.hints_remaining.to_string())])
+                    };
                     break; // Found the text, no need to continue
                 }
             }
@@ -500,17 +935,112 @@ pub fn update_hint_button_text(
     }
 }
 
+/// The tip on how to turn on debug mode, naming its shortcut.
+fn debug_tip(locale: &Locale, persistent_data: &PersistentData) -> String {
+    let key = persistent_data.user_settings.key_bindings.label(ShortcutAction::ToggleDebug);
+    locale.format("game.debug_tip", &[("key", &key)])
+}
+
 /// System to update the debug status display.
 pub fn update_debug_status_display(
     debug_mode: Res<DebugMode>,
+    locale: Res<Locale>,
+    persistent_data: Res<PersistentData>,
     mut debug_query: Query<&mut Text, With<DebugStatusDisplay>>,
 ) {
     if debug_mode.is_changed() {
         for mut text in &mut debug_query {
             if debug_mode.enabled && debug_mode.unlimited_hints {
-                text.0 = "🐛 DEBUG MODE: Unlimited Hints".to_string();
+                text.0 = locale.get("game.debug_on");
             } else {
-                text.0 = "Press ⌘D (Mac) or Ctrl+D (PC) for debug mode".to_string();
+                text.0 = debug_tip(&locale, &persistent_data);
+            }
+        }
+    }
+}
+
+/// System to show the latest hint explanation at the player's chosen verbosity
+/// (or just where to look, while the hint is still a nudge).
+pub fn update_hint_explanation_display(
+    last_hint: Res<LastHint>,
+    board: Res<BoardState>,
+    persistent_data: Res<PersistentData>,
+    mut explanation_query: Query<&mut Text, With<HintExplanationDisplay>>,
+    verbosity_button_query: Query<&Children, With<HintVerbosityButton>>,
+    mut text_query: Query<&mut Text, Without<HintExplanationDisplay>>,
+) {
+    let verbosity = persistent_data.user_settings.hint_verbosity;
+    let glyphs = persistent_data.user_settings.glyph_set;
+
+    for mut text in &mut explanation_query {
+        text.0 = last_hint
+            .explanation
+            .as_ref()
+            .map(|explanation| match last_hint.tier {
+                HintTier::Nudge => format!("💡 {}", explanation.render_nudge(&board)),
+                HintTier::Reveal => explanation.render(verbosity, glyphs),
+            })
+            .unwrap_or_default();
+    }
+
+    for children in &verbosity_button_query {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = format!("Detail: {}", verbosity.display_name());
+                break;
+            }
+        }
+    }
+}
+
+/// System to cycle the hint explanation verbosity when its button is pressed.
+/// The choice is saved to the user's settings.
+pub fn handle_hint_verbosity_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<HintVerbosityButton>)>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let settings = &mut persistent_data.user_settings;
+            settings.hint_verbosity = settings.hint_verbosity.next();
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+        }
+    }
+}
+
+/// System to toggle auto-candidate mode when its button is pressed.
+/// The choice is saved to the user's settings.
+pub fn handle_candidates_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<CandidatesButton>)>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let settings = &mut persistent_data.user_settings;
+            settings.auto_candidates = !settings.auto_candidates;
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+        }
+    }
+}
+
+/// System to keep the auto-candidate button label in sync with the setting.
+pub fn update_candidates_button_text(
+    persistent_data: Res<PersistentData>,
+    button_query: Query<&Children, With<CandidatesButton>>,
+    mut text_query: Query<&mut Text>,
+) {
+    let state = if persistent_data.user_settings.auto_candidates { "On" } else { "Off" };
+    for children in &button_query {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = format!("✏️ Candidates: {}", state);
+                break;
             }
         }
     }
@@ -520,6 +1050,7 @@ pub fn update_debug_status_display(
 pub fn tick_timer_display(
     _time: Res<Time>,
     session: Res<GameSession>,
+    locale: Res<Locale>,
     mut timer_query: Query<&mut Text, With<TimerDisplay>>,
 ) {
     // Update every frame to show live timer
@@ -528,80 +1059,33 @@ pub fn tick_timer_display(
             let elapsed = session.current_elapsed();
             let minutes = elapsed.as_secs() / 60;
             let seconds = elapsed.as_secs() % 60;
-            text.0 = format!("Time: {:02}:{:02}", minutes, seconds);
+            let time = format!("{:02}:{:02}", minutes, seconds);
+            text.0 = locale.format("game.time", &[("time", &time)]);
         }
     }
 }
 
-/// System to add advanced hover effects with row/column/box highlighting.
+/// System to outline the hovered cell (backgrounds, including the row,
+/// column, and box highlighting, are handled by `update_cell_colors`).
+#[allow(clippy::type_complexity)]
 pub fn update_cell_hover_effects(
     board: Res<BoardState>,
     theme: Res<Theme>,
-    mut cell_query: Query<
-        (&Cell, &Interaction, &mut BorderColor, &mut BackgroundColor),
-        (With<Button>, Changed<Interaction>),
-    >,
-    mut all_cells_query: Query<(&Cell, &mut BackgroundColor), Without<Button>>,
+    mut cell_query: Query<(&Cell, &Interaction, &mut BorderColor), (With<Button>, Changed<Interaction>)>,
 ) {
-    // Find the currently hovered cell
-    let mut hovered_cell: Option<(usize, usize)> = None;
-    
-    for (cell, interaction, mut border_color, mut bg_color) in &mut cell_query {
+    for (cell, interaction, mut border_color) in &mut cell_query {
         match interaction {
             Interaction::Hovered => {
-                hovered_cell = Some((cell.row, cell.col));
-                
                 // Enhanced border for hovered cell
                 if !board.is_given_cell(cell.row, cell.col) {
                     border_color.0 = theme.cell_highlight_color; // Theme-based hover color
-                    // Slightly brighten the hovered cell itself
-                    let base_color = get_cell_background_color(cell.row, cell.col, &theme);
-                    let [r, g, b, a] = base_color.to_linear().to_f32_array();
-                    *bg_color = BackgroundColor(Color::linear_rgba(r * 1.2, g * 1.2, b * 1.2, a));
                 } else {
                     border_color.0 = Color::srgb(0.6, 0.6, 0.6); // Darker border to show it's not interactive
                 }
             }
-            Interaction::None => {
-                // Reset to normal colors
-                if board.is_given_cell(cell.row, cell.col) {
-                    border_color.0 = Color::srgb(0.3, 0.3, 0.3); // Darker borders for given cells
-                } else {
-                    border_color.0 = Color::srgb(0.4, 0.4, 0.4); // Normal border for player cells
-                }
-                *bg_color = BackgroundColor(get_cell_background_color(cell.row, cell.col, &theme));
-            }
-            Interaction::Pressed => {
-                // Keep normal styling during press
-                if board.is_given_cell(cell.row, cell.col) {
-                    border_color.0 = Color::srgb(0.3, 0.3, 0.3);
-                } else {
-                    border_color.0 = Color::srgb(0.4, 0.4, 0.4);
-                }
-            }
-        }
-    }
-    
-    // Apply subtle highlighting to related cells (same row, column, or box)
-    if let Some((hovered_row, hovered_col)) = hovered_cell {
-        let hovered_box_row = hovered_row / 3;
-        let hovered_box_col = hovered_col / 3;
-        
-        for (cell, mut bg_color) in &mut all_cells_query {
-            let is_same_row = cell.row == hovered_row;
-            let is_same_col = cell.col == hovered_col;
-            let is_same_box = (cell.row / 3 == hovered_box_row) && (cell.col / 3 == hovered_box_col);
-            
-            if is_same_row || is_same_col || is_same_box {
-                // Subtle highlight for related cells
-                let base_color = get_cell_background_color(cell.row, cell.col, &theme);
-                let [r, g, b, a] = base_color.to_linear().to_f32_array();
-                *bg_color = BackgroundColor(Color::linear_rgba(
-                    r + 0.05, // Slight brightening
-                    g + 0.05,
-                    b + 0.05,
-                    a,
-                ));
+            Interaction::None | Interaction::Pressed => {
+                // Normal styling (givens get darker borders), kept during press
+                border_color.0 = theme.cell_border(board.is_given_cell(cell.row, cell.col));
             }
         }
     }
@@ -647,7 +1131,16 @@ pub fn setup_camera(mut commands: Commands) {
 }
 
 /// System that creates the customization screen UI.
-pub fn setup_customization_screen(mut commands: Commands) {
+pub fn setup_customization_screen(
+    mut commands: Commands,
+    selected_grid_size: Res<SelectedGridSize>,
+    selected_variant: Res<SelectedVariant>,
+    persistent_data: Res<PersistentData>,
+    roster: Res<ProfileRoster>,
+    locale: Res<Locale>,
+) {
+    commands.insert_resource(PendingConfirmation::default());
+
     // Create the main customization UI
     commands
         .spawn((
@@ -663,9 +1156,41 @@ pub fn setup_customization_screen(mut commands: Commands) {
             CustomizationScreenRoot, // Tag for cleanup
         ))
         .with_children(|parent| {
+            // Who's playing, in the corner (opens the profile screen)
+            parent
+                .spawn((
+                    Button,
+                    ProfilesButton,
+                    Focusable::Profiles,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(16.0),
+                        right: Val::Px(16.0),
+                        min_width: Val::Px(120.0),
+                        height: Val::Px(36.0),
+                        padding: UiRect::horizontal(Val::Px(10.0)),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                    BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        Text::new(profile_button_label(&roster)),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
             // Game Title
             parent.spawn((
-                Text::new("Nine Lives: Cat Sudoku"),
+                locale.text("menu.title"),
                 TextFont {
                     font_size: 36.0,
                     ..default()
@@ -679,7 +1204,7 @@ pub fn setup_customization_screen(mut commands: Commands) {
             
             // Subtitle
             parent.spawn((
-                Text::new("Choose your purrfect puzzle difficulty"),
+                locale.text("menu.subtitle"),
                 TextFont {
                     font_size: 18.0,
                     ..default()
@@ -711,6 +1236,7 @@ pub fn setup_customization_screen(mut commands: Commands) {
                             .spawn((
                                 Button,
                                 PresetButton { preset_id: index },
+                                Focusable::Preset(index),
                                 Node {
                                     width: Val::Px(280.0),
                                     height: Val::Px(120.0),
@@ -727,7 +1253,7 @@ pub fn setup_customization_screen(mut commands: Commands) {
                             .with_children(|button_parent| {
                                 // Preset name
                                 button_parent.spawn((
-                                    Text::new(preset.display_name()),
+                                    Text::new(locale.preset_name(*preset)),
                                     TextFont {
                                         font_size: 16.0,
                                         ..default()
@@ -741,7 +1267,7 @@ pub fn setup_customization_screen(mut commands: Commands) {
                                 
                                 // Preset description
                                 button_parent.spawn((
-                                    Text::new(preset.description()),
+                                    Text::new(locale.preset_description(*preset)),
                                     TextFont {
                                         font_size: 12.0,
                                         ..default()
@@ -750,6 +1276,7 @@ pub fn setup_customization_screen(mut commands: Commands) {
                                     Node {
                                         ..default()
                                     },
+                                    TextFit::new(246.0, 4, 12.0),
                                 ));
                             });
                     }
@@ -768,52 +1295,507 @@ pub fn setup_customization_screen(mut commands: Commands) {
                     max_width: Val::Px(500.0),
                     ..default()
                 },
+                TextFit::new(500.0, 3, 14.0),
                 SettingsSummary,
             ));
             
-            // Start Game button
+            // Board options row: rule set, board size, cat number glyphs, given style, double-click filling, and input model
             parent
-                .spawn((
-                    Button,
-                    StartGameButton,
-                    Node {
-                        width: Val::Px(200.0),
-                        height: Val::Px(50.0),
-                        align_items: AlignItems::Center,
-                        justify_content: JustifyContent::Center,
-                        border: UiRect::all(Val::Px(3.0)),
-                        ..default()
-                    },
-                    BackgroundColor(Color::srgb(0.2, 0.7, 0.2)),
-                    BorderColor(Color::srgb(0.3, 0.8, 0.3)),
-                ))
-                .with_children(|button_parent| {
-                    button_parent.spawn((
-                        Text::new("🎯 Start Game"),
-                        TextFont {
-                            font_size: 18.0,
-                            ..default()
-                        },
-                        TextColor(Color::WHITE),
-                    ));
-                });
-        });
-    
-    println!("Nine Lives Cat Sudoku customization screen initialized!");
-}
-
-/// System to clean up the customization screen when exiting that state.
-pub fn cleanup_customization_screen(
+                .spawn(Node {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(20.0),
+                    row_gap: Val::Px(10.0),
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                })
+                .with_children(|options_row| {
+                    // Rule set toggle (switches every preset to the Killer family and back)
+                    options_row
+                        .spawn((
+                            Button,
+                            VariantButton,
+                            Focusable::Variant,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                Text::new(variant_label(selected_variant.variant)),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    // Board size toggle
+                    options_row
+                        .spawn((
+                            Button,
+                            GridSizeButton,
+                            Focusable::GridSize,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                Text::new(grid_size_label(selected_grid_size.grid_size)),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    // Glyph set toggle
+                    options_row
+                        .spawn((
+                            Button,
+                            GlyphSetButton,
+                            Focusable::GlyphSet,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                Text::new(glyph_set_label(persistent_data.user_settings.glyph_set)),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    // Given cell style toggle
+                    options_row
+                        .spawn((
+                            Button,
+                            GivenStyleButton,
+                            Focusable::GivenStyle,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                Text::new(given_style_label(persistent_data.user_settings.given_style)),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    // Double-click filling toggle
+                    options_row
+                        .spawn((
+                            Button,
+                            DoubleClickFillButton,
+                            Focusable::DoubleClickFill,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                Text::new(double_click_fill_label(persistent_data.user_settings.double_click_fill)),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    // Nine lives toggle
+                    options_row
+                        .spawn((
+                            Button,
+                            NineLivesButton,
+                            Focusable::NineLives,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                Text::new(nine_lives_label(persistent_data.user_settings.nine_lives)),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    // Pause while the window is in the background
+                    options_row
+                        .spawn((
+                            Button,
+                            PauseWhenAwayButton,
+                            Focusable::PauseWhenAway,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                Text::new(pause_when_away_label(persistent_data.user_settings.pause_when_away)),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    // Zen mode (no clock, counters, or penalties)
+                    options_row
+                        .spawn((
+                            Button,
+                            GameModeButton,
+                            Focusable::GameMode,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                Text::new(game_mode_label(persistent_data.user_settings.game_mode)),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                });
+
+            // Continue button, when there's an unfinished game to pick back up
+            if let Some(save) = &persistent_data.current_save {
+                spawn_continue_button(parent, save);
+            }
+
+            // Start Game button
+            parent
+                .spawn((
+                    Button,
+                    StartGameButton,
+                    Focusable::StartGame,
+                    Node {
+                        width: Val::Px(200.0),
+                        height: Val::Px(50.0),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(3.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.7, 0.2)),
+                    BorderColor(Color::srgb(0.3, 0.8, 0.3)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        locale.text("menu.start"),
+                        StartGameLabel,
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
+            // Campaign, packs, time attack, tutorial, training, statistics, and settings buttons
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(10.0),
+                    margin: UiRect::top(Val::Px(15.0)),
+                    ..default()
+                })
+                .with_children(|nav_row| {
+                    nav_row
+                        .spawn((
+                            Button,
+                            CampaignButton,
+                            Focusable::Campaign,
+                            Node {
+                                width: Val::Px(120.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                            BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                locale.text("menu.campaign"),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    nav_row
+                        .spawn((
+                            Button,
+                            PacksButton,
+                            Focusable::PuzzlePacks,
+                            Node {
+                                width: Val::Px(120.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                            BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                locale.text("menu.packs"),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    nav_row
+                        .spawn((
+                            Button,
+                            TimeAttackButton,
+                            Focusable::TimeAttack,
+                            Node {
+                                width: Val::Px(120.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                            BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                locale.text("menu.time_attack"),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    nav_row
+                        .spawn((
+                            Button,
+                            TutorialButton,
+                            Focusable::Tutorial,
+                            Node {
+                                width: Val::Px(120.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                            BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                locale.text("menu.tutorial"),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    nav_row
+                        .spawn((
+                            Button,
+                            TrainingButton,
+                            Focusable::TrainingGrounds,
+                            Node {
+                                width: Val::Px(120.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                            BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                locale.text("menu.training"),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    nav_row
+                        .spawn((
+                            Button,
+                            StatisticsButton,
+                            Focusable::Statistics,
+                            Node {
+                                width: Val::Px(120.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                            BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                locale.text("menu.statistics"),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+
+                    nav_row
+                        .spawn((
+                            Button,
+                            SettingsButton,
+                            Focusable::Settings,
+                            Node {
+                                width: Val::Px(120.0),
+                                height: Val::Px(40.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                            BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                locale.text("menu.settings"),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+                });
+
+            // Enter a friend's share code
+            spawn_share_code_entry(parent);
+
+            // Confirmation dialog for discarding the saved game (shown on request)
+            spawn_confirm_dialog(parent);
+        });
+    
+    debug!("Nine Lives Cat Sudoku customization screen initialized!");
+}
+
+/// System to clean up the customization screen when exiting that state.
+pub fn cleanup_customization_screen(
     mut commands: Commands,
     query: Query<Entity, With<CustomizationScreenRoot>>,
 ) {
     for entity in &query {
         commands.entity(entity).despawn();
     }
-    println!("Cleaned up customization screen");
+    debug!("Cleaned up customization screen");
 }
 
-/// System to clean up the game screen when exiting that state.
+/// System to clean up the game screen when exiting that state. Leaving the
+/// game screen ends the puzzle, so the next one starts with every life, no
+/// score, and no streak (a continued game puts back what its save kept; see
+/// `ContinueGameStart`).
 pub fn cleanup_game_screen(
     mut commands: Commands,
     query: Query<Entity, With<GameScreenRoot>>,
@@ -821,7 +1803,10 @@ pub fn cleanup_game_screen(
     for entity in &query {
         commands.entity(entity).despawn();
     }
-    println!("Cleaned up game screen");
+    commands.insert_resource(Lives::default());
+    commands.insert_resource(ScoreTracker::default());
+    commands.insert_resource(StreakTracker::default());
+    debug!("Cleaned up game screen");
 }
 
 /// System to handle preset button interactions and update the selected preset.
@@ -838,7 +1823,7 @@ pub fn handle_preset_selection(
                 let presets = PresetKind::all();
                 if let Some(new_preset) = presets.get(preset_button.preset_id) {
                     selected_preset.preset = *new_preset;
-                    println!("Selected preset: {:?}", new_preset);
+                    debug!("Selected preset: {:?}", new_preset);
                 }
                 
                 // Visual feedback - pressed state only
@@ -848,11 +1833,11 @@ pub fn handle_preset_selection(
             Interaction::Hovered => {
                 // Only apply hover if this button is not currently selected
                 let presets = PresetKind::all();
-                if let Some(preset) = presets.get(preset_button.preset_id) {
-                    if *preset != selected_preset.preset {
-                        *bg_color = BackgroundColor(PRESET_HOVER_BG);
-                        *border_color = BorderColor(PRESET_HOVER_BORDER);
-                    }
+                if let Some(preset) = presets.get(preset_button.preset_id)
+                    && *preset != selected_preset.preset
+                {
+                    *bg_color = BackgroundColor(PRESET_HOVER_BG);
+                    *border_color = BorderColor(PRESET_HOVER_BORDER);
                 }
             }
             Interaction::None => {
@@ -863,13 +1848,270 @@ pub fn handle_preset_selection(
     }
 }
 
-/// System to update the settings summary when the selected preset changes.
+/// Label for the board size button on the customization screen.
+pub fn grid_size_label(grid_size: GridSize) -> String {
+    format!("Board: {}", grid_size.display_name())
+}
+
+/// System to cycle the board size on the customization screen.
+pub fn handle_grid_size_button(
+    interaction_query: ChangedButtons<GridSizeButton>,
+    mut text_query: Query<&mut Text>,
+    mut selected_grid_size: ResMut<SelectedGridSize>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            selected_grid_size.grid_size = selected_grid_size.grid_size.next();
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(child) {
+                    text.0 = grid_size_label(selected_grid_size.grid_size);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Label for the rule set button on the customization screen.
+pub fn variant_label(variant: Variant) -> String {
+    format!("Rules: {}", variant.display_name())
+}
+
+/// System to cycle the rule set on the customization screen.
+pub fn handle_variant_button(
+    interaction_query: ChangedButtons<VariantButton>,
+    mut text_query: Query<&mut Text>,
+    mut selected_variant: ResMut<SelectedVariant>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            selected_variant.variant = selected_variant.variant.next();
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(child) {
+                    text.0 = variant_label(selected_variant.variant);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Label for the glyph set button on the customization screen.
+pub fn glyph_set_label(glyph_set: GlyphSet) -> String {
+    format!("Cat numbers: {}", glyph_set.display_name())
+}
+
+/// System to cycle the glyph set used for cat numbers.
+/// The choice is saved to the user's settings.
+pub fn handle_glyph_set_button(
+    interaction_query: ChangedButtons<GlyphSetButton>,
+    mut text_query: Query<&mut Text>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let glyph_set = persistent_data.user_settings.glyph_set.next();
+            persistent_data.user_settings.glyph_set = glyph_set;
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(child) {
+                    text.0 = glyph_set_label(glyph_set);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Label for the given style button on the customization screen.
+pub fn given_style_label(given_style: GivenStyle) -> String {
+    format!("Givens: {}", given_style.display_name())
+}
+
+/// System to cycle how given cells are styled.
+/// The choice is saved to the user's settings.
+pub fn handle_given_style_button(
+    interaction_query: ChangedButtons<GivenStyleButton>,
+    mut text_query: Query<&mut Text>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let given_style = persistent_data.user_settings.given_style.next();
+            persistent_data.user_settings.given_style = given_style;
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(child) {
+                    text.0 = given_style_label(given_style);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Label for the double-click filling button on the customization screen.
+pub fn double_click_fill_label(enabled: bool) -> String {
+    format!("Double-click fill: {}", if enabled { "On" } else { "Off" })
+}
+
+/// System to turn double-click filling of single-candidate cells on or off.
+/// The choice is saved to the user's settings.
+pub fn handle_double_click_fill_button(
+    interaction_query: ChangedButtons<DoubleClickFillButton>,
+    mut text_query: Query<&mut Text>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let enabled = !persistent_data.user_settings.double_click_fill;
+            persistent_data.user_settings.double_click_fill = enabled;
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(child) {
+                    text.0 = double_click_fill_label(enabled);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Label for the nine lives button on the customization screen.
+pub fn nine_lives_label(enabled: bool) -> String {
+    format!("Nine lives: {}", if enabled { "On" } else { "Off" })
+}
+
+/// System to turn nine lives on or off.
+/// The choice is saved to the user's settings.
+pub fn handle_nine_lives_button(
+    interaction_query: ChangedButtons<NineLivesButton>,
+    mut text_query: Query<&mut Text>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let enabled = !persistent_data.user_settings.nine_lives;
+            persistent_data.user_settings.nine_lives = enabled;
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(child) {
+                    text.0 = nine_lives_label(enabled);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Label for the pause-while-away button on the customization screen.
+pub fn pause_when_away_label(enabled: bool) -> String {
+    format!("Pause when away: {}", if enabled { "On" } else { "Off" })
+}
+
+/// System to turn pausing the clock while the window is in the background
+/// on or off. The choice is saved to the user's settings.
+pub fn handle_pause_when_away_button(
+    interaction_query: ChangedButtons<PauseWhenAwayButton>,
+    mut text_query: Query<&mut Text>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let enabled = !persistent_data.user_settings.pause_when_away;
+            persistent_data.user_settings.pause_when_away = enabled;
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(child) {
+                    text.0 = pause_when_away_label(enabled);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Label for the Zen mode button on the customization screen.
+pub fn game_mode_label(mode: GameMode) -> String {
+    format!("🍃 Zen mode: {}", if mode == GameMode::Zen { "On" } else { "Off" })
+}
+
+/// System to turn Zen mode on or off.
+/// The choice is saved to the user's settings.
+pub fn handle_game_mode_button(
+    interaction_query: ChangedButtons<GameModeButton>,
+    mut text_query: Query<&mut Text>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let mode = persistent_data.user_settings.game_mode.next();
+            persistent_data.user_settings.game_mode = mode;
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(child) {
+                    text.0 = game_mode_label(mode);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// System that keeps the `GameMode` resource in step with the mode picked
+/// in the settings.
+pub fn sync_game_mode(persistent_data: Res<PersistentData>, mut game_mode: ResMut<GameMode>) {
+    let mode = persistent_data.user_settings.game_mode;
+    if *game_mode != mode {
+        *game_mode = mode;
+        info!("🍃 Game mode: {}", mode.display_name());
+    }
+}
+
+/// System that swaps in the theme picked in the settings, whenever it changes.
+pub fn sync_theme(persistent_data: Res<PersistentData>, mut theme: ResMut<Theme>) {
+    let kind = persistent_data.user_settings.theme;
+    if theme.kind != kind {
+        *theme = Theme::for_kind(kind);
+        info!("🎨 Theme changed to {}", kind.display_name());
+    }
+}
+
+/// System to update the settings summary when the selected preset, board size, or rules change.
 pub fn update_settings_summary(
     selected_preset: Res<SelectedPreset>,
+    selected_grid_size: Res<SelectedGridSize>,
+    selected_variant: Res<SelectedVariant>,
     mut summary_query: Query<&mut Text, With<SettingsSummary>>,
 ) {
-    if selected_preset.is_changed() {
-        let settings = PuzzleSettings::from_preset(selected_preset.preset);
+    if selected_preset.is_changed() || selected_grid_size.is_changed() || selected_variant.is_changed() {
+        let settings = PuzzleSettings::from_preset(selected_preset.preset)
+            .with_grid_size(selected_grid_size.grid_size)
+            .with_variant(selected_variant.variant);
         let summary_text = settings.description();
         
         for mut text in &mut summary_query {
@@ -878,7 +2120,22 @@ pub fn update_settings_summary(
     }
 }
 
+/// System that shows on the Start Game button when its puzzle is being
+/// generated, and puts the usual label back once it isn't.
+pub fn update_start_button_label(
+    generation: Option<Res<PuzzleGeneration>>,
+    locale: Res<Locale>,
+    mut label_query: Query<(&mut Text, &mut Localized), With<StartGameLabel>>,
+) {
+    let key = if generation.is_some() { "menu.generating" } else { "menu.start" };
+    for (mut text, mut localized) in &mut label_query {
+        *localized = Localized(key);
+        text.0 = locale.get(key);
+    }
+}
+
 /// System to handle Start Game button hover effects.
+#[allow(clippy::type_complexity)]
 pub fn update_start_button_colors(
     mut button_query: Query<
         (&Interaction, &mut BackgroundColor),
@@ -894,319 +2151,645 @@ pub fn update_start_button_colors(
     }
 }
 
-/// System that creates the visual 9x9 sudoku grid with clickable cells
-pub fn setup_grid(mut commands: Commands) {
-
-    // Create the main UI root node
+/// System that creates the visual sudoku grid with clickable cells,
+/// sized to match the board that was just generated.
+#[allow(clippy::too_many_arguments)]
+pub fn setup_grid(
+    mut commands: Commands,
+    orientation: Res<BoardOrientation>,
+    grid_scale: Res<GridScale>,
+    board: Res<BoardState>,
+    persistent_data: Res<PersistentData>,
+    last_generation: Res<LastGeneration>,
+    cat_emojis: Res<CatEmojis>,
+    theme: Res<Theme>,
+    game_mode: Res<GameMode>,
+    locale: Res<Locale>,
+    side_panel: Res<SidePanelState>,
+) {
+    let size = board.size();
+    let (grid_width, grid_height) = orientation.grid_size(grid_scale.0);
+    commands.insert_resource(SelectedCell::default());
+    commands.insert_resource(PendingConfirmation::default());
+    commands.insert_resource(LeaderboardPlacement::default());
+    commands.insert_resource(CampaignResult::default());
+    let (cell_width, cell_height) = orientation.cell_size(size, grid_scale.0);
+    // Until the cells are laid out, go by the size they're spawned at
+    let glyphs = persistent_data.user_settings.glyph_set;
+    commands.insert_resource(CellDetail::for_cells(Vec2::new(cell_width, cell_height), size, &cat_emojis, glyphs));
+
+    // Create the main UI root node, split into the main area and the side panel (see `layout`)
     commands
         .spawn((
             Node {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
-                align_items: AlignItems::Center,
-                justify_content: JustifyContent::Center,
-                flex_direction: FlexDirection::Column,
+                flex_direction: FlexDirection::Row,
                 ..default()
             },
             GameScreenRoot, // Tag for potential cleanup
         ))
         .with_children(|parent| {
-            // Title
-            parent.spawn((
-                Text::new("Nine Lives: Cat Sudoku"),
-                TextFont {
-                    font_size: 32.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-                Node {
-                    margin: UiRect::bottom(Val::Px(20.0)),
-                    ..default()
-                },
-            ));
-
-            // Game info panel (timer and move counter)
+            // Main area: the board and number pad
             parent
-                .spawn((
-                    Node {
-                        display: Display::Flex,
-                        flex_direction: FlexDirection::Row,
-                        column_gap: Val::Px(40.0),
-                        margin: UiRect::bottom(Val::Px(15.0)),
-                        align_items: AlignItems::Center,
-                        justify_content: JustifyContent::Center,
-                        ..default()
-                    },
-                ))
-                .with_children(|info_parent| {
-                    // Timer display
-                    info_parent.spawn((
-                        Text::new("Time: 00:00"),
+                .spawn((main_area_node(), GameMainArea))
+                .with_children(|main| {
+                    // Title
+                    main.spawn((
+                        locale.text("menu.title"),
                         TextFont {
-                            font_size: 16.0,
+                            font_size: 32.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        Node {
+                            margin: UiRect::bottom(Val::Px(20.0)),
                             ..default()
                         },
-                        TextColor(Color::srgb(0.9, 0.9, 0.9)),
-                        TimerDisplay,
                     ));
 
-                    // Move counter display
-                    info_parent.spawn((
-                        Text::new("Moves: 0"),
+                    // Lives left (shown when nine lives is on)
+                    spawn_lives_hud(main, persistent_data.user_settings.nine_lives && game_mode.has_penalties());
+
+                    // Time attack clock and score (shown during a run)
+                    spawn_time_attack_display(main);
+
+                    // Tutorial prompts (shown during the tutorial)
+                    spawn_tutorial_panel(main);
+
+                    // Debug status display
+                    main.spawn((
+                        Text::new(debug_tip(&locale, &persistent_data)),
                         TextFont {
-                            font_size: 16.0,
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        Node {
+                            margin: UiRect::bottom(Val::Px(10.0)),
                             ..default()
                         },
-                        TextColor(Color::srgb(0.9, 0.9, 0.9)),
-                        MoveCounterDisplay,
+                            DebugStatusDisplay,
                     ));
+
+                    // Game grid container
+                    main
+                        .spawn((
+                            Node {
+                                display: Display::Grid,
+                                grid_template_columns: RepeatedGridTrack::flex(size as u16, 1.0),
+                                grid_template_rows: RepeatedGridTrack::flex(size as u16, 1.0),
+                                column_gap: Val::Px(2.0),
+                                row_gap: Val::Px(2.0),
+                                width: Val::Px(grid_width),
+                                height: Val::Px(grid_height),
+                                padding: UiRect::all(Val::Px(10.0)),
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(theme.grid_background),
+                            GridContainer,
+                        ))
+                        .with_children(|grid_parent| {
+                            // Create the grid of cells, in on-screen slot order
+                            for slot_row in 0..size {
+                                for slot_col in 0..size {
+                                    let (row, col) = orientation.slot_to_board(slot_row, slot_col, size);
+                                    grid_parent
+                                        .spawn((
+                                            Button,
+                                            Cell { row, col },
+                                            Focusable::Board,
+                                            GridSlot { row: slot_row, col: slot_col },
+                                            Node {
+                                                width: Val::Px(cell_width),
+                                                height: Val::Px(cell_height),
+                                                align_items: AlignItems::Center,
+                                                justify_content: JustifyContent::Center,
+                                                border: UiRect::all(Val::Px(1.0)),
+                                                ..default()
+                                            },
+                                            BackgroundColor(Color::srgb(0.9, 0.9, 0.9)), // Initial color, will be themed
+                                            BorderColor(theme.cell_border(board.is_given_cell(row, col))),
+                                        ))
+                                        .with_children(|cell_parent| {
+                                            // Text node for displaying the multi-line cat ASCII art
+                                            cell_parent.spawn((
+                                                Text::new(" "),
+                                                TextFont {
+                                                    font_size: CELL_FONT_SIZE,
+                                                    ..default()
+                                                },
+                                                TextColor(Color::BLACK),
+                                                // Used to draw bold givens; invisible otherwise
+                                                TextShadow {
+                                                    offset: Vec2::ZERO,
+                                                    color: Color::NONE,
+                                                },
+                                                Node {
+                                                    align_items: AlignItems::Center,
+                                                    justify_content: JustifyContent::Center,
+                                                    ..default()
+                                                },
+                                            ));
+
+                                            // Sprite of the cat (hidden unless cats are drawn as sprites)
+                                            spawn_cell_sprite(cell_parent);
+                                            // Killer cage and jigsaw region outlines (hidden on classic boards)
+                                            spawn_cage_outline(cell_parent);
+                                            spawn_region_outline(cell_parent);
+                                            // Corner badge for givens (hidden unless the given style uses it)
+                                            spawn_given_badge(cell_parent);
+                                        });
+                                }
+                            }
+                        });
+
+                    // Number pad for choosing which cat to place
+                    spawn_number_pad(main, size, persistent_data.user_settings.glyph_set);
+
+                    // Hint explanation (filled in after the first hint)
+                    main.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.85, 0.6)),
+                        Node {
+                            margin: UiRect::top(Val::Px(12.0)),
+                            max_width: Val::Px(600.0),
+                            ..default()
+                        },
+                        TextFit::new(600.0, 5, 13.0).with_min_size(10.0),
+                        HintExplanationDisplay,
+                    ));
+
+                    // Share code and replay panels (shown after a win)
+                    spawn_share_code_panel(main);
+                    spawn_replay_panel(main);
+
+                    // Debug event log (shown in debug mode)
+                    spawn_event_log_panel(main);
+
+                    // Debug solver walkthrough (shown in debug mode)
+                    spawn_solver_steps_panel(main);
                 });
 
-            // Debug status display
-            parent.spawn((
-                Text::new("Press ⌘D (Mac) or Ctrl+D (PC) for debug mode"),
-                TextFont {
-                    font_size: 12.0,
-                    ..default()
-                },
-                TextColor(Color::srgb(0.7, 0.7, 0.7)),
-                Node {
-                    margin: UiRect::bottom(Val::Px(10.0)),
-                    ..default()
-                },
-                    DebugStatusDisplay,
-            ));
+            // Side panel toggle and drag handle
+            spawn_panel_rail(parent, &side_panel);
+
+            // Side panel: stats, controls, and the move history
+            spawn_side_panel(parent, &side_panel, |panel| {
+                spawn_panel_heading(panel, locale.text("panel.stats"));
+
+                // Game info (timer and move counter, or just a reminder to relax in Zen mode)
+                panel
+                    .spawn((
+                        Node {
+                            display: Display::Flex,
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(4.0),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|info_parent| {
+                        if !game_mode.shows_counters() {
+                            info_parent.spawn((
+                                locale.text("game.zen"),
+                                TextFont {
+                                    font_size: 16.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(0.7, 0.9, 0.75)),
+                            ));
+                            return;
+                        }
 
-            // Game grid container
-            parent
-                .spawn((
-                    Node {
-                        display: Display::Grid,
-                        grid_template_columns: RepeatedGridTrack::flex(9, 1.0),
-                        grid_template_rows: RepeatedGridTrack::flex(9, 1.0),
-                        column_gap: Val::Px(2.0),
-                        row_gap: Val::Px(2.0),
-                        width: Val::Px(720.0),
-                        height: Val::Px(630.0),
-                        padding: UiRect::all(Val::Px(10.0)),
-                        border: UiRect::all(Val::Px(2.0)),
+                        // Timer display
+                        info_parent.spawn((
+                            Text::new(locale.format("game.time", &[("time", "00:00")])),
+                            TextFont {
+                                font_size: 16.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                            TimerDisplay,
+                        ));
+
+                        // Move counter display
+                        info_parent.spawn((
+                            Text::new(locale.format("game.moves", &[("count", "0")])),
+                            TextFont {
+                                font_size: 16.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                            MoveCounterDisplay,
+                        ));
+
+                        // Score display
+                        info_parent.spawn((
+                            Text::new(locale.format("game.score", &[("score", "0")])),
+                            TextFont {
+                                font_size: 16.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                            ScoreDisplay,
+                        ));
+                    });
+
+                // How the puzzle was generated
+                panel.spawn((
+                    Text::new(last_generation.info_line()),
+                    TextFont {
+                        font_size: 11.0,
                         ..default()
                     },
-                    BackgroundColor(Color::srgb(0.2, 0.2, 0.2)), // Will be updated by theme
-                ))
-                .with_children(|grid_parent| {
-                    // Create 9x9 grid of cells
-                    for row in 0..GRID_SIZE {
-                        for col in 0..GRID_SIZE {
-                            grid_parent
-                                .spawn((
-                                    Button,
-                                    Cell { row, col },
-                                    Node {
-                                        width: Val::Px(75.0),
-                                        height: Val::Px(65.0),
-                                        align_items: AlignItems::Center,
-                                        justify_content: JustifyContent::Center,
-                                        border: UiRect::all(Val::Px(1.0)),
-                                        ..default()
-                                    },
-                                    BackgroundColor(Color::srgb(0.9, 0.9, 0.9)), // Initial color, will be themed
-                                    BorderColor(Color::srgb(0.4, 0.4, 0.4)),
-                                ))
-                                .with_children(|cell_parent| {
-                                    // Text node for displaying the multi-line cat ASCII art
-                                    cell_parent.spawn((
-                                        Text::new(" "),
-                                        TextFont {
-                                            font_size: 8.0,
+                    TextColor(Color::srgb(0.55, 0.55, 0.6)),
+                    GenerationInfoDisplay,
+                ));
+
+                spawn_panel_heading(panel, locale.text("panel.controls"));
+
+                // Buttons container - Split into two groups, each wrapping to the panel's width
+                panel
+                    .spawn((Node {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(10.0),
+                        ..default()
+                    },))
+                    .with_children(|buttons_container| {
+                        // Top group: New Game, Restart Puzzle, and Clear Board
+                        buttons_container
+                            .spawn((Node {
+                                display: Display::Flex,
+                                flex_direction: FlexDirection::Row,
+                                flex_wrap: FlexWrap::Wrap,
+                                column_gap: Val::Px(8.0),
+                                row_gap: Val::Px(8.0),
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },))
+                            .with_children(|top_row| {
+                                // New Game button
+                                top_row
+                                    .spawn((
+                                        Button,
+                                        NewGameButton,
+                                        Focusable::NewGame,
+                                        Node {
+                                            width: Val::Px(120.0),
+                                            height: Val::Px(40.0),
+                                            align_items: AlignItems::Center,
+                                            justify_content: JustifyContent::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
                                             ..default()
                                         },
-                                        TextColor(Color::BLACK),
+                                        BackgroundColor(theme.new_game_button.normal),
+                                        BorderColor(theme.new_game_button.border),
+                                    ))
+                                    .with_children(|button_parent| {
+                                        button_parent.spawn((
+                                            locale.text("game.new_game"),
+                                            TextFont {
+                                                font_size: 14.0,
+                                                ..default()
+                                            },
+                                            TextColor(theme.button_text_color),
+                                        ));
+                                    });
+
+                                // Restart button
+                                top_row
+                                    .spawn((
+                                        Button,
+                                        RestartButton,
+                                        Focusable::Restart,
                                         Node {
+                                            width: Val::Px(130.0),
+                                            height: Val::Px(40.0),
                                             align_items: AlignItems::Center,
                                             justify_content: JustifyContent::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
                                             ..default()
                                         },
-                                    ));
-                                });
-                        }
-                    }
-                });
-
-            // Buttons container - Split into two rows
-            parent
-                .spawn((Node {
-                    display: Display::Flex,
-                    flex_direction: FlexDirection::Column,
-                    row_gap: Val::Px(10.0),
-                    margin: UiRect::top(Val::Px(20.0)),
-                    align_items: AlignItems::Center,
-                    justify_content: JustifyContent::Center,
-                    ..default()
-                },))
-                .with_children(|buttons_container| {
-                    // Top row: New Game and Clear Board
-                    buttons_container
-                        .spawn((Node {
-                            display: Display::Flex,
-                            flex_direction: FlexDirection::Row,
-                            column_gap: Val::Px(20.0),
-                            align_items: AlignItems::Center,
-                            justify_content: JustifyContent::Center,
-                            ..default()
-                        },))
-                        .with_children(|top_row| {
-                            // New Game button
-                            top_row
-                                .spawn((
-                                    Button,
-                                    NewGameButton,
-                                    Node {
-                                        width: Val::Px(120.0),
-                                        height: Val::Px(40.0),
-                                        align_items: AlignItems::Center,
-                                        justify_content: JustifyContent::Center,
-                                        border: UiRect::all(Val::Px(2.0)),
-                                        ..default()
-                                    },
-                                    BackgroundColor(Color::srgb(0.3, 0.6, 0.3)),
-                                    BorderColor(Color::srgb(0.4, 0.8, 0.4)),
-                                ))
-                                .with_children(|button_parent| {
-                                    button_parent.spawn((
-                                        Text::new("New Game"),
-                                        TextFont {
-                                            font_size: 14.0,
+                                        BackgroundColor(theme.restart_button.normal),
+                                        BorderColor(theme.restart_button.border),
+                                    ))
+                                    .with_children(|button_parent| {
+                                        button_parent.spawn((
+                                            locale.text("game.restart"),
+                                            TextFont {
+                                                font_size: 14.0,
+                                                ..default()
+                                            },
+                                            TextColor(theme.button_text_color),
+                                        ));
+                                    });
+
+                                // Clear button
+                                top_row
+                                    .spawn((
+                                        Button,
+                                        ClearButton,
+                                        Focusable::Clear,
+                                        Node {
+                                            width: Val::Px(120.0),
+                                            height: Val::Px(40.0),
+                                            align_items: AlignItems::Center,
+                                            justify_content: JustifyContent::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
                                             ..default()
                                         },
-                                        TextColor(Color::WHITE),
-                                    ));
-                                });
-
-                            // Clear button
-                            top_row
-                                .spawn((
-                                    Button,
-                                    ClearButton,
-                                    Node {
-                                        width: Val::Px(120.0),
-                                        height: Val::Px(40.0),
-                                        align_items: AlignItems::Center,
-                                        justify_content: JustifyContent::Center,
-                                        border: UiRect::all(Val::Px(2.0)),
-                                        ..default()
-                                    },
-                                    BackgroundColor(Color::srgb(0.6, 0.3, 0.3)),
-                                    BorderColor(Color::srgb(0.8, 0.4, 0.4)),
-                                ))
-                                .with_children(|button_parent| {
-                                    button_parent.spawn((
-                                        Text::new("Clear Board"),
-                                        TextFont {
-                                            font_size: 14.0,
+                                        BackgroundColor(theme.clear_button.normal),
+                                        BorderColor(theme.clear_button.border),
+                                    ))
+                                    .with_children(|button_parent| {
+                                        button_parent.spawn((
+                                            locale.text("game.clear"),
+                                            TextFont {
+                                                font_size: 14.0,
+                                                ..default()
+                                            },
+                                            TextColor(theme.button_text_color),
+                                        ));
+                                    });
+
+                                // Give up and show the solution
+                                spawn_show_solution_button(
+                                    top_row,
+                                    theme.clear_button.normal,
+                                    theme.clear_button.border,
+                                    theme.button_text_color,
+                                );
+
+                                // Auto-candidate toggle
+                                top_row
+                                    .spawn((
+                                        Button,
+                                        CandidatesButton,
+                                        Focusable::Candidates,
+                                        Node {
+                                            width: Val::Px(140.0),
+                                            height: Val::Px(40.0),
+                                            align_items: AlignItems::Center,
+                                            justify_content: JustifyContent::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
                                             ..default()
                                         },
-                                        TextColor(Color::WHITE),
-                                    ));
-                                });
-                        });
+                                        BackgroundColor(theme.toggle_button.normal),
+                                        BorderColor(theme.toggle_button.border),
+                                    ))
+                                    .with_children(|button_parent| {
+                                        button_parent.spawn((
+                                            Text::new("✏️ Candidates: Off"),
+                                            TextFont {
+                                                font_size: 14.0,
+                                                ..default()
+                                            },
+                                            TextColor(theme.button_text_color),
+                                        ));
+                                    });
+                            });
 
-                    // Bottom row: Undo, Redo, Hint
-                    buttons_container
-                        .spawn((Node {
-                            display: Display::Flex,
-                            flex_direction: FlexDirection::Row,
-                            column_gap: Val::Px(15.0),
-                            align_items: AlignItems::Center,
-                            justify_content: JustifyContent::Center,
-                            ..default()
-                        },))
-                        .with_children(|bottom_row| {
-                            // Undo button
-                            bottom_row
-                                .spawn((
-                                    Button,
-                                    UndoButton,
-                                    Node {
-                                        width: Val::Px(80.0),
-                                        height: Val::Px(35.0),
-                                        align_items: AlignItems::Center,
-                                        justify_content: JustifyContent::Center,
-                                        border: UiRect::all(Val::Px(2.0)),
-                                        ..default()
-                                    },
-                                    BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
-                                    BorderColor(Color::srgb(0.4, 0.4, 0.8)),
-                                ))
-                                .with_children(|button_parent| {
-                                    button_parent.spawn((
-                                        Text::new("⟲ Undo"),
-                                        TextFont {
-                                            font_size: 12.0,
+                        // Bottom group: Undo, Redo, back to correct, Hint
+                        buttons_container
+                            .spawn((Node {
+                                display: Display::Flex,
+                                flex_direction: FlexDirection::Row,
+                                flex_wrap: FlexWrap::Wrap,
+                                column_gap: Val::Px(8.0),
+                                row_gap: Val::Px(8.0),
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },))
+                            .with_children(|bottom_row| {
+                                // Undo button
+                                bottom_row
+                                    .spawn((
+                                        Button,
+                                        UndoButton,
+                                        Focusable::Undo,
+                                        Node {
+                                            width: Val::Px(80.0),
+                                            height: Val::Px(35.0),
+                                            align_items: AlignItems::Center,
+                                            justify_content: JustifyContent::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
                                             ..default()
                                         },
-                                        TextColor(Color::WHITE),
-                                    ));
-                                });
-
-                            // Redo button
-                            bottom_row
-                                .spawn((
-                                    Button,
-                                    RedoButton,
-                                    Node {
-                                        width: Val::Px(80.0),
-                                        height: Val::Px(35.0),
-                                        align_items: AlignItems::Center,
-                                        justify_content: JustifyContent::Center,
-                                        border: UiRect::all(Val::Px(2.0)),
-                                        ..default()
-                                    },
-                                    BackgroundColor(Color::srgb(0.6, 0.3, 0.6)),
-                                    BorderColor(Color::srgb(0.8, 0.4, 0.8)),
-                                ))
-                                .with_children(|button_parent| {
-                                    button_parent.spawn((
-                                        Text::new("⟳ Redo"),
-                                        TextFont {
-                                            font_size: 12.0,
+                                        BackgroundColor(theme.undo_button.normal),
+                                        BorderColor(theme.undo_button.border),
+                                    ))
+                                    .with_children(|button_parent| {
+                                        button_parent.spawn((
+                                            locale.text("game.undo"),
+                                            TextFont {
+                                                font_size: 12.0,
+                                                ..default()
+                                            },
+                                            TextColor(theme.button_text_color),
+                                        ));
+                                    });
+
+                                // Redo button
+                                bottom_row
+                                    .spawn((
+                                        Button,
+                                        RedoButton,
+                                        Focusable::Redo,
+                                        Node {
+                                            width: Val::Px(80.0),
+                                            height: Val::Px(35.0),
+                                            align_items: AlignItems::Center,
+                                            justify_content: JustifyContent::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
                                             ..default()
                                         },
-                                        TextColor(Color::WHITE),
-                                    ));
-                                });
-
-                            // Hint button
-                            bottom_row
-                                .spawn((
-                                    Button,
-                                    HintButton,
-                                    Node {
-                                        width: Val::Px(80.0),
-                                        height: Val::Px(35.0),
-                                        align_items: AlignItems::Center,
-                                        justify_content: JustifyContent::Center,
-                                        border: UiRect::all(Val::Px(2.0)),
-                                        ..default()
-                                    },
-                                    BackgroundColor(Color::srgb(0.7, 0.4, 0.1)),
-                                    BorderColor(Color::srgb(0.9, 0.6, 0.3)),
-                                ))
-                                .with_children(|button_parent| {
-                                    button_parent.spawn((
-                                        Text::new("💡 Hint"),
-                                        TextFont {
-                                            font_size: 12.0,
+                                        BackgroundColor(theme.redo_button.normal),
+                                        BorderColor(theme.redo_button.border),
+                                    ))
+                                    .with_children(|button_parent| {
+                                        button_parent.spawn((
+                                            locale.text("game.redo"),
+                                            TextFont {
+                                                font_size: 12.0,
+                                                ..default()
+                                            },
+                                            TextColor(theme.button_text_color),
+                                        ));
+                                    });
+
+                                // Undo back to the last correct move
+                                bottom_row
+                                    .spawn((
+                                        Button,
+                                        UndoToCorrectButton,
+                                        Focusable::UndoToCorrect,
+                                        Node {
+                                            width: Val::Px(110.0),
+                                            height: Val::Px(35.0),
+                                            align_items: AlignItems::Center,
+                                            justify_content: JustifyContent::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
                                             ..default()
                                         },
-                                        TextColor(Color::WHITE),
-                                    ));
-                                });
-                        });
-                });
+                                        BackgroundColor(theme.undo_button.normal),
+                                        BorderColor(theme.undo_button.border),
+                                    ))
+                                    .with_children(|button_parent| {
+                                        button_parent.spawn((
+                                            locale.text("game.undo_to_correct"),
+                                            TextFont {
+                                                font_size: 12.0,
+                                                ..default()
+                                            },
+                                            TextColor(theme.button_text_color),
+                                        ));
+                                    });
+
+                                // Hint button
+                                bottom_row
+                                    .spawn((
+                                        Button,
+                                        HintButton,
+                                        Focusable::Hint,
+                                        Node {
+                                            width: Val::Px(80.0),
+                                            height: Val::Px(35.0),
+                                            align_items: AlignItems::Center,
+                                            justify_content: JustifyContent::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
+                                            ..default()
+                                        },
+                                        BackgroundColor(theme.hint_button.normal),
+                                        BorderColor(theme.hint_button.border),
+                                    ))
+                                    .with_children(|button_parent| {
+                                        button_parent.spawn((
+                                            locale.text("game.hint"),
+                                            TextFont {
+                                                font_size: 12.0,
+                                                ..default()
+                                            },
+                                            TextColor(theme.button_text_color),
+                                        ));
+                                    });
+
+                                // Check button
+                                spawn_check_button(
+                                    bottom_row,
+                                    theme.hint_button.normal,
+                                    theme.hint_button.border,
+                                    theme.button_text_color,
+                                );
+
+                                // Hint verbosity toggle
+                                bottom_row
+                                    .spawn((
+                                        Button,
+                                        HintVerbosityButton,
+                                        Focusable::HintVerbosity,
+                                        Node {
+                                            width: Val::Px(120.0),
+                                            height: Val::Px(35.0),
+                                            align_items: AlignItems::Center,
+                                            justify_content: JustifyContent::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
+                                            ..default()
+                                        },
+                                        BackgroundColor(theme.toggle_button.normal),
+                                        BorderColor(theme.toggle_button.border),
+                                    ))
+                                    .with_children(|button_parent| {
+                                        button_parent.spawn((
+                                            Text::new("Detail: Normal"),
+                                            TextFont {
+                                                font_size: 12.0,
+                                                ..default()
+                                            },
+                                            TextColor(theme.button_text_color),
+                                        ));
+                                    });
+
+                            });
+                    });
+
+                spawn_panel_heading(panel, locale.text("panel.history"));
+
+                // Move history panel toggle and the history itself
+                spawn_history_panel_button(
+                    panel,
+                    theme.toggle_button.normal,
+                    theme.toggle_button.border,
+                    theme.button_text_color,
+                );
+                spawn_history_panel(panel);
+            });
+
+            // Game-over overlay for when the last life goes
+            spawn_game_over_overlay(parent);
+
+            // Give-up overlay for while the solution is shown
+            spawn_gave_up_overlay(parent);
+
+            // Time's-up overlay for the end of a time attack run
+            spawn_time_attack_overlay(parent);
+
+            // Confirmation dialog for Clear Board, Restart Puzzle, Show Solution, and New Game (shown on request)
+            spawn_confirm_dialog(parent);
         });
 
-    println!("🎮 Nine Lives Cat Sudoku GAME SCREEN initialized!");
+    debug!("🎮 Nine Lives Cat Sudoku GAME SCREEN initialized!");
+}
+
+/// System that picks the board orientation from the primary window's shape.
+/// Runs on resize so rotating a tablet (or resizing the browser) flips the layout.
+pub fn detect_board_orientation(
+    mut resize_events: EventReader<bevy::window::WindowResized>,
+    mut orientation: ResMut<BoardOrientation>,
+) {
+    if let Some(event) = resize_events.read().last() {
+        let new_orientation = BoardOrientation::for_window(event.width, event.height);
+        if *orientation != new_orientation {
+            *orientation = new_orientation;
+            info!("📐 Board orientation changed to {:?}", new_orientation);
+        }
+    }
+}
+
+/// System that re-lays out the existing grid when the orientation or its
+/// scale changes. Each slot keeps its place on screen; only the board cell it
+/// shows is remapped.
+pub fn apply_board_orientation(
+    orientation: Res<BoardOrientation>,
+    grid_scale: Res<GridScale>,
+    board: Res<BoardState>,
+    mut grid_query: Query<&mut Node, (With<GridContainer>, Without<Cell>)>,
+    mut cell_query: Query<(&GridSlot, &mut Cell, &mut Node)>,
+) {
+    if !orientation.is_changed() && !grid_scale.is_changed() {
+        return;
+    }
+
+    let (grid_width, grid_height) = orientation.grid_size(grid_scale.0);
+    for mut node in &mut grid_query {
+        node.width = Val::Px(grid_width);
+        node.height = Val::Px(grid_height);
+    }
+
+    let size = board.size();
+    let (cell_width, cell_height) = orientation.cell_size(size, grid_scale.0);
+    for (slot, mut cell, mut node) in &mut cell_query {
+        let (row, col) = orientation.slot_to_board(slot.row, slot.col, size);
+        cell.row = row;
+        cell.col = col;
+        node.width = Val::Px(cell_width);
+        node.height = Val::Px(cell_height);
+    }
 }
 
 /// A system that transitions the app from `Loading` to `Customization` once resources are loaded.
@@ -1214,60 +2797,76 @@ pub fn transition_to_customization(
     mut app_state: ResMut<NextState<AppState>>,
     cat_emojis: Option<Res<CatEmojis>>,
     selected_preset: Option<Res<SelectedPreset>>,
+    roster: Res<ProfileRoster>,
 ) {
-    // We transition once all required resources are loaded
+    // We transition once all required resources are loaded, asking who's
+    // playing first if there's a choice
     if cat_emojis.is_some() && selected_preset.is_some() {
-        app_state.set(AppState::Customization);
+        app_state.set(if roster.profiles.len() > 1 { AppState::Profiles } else { AppState::Customization });
     }
 }
 
 /// A system that transitions from `Customization` to `Ready` when "Start Game" is pressed.
-/// This system also generates the initial puzzle using the selected settings.
+/// This system also sets up the initial puzzle using the selected settings (see
+/// `PuzzleSwap::load_fresh`). If the puzzle has to be generated, the button
+/// shows it's on its way and `finish_puzzle_generation` makes the transition.
 pub fn transition_to_game(
     mut app_state: ResMut<NextState<AppState>>,
     mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<StartGameButton>)>,
     selected_preset: Res<SelectedPreset>,
-    mut commands: Commands,
-    mut board: ResMut<BoardState>,
-    mut session: ResMut<GameSession>,
-    mut history: ResMut<GameHistory>,
-    mut solution: ResMut<Solution>,
-    mut hint_system: ResMut<HintSystem>,
+    selected_grid_size: Res<SelectedGridSize>,
+    selected_variant: Res<SelectedVariant>,
+    generation: Option<Res<PuzzleGeneration>>,
+    mut puzzles: PuzzleSwap,
 ) {
+    if generation.is_some() {
+        return;
+    }
     for interaction in &mut interaction_query {
         if *interaction == Interaction::Pressed {
-            println!("🎯 Start Game button pressed!");
+            debug!("🎯 Start Game button pressed!");
             
             // Store the selected settings as a resource for the game to use
-            let settings = PuzzleSettings::from_preset(selected_preset.preset);
-            println!("📋 Generated settings: {}", settings.description());
-            commands.insert_resource(settings.clone());
-            
-            // Generate a new puzzle using the selected settings
-            if let Some(new_solution) = board.generate_puzzle_with_settings(&settings) {
-                *solution = new_solution;
-                println!("Generated new puzzle with settings: {}", settings.description());
-            } else {
-                // Fallback: generate a simple puzzle if the advanced generation fails
-                *solution = board.generate_puzzle(35); // Default easy puzzle
-                println!("Fallback: Generated simple puzzle (advanced generation failed)");
+            let settings = PuzzleSettings::from_preset(selected_preset.preset)
+                .with_grid_size(selected_grid_size.grid_size)
+                .with_variant(selected_variant.variant);
+            debug!("📋 Generated settings: {}", settings.description());
+            if !puzzles.load_fresh(settings) {
+                continue;
             }
-            
-            // Reset the session timer and move counter
-            session.reset();
-            // Clear move history
-            history.clear();
-            // Reset hints based on settings
-            hint_system.reset(settings.max_hints);
-            
+
             // Transition to the game screen
-            println!("🔄 Transitioning to Ready state...");
+            debug!("🔄 Transitioning to Ready state...");
             app_state.set(AppState::Ready);
-            println!("✅ State transition triggered for preset: {:?}", selected_preset.preset);
+            debug!("✅ State transition triggered for preset: {:?}", selected_preset.preset);
         }
     }
 }
 
+/// A pre-generated puzzle to play with the given settings, if the pool has
+/// one ready, along with how it was made.
+pub fn pooled_puzzle(pool: &mut PuzzlePool, settings: &PuzzleSettings) -> Option<(BoardState, Solution, LastGeneration)> {
+    let (board, solution, report) = pool.take(settings)?;
+    info!("⚡ Took a pre-generated puzzle ({} more ready)", pool.count(settings));
+    if let Err(e) = pool.save() {
+        warn!("⚠️ Failed to save puzzle pool: {}", e);
+    }
+    Some((board, solution, LastGeneration { report, pre_generated: true }))
+}
+
+/// A simple classic puzzle for when generating one to the settings fails:
+/// the selected board size, with as many givens as an easy puzzle of that
+/// size has (so a small board doesn't start out solved).
+pub fn fallback_puzzle(settings: &PuzzleSettings) -> (BoardState, Solution) {
+    let givens = PuzzleSettings::from_preset(PresetKind::CozyKitten)
+        .with_grid_size(settings.grid_size)
+        .givens_range
+        .0;
+    let mut board = BoardState::with_size(settings.grid_size);
+    let solution = board.generate_puzzle(givens);
+    (board, solution)
+}
+
 /// UI Plugin for Nine Lives Cat Sudoku.
 /// This plugin handles all UI-related functionality including states, systems, and resources.
 pub struct UiPlugin;
@@ -1275,18 +2874,82 @@ pub struct UiPlugin;
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<AppState>()
+            .init_resource::<BoardOrientation>()
+            .init_resource::<GridScale>()
+            .init_resource::<ActiveDigit>()
+            .init_resource::<SelectedCell>()
+            .init_resource::<LastGeneration>()
+            .init_resource::<SelectedGridSize>()
+            .init_resource::<SelectedVariant>()
+            .init_resource::<MusicDirector>()
+            .init_resource::<ShareCodeEntry>()
+            .init_resource::<EventLogFilter>()
+            .init_resource::<SolverWalkthrough>()
+            .init_resource::<HistoryPanelState>()
+            .init_resource::<SidePanelState>()
+            .init_resource::<FocusManager>()
+            .init_resource::<PendingConfirmation>()
+            .init_resource::<LeaderboardPlacement>()
+            .init_resource::<CampaignResult>()
+            .init_resource::<PackBrowser>()
+            .init_resource::<ProfileRoster>()
+            .init_resource::<ProfileNameEntry>()
+            .init_resource::<GameMode>()
+            .init_resource::<ScoreTracker>()
+            .init_resource::<StreakTracker>()
+            .init_resource::<Locale>()
+            .init_resource::<TouchMode>()
+            .init_resource::<HeatmapOverlay>()
+            .init_resource::<ConflictTracker>()
+            .init_resource::<ShortcutCapture>()
+            .init_resource::<bevy::input_focus::InputFocus>()
+            .init_resource::<ArtPackLibrary>()
+            .init_asset::<ArtPack>()
+            .init_asset_loader::<ArtPackLoader>()
+            .add_event::<FocusAction>()
+            .add_event::<ConfirmedAction>()
+            .add_event::<HouseCompleted>()
+            .add_event::<ProgressChecked>()
+            .add_event::<StreakReached>()
+            .add_event::<ErrorToast>()
             // Startup: Initialize resources
             .add_systems(Startup, (
                 setup_camera,
                 setup_theme, 
                 setup_cat_emojis, 
-                setup_selected_preset
+                setup_selected_preset,
+                load_art_packs,
+                load_cat_sprites,
+                setup_paw_cursors,
             ))
             // State transitions
             .add_systems(OnEnter(AppState::Customization), setup_customization_screen)
-            .add_systems(OnExit(AppState::Customization), cleanup_customization_screen)
+            .add_systems(OnExit(AppState::Customization), (cleanup_customization_screen, cancel_puzzle_generation))
             .add_systems(OnEnter(AppState::Ready), setup_grid)
-            .add_systems(OnExit(AppState::Ready), cleanup_game_screen)
+            .add_systems(
+                OnExit(AppState::Ready),
+                (
+                    cleanup_game_screen,
+                    end_replay_viewer,
+                    end_solution_reveal,
+                    end_time_attack,
+                    end_tutorial,
+                    clear_streak_popups,
+                    cancel_puzzle_generation,
+                ),
+            )
+            .add_systems(OnEnter(AppState::Statistics), setup_statistics_screen)
+            .add_systems(OnExit(AppState::Statistics), (cleanup_statistics_screen, end_best_replay_viewer))
+            .add_systems(OnEnter(AppState::Settings), setup_settings_screen)
+            .add_systems(OnExit(AppState::Settings), cleanup_settings_screen)
+            .add_systems(OnEnter(AppState::Campaign), setup_campaign_screen)
+            .add_systems(OnExit(AppState::Campaign), cleanup_campaign_screen)
+            .add_systems(OnEnter(AppState::Packs), setup_packs_screen)
+            .add_systems(OnExit(AppState::Packs), cleanup_packs_screen)
+            .add_systems(OnEnter(AppState::Profiles), setup_profiles_screen)
+            .add_systems(OnExit(AppState::Profiles), cleanup_profiles_screen)
+            .add_systems(OnEnter(AppState::TrainingGrounds), setup_training_screen)
+            .add_systems(OnExit(AppState::TrainingGrounds), cleanup_training_screen)
             // Update systems
             .add_systems(
                 Update,
@@ -1301,16 +2964,45 @@ impl Plugin for UiPlugin {
                         .run_if(in_state(AppState::Customization)),
                     update_settings_summary.run_if(in_state(AppState::Customization)),
                     update_start_button_colors.run_if(in_state(AppState::Customization)),
+                    update_start_button_label
+                        .run_if(resource_added::<PuzzleGeneration>.or(resource_removed::<PuzzleGeneration>))
+                        .run_if(in_state(AppState::Customization)),
                     transition_to_game.run_if(in_state(AppState::Customization)),
                     
                     // Game state systems
+                    update_cell_detail.before(update_cell_text).run_if(in_state(AppState::Ready)),
                     update_cell_text
-                        .run_if(resource_changed::<BoardState>)
+                        .run_if(
+                            (|b: Res<BoardState>, p: Res<PersistentData>, d: Res<CellDetail>, c: Res<CatEmojis>| {
+                                b.is_changed() || p.is_changed() || d.is_changed() || c.is_changed()
+                            })
+                            // Sprites that finish loading replace their text stand-ins
+                            .or(on_event::<AssetEvent<Image>>),
+                        )
                         .run_if(in_state(AppState::Ready)),
+                    sync_conflict_tracker
+                        .before(update_cell_colors)
+                        .before(start_conflict_flash)
+                        .run_if(resource_changed::<BoardState>),
                     update_cell_colors
-                        .run_if(|b: Res<BoardState>, s: Res<GameState>, t: Res<Theme>| {
-                            b.is_changed() || s.is_changed() || t.is_changed()
-                        })
+                        .run_if(
+                            |b: Res<BoardState>,
+                             s: Res<GameState>,
+                             t: Res<Theme>,
+                             p: Res<PersistentData>,
+                             selected: Res<SelectedCell>,
+                             hover: Query<(), (With<Cell>, Changed<Interaction>)>| {
+                                b.is_changed()
+                                    || s.is_changed()
+                                    || t.is_changed()
+                                    || p.is_changed()
+                                    || selected.is_changed()
+                                    || !hover.is_empty()
+                            },
+                        )
+                        .run_if(in_state(AppState::Ready)),
+                    update_given_badges
+                        .run_if(|b: Res<BoardState>, p: Res<PersistentData>| b.is_changed() || p.is_changed())
                         .run_if(in_state(AppState::Ready)),
                     update_button_colors.run_if(in_state(AppState::Ready)),
                     update_cell_hover_effects.run_if(in_state(AppState::Ready)),
@@ -1320,6 +3012,9 @@ impl Plugin for UiPlugin {
                     update_move_counter_display
                         .run_if(resource_changed::<GameSession>)
                         .run_if(in_state(AppState::Ready)),
+                    update_score_display
+                        .run_if(resource_changed::<ScoreTracker>)
+                        .run_if(in_state(AppState::Ready)),
                     update_hint_button_text
                         .run_if(|h: Res<HintSystem>, d: Res<DebugMode>| h.is_changed() || d.is_changed())
                         .run_if(in_state(AppState::Ready)),
@@ -1328,6 +3023,432 @@ impl Plugin for UiPlugin {
                         .run_if(in_state(AppState::Ready)),
                     tick_timer_display.run_if(in_state(AppState::Ready)),
                 ),
+            )
+            // Number pad systems
+            .add_systems(
+                Update,
+                (
+                    handle_number_pad_selection,
+                    handle_notes_button,
+                    sync_number_pad_highlights.run_if(resource_changed::<ActiveDigit>.or(resource_changed::<BoardState>)),
+                    update_number_pad_counts.run_if(resource_changed::<BoardState>),
+                    handle_assist_mode_button,
+                    update_assist_mode_button_text.run_if(resource_changed::<PersistentData>),
+                    handle_input_model_button,
+                    update_input_model_button_text.run_if(resource_changed::<PersistentData>),
+                    update_placement_preview.after(update_cell_hover_effects),
+                    update_selected_cell_border,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Ready)),
+            )
+            // Auto-candidate systems
+            .add_systems(
+                Update,
+                (
+                    handle_candidates_button,
+                    update_candidates_button_text.run_if(resource_changed::<PersistentData>),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Ready)),
+            )
+            // Hint explanation systems
+            .add_systems(
+                Update,
+                (
+                    handle_hint_verbosity_button,
+                    update_hint_explanation_display.run_if(
+                        |h: Res<LastHint>, p: Res<PersistentData>| h.is_changed() || p.is_changed(),
+                    ),
+                    start_unit_highlight.run_if(resource_changed::<LastHint>),
+                    start_hint_pulse.run_if(resource_changed::<LastHint>),
+                    fade_unit_highlight,
+                    pulse_hinted_cells,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Ready)),
+            )
+            // Continuing a saved game
+            .add_systems(
+                Update,
+                (
+                    handle_continue_button,
+                    handle_discard_save_button,
+                    discard_saved_game,
+                    update_continue_button_colors,
+                )
+                    .run_if(in_state(AppState::Customization)),
+            )
+            // Statistics navigation
+            .add_systems(
+                Update,
+                (
+                    handle_statistics_button.run_if(in_state(AppState::Customization)),
+                    handle_statistics_back_button.run_if(in_state(AppState::Statistics)),
+                    handle_summary_export_button.run_if(in_state(AppState::Statistics)),
+                    handle_stats_export_buttons.run_if(in_state(AppState::Statistics)),
+                ),
+            )
+            // Campaign screen
+            .add_systems(
+                Update,
+                (
+                    handle_campaign_button.run_if(in_state(AppState::Customization)),
+                    (handle_campaign_back_button, handle_campaign_level_buttons, update_campaign_level_colors)
+                        .run_if(in_state(AppState::Campaign)),
+                ),
+            )
+            // Training Grounds screen
+            .add_systems(
+                Update,
+                (
+                    handle_training_button.run_if(in_state(AppState::Customization)),
+                    (handle_training_back_button, handle_practice_buttons, update_practice_button_colors)
+                        .run_if(in_state(AppState::TrainingGrounds)),
+                ),
+            )
+            // Puzzle packs screen
+            .add_systems(
+                Update,
+                (
+                    handle_packs_button.run_if(in_state(AppState::Customization)),
+                    (
+                        handle_packs_back_button,
+                        import_dropped_packs,
+                        handle_pack_select_buttons,
+                        handle_pack_puzzle_buttons,
+                        update_pack_browser,
+                        scroll_list_system::<PackPuzzleGrid>(packs::SCROLL_LINE_HEIGHT),
+                    )
+                        .chain()
+                        .run_if(in_state(AppState::Packs)),
+                ),
+            )
+            // Profile screen
+            .add_systems(
+                Update,
+                (
+                    handle_profiles_button.run_if(in_state(AppState::Customization)),
+                    (
+                        handle_profiles_back_button,
+                        handle_profile_select_buttons,
+                        handle_new_profile_button,
+                        profile_name_typing_system,
+                        update_profile_name_entry_display.run_if(resource_changed::<ProfileNameEntry>),
+                        update_profile_list,
+                    )
+                        .chain()
+                        .run_if(in_state(AppState::Profiles)),
+                ),
+            )
+            // Record replays on the statistics screen
+            .add_systems(
+                Update,
+                (
+                    handle_watch_best_replay_buttons,
+                    (handle_best_replay_controls, play_best_replay).run_if(resource_exists::<BestReplayViewer>),
+                    update_best_replay_panel,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Statistics)),
+            )
+            // Settings navigation
+            .add_systems(
+                Update,
+                (
+                    handle_settings_button.run_if(in_state(AppState::Customization)),
+                    handle_settings_back_button.run_if(in_state(AppState::Settings)),
+                    handle_setting_step_buttons.run_if(in_state(AppState::Settings)),
+                    update_setting_values
+                        .run_if(resource_changed::<PersistentData>.or(resource_changed::<Locale>))
+                        .run_if(in_state(AppState::Settings)),
+                    (capture_shortcut_system, handle_shortcut_buttons, update_shortcut_labels)
+                        .chain()
+                        .run_if(in_state(AppState::Settings)),
+                )
+                    .chain(),
+            )
+            // Error toasts, on every screen
+            .add_systems(Update, (show_error_toasts, fade_error_toasts).chain())
+            // Paw pointer, on every screen
+            .add_systems(Update, update_cursor.run_if(resource_exists::<PawCursors>))
+            // Window icon and title
+            .add_systems(Update, (set_window_icon, update_window_title))
+            // Streak cheers
+            .add_systems(
+                Update,
+                (show_streak_feedback, animate_streak_popups).chain().run_if(in_state(AppState::Ready)),
+            )
+            // Conflict feedback, house completion, and progress check systems
+            .add_systems(
+                Update,
+                (
+                    start_conflict_flash.run_if(resource_changed::<BoardState>),
+                    animate_conflict_flash,
+                    start_house_flash,
+                    fade_house_flash,
+                    start_check_marks,
+                    fade_check_marks,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Ready)),
+            )
+            // Art pack systems (before the cells are drawn with the art)
+            .add_systems(
+                Update,
+                (
+                    collect_art_packs,
+                    apply_art_pack.run_if(|l: Res<ArtPackLibrary>, p: Res<PersistentData>| l.is_changed() || p.is_changed()),
+                )
+                    .chain()
+                    .before(update_cell_detail),
+            )
+            // Theme and language systems
+            .add_systems(
+                Update,
+                (
+                    sync_theme.run_if(resource_exists_and_changed::<PersistentData>),
+                    sync_locale.run_if(resource_exists_and_changed::<PersistentData>),
+                    localize_texts.run_if(resource_changed::<Locale>),
+                    sync_game_mode.run_if(resource_exists_and_changed::<PersistentData>),
+                    apply_theme_to_grid
+                        .run_if(resource_changed::<Theme>)
+                        .run_if(in_state(AppState::Ready)),
+                )
+                    .chain(),
+            )
+            // Board option buttons on the customization screen
+            .add_systems(
+                Update,
+                (
+                    handle_grid_size_button,
+                    handle_variant_button,
+                    handle_glyph_set_button,
+                    handle_given_style_button,
+                    handle_double_click_fill_button,
+                    handle_nine_lives_button,
+                    handle_pause_when_away_button,
+                    handle_game_mode_button,
+                )
+                    .run_if(in_state(AppState::Customization)),
+            )
+            // Share code systems
+            .add_systems(
+                Update,
+                (
+                    handle_enter_share_code_button.run_if(in_state(AppState::Customization)),
+                    share_code_typing_system.run_if(in_state(AppState::Customization)),
+                    update_share_code_entry_display
+                        .run_if(resource_changed::<ShareCodeEntry>)
+                        .run_if(in_state(AppState::Customization)),
+                    update_share_panel_visibility
+                        .run_if(|s: Res<GameState>, v: Option<Res<ReplayViewer>>| {
+                            s.is_changed() || v.is_some_and(|v| v.is_added())
+                        })
+                        .run_if(in_state(AppState::Ready)),
+                    handle_copy_share_code_button.run_if(in_state(AppState::Ready)),
+                    handle_heatmap_button.run_if(in_state(AppState::Ready)),
+                    update_heatmap_labels
+                        .run_if(resource_changed::<HeatmapOverlay>)
+                        .run_if(in_state(AppState::Ready)),
+                    update_new_record_display
+                        .run_if(resource_changed::<LeaderboardPlacement>)
+                        .run_if(in_state(AppState::Ready)),
+                    update_campaign_result_display
+                        .run_if(resource_changed::<CampaignResult>)
+                        .run_if(in_state(AppState::Ready)),
+                )
+                    .chain(),
+            )
+            // Replay viewer systems
+            .add_systems(
+                Update,
+                (
+                    handle_watch_replay_button,
+                    scrub_replay_system.run_if(resource_exists::<ReplayViewer>),
+                    update_replay_timeline.run_if(resource_exists_and_changed::<ReplayViewer>),
+                    handle_replay_done_button.run_if(resource_exists::<ReplayViewer>),
+                    update_replay_panel_visibility.run_if(
+                        |s: Res<GameState>, v: Option<Res<ReplayViewer>>, mut watching: Local<bool>| {
+                            let changed = s.is_changed() || v.is_some() != *watching;
+                            *watching = v.is_some();
+                            changed
+                        },
+                    ),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Ready)),
+            )
+            // Debug event log systems
+            .add_systems(
+                Update,
+                (
+                    update_event_log_visibility
+                        .run_if(|d: Res<DebugMode>, s: Res<State<AppState>>| d.is_changed() || s.is_changed()),
+                    handle_event_log_filter_buttons,
+                    update_event_log_text.run_if(
+                        |l: Option<Res<GameEventLog>>, f: Res<EventLogFilter>, s: Res<State<AppState>>| {
+                            l.is_some_and(|l| l.is_changed()) || f.is_changed() || s.is_changed()
+                        },
+                    ),
+                    scroll_list_system::<EventLogList>(event_log::SCROLL_LINE_HEIGHT),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Ready)),
+            )
+            // Debug solver walkthrough systems
+            .add_systems(
+                Update,
+                (
+                    update_solver_panel_visibility
+                        .run_if(|d: Res<DebugMode>, s: Res<State<AppState>>| d.is_changed() || s.is_changed()),
+                    handle_solver_step_buttons,
+                    update_solver_caption.run_if(resource_changed::<SolverWalkthrough>),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Ready)),
+            )
+            // Side panel systems
+            .add_systems(
+                Update,
+                (
+                    handle_side_panel_toggle,
+                    drag_split_handle,
+                    apply_side_panel_layout.run_if(resource_changed::<SidePanelState>),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Ready)),
+            )
+            // Move history panel systems
+            .add_systems(
+                Update,
+                (
+                    handle_history_panel_button,
+                    update_history_panel_visibility
+                        .run_if(|p: Res<HistoryPanelState>, s: Res<State<AppState>>| p.is_changed() || s.is_changed()),
+                    update_history_list.run_if(
+                        |h: Res<GameHistory>, p: Res<HistoryPanelState>, s: Res<State<AppState>>| {
+                            h.is_changed() || p.is_changed() || s.is_changed()
+                        },
+                    ),
+                    update_history_entry_colors,
+                    scroll_list_system::<HistoryList>(history_panel::SCROLL_LINE_HEIGHT),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Ready)),
+            )
+            // Confirmation dialog systems
+            .add_systems(
+                Update,
+                (
+                    handle_confirm_dialog_buttons,
+                    update_confirm_dialog.run_if(resource_changed::<PendingConfirmation>),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Ready).or(in_state(AppState::Customization))),
+            )
+            // Lives and give-up systems
+            .add_systems(
+                Update,
+                (
+                    handle_try_again_button,
+                    update_lives_hud.run_if(
+                        |l: Option<Res<Lives>>, p: Res<PersistentData>| l.is_some_and(|l| l.is_changed()) || p.is_changed(),
+                    ),
+                    shake_lives_hud,
+                    update_gave_up_overlay,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Ready)),
+            )
+            // Time attack systems
+            .add_systems(
+                Update,
+                (
+                    handle_time_attack_button.run_if(in_state(AppState::Customization)),
+                    (
+                        update_time_attack_display,
+                        update_time_attack_overlay,
+                        update_generation_info_display
+                            .run_if(resource_changed::<LastGeneration>.or(resource_added::<PuzzleGeneration>)),
+                    )
+                        .run_if(in_state(AppState::Ready)),
+                ),
+            )
+            // Background puzzle generation systems
+            .add_systems(
+                Update,
+                (
+                    cancel_puzzle_generation.run_if(generation_settings_changed),
+                    finish_puzzle_generation.run_if(resource_exists::<PuzzleGeneration>),
+                )
+                    .chain(),
+            )
+            // Tutorial systems
+            .add_systems(
+                Update,
+                (
+                    handle_tutorial_button.run_if(in_state(AppState::Customization)),
+                    (
+                        handle_tutorial_buttons.run_if(resource_exists::<Tutorial>),
+                        update_tutorial_panel,
+                        update_tutorial_mark,
+                    )
+                        .chain()
+                        .run_if(in_state(AppState::Ready)),
+                ),
+            )
+            // Keep long text within its space (before the UI lays it out)
+            .add_systems(PostUpdate, fit_text_system.before(bevy::ui::UiSystem::Prepare))
+            // Keyboard and gamepad focus (after the UI's own interaction update, so a
+            // keyboard press reaches the button handlers in the same frame)
+            .add_systems(
+                PreUpdate,
+                (keyboard_focus_input, gamepad_focus_input, apply_focus_actions)
+                    .chain()
+                    .after(bevy::ui::UiSystem::Focus),
+            )
+            .add_systems(Update, update_focus_outline)
+            // Finger-sized buttons on touch screens
+            .add_systems(Update, enlarge_touch_targets)
+            // Screen readers
+            .add_systems(
+                Update,
+                (
+                    sync_input_focus.run_if(resource_changed::<FocusManager>),
+                    describe_cells_for_screen_readers.run_if(in_state(AppState::Ready)),
+                ),
+            )
+            // Layout systems (run in every state so the orientation and scale are ready before the grid spawns)
+            .add_systems(
+                Update,
+                (
+                    detect_board_orientation,
+                    fit_ui_to_window.run_if(
+                        on_event::<bevy::window::WindowResized>
+                            .or(resource_changed::<PersistentData>)
+                            .or(resource_changed::<BoardOrientation>)
+                            .or(resource_changed::<SidePanelState>),
+                    ),
+                    apply_board_orientation.run_if(in_state(AppState::Ready)),
+                    update_cage_outlines
+                        .run_if(|b: Res<BoardState>, o: Res<BoardOrientation>| b.is_changed() || o.is_changed())
+                        .run_if(in_state(AppState::Ready)),
+                    update_region_outlines
+                        .run_if(|b: Res<BoardState>, o: Res<BoardOrientation>| b.is_changed() || o.is_changed())
+                        .run_if(in_state(AppState::Ready)),
+                )
+                    .chain(),
+            )
+            // Background music (crossfades follow every screen and game state change)
+            .add_systems(
+                Update,
+                (
+                    // Scrubbing a replay flips the game state back and forth, so the music holds still
+                    music_director_system.run_if(not(resource_exists::<ReplayViewer>)),
+                    crossfade_music_system,
+                )
+                    .chain(),
             );
     }
 }
@@ -1341,6 +3462,7 @@ pub fn add_ui(app: &mut App) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use nine_lives_core::GRID_SIZE;
 
     #[test]
     fn test_cat_emojis_creation() {
@@ -1358,10 +3480,126 @@ mod tests {
         assert!(cat_emojis.emojis[8].contains("(  9  )")); // Ninth kitten has number 9
     }
 
+    #[test]
+    fn test_board_orientation_round_trip() {
+        for orientation in [BoardOrientation::Landscape, BoardOrientation::Portrait] {
+            for size in [4, 6, GRID_SIZE] {
+                for row in 0..size {
+                    for col in 0..size {
+                        let (slot_row, slot_col) = orientation.board_to_slot(row, col, size);
+                        assert_eq!(orientation.slot_to_board(slot_row, slot_col, size), (row, col));
+                    }
+                }
+            }
+        }
+
+        // Portrait rotates clockwise: the board's bottom-left cell lands in the top-left slot
+        assert_eq!(BoardOrientation::Portrait.slot_to_board(0, 0, GRID_SIZE), (GRID_SIZE - 1, 0));
+        assert_eq!(BoardOrientation::for_window(600.0, 900.0), BoardOrientation::Portrait);
+        assert_eq!(BoardOrientation::for_window(900.0, 600.0), BoardOrientation::Landscape);
+    }
+
+    #[test]
+    fn test_format_candidates() {
+        assert_eq!(format_candidates(&[0, 4, 8], GridSize::Classic, GlyphSet::Arabic), "1 · ·\n· 5 ·\n· · 9");
+        assert_eq!(format_candidates(&[], GridSize::Classic, GlyphSet::Arabic), "· · ·\n· · ·\n· · ·");
+        assert_eq!(format_candidates(&[1, 5], GridSize::Small, GlyphSet::Arabic), "· 2 ·\n· · 6");
+        assert_eq!(format_candidates(&[1, 5], GridSize::Small, GlyphSet::Hanzi), "· 二 ·\n· · 六");
+    }
+
+    #[test]
+    fn test_theme_follows_settings() {
+        let mut app = App::new();
+        app.insert_resource(Theme::default()).insert_resource(PersistentData::default());
+        app.add_systems(Update, sync_theme);
+        app.update();
+        assert_eq!(app.world().resource::<Theme>().kind, ThemeKind::Classic);
+
+        app.world_mut().resource_mut::<PersistentData>().user_settings.theme = ThemeKind::HighContrast;
+        app.update();
+        let theme = app.world().resource::<Theme>();
+        assert_eq!(theme.kind, ThemeKind::HighContrast);
+        assert_eq!(theme.name, "High Contrast");
+
+        let colors = theme.undo_button;
+        assert_eq!(colors.background(Interaction::None), colors.normal);
+        assert_eq!(colors.background(Interaction::Pressed), colors.pressed);
+    }
+
+    #[test]
+    fn test_music_follows_state() {
+        let standard = GameMode::Standard;
+        assert_eq!(MusicTrack::for_state(&AppState::Loading, &GameState::Playing, &standard), None);
+        assert_eq!(MusicTrack::for_state(&AppState::Customization, &GameState::Won, &standard), Some(MusicTrack::Menu));
+        assert_eq!(MusicTrack::for_state(&AppState::Statistics, &GameState::Playing, &standard), Some(MusicTrack::Menu));
+        assert_eq!(MusicTrack::for_state(&AppState::Ready, &GameState::Playing, &standard), Some(MusicTrack::Gameplay));
+        assert_eq!(MusicTrack::for_state(&AppState::Ready, &GameState::Paused, &standard), Some(MusicTrack::Gameplay));
+        assert_eq!(MusicTrack::for_state(&AppState::Ready, &GameState::Won, &standard), Some(MusicTrack::Victory));
+        assert_eq!(MusicTrack::for_state(&AppState::Ready, &GameState::Playing, &GameMode::Zen), Some(MusicTrack::Zen));
+        assert_eq!(MusicTrack::for_state(&AppState::Ready, &GameState::Won, &GameMode::Zen), Some(MusicTrack::Victory));
+        assert!(!MusicTrack::Victory.loops());
+    }
+
+    #[test]
+    fn test_leaderboard_placement_message() {
+        let placement = LeaderboardPlacement { preset: PresetKind::CuriousCat, rank: None };
+        let locale = Locale::default();
+        assert_eq!(placement.message(&locale), None);
+        let placement = LeaderboardPlacement { rank: Some(0), ..placement };
+        assert_eq!(placement.message(&locale).unwrap(), "🏆 New record for 😸 Curious Cat!");
+        let placement = LeaderboardPlacement { rank: Some(2), ..placement };
+        assert_eq!(placement.message(&locale).unwrap(), "🏅 #3 on the 😸 Curious Cat leaderboard");
+        let spanish = Locale::for_language(nine_lives_core::Language::Spanish);
+        assert_eq!(placement.message(&spanish).unwrap(), "🏅 N.º 3 en la clasificación de 😸 Gato Curioso");
+
+        let entry = nine_lives_core::LeaderboardEntry { seconds: 192, completed_at: 0, hints_used: 1, mistakes: 2, score: 415 };
+        assert_eq!(leaderboard_entry_line(0, &entry), "1. 03:12  1970-01-01\n    💡1  ✗2  415 pts");
+    }
+
+    #[test]
+    fn test_focus_order() {
+        let [new_game, cell_a, cell_b, pad, hint] = [1, 2, 3, 4, 5].map(Entity::from_raw);
+        let stops = [
+            (Focusable::Board, cell_a),
+            (Focusable::Board, cell_b),
+            (Focusable::NumberPad(0), pad),
+            (Focusable::NewGame, new_game),
+            (Focusable::Hint, hint),
+        ];
+        let mut focus = FocusManager::default();
+
+        // The board's cells are a single stop, and focus wraps at either end
+        assert_eq!(focus.step(&stops, true), Some(cell_a));
+        assert_eq!(focus.step(&stops, true), Some(pad));
+        assert_eq!(focus.step(&stops, false), Some(cell_a));
+        assert_eq!(focus.step(&stops, false), Some(hint));
+        assert_eq!(focus.step(&stops, true), Some(cell_a));
+
+        // Tabbing back onto the board returns to the last focused cell
+        focus.focus(cell_b, Focusable::Board);
+        assert_eq!(focus.step(&stops, true), Some(pad));
+        assert_eq!(focus.step(&stops, false), Some(cell_b));
+
+        assert_eq!(focus.step(&[], true), None);
+        assert_eq!(focus.focused, None);
+    }
+
     #[test]
     fn test_cell_component() {
         let cell = Cell { row: 5, col: 3 };
         assert_eq!(cell.row, 5);
         assert_eq!(cell.col, 3);
     }
+
+    #[test]
+    fn test_fallback_puzzle_fits_the_board_size() {
+        for grid_size in [GridSize::Mini, GridSize::Small, GridSize::Classic] {
+            let settings = PuzzleSettings::from_preset(PresetKind::NightProwler).with_grid_size(grid_size);
+            let (board, solution) = fallback_puzzle(&settings);
+            let cells = board.size() * board.size();
+            let givens = board.cells.iter().flatten().filter(|cell| cell.is_some()).count();
+            assert_eq!((board.grid_size, solution.cells.len()), (grid_size, board.size()));
+            assert!(givens > 0 && givens < cells, "A {:?} fallback has cats left to place", grid_size);
+        }
+    }
 }
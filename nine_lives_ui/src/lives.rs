@@ -0,0 +1,248 @@
+//! Lives display for Nine Lives Cat Sudoku.
+//!
+//! With lives turned on, a row of nine little cats above the board shows the
+//! lives left in the puzzle. Lost lives grey out, and the row shakes whenever
+//! one goes. When the last one goes, a game-over overlay covers the board and
+//! offers to try the puzzle again or start a new one.
+
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use nine_lives_core::{GameMode, Lives, PersistentData, STARTING_LIVES};
+
+use crate::{ConfirmAction, ConfirmedAction, Focusable, NewGameButton};
+
+/// Color of a life the player still has
+const LIFE_COLOR: Color = Color::WHITE;
+/// Color of a lost life
+const LOST_LIFE_COLOR: Color = Color::srgba(0.4, 0.4, 0.4, 0.5);
+/// How long the row shakes after a life is lost, in seconds
+const SHAKE_SECONDS: f32 = 0.4;
+/// How far the row swings either way at the start of a shake, in pixels
+const SHAKE_DISTANCE: f32 = 6.0;
+/// How many times a second the row swings back and forth
+const SHAKE_FREQUENCY: f32 = 12.0;
+
+// --- Components ---
+
+/// Component to tag the row of lives, with the time left in its shake.
+#[derive(Component, Default)]
+pub struct LivesHud {
+    pub shake_remaining: f32,
+}
+
+/// Component for one life in the row, counting from the left.
+#[derive(Component)]
+pub struct LifeIcon(pub usize);
+
+/// Component to tag the game-over overlay.
+#[derive(Component)]
+pub struct GameOverOverlay;
+
+/// Component to tag the game-over overlay's "Try Again" button.
+#[derive(Component)]
+pub struct TryAgainButton;
+
+// --- Layout ---
+
+/// Spawn the row of lives (hidden unless lives are turned on).
+pub fn spawn_lives_hud(parent: &mut ChildSpawnerCommands, enabled: bool) {
+    parent
+        .spawn((
+            LivesHud::default(),
+            Node {
+                display: if enabled { Display::Flex } else { Display::None },
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(2.0),
+                margin: UiRect::bottom(Val::Px(8.0)),
+                ..default()
+            },
+        ))
+        .with_children(|row| {
+            for index in 0..STARTING_LIVES {
+                row.spawn((
+                    LifeIcon(index),
+                    Text::new("🐱"),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(LIFE_COLOR),
+                ));
+            }
+        });
+}
+
+/// Spawn the (initially hidden) game-over overlay on the game screen.
+/// Like the confirmation dialog, it blocks clicks on the board underneath.
+pub fn spawn_game_over_overlay(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            GameOverOverlay,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.0, 0.0, 0.75)),
+            FocusPolicy::Block,
+            GlobalZIndex(10),
+        ))
+        .with_children(|overlay| {
+            overlay
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(16.0),
+                        padding: UiRect::all(Val::Px(24.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.1, 0.12)),
+                    BorderColor(Color::srgb(0.7, 0.3, 0.3)),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::new("😿 Out of lives!"),
+                        TextFont {
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 0.6, 0.6)),
+                    ));
+                    panel.spawn((
+                        Text::new("All nine lives are spent on this puzzle."),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                    ));
+
+                    panel
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(16.0),
+                            ..default()
+                        })
+                        .with_children(|buttons| {
+                            buttons
+                                .spawn((
+                                    Button,
+                                    TryAgainButton,
+                                    Focusable::TryAgain,
+                                    Node {
+                                        width: Val::Px(120.0),
+                                        height: Val::Px(40.0),
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::Center,
+                                        border: UiRect::all(Val::Px(2.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(Color::srgb(0.2, 0.5, 0.5)),
+                                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                                ))
+                                .with_children(|button_parent| {
+                                    button_parent.spawn((
+                                        Text::new("Try Again"),
+                                        TextFont {
+                                            font_size: 14.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::WHITE),
+                                    ));
+                                });
+
+                            // Goes through the same handler as the New Game button
+                            buttons
+                                .spawn((
+                                    Button,
+                                    NewGameButton,
+                                    Focusable::GameOverNewGame,
+                                    Node {
+                                        width: Val::Px(120.0),
+                                        height: Val::Px(40.0),
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::Center,
+                                        border: UiRect::all(Val::Px(2.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(Color::srgb(0.2, 0.6, 0.2)),
+                                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                                ))
+                                .with_children(|button_parent| {
+                                    button_parent.spawn((
+                                        Text::new("New Game"),
+                                        TextFont {
+                                            font_size: 14.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::WHITE),
+                                    ));
+                                });
+                        });
+                });
+        });
+}
+
+// --- Systems ---
+
+/// System that greys out lost lives, starts a shake when one goes, and shows
+/// the game-over overlay once they're all gone.
+pub fn update_lives_hud(
+    lives: Res<Lives>,
+    persistent_data: Res<PersistentData>,
+    game_mode: Res<GameMode>,
+    mut shown: Local<Option<usize>>,
+    mut hud_query: Query<(&mut LivesHud, &mut Node), Without<GameOverOverlay>>,
+    mut icon_query: Query<(&LifeIcon, &mut TextColor)>,
+    mut overlay_query: Query<&mut Node, With<GameOverOverlay>>,
+) {
+    // Zen mode puts the lives away along with every other penalty
+    let enabled = persistent_data.user_settings.nine_lives && game_mode.has_penalties();
+    let life_lost = shown.is_some_and(|shown| lives.remaining < shown);
+    *shown = Some(lives.remaining);
+
+    for (mut hud, mut node) in &mut hud_query {
+        node.display = if enabled { Display::Flex } else { Display::None };
+        if life_lost {
+            hud.shake_remaining = SHAKE_SECONDS;
+        }
+    }
+    for (icon, mut color) in &mut icon_query {
+        color.0 = if icon.0 < lives.remaining { LIFE_COLOR } else { LOST_LIFE_COLOR };
+    }
+    for mut node in &mut overlay_query {
+        node.display = if enabled && lives.is_out() { Display::Flex } else { Display::None };
+    }
+}
+
+/// System that shakes the row of lives side to side, dying down over the shake.
+pub fn shake_lives_hud(time: Res<Time>, mut hud_query: Query<(&mut LivesHud, &mut Node)>) {
+    for (mut hud, mut node) in &mut hud_query {
+        if hud.shake_remaining <= 0.0 {
+            continue;
+        }
+        hud.shake_remaining = (hud.shake_remaining - time.delta_secs()).max(0.0);
+        let elapsed = SHAKE_SECONDS - hud.shake_remaining;
+        let swing = (elapsed * SHAKE_FREQUENCY * std::f32::consts::TAU).sin();
+        node.left = Val::Px(swing * SHAKE_DISTANCE * hud.shake_remaining / SHAKE_SECONDS);
+    }
+}
+
+/// System that restarts the puzzle from the game-over overlay. No need to
+/// ask first: there's nothing left to lose.
+pub fn handle_try_again_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<TryAgainButton>)>,
+    mut confirmed: EventWriter<ConfirmedAction>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            confirmed.write(ConfirmedAction(ConfirmAction::RestartPuzzle));
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Localization for Nine Lives Cat Sudoku.
+//!
+//! The interface's words live in one strings file per language, under
+//! `nine_lives_ui/locales/` and built into the game (so the web build needs
+//! nothing extra). Each line is `key = text`, with `{name}` placeholders the
+//! game fills in; keys ending in a number (`streak.1.2`) are variants of the
+//! key without one, so the kitty puns needn't be translated word for word.
+//!
+//! The `Locale` resource holds the strings of the language picked in the
+//! settings (see `UserSettings::language`), falling back to English for
+//! anything a language file leaves out. It's swapped by `sync_locale` when
+//! the setting changes, and `localize_texts` rewrites every `Localized` text
+//! on screen, so the language switches straight away.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use nine_lives_core::{Language, PersistentData, PresetKind};
+
+/// The strings file for `language`.
+fn source(language: Language) -> &'static str {
+    match language {
+        Language::English => include_str!("../locales/en.lang"),
+        Language::Spanish => include_str!("../locales/es.lang"),
+    }
+}
+
+/// Read a strings file into its keys and texts, skipping blank lines and
+/// `#` comments. `\n` in a text is a line break.
+pub fn parse_strings(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().replace("\\n", "\n")))
+        .collect()
+}
+
+/// Fill the `{name}` placeholders in `template` with `args`.
+pub fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
+
+/// The key naming a preset in the strings files.
+fn preset_key(preset: PresetKind) -> &'static str {
+    match preset {
+        PresetKind::CozyKitten => "cozy_kitten",
+        PresetKind::CuriousCat => "curious_cat",
+        PresetKind::StreetwiseStray => "streetwise_stray",
+        PresetKind::NightProwler => "night_prowler",
+    }
+}
+
+/// The interface's words in the player's language.
+#[derive(Resource, Clone, Debug)]
+pub struct Locale {
+    pub language: Language,
+    strings: HashMap<String, String>,
+    /// English, for keys the language leaves out
+    fallback: HashMap<String, String>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::for_language(Language::English)
+    }
+}
+
+impl Locale {
+    /// The strings for `language`.
+    pub fn for_language(language: Language) -> Self {
+        Self {
+            language,
+            strings: parse_strings(source(language)),
+            fallback: parse_strings(source(Language::English)),
+        }
+    }
+
+    /// The text for `key`, in English if the language hasn't got it, or the
+    /// key itself if nobody has.
+    pub fn get(&self, key: &str) -> String {
+        self.strings
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// The text for `key` with its placeholders filled in.
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        fill(&self.get(key), args)
+    }
+
+    /// One of the variants of `key` (`key`, `key.2`, `key.3`, ...), chosen by
+    /// `seed`. Languages with no variants of their own use English's.
+    pub fn variant(&self, key: &str, seed: usize) -> String {
+        let strings = if self.strings.contains_key(key) { &self.strings } else { &self.fallback };
+        let variants: Vec<&String> = std::iter::once(key.to_string())
+            .chain((2..).map(|n| format!("{}.{}", key, n)))
+            .map_while(|variant_key| strings.get(&variant_key))
+            .collect();
+        match variants.len() {
+            0 => key.to_string(),
+            count => variants[seed % count].clone(),
+        }
+    }
+
+    /// A preset's name.
+    pub fn preset_name(&self, preset: PresetKind) -> String {
+        self.get(&format!("preset.{}.name", preset_key(preset)))
+    }
+
+    /// A preset's short description.
+    pub fn preset_description(&self, preset: PresetKind) -> String {
+        self.get(&format!("preset.{}.description", preset_key(preset)))
+    }
+
+    /// A text showing `key`, kept in the player's language.
+    pub fn text(&self, key: &'static str) -> (Text, Localized) {
+        (Text::new(self.get(key)), Localized(key))
+    }
+}
+
+/// Component for a text that shows the string for a key, rewritten when the
+/// language changes.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Localized(pub &'static str);
+
+// --- Systems ---
+
+/// System that swaps in the language picked in the settings, whenever it changes.
+pub fn sync_locale(persistent_data: Res<PersistentData>, mut locale: ResMut<Locale>) {
+    let language = persistent_data.user_settings.language;
+    if locale.language != language {
+        *locale = Locale::for_language(language);
+        info!("🌐 Language changed to {}", language.display_name());
+    }
+}
+
+/// System that rewrites the `Localized` texts on screen in the new language.
+pub fn localize_texts(locale: Res<Locale>, mut text_query: Query<(&Localized, &mut Text)>) {
+    for (localized, mut text) in &mut text_query {
+        text.0 = locale.get(localized.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locales() {
+        let english = Locale::default();
+        let spanish = Locale::for_language(Language::Spanish);
+        assert_eq!(english.get("game.new_game"), "New Game");
+        assert_eq!(spanish.get("game.new_game"), "Nueva partida");
+        assert_eq!(english.format("game.moves", &[("count", "12")]), "Moves: 12");
+        assert_eq!(english.get("no.such.key"), "no.such.key");
+
+        // Every language has every English string
+        for language in Language::all() {
+            let strings = parse_strings(source(language));
+            for key in parse_strings(source(Language::English)).keys() {
+                assert!(strings.contains_key(key), "{} is missing {}", language.code(), key);
+            }
+        }
+
+        // Puns come in variants, picked by the seed
+        assert_eq!(english.variant("streak.1", 0), "{count} purr-fect in a row!");
+        assert_ne!(english.variant("streak.1", 0), english.variant("streak.1", 1));
+        assert_eq!(english.variant("streak.1", 2), english.variant("streak.1", 0));
+        assert_eq!(spanish.preset_name(PresetKind::NightProwler), "😾 Merodeador Nocturno");
+
+        let mut partial = Locale::for_language(Language::Spanish);
+        partial.strings.clear();
+        assert_eq!(partial.get("game.undo"), "⟲ Undo", "Missing strings fall back to English");
+        assert_eq!(partial.variant("streak.2", 0), english.variant("streak.2", 0));
+    }
+}
@@ -0,0 +1,152 @@
+//! Background music for Nine Lives Cat Sudoku.
+//!
+//! The music director picks a track for the current screen and game state (a
+//! calm theme on the menus, a focused loop while solving, a soft soundscape
+//! instead in Zen mode, a short sting on a win) and crossfades to it whenever
+//! that choice changes. Tracks are loaded
+//! from `assets/music/`; a missing file just means that track stays silent.
+
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use nine_lives_core::{GameMode, GameState, PersistentData};
+
+use crate::AppState;
+
+/// How long a crossfade between two tracks takes, in seconds
+const CROSSFADE_SECONDS: f32 = 1.5;
+
+/// A piece of background music.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MusicTrack {
+    /// Calm theme for the menus and statistics screen.
+    Menu,
+    /// Focused loop while solving.
+    Gameplay,
+    /// Soft soundscape while solving in Zen mode.
+    Zen,
+    /// One-shot sting when the puzzle is solved.
+    Victory,
+}
+
+impl MusicTrack {
+    /// The track that should be playing for the given screen, game state, and mode.
+    pub fn for_state(app_state: &AppState, game_state: &GameState, game_mode: &GameMode) -> Option<Self> {
+        match (app_state, game_state) {
+            (AppState::Loading, _) => None,
+            (
+                AppState::Customization
+                | AppState::Statistics
+                | AppState::Settings
+                | AppState::Campaign
+                | AppState::Packs
+                | AppState::Profiles
+                | AppState::TrainingGrounds,
+                _,
+            ) => Some(MusicTrack::Menu),
+            (AppState::Ready, GameState::Won) => Some(MusicTrack::Victory),
+            (AppState::Ready, _) if *game_mode == GameMode::Zen => Some(MusicTrack::Zen),
+            (AppState::Ready, _) => Some(MusicTrack::Gameplay),
+        }
+    }
+
+    /// Asset path of the track.
+    pub fn path(&self) -> &'static str {
+        match self {
+            MusicTrack::Menu => "music/menu.ogg",
+            MusicTrack::Gameplay => "music/gameplay.ogg",
+            MusicTrack::Zen => "music/zen.ogg",
+            MusicTrack::Victory => "music/victory.ogg",
+        }
+    }
+
+    /// Whether the track loops; the victory sting plays once.
+    pub fn loops(&self) -> bool {
+        !matches!(self, MusicTrack::Victory)
+    }
+}
+
+// --- Components & Resources ---
+
+/// Component on every playing music entity, tracking its crossfade.
+#[derive(Component)]
+pub struct MusicChannel {
+    pub track: MusicTrack,
+    /// Current fade level, from 0.0 (silent) to 1.0 (full volume)
+    pub level: f32,
+    /// Whether the channel is fading in (the current track) or out
+    pub fading_in: bool,
+}
+
+/// Resource remembering which track the director last asked for.
+#[derive(Resource, Default)]
+pub struct MusicDirector {
+    pub current: Option<MusicTrack>,
+}
+
+// --- Systems ---
+
+/// System that starts the right track for the current state, fading out
+/// whatever was playing before.
+pub fn music_director_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    app_state: Res<State<AppState>>,
+    game_state: Res<GameState>,
+    game_mode: Res<GameMode>,
+    mut director: ResMut<MusicDirector>,
+    mut channels: Query<&mut MusicChannel>,
+) {
+    let wanted = MusicTrack::for_state(app_state.get(), &game_state, &game_mode);
+    if wanted == director.current {
+        return;
+    }
+    director.current = wanted;
+
+    for mut channel in &mut channels {
+        channel.fading_in = false;
+    }
+
+    if let Some(track) = wanted {
+        let playback = if track.loops() { PlaybackSettings::LOOP } else { PlaybackSettings::DESPAWN };
+        commands.spawn((
+            AudioPlayer::new(asset_server.load(track.path())),
+            playback.with_volume(Volume::Linear(0.0)),
+            MusicChannel {
+                track,
+                level: 0.0,
+                fading_in: true,
+            },
+        ));
+        info!("🎵 Music: {:?}", track);
+    }
+}
+
+/// System that ramps each music channel towards its target level, scaled by
+/// the player's volume setting, and removes channels once they've faded out.
+pub fn crossfade_music_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    persistent_data: Res<PersistentData>,
+    mut channels: Query<(Entity, &mut MusicChannel, Option<&mut AudioSink>)>,
+) {
+    let step = time.delta_secs() / CROSSFADE_SECONDS;
+    let volume = persistent_data.user_settings.volume.clamp(0.0, 1.0);
+
+    for (entity, mut channel, sink) in &mut channels {
+        channel.level = if channel.fading_in {
+            (channel.level + step).min(1.0)
+        } else {
+            (channel.level - step).max(0.0)
+        };
+
+        if !channel.fading_in && channel.level <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // The sink only appears once the track has loaded
+        if let Some(mut sink) = sink {
+            sink.set_volume(Volume::Linear(channel.level * volume));
+        }
+    }
+}
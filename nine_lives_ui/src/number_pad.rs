@@ -0,0 +1,489 @@
+//! Number pad for Nine Lives Cat Sudoku.
+//!
+//! A row of cat buttons below the grid, each counting how many of its cat
+//! are still to be placed (greyed out once they all are). Selecting one makes it the `ActiveDigit`:
+//! clicking a cell then places that cat (with the cat-first input model,
+//! click after click, stamping it across the board), and in assisted mode
+//! hovering a cell previews whether the placement would clash. With the
+//! select-then-type input model the buttons type into the `SelectedCell`
+//! instead (the controller handles those presses).
+//!
+//! The eraser at the end of the row empties cells: it's a tool like the cats
+//! (clicks empty cells until it's pressed again, or a cat is picked), or with
+//! select-then-type it empties the selected cell at once.
+//!
+//! The notes toggle after it switches notes mode: cats go into cells as
+//! notes (see `nine_lives_core::notes`) rather than being placed, and
+//! pressing a noted cat again takes the note away.
+//!
+//! Beside it, toggles switch between assisted and strict modes and step
+//! through the input models, each showing the one in use.
+
+use bevy::prelude::*;
+use nine_lives_core::{AssistMode, BoardState, GlyphSet, InputModel, PersistentData};
+
+use crate::{Cell, ErrorToast, Focusable, Theme};
+
+/// Normal number pad button background color
+const PAD_NORMAL_BG: Color = Color::srgb(0.25, 0.25, 0.35);
+/// Selected number pad button background color
+const PAD_SELECTED_BG: Color = Color::srgb(0.2, 0.5, 0.7);
+/// Background of a number pad button whose cats are all placed
+const PAD_DONE_BG: Color = Color::srgb(0.18, 0.18, 0.2);
+/// Text color of a number pad button
+const PAD_TEXT: Color = Color::WHITE;
+/// Text color of a number pad button whose cats are all placed
+const PAD_DONE_TEXT: Color = Color::srgb(0.45, 0.45, 0.5);
+/// Border shown on a hovered cell when the active cat fits there
+const PREVIEW_VALID_BORDER: Color = Color::srgb(0.2, 0.8, 0.3);
+/// Border shown on a hovered cell when the active cat would clash
+const PREVIEW_CONFLICT_BORDER: Color = Color::srgb(0.9, 0.2, 0.2);
+/// Border shown on the cell selected for typing
+const SELECTED_CELL_BORDER: Color = Color::srgb(0.2, 0.5, 0.9);
+
+// --- Components & Resources ---
+
+/// The cat currently selected on the number pad, if any, or the eraser.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ActiveDigit {
+    pub value: Option<usize>,
+    /// Whether the eraser is picked instead of a cat
+    pub erasing: bool,
+    /// Whether cats are noted in cells rather than placed
+    pub noting: bool,
+}
+
+/// The cell typed cats go into, with the select-then-type input model.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SelectedCell {
+    pub cell: Option<(usize, usize)>,
+}
+
+/// Component to tag a number pad button with the cat value it selects.
+#[derive(Component)]
+pub struct NumberPadButton {
+    pub value: usize,
+}
+
+/// Component to tag the count of a cat still to be placed, under its
+/// number pad button's glyph.
+#[derive(Component)]
+pub struct NumberPadCount {
+    pub value: usize,
+}
+
+/// Component to tag the eraser button on the number pad.
+#[derive(Component)]
+pub struct EraserButton;
+
+/// Component to tag the notes mode toggle on the number pad.
+#[derive(Component)]
+pub struct NotesButton;
+
+/// Component to tag the button that switches between assisted and strict modes.
+#[derive(Component)]
+pub struct AssistModeButton;
+
+/// Component to tag the button that steps through the input models.
+#[derive(Component)]
+pub struct InputModelButton;
+
+/// Label for the input model button, like "✋ Cat first".
+fn input_model_label(model: InputModel) -> String {
+    format!("✋ {}", model.display_name())
+}
+
+// --- Layout ---
+
+/// Spawn the number pad row as a child of the game screen,
+/// with one button per cat on a board of `size` rows.
+pub fn spawn_number_pad(parent: &mut ChildSpawnerCommands, size: usize, glyphs: GlyphSet) {
+    parent
+        .spawn(Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(6.0),
+            margin: UiRect::top(Val::Px(12.0)),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        })
+        .with_children(|pad| {
+            for value in 0..size {
+                pad.spawn((
+                    Button,
+                    NumberPadButton { value },
+                    Focusable::NumberPad(value),
+                    Node {
+                        width: Val::Px(40.0),
+                        height: Val::Px(35.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(PAD_NORMAL_BG),
+                    BorderColor(Color::srgb(0.4, 0.4, 0.5)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        Text::new(format!("🐱{}", glyphs.glyph(value))),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(PAD_TEXT),
+                    ));
+                    button_parent.spawn((
+                        Text::new(format!("×{}", size)),
+                        TextFont {
+                            font_size: 9.0,
+                            ..default()
+                        },
+                        TextColor(PAD_TEXT),
+                        NumberPadCount { value },
+                    ));
+                });
+            }
+
+            // Eraser
+            pad.spawn((
+                Button,
+                EraserButton,
+                Focusable::Eraser,
+                Node {
+                    width: Val::Px(70.0),
+                    height: Val::Px(35.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BackgroundColor(PAD_NORMAL_BG),
+                BorderColor(Color::srgb(0.4, 0.4, 0.5)),
+            ))
+            .with_children(|button_parent| {
+                button_parent.spawn((
+                    Text::new("🧹 Erase"),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            });
+
+            // Notes mode
+            pad.spawn((
+                Button,
+                NotesButton,
+                Focusable::Notes,
+                Node {
+                    width: Val::Px(70.0),
+                    height: Val::Px(35.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BackgroundColor(PAD_NORMAL_BG),
+                BorderColor(Color::srgb(0.4, 0.4, 0.5)),
+            ))
+            .with_children(|button_parent| {
+                button_parent.spawn((
+                    Text::new("✏️ Notes"),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            });
+
+            // Assisted / strict toggle
+            pad.spawn((
+                Button,
+                AssistModeButton,
+                Focusable::AssistMode,
+                Node {
+                    width: Val::Px(100.0),
+                    height: Val::Px(35.0),
+                    margin: UiRect::left(Val::Px(10.0)),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+            ))
+            .with_children(|button_parent| {
+                button_parent.spawn((
+                    Text::new(AssistMode::default().display_name()),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            });
+
+            // Input model toggle
+            pad.spawn((
+                Button,
+                InputModelButton,
+                Focusable::InputModel,
+                Node {
+                    width: Val::Px(140.0),
+                    height: Val::Px(35.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+            ))
+            .with_children(|button_parent| {
+                button_parent.spawn((
+                    Text::new(input_model_label(InputModel::default())),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            });
+        });
+}
+
+// --- Systems ---
+
+/// System to select (or deselect) a cat on the number pad, for the input
+/// models that pick a cat before clicking cells.
+pub fn handle_number_pad_selection(
+    interaction_query: Query<(&Interaction, &NumberPadButton), Changed<Interaction>>,
+    persistent_data: Res<PersistentData>,
+    mut active_digit: ResMut<ActiveDigit>,
+) {
+    if !persistent_data.user_settings.input_model.picks_from_number_pad() {
+        return;
+    }
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            // Pressing the active cat again deselects it
+            active_digit.value = if active_digit.value == Some(button.value) {
+                None
+            } else {
+                Some(button.value)
+            };
+            active_digit.erasing = false;
+        }
+    }
+}
+
+/// System to switch notes mode on or off.
+pub fn handle_notes_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<NotesButton>)>,
+    mut active_digit: ResMut<ActiveDigit>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            active_digit.noting = !active_digit.noting;
+        }
+    }
+}
+
+/// System that highlights the active cat (or the eraser, and the notes
+/// toggle while it's on) on the number pad, and darkens the cats that are
+/// all placed.
+#[allow(clippy::type_complexity)]
+pub fn sync_number_pad_highlights(
+    board: Res<BoardState>,
+    active_digit: Res<ActiveDigit>,
+    mut button_query: Query<(&NumberPadButton, &mut BackgroundColor)>,
+    mut eraser_query: Query<&mut BackgroundColor, (With<EraserButton>, Without<NumberPadButton>)>,
+    mut notes_query: Query<&mut BackgroundColor, (With<NotesButton>, Without<NumberPadButton>, Without<EraserButton>)>,
+) {
+    let remaining = board.remaining_counts();
+    for (button, mut bg_color) in &mut button_query {
+        bg_color.0 = if active_digit.value == Some(button.value) {
+            PAD_SELECTED_BG
+        } else if remaining.get(button.value) == Some(&0) {
+            PAD_DONE_BG
+        } else {
+            PAD_NORMAL_BG
+        };
+    }
+    for mut bg_color in &mut eraser_query {
+        bg_color.0 = if active_digit.erasing { PAD_SELECTED_BG } else { PAD_NORMAL_BG };
+    }
+    for mut bg_color in &mut notes_query {
+        bg_color.0 = if active_digit.noting { PAD_SELECTED_BG } else { PAD_NORMAL_BG };
+    }
+}
+
+/// The count under a number pad cat: "×3" while some are left, "✓" once
+/// they're all placed.
+fn remaining_label(left: usize) -> String {
+    if left == 0 { "✓".to_string() } else { format!("×{}", left) }
+}
+
+/// System that counts down each cat on the number pad as it's placed, and
+/// greys out its button once they all are.
+pub fn update_number_pad_counts(
+    board: Res<BoardState>,
+    button_query: Query<(&NumberPadButton, &Children)>,
+    mut text_query: Query<(&mut Text, &mut TextColor, Option<&NumberPadCount>)>,
+) {
+    let remaining = board.remaining_counts();
+    for (button, children) in &button_query {
+        let left = remaining.get(button.value).copied().unwrap_or(0);
+        for child in children.iter() {
+            if let Ok((mut text, mut color, count)) = text_query.get_mut(child) {
+                color.0 = if left == 0 { PAD_DONE_TEXT } else { PAD_TEXT };
+                if count.is_some() {
+                    text.0 = remaining_label(left);
+                }
+            }
+        }
+    }
+}
+
+/// System to switch between assisted and strict modes.
+/// The choice is saved to the user's settings.
+pub fn handle_assist_mode_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<AssistModeButton>)>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let settings = &mut persistent_data.user_settings;
+            settings.assist_mode = match settings.assist_mode {
+                AssistMode::Assisted => AssistMode::Strict,
+                AssistMode::Strict => AssistMode::Assisted,
+            };
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+        }
+    }
+}
+
+/// System to keep the assist mode button label in sync with the setting.
+pub fn update_assist_mode_button_text(
+    persistent_data: Res<PersistentData>,
+    button_query: Query<&Children, With<AssistModeButton>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for children in &button_query {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = persistent_data.user_settings.assist_mode.display_name().to_string();
+                break;
+            }
+        }
+    }
+}
+
+/// System to step to the next input model. The picked cat and selected
+/// cell are dropped, since they mean something else in the new model (notes
+/// mode stays as it was), and the choice is saved to the user's settings.
+pub fn handle_input_model_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<InputModelButton>)>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut active_digit: ResMut<ActiveDigit>,
+    mut selected: ResMut<SelectedCell>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let settings = &mut persistent_data.user_settings;
+            settings.input_model = settings.input_model.next();
+            *active_digit = ActiveDigit { noting: active_digit.noting, ..default() };
+            *selected = SelectedCell::default();
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+        }
+    }
+}
+
+/// System to keep the input model button label in sync with the setting.
+pub fn update_input_model_button_text(
+    persistent_data: Res<PersistentData>,
+    button_query: Query<&Children, With<InputModelButton>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for children in &button_query {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = input_model_label(persistent_data.user_settings.input_model);
+                break;
+            }
+        }
+    }
+}
+
+/// System that previews the active cat on the hovered cell: a green outline
+/// if it fits, red if it would clash. Only runs in assisted mode, and must
+/// run after the regular hover effects so it wins the border color.
+pub fn update_placement_preview(
+    board: Res<BoardState>,
+    active_digit: Res<ActiveDigit>,
+    persistent_data: Res<PersistentData>,
+    mut cell_query: Query<(&Cell, &Interaction, &mut BorderColor)>,
+) {
+    let Some(value) = active_digit.value else {
+        return;
+    };
+    if !persistent_data.user_settings.assist_mode.shows_previews() {
+        return;
+    }
+
+    for (cell, interaction, mut border_color) in &mut cell_query {
+        if *interaction != Interaction::Hovered || board.is_given_cell(cell.row, cell.col) {
+            continue;
+        }
+        border_color.0 = if board.is_valid_placement(cell.row, cell.col, value) {
+            PREVIEW_VALID_BORDER
+        } else {
+            PREVIEW_CONFLICT_BORDER
+        };
+    }
+}
+
+/// System that outlines the cell selected for typing in blue, and puts the
+/// previously selected cell's border back. Runs after the hover effects so
+/// the selection stays visible under the cursor.
+pub fn update_selected_cell_border(
+    board: Res<BoardState>,
+    selected: Res<SelectedCell>,
+    theme: Res<Theme>,
+    mut previous: Local<Option<(usize, usize)>>,
+    mut cell_query: Query<(&Cell, &mut BorderColor)>,
+) {
+    for (cell, mut border_color) in &mut cell_query {
+        let position = Some((cell.row, cell.col));
+        if position == selected.cell {
+            border_color.set_if_neq(BorderColor(SELECTED_CELL_BORDER));
+        } else if position == *previous {
+            border_color.0 = theme.cell_border(board.is_given_cell(cell.row, cell.col));
+        }
+    }
+    *previous = selected.cell;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_label() {
+        assert_eq!(remaining_label(9), "×9");
+        assert_eq!(remaining_label(1), "×1");
+        assert_eq!(remaining_label(0), "✓");
+    }
+}
@@ -0,0 +1,405 @@
+//! Puzzle Packs screen for Nine Lives Cat Sudoku.
+//!
+//! Reached with the 📦 Packs button on the customization screen. Dropping an
+//! `.sdm` or `.json` pack file (see `nine_lives_core::packs`) onto the window
+//! while here imports it into the `PackLibrary`, which is saved alongside the
+//! other save files. The imported packs are listed across the top; picking
+//! one shows its puzzles, solved ones ticked, and picking a puzzle plays it.
+
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use bevy::window::FileDragAndDrop;
+use nine_lives_core::{NineLivesError, PackLibrary, PuzzlePack, solve_puzzle};
+
+use crate::{AppState, Focusable, LastGeneration, PuzzleSwap};
+
+/// Height of the scrolling grid of puzzles
+const GRID_HEIGHT: f32 = 300.0;
+/// Pixels scrolled per line of mouse wheel movement (see `scroll_list_system`)
+pub(crate) const SCROLL_LINE_HEIGHT: f32 = 40.0;
+/// Background of a pack's button, or a puzzle's
+const PACK_BG: Color = Color::srgb(0.25, 0.3, 0.5);
+/// Background of the selected pack's button
+const SELECTED_PACK_BG: Color = Color::srgb(0.35, 0.5, 0.75);
+/// Background of a puzzle that's been solved
+const SOLVED_BG: Color = Color::srgb(0.2, 0.45, 0.3);
+
+// --- Components & Resources ---
+
+/// Component to tag the packs screen root for cleanup.
+#[derive(Component)]
+pub struct PacksScreenRoot;
+
+/// Component to tag the "Packs" button on the customization screen.
+#[derive(Component)]
+pub struct PacksButton;
+
+/// Component to tag the "Back" button on the packs screen.
+#[derive(Component)]
+pub struct PacksBackButton;
+
+/// Component to tag the row of pack buttons.
+#[derive(Component)]
+pub struct PackList;
+
+/// Component to tag the line describing the selected pack.
+#[derive(Component)]
+pub struct PackDetailsText;
+
+/// Component to tag the scrolling grid of the selected pack's puzzles.
+#[derive(Component)]
+pub struct PackPuzzleGrid;
+
+/// Component to tag the line reporting imports and problems.
+#[derive(Component)]
+pub struct PackStatusText;
+
+/// Component for a pack's button, holding its place in the library.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackSelectButton(pub usize);
+
+/// Component for a puzzle's button, holding its index in the selected pack.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackPuzzleButton(pub usize);
+
+/// Resource tracking the pack picked on the packs screen, and the latest
+/// import result or problem to report.
+#[derive(Resource, Debug, Default)]
+pub struct PackBrowser {
+    pub selected: usize,
+    pub status: String,
+}
+
+/// The line describing a pack, like "Alley Cats by Tom • Hard • 3/20 solved".
+pub fn pack_details(pack: &PuzzlePack) -> String {
+    let mut details = pack.name.clone();
+    if let Some(author) = &pack.author {
+        details.push_str(&format!(" by {}", author));
+    }
+    if let Some(difficulty) = pack.difficulty {
+        details.push_str(&format!(" • {}", difficulty.name()));
+    }
+    details.push_str(&format!(" • {}", pack.progress_label()));
+    details
+}
+
+// --- Layout ---
+
+/// System that creates the packs screen UI. The pack list and puzzle grid
+/// are filled in by `update_pack_browser`.
+pub fn setup_packs_screen(mut commands: Commands, mut browser: ResMut<PackBrowser>) {
+    browser.status = "Drop an .sdm or .json puzzle pack onto the window to import it.".to_string();
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+            PacksScreenRoot,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new("📦 Puzzle Packs"),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.8)),
+                PackStatusText,
+            ));
+
+            parent.spawn((
+                PackList,
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(8.0),
+                    row_gap: Val::Px(8.0),
+                    max_width: Val::Px(640.0),
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                PackDetailsText,
+            ));
+            parent.spawn((
+                PackPuzzleGrid,
+                RelativeCursorPosition::default(),
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    align_content: AlignContent::FlexStart,
+                    column_gap: Val::Px(6.0),
+                    row_gap: Val::Px(6.0),
+                    width: Val::Px(600.0),
+                    max_height: Val::Px(GRID_HEIGHT),
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+                ScrollPosition::default(),
+            ));
+
+            // Back button
+            parent
+                .spawn((
+                    Button,
+                    PacksBackButton,
+                    Focusable::PacksBack,
+                    Node {
+                        width: Val::Px(160.0),
+                        height: Val::Px(45.0),
+                        margin: UiRect::top(Val::Px(20.0)),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                    BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        Text::new("⬅ Back"),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+/// Spawn a small button with a label.
+fn spawn_pack_button(parent: &mut ChildSpawnerCommands, bundle: impl Bundle, label: String, width: f32, background: Color) {
+    parent
+        .spawn((
+            Button,
+            bundle,
+            Node {
+                min_width: Val::Px(width),
+                height: Val::Px(34.0),
+                padding: UiRect::horizontal(Val::Px(8.0)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(background),
+            BorderColor(Color::srgb(0.45, 0.5, 0.8)),
+        ))
+        .with_children(|button_parent| {
+            button_parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+// --- Systems ---
+
+/// System to clean up the packs screen when exiting that state.
+pub fn cleanup_packs_screen(mut commands: Commands, query: Query<Entity, With<PacksScreenRoot>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// System that opens the packs screen from the customization screen.
+pub fn handle_packs_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<PacksButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::Packs);
+        }
+    }
+}
+
+/// System that returns from the packs screen to the customization screen.
+pub fn handle_packs_back_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<PacksBackButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::Customization);
+        }
+    }
+}
+
+/// System that imports pack files dropped onto the window, saving the
+/// library and picking the new pack.
+pub fn import_dropped_packs(
+    mut drops: EventReader<FileDragAndDrop>,
+    mut library: ResMut<PackLibrary>,
+    mut browser: ResMut<PackBrowser>,
+) {
+    for drop in drops.read() {
+        let FileDragAndDrop::DroppedFile { path_buf, .. } = drop else {
+            continue;
+        };
+        browser.status = match PuzzlePack::import(path_buf) {
+            Ok(pack) => {
+                let message = format!("📦 Imported {} ({} puzzles)", pack.name, pack.puzzles.len());
+                browser.selected = library.add(pack);
+                match library.save() {
+                    Ok(()) => message,
+                    Err(e) => format!("{}, but it couldn't be saved: {}", message, e),
+                }
+            }
+            Err(error) => format!("⚠️ Couldn't import: {}", error),
+        };
+        info!("{}", browser.status);
+    }
+}
+
+/// System that picks a pack when its button is pressed.
+pub fn handle_pack_select_buttons(
+    interaction_query: Query<(&Interaction, &PackSelectButton), Changed<Interaction>>,
+    mut browser: ResMut<PackBrowser>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            browser.selected = button.0;
+        }
+    }
+}
+
+/// System that lists the packs and the selected pack's puzzles, whenever
+/// the library or the selection changes (and when the screen opens).
+#[allow(clippy::too_many_arguments)]
+pub fn update_pack_browser(
+    mut commands: Commands,
+    library: Res<PackLibrary>,
+    browser: Res<PackBrowser>,
+    opened: Query<(), Added<PackList>>,
+    list_query: Query<Entity, With<PackList>>,
+    grid_query: Query<Entity, With<PackPuzzleGrid>>,
+    mut details_query: Query<&mut Text, (With<PackDetailsText>, Without<PackStatusText>)>,
+    mut status_query: Query<&mut Text, With<PackStatusText>>,
+) {
+    if opened.is_empty() && !library.is_changed() && !browser.is_changed() {
+        return;
+    }
+    for mut text in &mut status_query {
+        text.0.clone_from(&browser.status);
+    }
+
+    let selected = library.packs.get(browser.selected);
+    for mut text in &mut details_query {
+        text.0 = selected.map(pack_details).unwrap_or_default();
+    }
+    for list in &list_query {
+        commands.entity(list).despawn_related::<Children>();
+        commands.entity(list).with_children(|list| {
+            for (index, pack) in library.packs.iter().enumerate() {
+                let background = if index == browser.selected { SELECTED_PACK_BG } else { PACK_BG };
+                let bundle = (PackSelectButton(index), Focusable::PackSelect(index));
+                spawn_pack_button(list, bundle, pack.name.clone(), 80.0, background);
+            }
+        });
+    }
+    for grid in &grid_query {
+        commands.entity(grid).despawn_related::<Children>();
+        let Some(pack) = selected else {
+            continue;
+        };
+        commands.entity(grid).with_children(|grid| {
+            for index in 0..pack.puzzles.len() {
+                let solved = pack.completed.contains(&index);
+                let label = if solved { format!("✓ {}", index + 1) } else { (index + 1).to_string() };
+                let bundle = (PackPuzzleButton(index), Focusable::PackPuzzle(index));
+                spawn_pack_button(grid, bundle, label, 52.0, if solved { SOLVED_BG } else { PACK_BG });
+            }
+        });
+    }
+}
+
+/// Everything needed to start a pack puzzle.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct PackPuzzleStart<'w, 's> {
+    app_state: ResMut<'w, NextState<AppState>>,
+    puzzles: PuzzleSwap<'w, 's>,
+}
+
+impl PackPuzzleStart<'_, '_> {
+    /// Start one of a pack's puzzles, like the Start Game button does for a
+    /// generated one. Explains what went wrong if it can't be played.
+    fn start(&mut self, pack: &PuzzlePack, index: usize) -> Result<(), String> {
+        let Some(board) = pack.board(index) else {
+            return Err(format!("⚠️ Puzzle {} can't be read", index + 1));
+        };
+        let new_solution = match solve_puzzle(&board) {
+            Ok(solution) => solution,
+            Err(NineLivesError::InvalidPuzzle { reason }) => return Err(format!("⚠️ Puzzle {} {}", index + 1, reason)),
+            Err(e) => return Err(format!("⚠️ Puzzle {}: {}", index + 1, e)),
+        };
+
+        let settings = pack.settings(index, &board);
+        info!("📦 Starting puzzle {} of {}: {}", index + 1, pack.name, settings.description());
+        self.puzzles.load(settings, board, new_solution, LastGeneration::default());
+        self.app_state.set(AppState::Ready);
+        Ok(())
+    }
+}
+
+/// System that plays a puzzle from the selected pack when its button is pressed.
+pub fn handle_pack_puzzle_buttons(
+    interaction_query: Query<(&Interaction, &PackPuzzleButton), Changed<Interaction>>,
+    library: Res<PackLibrary>,
+    mut browser: ResMut<PackBrowser>,
+    mut start: PackPuzzleStart,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed
+            && let Some(pack) = library.packs.get(browser.selected)
+            && let Err(message) = start.start(pack, button.0)
+        {
+            browser.status = message;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nine_lives_core::Difficulty;
+
+    #[test]
+    fn test_pack_details() {
+        let mut pack = PuzzlePack::from_sdm("Weekend", &".".repeat(81)).unwrap();
+        assert_eq!(pack_details(&pack), "Weekend • 0/1 solved");
+        pack.author = Some("Tom".to_string());
+        pack.difficulty = Some(Difficulty::Hard);
+        pack.completed.insert(0);
+        assert_eq!(pack_details(&pack), "Weekend by Tom • Hard • 1/1 solved");
+    }
+}
@@ -0,0 +1,377 @@
+//! Profile picker for Nine Lives Cat Sudoku.
+//!
+//! With more than one profile (see `nine_lives_core::ProfileRoster`), the game
+//! opens on the picker, asking who's playing. It's also reached with the 👤
+//! button in the corner of the customization screen. Picking a profile loads
+//! its settings, statistics, saved game, and campaign stars in place of the
+//! current one's; "New Profile" takes a name, adds the profile, and picks it.
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use nine_lives_core::{DEFAULT_PROFILE_NAME, MAX_PROFILE_NAME_LENGTH, PersistentData, ProfileRoster};
+
+use crate::{AppState, ErrorToast, Focusable};
+
+/// Background of a profile's button
+const PROFILE_BG: Color = Color::srgb(0.25, 0.3, 0.5);
+/// Background of the active profile's button
+const ACTIVE_PROFILE_BG: Color = Color::srgb(0.35, 0.5, 0.75);
+
+// --- Components & Resources ---
+
+/// Component to tag the profile screen root for cleanup.
+#[derive(Component)]
+pub struct ProfilesScreenRoot;
+
+/// Component to tag the profile button on the customization screen.
+#[derive(Component)]
+pub struct ProfilesButton;
+
+/// Component to tag the "Back" button on the profile screen.
+#[derive(Component)]
+pub struct ProfilesBackButton;
+
+/// Component to tag the "New Profile" button.
+#[derive(Component)]
+pub struct NewProfileButton;
+
+/// Component to tag the column of profile buttons.
+#[derive(Component)]
+pub struct ProfileList;
+
+/// Component to tag the line showing the name being typed, or problems.
+#[derive(Component)]
+pub struct ProfileNameEntryDisplay;
+
+/// Component for a profile's button, holding its place in the roster.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileSelectButton(pub usize);
+
+/// Resource holding the name being typed for a new profile.
+#[derive(Resource, Default)]
+pub struct ProfileNameEntry {
+    /// Whether key presses currently go into the name
+    pub active: bool,
+    pub name: String,
+    /// Feedback from the last attempt to add a profile
+    pub message: Option<String>,
+}
+
+impl ProfileNameEntry {
+    /// The line of text shown under the "New Profile" button.
+    pub fn display_text(&self) -> String {
+        if self.active {
+            format!("Name: {}▏\nEnter to add • Esc to cancel", self.name)
+        } else {
+            self.message.clone().unwrap_or_default()
+        }
+    }
+}
+
+/// The label of the profile button on the customization screen.
+pub fn profile_button_label(roster: &ProfileRoster) -> String {
+    format!("👤 {}", roster.active_profile().map_or(DEFAULT_PROFILE_NAME, |profile| profile.name.as_str()))
+}
+
+// --- Layout ---
+
+/// System that creates the profile screen UI. The profile buttons are filled
+/// in by `update_profile_list`.
+pub fn setup_profiles_screen(mut commands: Commands, mut entry: ResMut<ProfileNameEntry>) {
+    *entry = ProfileNameEntry::default();
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+            ProfilesScreenRoot,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new("👤 Who's playing?"),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+            ));
+
+            parent.spawn((
+                ProfileList,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(8.0),
+                    ..default()
+                },
+            ));
+
+            spawn_profile_button(
+                parent,
+                (NewProfileButton, Focusable::NewProfile),
+                "➕ New Profile".to_string(),
+                Color::srgb(0.2, 0.5, 0.3),
+            );
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.85, 0.6)),
+                TextLayout::new_with_justify(JustifyText::Center),
+                ProfileNameEntryDisplay,
+            ));
+
+            // Back button
+            parent
+                .spawn((
+                    Button,
+                    ProfilesBackButton,
+                    Focusable::ProfilesBack,
+                    Node {
+                        width: Val::Px(160.0),
+                        height: Val::Px(45.0),
+                        margin: UiRect::top(Val::Px(20.0)),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                    BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        Text::new("⬅ Back"),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+/// Spawn one of the profile screen's wide buttons with a label.
+fn spawn_profile_button(parent: &mut ChildSpawnerCommands, bundle: impl Bundle, label: String, background: Color) {
+    parent
+        .spawn((
+            Button,
+            bundle,
+            Node {
+                width: Val::Px(240.0),
+                height: Val::Px(40.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(background),
+            BorderColor(Color::srgb(0.45, 0.5, 0.8)),
+        ))
+        .with_children(|button_parent| {
+            button_parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+// --- Systems ---
+
+/// System to clean up the profile screen when exiting that state.
+pub fn cleanup_profiles_screen(mut commands: Commands, query: Query<Entity, With<ProfilesScreenRoot>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// System that opens the profile screen from the customization screen.
+pub fn handle_profiles_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ProfilesButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::Profiles);
+        }
+    }
+}
+
+/// System that returns from the profile screen to the customization screen,
+/// keeping the profile that was playing.
+pub fn handle_profiles_back_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ProfilesBackButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::Customization);
+        }
+    }
+}
+
+/// Everything needed to switch to another profile.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct ProfileSwitch<'w> {
+    app_state: ResMut<'w, NextState<AppState>>,
+    roster: ResMut<'w, ProfileRoster>,
+    persistent_data: ResMut<'w, PersistentData>,
+    toasts: EventWriter<'w, ErrorToast>,
+}
+
+impl ProfileSwitch<'_> {
+    /// Make the profile at `index` the one playing, load its data, and go on
+    /// to the customization screen.
+    fn switch(&mut self, index: usize) {
+        if index >= self.roster.profiles.len() {
+            return;
+        }
+        if index != self.roster.active {
+            self.roster.active = index;
+            *self.persistent_data = self.roster.load_active_data();
+        }
+        if let Err(e) = self.roster.save() {
+            self.toasts.write(ErrorToast::new("Profiles", &e));
+        }
+        info!("👤 Playing as {}", self.roster.profiles[index].name);
+        self.app_state.set(AppState::Customization);
+    }
+}
+
+/// System that switches to a profile when its button is pressed.
+pub fn handle_profile_select_buttons(
+    interaction_query: Query<(&Interaction, &ProfileSelectButton), Changed<Interaction>>,
+    mut switch: ProfileSwitch,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            switch.switch(button.0);
+        }
+    }
+}
+
+/// System that starts or cancels typing a new profile's name.
+pub fn handle_new_profile_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<NewProfileButton>)>,
+    mut entry: ResMut<ProfileNameEntry>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            entry.active = !entry.active;
+            entry.name.clear();
+            entry.message = None;
+        }
+    }
+}
+
+/// System that feeds key presses into the new profile's name. Enter adds
+/// the profile and switches to it, and Escape cancels.
+pub fn profile_name_typing_system(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut entry: ResMut<ProfileNameEntry>,
+    mut switch: ProfileSwitch,
+) {
+    if !entry.active {
+        keyboard_events.clear();
+        return;
+    }
+
+    for event in keyboard_events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => {
+                let room = MAX_PROFILE_NAME_LENGTH.saturating_sub(entry.name.chars().count());
+                entry.name.extend(text.chars().filter(|c| !c.is_control()).take(room));
+            }
+            Key::Space if entry.name.chars().count() < MAX_PROFILE_NAME_LENGTH => entry.name.push(' '),
+            Key::Backspace => {
+                entry.name.pop();
+            }
+            Key::Escape => {
+                entry.active = false;
+                entry.name.clear();
+            }
+            Key::Enter => {
+                let name = std::mem::take(&mut entry.name);
+                entry.active = false;
+                match switch.roster.add(&name) {
+                    Ok(index) => switch.switch(index),
+                    Err(problem) => entry.message = Some(format!("⚠️ Couldn't add the profile: {}", problem)),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// System that keeps the line under the "New Profile" button up to date.
+pub fn update_profile_name_entry_display(
+    entry: Res<ProfileNameEntry>,
+    mut display_query: Query<&mut Text, With<ProfileNameEntryDisplay>>,
+) {
+    for mut text in &mut display_query {
+        text.0 = entry.display_text();
+    }
+}
+
+/// System that lists the profiles, whenever the roster changes (and when
+/// the screen opens).
+pub fn update_profile_list(
+    mut commands: Commands,
+    roster: Res<ProfileRoster>,
+    opened: Query<(), Added<ProfileList>>,
+    list_query: Query<Entity, With<ProfileList>>,
+) {
+    if opened.is_empty() && !roster.is_changed() {
+        return;
+    }
+    for list in &list_query {
+        commands.entity(list).despawn_related::<Children>();
+        commands.entity(list).with_children(|list| {
+            for (index, profile) in roster.profiles.iter().enumerate() {
+                let background = if index == roster.active { ACTIVE_PROFILE_BG } else { PROFILE_BG };
+                let bundle = (ProfileSelectButton(index), Focusable::ProfileSelect(index));
+                spawn_profile_button(list, bundle, profile.name.clone(), background);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_labels() {
+        let mut roster = ProfileRoster::default();
+        assert_eq!(profile_button_label(&roster), "👤 Player");
+        roster.active = roster.add("Whiskers").unwrap();
+        assert_eq!(profile_button_label(&roster), "👤 Whiskers");
+
+        let mut entry = ProfileNameEntry { active: true, name: "Wh".to_string(), message: None };
+        assert_eq!(entry.display_text(), "Name: Wh▏\nEnter to add • Esc to cancel");
+        entry.active = false;
+        assert_eq!(entry.display_text(), "");
+    }
+}
@@ -0,0 +1,153 @@
+//! Checking progress for Nine Lives Cat Sudoku.
+//!
+//! The ✔ Check button compares the player's cats against the solution (what
+//! that costs is picked in the settings; see `CheckCost`). The controller
+//! does the checking and sends a `ProgressChecked` event: wrong cats then get
+//! a red outline for a couple of seconds, or if every cat is right, they all
+//! get a green one.
+
+use bevy::prelude::*;
+use nine_lives_core::BoardState;
+
+use crate::{Cell, FocusManager, Focusable};
+
+/// How long checked cells stay marked, in seconds
+const CHECK_MARK_SECONDS: f32 = 2.0;
+/// Outline on a cat that doesn't match the solution
+const CHECK_WRONG_COLOR: Color = Color::srgb(0.95, 0.3, 0.2);
+/// Outline on every cat when they're all right
+const CHECK_RIGHT_COLOR: Color = Color::srgb(0.3, 0.85, 0.4);
+/// Width of a check mark's outline
+const CHECK_MARK_WIDTH: f32 = 3.0;
+
+// --- Events & Components ---
+
+/// Event sent when the board has been checked against the solution.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ProgressChecked {
+    /// The player's cats that don't match the solution
+    pub wrong: Vec<(usize, usize)>,
+}
+
+/// Component for the button that checks the board.
+#[derive(Component)]
+pub struct CheckButton;
+
+/// Component on a checked cell while it's marked. Removed (along with its
+/// outline) once the timer runs out.
+#[derive(Component)]
+pub struct CheckMark {
+    pub timer: Timer,
+    pub color: Color,
+}
+
+/// The cells a check marks, and in what color: the wrong cats, or when there
+/// are none, every cat the player placed.
+pub fn check_marks(board: &BoardState, wrong: &[(usize, usize)]) -> (Vec<(usize, usize)>, Color) {
+    if !wrong.is_empty() {
+        return (wrong.to_vec(), CHECK_WRONG_COLOR);
+    }
+    let placed = (0..board.size())
+        .flat_map(|row| (0..board.size()).map(move |col| (row, col)))
+        .filter(|&(row, col)| board.cells[row][col].is_some() && !board.is_given_cell(row, col))
+        .collect();
+    (placed, CHECK_RIGHT_COLOR)
+}
+
+// --- Layout ---
+
+/// Spawn the Check button.
+pub fn spawn_check_button(parent: &mut ChildSpawnerCommands, normal: Color, border: Color, text: Color) {
+    parent
+        .spawn((
+            Button,
+            CheckButton,
+            Focusable::Check,
+            Node {
+                width: Val::Px(80.0),
+                height: Val::Px(35.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(normal),
+            BorderColor(border),
+        ))
+        .with_children(|button_parent| {
+            button_parent.spawn((
+                Text::new("✔ Check"),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(text),
+            ));
+        });
+}
+
+// --- Systems ---
+
+/// System that marks the cells of every check just made.
+pub fn start_check_marks(
+    mut commands: Commands,
+    mut events: EventReader<ProgressChecked>,
+    board: Res<BoardState>,
+    cell_query: Query<(Entity, &Cell)>,
+) {
+    let Some(event) = events.read().last() else {
+        return;
+    };
+    let (cells, color) = check_marks(&board, &event.wrong);
+    for (entity, cell) in &cell_query {
+        if cells.contains(&(cell.row, cell.col)) {
+            commands.entity(entity).insert((
+                CheckMark {
+                    timer: Timer::from_seconds(CHECK_MARK_SECONDS, TimerMode::Once),
+                    color,
+                },
+                Outline::new(Val::Px(CHECK_MARK_WIDTH), Val::ZERO, color),
+            ));
+        }
+    }
+}
+
+/// System that fades check marks out over their last half and removes them
+/// when they're done. A focused cell keeps its focus outline meanwhile.
+pub fn fade_check_marks(
+    mut commands: Commands,
+    time: Res<Time>,
+    focus: Res<FocusManager>,
+    mut mark_query: Query<(Entity, &mut CheckMark, &mut Outline)>,
+) {
+    for (entity, mut mark, mut outline) in &mut mark_query {
+        mark.timer.tick(time.delta());
+        if mark.timer.finished() {
+            commands.entity(entity).remove::<(CheckMark, Outline)>();
+        } else if focus.focused != Some(entity) {
+            let alpha = ((1.0 - mark.timer.fraction()) * 2.0).min(1.0);
+            *outline = Outline::new(Val::Px(CHECK_MARK_WIDTH), Val::ZERO, mark.color.with_alpha(alpha));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nine_lives_core::CellType;
+
+    #[test]
+    fn test_check_marks() {
+        let mut board = BoardState::new();
+        board.cells[0][0] = Some(0);
+        board.cell_types[0][0] = Some(CellType::Given);
+        board.cells[1][1] = Some(1);
+        board.cell_types[1][1] = Some(CellType::Player);
+        board.cells[2][2] = Some(2);
+        board.cell_types[2][2] = Some(CellType::Player);
+
+        assert_eq!(check_marks(&board, &[(2, 2)]), (vec![(2, 2)], CHECK_WRONG_COLOR));
+        // All right: every player cat lights up, givens left alone
+        assert_eq!(check_marks(&board, &[]), (vec![(1, 1), (2, 2)], CHECK_RIGHT_COLOR));
+    }
+}
@@ -0,0 +1,204 @@
+//! Swapping a new puzzle in for Nine Lives Cat Sudoku.
+//!
+//! Whatever starts a new puzzle (Start Game, a pack's puzzle button, New Game
+//! with the same settings, a time attack run) goes through `PuzzleSwap`, so
+//! every new puzzle starts the same way: a fresh timer, move count, history,
+//! replay, score, streak, nine lives, and hints.
+//!
+//! A puzzle the pool doesn't have ready is generated on a background task
+//! (see `PuzzleGeneration`) and swapped in by `finish_puzzle_generation`
+//! once it's done, so the menu and the game never stall waiting for it.
+
+use bevy::app::AppExit;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, Task, futures::check_ready};
+use nine_lives_core::{
+    BoardState, CancellationToken, GameEvent, GameHistory, GameSession, GameState, GenerationReport, HintSystem,
+    LastHint, Lives, NineLivesError, PuzzlePool, PuzzleSettings, Replay, ScoreTracker, Solution, StreakTracker,
+    TimeAttack,
+};
+
+use crate::{
+    AppState, ErrorToast, LastGeneration, LeaderboardPlacement, SelectedGridSize, SelectedPreset, SelectedVariant,
+    SolutionReveal, fallback_puzzle, pooled_puzzle,
+};
+
+/// The current puzzle's progress beyond the board itself: everything a
+/// restart starts over.
+#[derive(SystemParam)]
+pub struct PuzzleProgress<'w> {
+    pub session: ResMut<'w, GameSession>,
+    pub history: ResMut<'w, GameHistory>,
+    pub last_hint: ResMut<'w, LastHint>,
+    pub replay: ResMut<'w, Replay>,
+    pub lives: ResMut<'w, Lives>,
+    pub score: ResMut<'w, ScoreTracker>,
+    pub streak: ResMut<'w, StreakTracker>,
+}
+
+impl PuzzleProgress<'_> {
+    /// Start over from `board`, whether it's the same puzzle or a new one:
+    /// a fresh timer, move count, history, replay, score, streak, and nine lives.
+    pub fn restart(&mut self, board: &BoardState) {
+        self.session.reset();
+        self.history.clear();
+        *self.last_hint = LastHint::default();
+        *self.replay = Replay::new(board);
+        *self.lives = Lives::default();
+        *self.score = ScoreTracker::default();
+        *self.streak = StreakTracker::default();
+    }
+}
+
+/// Everything that changes to swap a new puzzle in.
+#[derive(SystemParam)]
+pub struct PuzzleSwap<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pub board: ResMut<'w, BoardState>,
+    pub solution: ResMut<'w, Solution>,
+    pub progress: PuzzleProgress<'w>,
+    pub hint_system: ResMut<'w, HintSystem>,
+    pub pool: ResMut<'w, PuzzlePool>,
+    pub last_generation: ResMut<'w, LastGeneration>,
+    pub game_state: ResMut<'w, GameState>,
+    pub events: EventWriter<'w, GameEvent>,
+    pub toasts: EventWriter<'w, ErrorToast>,
+}
+
+impl PuzzleSwap<'_, '_> {
+    /// Swap in the run's next puzzle: classic, at the difficulty the run is up
+    /// to. Returns whether it's in already (see `load_fresh`).
+    pub fn load_next(&mut self, run: &TimeAttack) -> bool {
+        let settings = PuzzleSettings::from_preset(run.next_preset());
+        info!("⏱️ Next time attack puzzle: {}", settings.description());
+        self.load_fresh(settings)
+    }
+
+    /// Swap in a new puzzle with the given settings, straight away if the pool
+    /// has one ready (returning true). Otherwise starts generating one in the
+    /// background, for `finish_puzzle_generation` to swap in once it's done.
+    pub fn load_fresh(&mut self, settings: PuzzleSettings) -> bool {
+        let Some((board, solution, generation)) = pooled_puzzle(&mut self.pool, &settings) else {
+            info!("⏳ Generating a puzzle in the background: {}", settings.description());
+            self.commands.insert_resource(PuzzleGeneration::start(settings));
+            return false;
+        };
+        info!("🔄 New puzzle: {}", settings.description());
+        self.load(settings, board, solution, generation);
+        true
+    }
+
+    /// Swap in a puzzle, starting it fresh: a new timer, move count, replay,
+    /// score, streak, and nine lives.
+    pub fn load(&mut self, settings: PuzzleSettings, board: BoardState, solution: Solution, generation: LastGeneration) {
+        *self.board = board;
+        *self.solution = solution;
+        *self.last_generation = generation;
+        self.progress.restart(&self.board);
+        self.hint_system.reset(settings.max_hints);
+        self.commands.remove_resource::<SolutionReveal>();
+        self.commands.insert_resource(LeaderboardPlacement::default());
+        // Straight back to playing, so nothing else takes the solve for a finished game
+        *self.game_state = GameState::Playing;
+        self.events.write(GameEvent::StateChanged(GameState::Playing));
+        self.commands.insert_resource(settings);
+    }
+}
+
+/// A puzzle being generated in the background for `PuzzleSwap::load_fresh`.
+/// Removing the resource cancels it.
+#[derive(Resource)]
+pub struct PuzzleGeneration {
+    pub settings: PuzzleSettings,
+    task: Task<Result<(BoardState, Solution, GenerationReport), NineLivesError>>,
+    cancel: CancellationToken,
+}
+
+impl PuzzleGeneration {
+    /// Start generating a puzzle with the given settings.
+    pub fn start(settings: PuzzleSettings) -> Self {
+        let task_settings = settings.clone();
+        let cancel = CancellationToken::new();
+        let task_cancel = cancel.clone();
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let mut board = BoardState::new();
+            board
+                .generate_puzzle_cancellable(&task_settings, &task_cancel)
+                .map(|(solution, report)| (board, solution, report))
+        });
+        Self { settings, task, cancel }
+    }
+}
+
+/// Dropping a task doesn't stop a search already running on another
+/// thread, so let the generator know it's no longer wanted.
+impl Drop for PuzzleGeneration {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+/// System that swaps the generated puzzle in once it's done, going on to the
+/// game screen if it isn't there already. If generating it failed, says so
+/// and swaps in a simple one instead.
+pub fn finish_puzzle_generation(
+    mut generation: ResMut<PuzzleGeneration>,
+    mut puzzles: PuzzleSwap,
+    app_state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let Some(generated) = check_ready(&mut generation.task) else {
+        return;
+    };
+    puzzles.commands.remove_resource::<PuzzleGeneration>();
+    let settings = generation.settings.clone();
+    let (board, solution, last_generation) = match generated {
+        Ok((board, solution, report)) => {
+            info!("Generated new puzzle with settings: {} ({})", settings.description(), report.summary());
+            (board, solution, LastGeneration { report: Some(report), pre_generated: false })
+        }
+        Err(NineLivesError::Cancelled) => return,
+        Err(e) => {
+            puzzles.toasts.write(ErrorToast::new(&settings.description(), &e));
+            warn!("Fallback: Generated simple puzzle ({})", e);
+            let (board, solution) = fallback_puzzle(&settings);
+            (board, solution, LastGeneration::default())
+        }
+    };
+    info!("🔄 New puzzle: {}", settings.description());
+    puzzles.load(settings, board, solution, last_generation);
+    if *app_state.get() != AppState::Ready {
+        next_state.set(AppState::Ready);
+    }
+}
+
+/// System that cancels the puzzle being generated, if any: when leaving the
+/// screen that asked for it, picking other settings, or quitting. A time
+/// attack run still waiting on its first puzzle goes with it.
+pub fn cancel_puzzle_generation(
+    mut commands: Commands,
+    generation: Option<Res<PuzzleGeneration>>,
+    app_state: Res<State<AppState>>,
+) {
+    let Some(generation) = generation else {
+        return;
+    };
+    info!("🛑 Cancelled generating {}", generation.settings.description());
+    commands.remove_resource::<PuzzleGeneration>();
+    if *app_state.get() != AppState::Ready {
+        commands.remove_resource::<TimeAttack>();
+    }
+}
+
+/// Run condition for `cancel_puzzle_generation` in `Update`: the player
+/// picked other settings, or the game is quitting.
+pub fn generation_settings_changed(
+    selected_preset: Res<SelectedPreset>,
+    selected_grid_size: Res<SelectedGridSize>,
+    selected_variant: Res<SelectedVariant>,
+    mut exits: EventReader<AppExit>,
+) -> bool {
+    let quitting = exits.read().next().is_some();
+    quitting || selected_preset.is_changed() || selected_grid_size.is_changed() || selected_variant.is_changed()
+}
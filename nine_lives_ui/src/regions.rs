@@ -0,0 +1,89 @@
+//! Jigsaw region rendering for Nine Lives Cat Sudoku.
+//!
+//! Standard boxes are told apart by alternating cell colors, which doesn't
+//! work for irregular shapes. On Jigsaw boards every cell instead carries an
+//! outline node that overhangs into the grid gap, with a thick border on each
+//! side where the neighbouring cell belongs to another region.
+
+use bevy::prelude::*;
+use nine_lives_core::BoardState;
+
+use crate::{BoardOrientation, Cell, GridSlot};
+
+/// Color of the region outlines
+const REGION_OUTLINE_COLOR: Color = Color::srgb(0.1, 0.1, 0.15);
+/// Width of a region outline
+const REGION_OUTLINE_WIDTH: f32 = 2.0;
+/// How far the outline reaches past the cell (its 1px border plus half the
+/// 2px grid gap), so the outlines of neighbouring cells meet in the gap
+const REGION_OVERHANG: f32 = -2.0;
+
+// --- Components ---
+
+/// Component to tag the region outline drawn around a cell.
+#[derive(Component)]
+pub struct RegionOutline;
+
+// --- Layout ---
+
+/// Spawn the (initially invisible) region outline inside a cell.
+/// Must come after the cell's cat text, which other systems expect as the first child.
+pub fn spawn_region_outline(cell_parent: &mut ChildSpawnerCommands) {
+    cell_parent.spawn((
+        RegionOutline,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(REGION_OVERHANG),
+            right: Val::Px(REGION_OVERHANG),
+            top: Val::Px(REGION_OVERHANG),
+            bottom: Val::Px(REGION_OVERHANG),
+            border: UiRect::ZERO,
+            ..default()
+        },
+        BorderColor(REGION_OUTLINE_COLOR),
+    ));
+}
+
+// --- Systems ---
+
+/// System that outlines the jigsaw regions of the current board.
+/// Works in on-screen slots so the outlines follow the board when it's
+/// rotated; boards with standard boxes get no outlines at all.
+pub fn update_region_outlines(
+    board: Res<BoardState>,
+    orientation: Res<BoardOrientation>,
+    cell_query: Query<(&Cell, &GridSlot, &Children)>,
+    mut outline_query: Query<&mut Node, With<RegionOutline>>,
+) {
+    let size = board.size();
+    let jigsaw = !board.regions.is_boxes(board.grid_size);
+
+    for (cell, slot, children) in &cell_query {
+        let region = board.regions.region_of(cell.row, cell.col);
+
+        // Outer edges of the board already have the grid's own border
+        let is_edge = |row_offset: isize, col_offset: isize| {
+            let (row, col) = (slot.row as isize + row_offset, slot.col as isize + col_offset);
+            if row < 0 || col < 0 || row >= size as isize || col >= size as isize {
+                return false;
+            }
+            let (board_row, board_col) = orientation.slot_to_board(row as usize, col as usize, size);
+            board.regions.region_of(board_row, board_col) != region
+        };
+        let width = |edge: bool| if jigsaw && edge { Val::Px(REGION_OUTLINE_WIDTH) } else { Val::ZERO };
+        let border = UiRect::new(
+            width(is_edge(0, -1)),
+            width(is_edge(0, 1)),
+            width(is_edge(-1, 0)),
+            width(is_edge(1, 0)),
+        );
+
+        for child in children.iter() {
+            if let Ok(mut node) = outline_query.get_mut(child)
+                && node.border != border
+            {
+                node.border = border;
+            }
+        }
+    }
+}
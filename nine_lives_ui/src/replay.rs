@@ -0,0 +1,341 @@
+//! Replay viewer for Nine Lives Cat Sudoku.
+//!
+//! After a win, "Watch replay" turns the board into a replay of the solve.
+//! A timeline under the board can be dragged (or stepped with the arrow keys)
+//! to any point in the game, with hints and mistakes marked along it. The
+//! board is moved between points by applying or undoing the recorded moves,
+//! and put back exactly as it was when the viewer is closed.
+
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use nine_lives_core::{BoardState, GameState, Replay, ReplayEventKind};
+
+use crate::Focusable;
+
+/// Width of the replay timeline
+const TIMELINE_WIDTH: f32 = 400.0;
+/// Height of the replay timeline
+const TIMELINE_HEIGHT: f32 = 18.0;
+/// Width of a hint or mistake mark on the timeline
+const MARKER_WIDTH: f32 = 3.0;
+/// Width of the position handle on the timeline
+const HANDLE_WIDTH: f32 = 6.0;
+
+// --- Components & Resources ---
+
+/// Component to tag the replay panel, shown once the puzzle is solved.
+#[derive(Component)]
+pub struct ReplayPanel;
+
+/// Component to tag the "Watch replay" button.
+#[derive(Component)]
+pub struct WatchReplayButton;
+
+/// Component to tag the row holding the timeline and its controls, shown while watching.
+#[derive(Component)]
+pub struct ReplayControls;
+
+/// Component to tag the draggable replay timeline.
+#[derive(Component)]
+pub struct ReplayTimeline;
+
+/// Component to tag a hint or mistake mark on the timeline.
+#[derive(Component)]
+pub struct ReplayMarker;
+
+/// Component to tag the handle showing the current point on the timeline.
+#[derive(Component)]
+pub struct ReplayHandle;
+
+/// Component to tag the "Step 12/40" text next to the timeline.
+#[derive(Component)]
+pub struct ReplayPositionText;
+
+/// Component to tag the button that closes the replay viewer.
+#[derive(Component)]
+pub struct ReplayDoneButton;
+
+/// Resource present while a replay is being watched. Gameplay systems pause
+/// while it exists, since the board on screen is the replay's.
+#[derive(Resource)]
+pub struct ReplayViewer {
+    /// How many recorded steps the board currently shows
+    pub position: usize,
+    /// The board as it was before watching, restored afterwards
+    pub live_board: BoardState,
+}
+
+/// Timeline color of a key event.
+fn marker_color(kind: ReplayEventKind) -> Color {
+    match kind {
+        ReplayEventKind::Hint => Color::srgb(1.0, 0.75, 0.2),
+        ReplayEventKind::Mistake => Color::srgb(0.9, 0.3, 0.3),
+        ReplayEventKind::Move => Color::srgb(0.6, 0.6, 0.6),
+    }
+}
+
+/// Where a position sits along the timeline, as a percentage.
+fn timeline_percent(position: usize, len: usize) -> f32 {
+    if len == 0 { 100.0 } else { position as f32 / len as f32 * 100.0 }
+}
+
+// --- Layout ---
+
+/// Spawn the (initially hidden) replay panel on the game screen.
+pub fn spawn_replay_panel(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            ReplayPanel,
+            Node {
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(6.0),
+                margin: UiRect::top(Val::Px(8.0)),
+                ..default()
+            },
+        ))
+        .with_children(|panel| {
+            panel
+                .spawn((
+                    Button,
+                    WatchReplayButton,
+                    Focusable::WatchReplay,
+                    Node {
+                        width: Val::Px(180.0),
+                        height: Val::Px(35.0),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+                    BorderColor(Color::srgb(0.5, 0.5, 0.8)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        Text::new("▶ Watch replay"),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
+            panel
+                .spawn((
+                    ReplayControls,
+                    Node {
+                        display: Display::None,
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(12.0),
+                        ..default()
+                    },
+                ))
+                .with_children(|controls| {
+                    controls
+                        .spawn((
+                            Button,
+                            ReplayTimeline,
+                            RelativeCursorPosition::default(),
+                            Node {
+                                width: Val::Px(TIMELINE_WIDTH),
+                                height: Val::Px(TIMELINE_HEIGHT),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.25, 0.25, 0.3)),
+                        ))
+                        .with_children(|timeline| {
+                            timeline.spawn((
+                                ReplayHandle,
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    left: Val::Percent(100.0),
+                                    width: Val::Px(HANDLE_WIDTH),
+                                    height: Val::Percent(100.0),
+                                    margin: UiRect::left(Val::Px(-HANDLE_WIDTH / 2.0)),
+                                    ..default()
+                                },
+                                BackgroundColor(Color::WHITE),
+                            ));
+                        });
+
+                    controls.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        ReplayPositionText,
+                    ));
+
+                    controls
+                        .spawn((
+                            Button,
+                            ReplayDoneButton,
+                            Focusable::ReplayDone,
+                            Node {
+                                width: Val::Px(70.0),
+                                height: Val::Px(28.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                Text::new("Done"),
+                                TextFont {
+                                    font_size: 12.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+                });
+        });
+}
+
+// --- Systems ---
+
+/// System that shows the replay panel after a win, and swaps the "Watch
+/// replay" button for the timeline while a replay is on.
+#[allow(clippy::type_complexity)]
+pub fn update_replay_panel_visibility(
+    game_state: Res<GameState>,
+    viewer: Option<Res<ReplayViewer>>,
+    mut panel_query: Query<&mut Node, With<ReplayPanel>>,
+    mut button_query: Query<&mut Node, (With<WatchReplayButton>, Without<ReplayPanel>)>,
+    mut controls_query: Query<&mut Node, (With<ReplayControls>, Without<ReplayPanel>, Without<WatchReplayButton>)>,
+) {
+    let watching = viewer.is_some();
+    let shown = |visible: bool| if visible { Display::Flex } else { Display::None };
+
+    for mut node in &mut panel_query {
+        node.display = shown(watching || *game_state == GameState::Won);
+    }
+    for mut node in &mut button_query {
+        node.display = shown(!watching);
+    }
+    for mut node in &mut controls_query {
+        node.display = shown(watching);
+    }
+}
+
+/// System that starts watching the replay, marking its hints and mistakes on the timeline.
+pub fn handle_watch_replay_button(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<WatchReplayButton>)>,
+    board: Res<BoardState>,
+    replay: Res<Replay>,
+    timeline_query: Query<Entity, With<ReplayTimeline>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if replay.is_empty() {
+            info!("🎬 Nothing to replay yet");
+            continue;
+        }
+
+        commands.insert_resource(ReplayViewer {
+            position: replay.len(),
+            live_board: board.clone(),
+        });
+        for timeline in &timeline_query {
+            commands.entity(timeline).with_children(|timeline| {
+                for (index, kind) in replay.key_events() {
+                    timeline.spawn((
+                        ReplayMarker,
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: Val::Percent(timeline_percent(index + 1, replay.len())),
+                            width: Val::Px(MARKER_WIDTH),
+                            height: Val::Percent(100.0),
+                            margin: UiRect::left(Val::Px(-MARKER_WIDTH)),
+                            ..default()
+                        },
+                        BackgroundColor(marker_color(kind)),
+                    ));
+                }
+            });
+        }
+        info!("🎬 Watching a replay of {} moves", replay.len());
+    }
+}
+
+/// System that moves the board to the point on the timeline being dragged,
+/// or one step at a time with the left and right arrow keys.
+pub fn scrub_replay_system(
+    timeline_query: Query<(&Interaction, &RelativeCursorPosition), With<ReplayTimeline>>,
+    input: Res<ButtonInput<KeyCode>>,
+    replay: Res<Replay>,
+    mut board: ResMut<BoardState>,
+    mut viewer: ResMut<ReplayViewer>,
+) {
+    let mut target = viewer.position;
+    for (interaction, cursor) in &timeline_query {
+        if *interaction == Interaction::Pressed
+            && let Some(normalized) = cursor.normalized
+        {
+            target = (normalized.x.clamp(0.0, 1.0) * replay.len() as f32).round() as usize;
+        }
+    }
+    if input.just_pressed(KeyCode::ArrowLeft) {
+        target = target.saturating_sub(1);
+    }
+    if input.just_pressed(KeyCode::ArrowRight) {
+        target += 1;
+    }
+
+    if target != viewer.position {
+        let position = replay.seek(&mut board, viewer.position, target);
+        viewer.position = position;
+    }
+}
+
+/// System that moves the timeline handle and step counter to the current point.
+pub fn update_replay_timeline(
+    replay: Res<Replay>,
+    viewer: Res<ReplayViewer>,
+    mut handle_query: Query<&mut Node, With<ReplayHandle>>,
+    mut text_query: Query<&mut Text, With<ReplayPositionText>>,
+) {
+    for mut node in &mut handle_query {
+        node.left = Val::Percent(timeline_percent(viewer.position, replay.len()));
+    }
+    for mut text in &mut text_query {
+        text.0 = format!("Step {}/{}", viewer.position, replay.len());
+    }
+}
+
+/// System that closes the replay viewer and puts the live board back.
+pub fn handle_replay_done_button(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ReplayDoneButton>)>,
+    viewer: Res<ReplayViewer>,
+    mut board: ResMut<BoardState>,
+    marker_query: Query<Entity, With<ReplayMarker>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            *board = viewer.live_board.clone();
+            commands.remove_resource::<ReplayViewer>();
+            for marker in &marker_query {
+                commands.entity(marker).despawn();
+            }
+        }
+    }
+}
+
+/// System that drops the replay viewer when leaving the game screen.
+pub fn end_replay_viewer(mut commands: Commands) {
+    commands.remove_resource::<ReplayViewer>();
+}
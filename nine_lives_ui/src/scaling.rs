@@ -0,0 +1,119 @@
+//! Fitting the interface to the window for Nine Lives Cat Sudoku.
+//!
+//! Two things follow the window's size. The whole interface is drawn at
+//! Bevy's `UiScale`: the player's chosen interface size (see
+//! `UserSettings::ui_scale`), shrunk further on windows smaller than the one
+//! the game opens in so the buttons stay on screen. And the board is drawn
+//! at a `GridScale` that fills the room the rest of the game screen leaves
+//! (including the side panel, when it's open), so it's neither cut off on
+//! small windows nor tiny on large displays.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use nine_lives_core::PersistentData;
+
+use crate::{BoardOrientation, SidePanelState};
+
+/// Width of the window the interface is laid out for (the window the game opens in)
+pub const DESIGN_WIDTH: f32 = 700.0;
+/// Height of the window the interface is laid out for
+pub const DESIGN_HEIGHT: f32 = 800.0;
+/// Room the game screen keeps beside the board (besides the side panel), in interface pixels
+const BOARD_MARGIN_WIDTH: f32 = 40.0;
+/// Room the game screen keeps above and below the board for the title,
+/// number pad, and hint explanation, in interface pixels
+const BOARD_MARGIN_HEIGHT: f32 = 240.0;
+/// Smallest and largest the board is drawn, relative to its designed size
+const GRID_SCALE_RANGE: (f32, f32) = (0.4, 2.0);
+
+/// How big the board is drawn, relative to its designed size (see
+/// `BoardOrientation::grid_size`).
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct GridScale(pub f32);
+
+impl Default for GridScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl GridScale {
+    /// The scale that fits the board into a window of the given size, with
+    /// the interface drawn at `ui_scale` and `side_width` interface pixels
+    /// taken by the side panel.
+    pub fn for_window(width: f32, height: f32, orientation: BoardOrientation, ui_scale: f32, side_width: f32) -> Self {
+        let (grid_width, grid_height) = orientation.grid_size(1.0);
+        let room_width = width / ui_scale - BOARD_MARGIN_WIDTH - side_width;
+        let room_height = height / ui_scale - BOARD_MARGIN_HEIGHT;
+        let fit = (room_width / grid_width).min(room_height / grid_height);
+        Self(fit.clamp(GRID_SCALE_RANGE.0, GRID_SCALE_RANGE.1))
+    }
+}
+
+/// The scale to draw the interface at in a window of the given size: the
+/// player's `chosen` size, shrunk to fit windows smaller than the designed one.
+pub fn fitted_ui_scale(width: f32, height: f32, chosen: f32) -> f32 {
+    let fit = (width / DESIGN_WIDTH).min(height / DESIGN_HEIGHT).min(1.0);
+    chosen * fit.max(0.1)
+}
+
+// --- Systems ---
+
+/// System that fits the interface and the board to the primary window. Runs
+/// when the window is resized, when the settings change, and when the side
+/// panel is collapsed, expanded, or resized.
+pub fn fit_ui_to_window(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    persistent_data: Res<PersistentData>,
+    orientation: Res<BoardOrientation>,
+    side_panel: Res<SidePanelState>,
+    mut ui_scale: ResMut<UiScale>,
+    mut grid_scale: ResMut<GridScale>,
+) {
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let (width, height) = (window.width(), window.height());
+    let scale = fitted_ui_scale(width, height, persistent_data.user_settings.ui_scale);
+    if ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+    let fitted = GridScale::for_window(width, height, *orientation, scale, side_panel.occupied_width());
+    if *grid_scale != fitted {
+        *grid_scale = fitted;
+        debug!("📐 Board drawn at {:.0}% for a {:.0}×{:.0} window", fitted.0 * 100.0, width, height);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fitting_to_the_window() {
+        // The designed window draws the interface as chosen; smaller ones shrink it
+        assert_eq!(fitted_ui_scale(DESIGN_WIDTH, DESIGN_HEIGHT, 1.0), 1.0);
+        assert_eq!(fitted_ui_scale(2.0 * DESIGN_WIDTH, 2.0 * DESIGN_HEIGHT, 1.2), 1.2);
+        assert_eq!(fitted_ui_scale(DESIGN_WIDTH / 2.0, DESIGN_HEIGHT, 1.0), 0.5);
+
+        // The board grows into big windows and shrinks into small ones
+        let landscape = BoardOrientation::Landscape;
+        let small = GridScale::for_window(DESIGN_WIDTH, DESIGN_HEIGHT, landscape, 1.0, 0.0);
+        let large = GridScale::for_window(2560.0, 1440.0, landscape, 1.0, 0.0);
+        assert!(small.0 < 1.0 && large.0 > 1.0);
+        let (width, height) = landscape.grid_size(small.0);
+        assert!(width <= DESIGN_WIDTH - BOARD_MARGIN_WIDTH + 0.01);
+        assert!(height <= DESIGN_HEIGHT - BOARD_MARGIN_HEIGHT + 0.01);
+
+        // A bigger interface leaves less room for the board
+        assert!(GridScale::for_window(2560.0, 1440.0, landscape, 1.5, 0.0).0 < large.0);
+        assert_eq!(GridScale::for_window(100.0, 100.0, landscape, 1.0, 0.0).0, GRID_SCALE_RANGE.0);
+
+        // An open side panel leaves less room than a collapsed one
+        let open = SidePanelState::default();
+        let collapsed = SidePanelState { open: false, ..open };
+        let beside_open = GridScale::for_window(1000.0, 1000.0, landscape, 1.0, open.occupied_width());
+        let beside_collapsed = GridScale::for_window(1000.0, 1000.0, landscape, 1.0, collapsed.occupied_width());
+        assert!(beside_open.0 < beside_collapsed.0);
+    }
+}
@@ -0,0 +1,80 @@
+//! Mouse wheel scrolling for Nine Lives Cat Sudoku's scrolling lists.
+//!
+//! Each list (the event log, the move history, the pack browser) is a node
+//! with `Overflow::scroll_y()`, a `ScrollPosition`, and a
+//! `RelativeCursorPosition`, tagged with its own marker component.
+//! `scroll_list_system` makes the system that scrolls one kind of list.
+
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+
+/// The lists tagged `M`, with what scrolling them needs.
+type ScrollLists<'w, 's, M> =
+    Query<'w, 's, (&'static RelativeCursorPosition, &'static ComputedNode, &'static mut ScrollPosition), With<M>>;
+
+/// Make a system that scrolls the lists tagged `M` with the mouse wheel while
+/// the cursor is over them, `line_height` pixels per line of wheel movement.
+/// The list stops at its top and at the bottom of its content.
+pub fn scroll_list_system<M: Component>(
+    line_height: f32,
+) -> impl FnMut(EventReader<MouseWheel>, ScrollLists<M>) {
+    move |mut wheel_events, mut list_query| {
+        for event in wheel_events.read() {
+            let lines = match event.unit {
+                MouseScrollUnit::Line => event.y,
+                MouseScrollUnit::Pixel => event.y / line_height,
+            };
+            for (cursor, node, mut scroll) in &mut list_query {
+                if cursor.mouse_over() {
+                    // Node sizes are in physical pixels, scroll offsets in logical ones
+                    let max_offset = (node.content_size().y - node.size().y).max(0.0) * node.inverse_scale_factor();
+                    scroll.offset_y = (scroll.offset_y - lines * line_height).clamp(0.0, max_offset);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component)]
+    struct TestList;
+
+    #[test]
+    fn test_scroll_stops_at_both_ends() {
+        let mut app = App::new();
+        app.add_event::<MouseWheel>().add_systems(Update, scroll_list_system::<TestList>(20.0));
+        let list = app
+            .world_mut()
+            .spawn((
+                TestList,
+                RelativeCursorPosition {
+                    normalized_visible_node_rect: Rect::new(-0.5, -0.5, 0.5, 0.5),
+                    normalized: Some(Vec2::ZERO),
+                },
+                ComputedNode {
+                    size: Vec2::new(100.0, 200.0),
+                    content_size: Vec2::new(100.0, 300.0),
+                    inverse_scale_factor: 1.0,
+                    ..default()
+                },
+                ScrollPosition::default(),
+            ))
+            .id();
+        let scroll_by = |app: &mut App, y: f32| {
+            app.world_mut().send_event(MouseWheel { unit: MouseScrollUnit::Line, x: 0.0, y, window: Entity::PLACEHOLDER });
+            app.update();
+            app.world().get::<ScrollPosition>(list).unwrap().offset_y
+        };
+
+        // Wheel down scrolls down a line at a time, as far as the content goes
+        assert_eq!(scroll_by(&mut app, -2.0), 40.0);
+        assert_eq!(scroll_by(&mut app, -10.0), 100.0);
+        // and back up as far as the top
+        assert_eq!(scroll_by(&mut app, 1.0), 80.0);
+        assert_eq!(scroll_by(&mut app, 10.0), 0.0);
+    }
+}
@@ -0,0 +1,462 @@
+//! Settings screen for Nine Lives Cat Sudoku.
+//!
+//! Player preferences that aren't about the next puzzle (language, volume,
+//! interface size, theme, cat art and how it's drawn, saving, how mistakes are shown, highlighting, the pointer, the clock in the window title, input, and what checking
+//! the board costs) live here rather than on the customization screen. Each setting is a row with arrow
+//! buttons stepping through its values; every change is saved straight away.
+//! The keyboard shortcuts are listed below them (see `spawn_shortcut_rows`).
+
+use bevy::prelude::*;
+use nine_lives_core::{
+    CatRendering, CheckCost, ErrorPolicy, HighlightIntensity, InputModel, Language, PersistentData, ThemeKind,
+    UserSettings,
+};
+
+use crate::{AppState, ArtPackLibrary, ErrorToast, Focusable, Locale, ShortcutCapture, spawn_shortcut_rows};
+
+/// How much one press of a volume arrow changes the volume
+const VOLUME_STEP: f32 = 0.1;
+/// How much one press of an interface size arrow changes the size
+const UI_SCALE_STEP: f32 = 0.1;
+/// Smallest and largest interface size players can choose
+const UI_SCALE_RANGE: (f32, f32) = (0.7, 1.5);
+/// The autosave intervals players can choose from, in seconds
+const AUTOSAVE_INTERVALS_SECS: [u32; 4] = [30, 60, 120, 300];
+
+// --- Components ---
+
+/// Component to tag the settings screen root for cleanup.
+#[derive(Component)]
+pub struct SettingsScreenRoot;
+
+/// Component to tag the "Settings" button on the customization screen.
+#[derive(Component)]
+pub struct SettingsButton;
+
+/// Component to tag the "Back" button on the settings screen.
+#[derive(Component)]
+pub struct SettingsBackButton;
+
+/// Component for the arrow buttons that step a setting back or forward.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettingStepButton {
+    pub setting: Setting,
+    pub forward: bool,
+}
+
+/// Component to tag the text showing a setting's current value.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettingValueText(pub Setting);
+
+// --- Settings ---
+
+/// The settings on the settings screen, each a field of `UserSettings`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    Language,
+    Volume,
+    UiScale,
+    Theme,
+    ArtPack,
+    CatRendering,
+    AutoSave,
+    AutosaveInterval,
+    ErrorPolicy,
+    HighlightHouses,
+    SelectionHighlight,
+    PawCursor,
+    TitleClock,
+    InputModel,
+    CheckCost,
+}
+
+impl Setting {
+    /// Get all settings in the order they're listed on screen.
+    pub fn all() -> [Setting; 15] {
+        [
+            Setting::Language,
+            Setting::Volume,
+            Setting::UiScale,
+            Setting::Theme,
+            Setting::ArtPack,
+            Setting::CatRendering,
+            Setting::AutoSave,
+            Setting::AutosaveInterval,
+            Setting::ErrorPolicy,
+            Setting::HighlightHouses,
+            Setting::SelectionHighlight,
+            Setting::PawCursor,
+            Setting::TitleClock,
+            Setting::InputModel,
+            Setting::CheckCost,
+        ]
+    }
+
+    /// The key of this setting's name in the strings files (see `Locale`).
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            Setting::Language => "setting.language",
+            Setting::Volume => "setting.volume",
+            Setting::UiScale => "setting.ui_scale",
+            Setting::Theme => "setting.theme",
+            Setting::ArtPack => "setting.art_pack",
+            Setting::CatRendering => "setting.cat_rendering",
+            Setting::AutoSave => "setting.auto_save",
+            Setting::AutosaveInterval => "setting.autosave_interval",
+            Setting::ErrorPolicy => "setting.error_policy",
+            Setting::HighlightHouses => "setting.highlight_houses",
+            Setting::SelectionHighlight => "setting.selection_highlight",
+            Setting::PawCursor => "setting.paw_cursor",
+            Setting::TitleClock => "setting.title_clock",
+            Setting::InputModel => "setting.input_model",
+            Setting::CheckCost => "setting.check_cost",
+        }
+    }
+
+    /// The setting's current value, as shown on screen in the player's
+    /// language. Art packs are named from those in `art_packs`.
+    pub fn value_label(&self, settings: &UserSettings, art_packs: &ArtPackLibrary, locale: &Locale) -> String {
+        let on_off = |enabled: bool| locale.get(if enabled { "settings.on" } else { "settings.off" });
+        match self {
+            Setting::Language => settings.language.display_name().to_string(),
+            Setting::Volume => format!("{:.0}%", settings.volume * 100.0),
+            Setting::UiScale => format!("{:.0}%", settings.ui_scale * 100.0),
+            Setting::Theme => settings.theme.display_name().to_string(),
+            Setting::ArtPack => art_packs.display_name(settings.art_pack.as_deref()),
+            Setting::CatRendering => settings.cat_rendering.display_name().to_string(),
+            Setting::AutoSave => on_off(settings.auto_save_enabled),
+            Setting::AutosaveInterval => match settings.autosave_interval_secs {
+                secs if secs < 60 => format!("{} s", secs),
+                secs => format!("{} min", secs / 60),
+            },
+            Setting::ErrorPolicy => settings.error_policy.display_name().to_string(),
+            Setting::HighlightHouses => on_off(settings.highlight_houses),
+            Setting::SelectionHighlight => settings.selection_highlight.display_name().to_string(),
+            Setting::PawCursor => on_off(settings.paw_cursor),
+            Setting::TitleClock => on_off(settings.title_clock),
+            Setting::InputModel => settings.input_model.display_name().to_string(),
+            Setting::CheckCost => settings.check_cost.display_name().to_string(),
+        }
+    }
+
+    /// Step the setting to its next (or previous) value. Volume and interface
+    /// size stop at either end; everything else wraps around. Art packs are chosen from
+    /// those in `art_packs`.
+    pub fn step(&self, settings: &mut UserSettings, forward: bool, art_packs: &ArtPackLibrary) {
+        match self {
+            Setting::Language => settings.language = step_through(&Language::all(), settings.language, forward),
+            Setting::Volume => {
+                let step = if forward { VOLUME_STEP } else { -VOLUME_STEP };
+                settings.volume = ((settings.volume + step) / VOLUME_STEP).round().clamp(0.0, 10.0) * VOLUME_STEP;
+            }
+            Setting::UiScale => {
+                let step = if forward { UI_SCALE_STEP } else { -UI_SCALE_STEP };
+                let (min, max) = (UI_SCALE_RANGE.0 / UI_SCALE_STEP, UI_SCALE_RANGE.1 / UI_SCALE_STEP);
+                settings.ui_scale =
+                    ((settings.ui_scale + step) / UI_SCALE_STEP).round().clamp(min.round(), max.round()) * UI_SCALE_STEP;
+            }
+            Setting::Theme => settings.theme = step_through(&ThemeKind::all(), settings.theme, forward),
+            Setting::ArtPack => {
+                settings.art_pack = step_through(&art_packs.choices(), settings.art_pack.clone(), forward)
+            }
+            Setting::CatRendering => {
+                settings.cat_rendering = step_through(&CatRendering::all(), settings.cat_rendering, forward)
+            }
+            Setting::AutoSave => settings.auto_save_enabled = !settings.auto_save_enabled,
+            Setting::AutosaveInterval => {
+                settings.autosave_interval_secs =
+                    step_through(&AUTOSAVE_INTERVALS_SECS, settings.autosave_interval_secs, forward)
+            }
+            Setting::ErrorPolicy => {
+                settings.error_policy = step_through(&ErrorPolicy::all(), settings.error_policy, forward)
+            }
+            Setting::HighlightHouses => settings.highlight_houses = !settings.highlight_houses,
+            Setting::SelectionHighlight => {
+                settings.selection_highlight =
+                    step_through(&HighlightIntensity::all(), settings.selection_highlight, forward)
+            }
+            Setting::PawCursor => settings.paw_cursor = !settings.paw_cursor,
+            Setting::TitleClock => settings.title_clock = !settings.title_clock,
+            Setting::InputModel => {
+                settings.input_model = step_through(&InputModel::all(), settings.input_model, forward)
+            }
+            Setting::CheckCost => settings.check_cost = step_through(&CheckCost::all(), settings.check_cost, forward),
+        }
+    }
+}
+
+/// The value after (or before) `current` in `all`, wrapping around. Values
+/// not in the list step to its first entry.
+fn step_through<T: Clone + PartialEq>(all: &[T], current: T, forward: bool) -> T {
+    match all.iter().position(|value| *value == current) {
+        Some(index) if forward => all[(index + 1) % all.len()].clone(),
+        Some(index) => all[(index + all.len() - 1) % all.len()].clone(),
+        None => all[0].clone(),
+    }
+}
+
+// --- Systems ---
+
+/// System that creates the settings screen UI.
+pub fn setup_settings_screen(
+    mut commands: Commands,
+    data: Res<PersistentData>,
+    art_packs: Res<ArtPackLibrary>,
+    locale: Res<Locale>,
+    mut capture: ResMut<ShortcutCapture>,
+) {
+    *capture = ShortcutCapture::default();
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+            SettingsScreenRoot,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                locale.text("settings.title"),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                },
+            ));
+
+            // One row per setting: name, back arrow, value, forward arrow
+            for (index, setting) in Setting::all().into_iter().enumerate() {
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(10.0),
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn((
+                            locale.text(setting.name_key()),
+                            TextFont {
+                                font_size: 16.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.85, 0.85, 0.95)),
+                            Node {
+                                width: Val::Px(260.0),
+                                ..default()
+                            },
+                        ));
+
+                        for forward in [false, true] {
+                            let mut button = row.spawn((
+                                Button,
+                                SettingStepButton { setting, forward },
+                                Focusable::SettingStep(index * 2 + forward as usize),
+                                Node {
+                                    width: Val::Px(36.0),
+                                    height: Val::Px(32.0),
+                                    align_items: AlignItems::Center,
+                                    justify_content: JustifyContent::Center,
+                                    border: UiRect::all(Val::Px(2.0)),
+                                    ..default()
+                                },
+                                BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                                BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                            ));
+                            button.with_children(|button_parent| {
+                                button_parent.spawn((
+                                    Text::new(if forward { "▶" } else { "◀" }),
+                                    TextFont {
+                                        font_size: 14.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                ));
+                            });
+
+                            // The value sits between the two arrows
+                            if !forward {
+                                row.spawn((
+                                    Text::new(setting.value_label(&data.user_settings, &art_packs, &locale)),
+                                    TextFont {
+                                        font_size: 16.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                    TextLayout::new_with_justify(JustifyText::Center),
+                                    Node {
+                                        width: Val::Px(160.0),
+                                        ..default()
+                                    },
+                                    SettingValueText(setting),
+                                ));
+                            }
+                        }
+                    });
+            }
+
+            spawn_shortcut_rows(parent, &locale);
+
+            // Back button
+            parent
+                .spawn((
+                    Button,
+                    SettingsBackButton,
+                    Focusable::SettingsBack,
+                    Node {
+                        width: Val::Px(160.0),
+                        height: Val::Px(45.0),
+                        margin: UiRect::top(Val::Px(30.0)),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                    BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        locale.text("settings.back"),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+/// System to clean up the settings screen when exiting that state.
+pub fn cleanup_settings_screen(mut commands: Commands, query: Query<Entity, With<SettingsScreenRoot>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// System that opens the settings screen from the customization screen.
+pub fn handle_settings_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SettingsButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::Settings);
+        }
+    }
+}
+
+/// System that returns from the settings screen to the customization screen.
+pub fn handle_settings_back_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SettingsBackButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::Customization);
+        }
+    }
+}
+
+/// System that steps a setting when one of its arrows is pressed and saves
+/// it to the user's settings.
+pub fn handle_setting_step_buttons(
+    interaction_query: Query<(&Interaction, &SettingStepButton), Changed<Interaction>>,
+    mut persistent_data: ResMut<PersistentData>,
+    art_packs: Res<ArtPackLibrary>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        button.setting.step(&mut persistent_data.user_settings, button.forward, &art_packs);
+        if let Err(e) = persistent_data.save() {
+            toasts.write(ErrorToast::new("Settings", &e));
+        }
+    }
+}
+
+/// System that shows each setting's value, whenever the settings or the
+/// language change.
+pub fn update_setting_values(
+    mut value_query: Query<(&SettingValueText, &mut Text)>,
+    persistent_data: Res<PersistentData>,
+    art_packs: Res<ArtPackLibrary>,
+    locale: Res<Locale>,
+) {
+    for (value, mut text) in &mut value_query {
+        text.0 = value.0.value_label(&persistent_data.user_settings, &art_packs, &locale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setting_steps() {
+        let mut settings = UserSettings {
+            volume: 0.7,
+            ..UserSettings::default()
+        };
+        let art_packs = ArtPackLibrary::default();
+        let locale = Locale::default();
+
+        // Volume moves in tenths and stops at the ends
+        Setting::Volume.step(&mut settings, true, &art_packs);
+        assert_eq!(Setting::Volume.value_label(&settings, &art_packs, &locale), "80%");
+        for _ in 0..5 {
+            Setting::Volume.step(&mut settings, true, &art_packs);
+        }
+        assert_eq!(settings.volume, 1.0);
+
+        // So does the interface size
+        Setting::UiScale.step(&mut settings, false, &art_packs);
+        assert_eq!(Setting::UiScale.value_label(&settings, &art_packs, &locale), "90%");
+        for _ in 0..10 {
+            Setting::UiScale.step(&mut settings, true, &art_packs);
+        }
+        assert_eq!(Setting::UiScale.value_label(&settings, &art_packs, &locale), "150%");
+
+        // Choices wrap around in both directions
+        Setting::Theme.step(&mut settings, false, &art_packs);
+        assert_eq!(settings.theme, ThemeKind::HighContrast);
+        Setting::Theme.step(&mut settings, true, &art_packs);
+        assert_eq!(settings.theme, ThemeKind::Classic);
+
+        assert_eq!(Setting::AutosaveInterval.value_label(&settings, &art_packs, &locale), "1 min");
+        Setting::AutosaveInterval.step(&mut settings, false, &art_packs);
+        assert_eq!(Setting::AutosaveInterval.value_label(&settings, &art_packs, &locale), "30 s");
+
+        // Toggles flip either way
+        Setting::HighlightHouses.step(&mut settings, true, &art_packs);
+        assert!(!settings.highlight_houses);
+        assert_eq!(Setting::HighlightHouses.value_label(&settings, &art_packs, &locale), "Off");
+        Setting::SelectionHighlight.step(&mut settings, true, &art_packs);
+        assert_eq!(Setting::SelectionHighlight.value_label(&settings, &art_packs, &locale), "Strong");
+
+        // With no packs loaded, only the built-in cats can be chosen
+        Setting::ArtPack.step(&mut settings, true, &art_packs);
+        assert_eq!(settings.art_pack, None);
+        assert_eq!(Setting::ArtPack.value_label(&settings, &art_packs, &locale), "Classic");
+
+        // Values are shown in the player's language
+        Setting::Language.step(&mut settings, true, &art_packs);
+        assert_eq!(settings.language, Language::Spanish);
+        let spanish = Locale::for_language(settings.language);
+        assert_eq!(Setting::HighlightHouses.value_label(&settings, &art_packs, &spanish), "No");
+    }
+}
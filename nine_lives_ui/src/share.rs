@@ -0,0 +1,391 @@
+//! Puzzle sharing for Nine Lives Cat Sudoku.
+//!
+//! After a win the game screen offers "Copy share code", which puts the
+//! puzzle's code on the clipboard (and shows it, for reading out loud). The
+//! customization screen has "Enter code": type or paste a friend's code and
+//! press Enter to play the same puzzle.
+//!
+//! There's no clipboard API in Bevy, so copying and pasting go through the
+//! platform's command-line tools (`pbcopy`, `clip`, `wl-copy`, `xclip`, ...).
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, GameHistory, GameSession, GameState, HintSystem, NineLivesError, PuzzleSettings, Solution, solve_puzzle};
+
+use crate::{
+    AppState, ChangedButtons, Focusable, LastGeneration, NewRecordDisplay, ReplayViewer, TextFit, spawn_campaign_result_display,
+    spawn_heatmap_toggle,
+};
+
+/// Longest code the entry box accepts (a Mega Clowder Killer code runs to about 650 characters)
+const MAX_CODE_LENGTH: usize = 1000;
+
+// --- Components & Resources ---
+
+/// Component to tag the "Copy share code" button on the game screen.
+#[derive(Component)]
+pub struct CopyShareCodeButton;
+
+/// Component to tag the panel holding the copy button, shown once the puzzle is solved.
+#[derive(Component)]
+pub struct ShareCodePanel;
+
+/// Component to tag the text that shows the copied share code.
+#[derive(Component)]
+pub struct ShareCodeDisplay;
+
+/// Component to tag the "Enter code" button on the customization screen.
+#[derive(Component)]
+pub struct EnterShareCodeButton;
+
+/// Component to tag the text showing the code being typed.
+#[derive(Component)]
+pub struct ShareCodeEntryDisplay;
+
+/// Resource holding the share code being typed on the customization screen.
+#[derive(Resource, Default)]
+pub struct ShareCodeEntry {
+    /// Whether key presses currently go into the code
+    pub active: bool,
+    pub code: String,
+    /// Feedback from the last attempt to start a shared puzzle
+    pub message: Option<String>,
+}
+
+impl ShareCodeEntry {
+    /// The line of text shown under the "Enter code" button.
+    pub fn display_text(&self) -> String {
+        if self.active {
+            let paste = if cfg!(target_os = "macos") { "⌘V" } else { "Ctrl+V" };
+            format!("Code: {}▏\nEnter to play • Esc to cancel • {} to paste", self.code, paste)
+        } else {
+            self.message.clone().unwrap_or_default()
+        }
+    }
+}
+
+// --- Clipboard ---
+
+/// Put text on the system clipboard. Returns whether any clipboard tool took it.
+fn copy_to_clipboard(text: &str) -> bool {
+    let tools: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else if cfg!(target_os = "windows") {
+        &[("clip", &[])]
+    } else {
+        &[("wl-copy", &[]), ("xclip", &["-selection", "clipboard"]), ("xsel", &["--clipboard", "--input"])]
+    };
+
+    tools.iter().any(|(program, args)| {
+        let Ok(mut child) = Command::new(program).args(*args).stdin(Stdio::piped()).spawn() else {
+            return false;
+        };
+        let written = child.stdin.take().is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        child.wait().is_ok_and(|status| status.success()) && written
+    })
+}
+
+/// Read text from the system clipboard, if any clipboard tool is available.
+fn paste_from_clipboard() -> Option<String> {
+    let tools: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbpaste", &[])]
+    } else if cfg!(target_os = "windows") {
+        &[("powershell", &["-NoProfile", "-Command", "Get-Clipboard"])]
+    } else {
+        &[("wl-paste", &["--no-newline"]), ("xclip", &["-o", "-selection", "clipboard"]), ("xsel", &["--clipboard", "--output"])]
+    };
+
+    tools.iter().find_map(|(program, args)| {
+        let output = Command::new(program).args(*args).output().ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    })
+}
+
+// --- Layout ---
+
+/// Spawn the (initially hidden) share panel on the game screen.
+pub fn spawn_share_code_panel(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            ShareCodePanel,
+            Node {
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(6.0),
+                margin: UiRect::top(Val::Px(12.0)),
+                ..default()
+            },
+        ))
+        .with_children(|panel| {
+            // New leaderboard place, if the solve made one
+            panel.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.4)),
+                NewRecordDisplay,
+            ));
+            // Stars earned, for a campaign puzzle
+            spawn_campaign_result_display(panel);
+
+            panel
+                .spawn((
+                    Button,
+                    CopyShareCodeButton,
+                    Focusable::CopyShareCode,
+                    Node {
+                        width: Val::Px(180.0),
+                        height: Val::Px(35.0),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.4, 0.6)),
+                    BorderColor(Color::srgb(0.3, 0.6, 0.9)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        Text::new("🔗 Copy share code"),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
+            panel.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.9, 1.0)),
+                Node {
+                    max_width: Val::Px(600.0),
+                    ..default()
+                },
+                TextFit::new(600.0, 3, 12.0),
+                ShareCodeDisplay,
+            ));
+
+            // Where mistakes tend to be made
+            spawn_heatmap_toggle(panel);
+        });
+}
+
+/// Spawn the "Enter code" button and its entry line on the customization screen.
+pub fn spawn_share_code_entry(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Button,
+            EnterShareCodeButton,
+            Focusable::EnterShareCode,
+            Node {
+                width: Val::Px(160.0),
+                height: Val::Px(40.0),
+                margin: UiRect::top(Val::Px(15.0)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.2, 0.4, 0.6)),
+            BorderColor(Color::srgb(0.3, 0.6, 0.9)),
+        ))
+        .with_children(|button_parent| {
+            button_parent.spawn((
+                Text::new("🔗 Enter code"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 13.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.8, 0.9, 1.0)),
+        Node {
+            margin: UiRect::top(Val::Px(8.0)),
+            max_width: Val::Px(600.0),
+            ..default()
+        },
+        ShareCodeEntryDisplay,
+    ));
+}
+
+// --- Systems ---
+
+/// System that shows the share panel once the puzzle is solved (and keeps it
+/// up while a replay of the solve is being watched).
+pub fn update_share_panel_visibility(
+    game_state: Res<GameState>,
+    viewer: Option<Res<ReplayViewer>>,
+    mut panel_query: Query<&mut Node, With<ShareCodePanel>>,
+) {
+    let solved = *game_state == GameState::Won || viewer.is_some();
+    let display = if solved { Display::Flex } else { Display::None };
+    for mut node in &mut panel_query {
+        if node.display != display {
+            node.display = display;
+        }
+    }
+}
+
+/// System that copies the current puzzle's share code when the button is pressed.
+pub fn handle_copy_share_code_button(
+    interaction_query: ChangedButtons<CopyShareCodeButton>,
+    board: Res<BoardState>,
+    settings: Res<PuzzleSettings>,
+    mut text_query: Query<&mut Text, Without<ShareCodeDisplay>>,
+    mut display_query: Query<&mut Text, With<ShareCodeDisplay>>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let code = board.to_share_code(&settings);
+            let copied = copy_to_clipboard(&code);
+            info!("🔗 Share code: {}", code);
+
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(child) {
+                    text.0 = if copied { "✅ Copied!" } else { "🔗 Copy share code" }.to_string();
+                    break;
+                }
+            }
+            for mut text in &mut display_query {
+                text.0 = if copied {
+                    code.clone()
+                } else {
+                    format!("{}\n(No clipboard available - copy the code from here)", code)
+                };
+            }
+        }
+    }
+}
+
+/// System that starts or cancels typing a share code.
+pub fn handle_enter_share_code_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<EnterShareCodeButton>)>,
+    mut entry: ResMut<ShareCodeEntry>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            entry.active = !entry.active;
+            entry.code.clear();
+            entry.message = None;
+        }
+    }
+}
+
+/// System that feeds key presses into the share code being typed.
+/// Enter starts the shared puzzle, Escape cancels, and Cmd/Ctrl+V pastes.
+pub fn share_code_typing_system(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    input: Res<ButtonInput<KeyCode>>,
+    mut entry: ResMut<ShareCodeEntry>,
+    mut start: ShareCodeStart,
+) {
+    if !entry.active {
+        keyboard_events.clear();
+        return;
+    }
+
+    let modifier_pressed = if cfg!(target_os = "macos") {
+        input.pressed(KeyCode::SuperLeft) || input.pressed(KeyCode::SuperRight)
+    } else {
+        input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight)
+    };
+
+    for event in keyboard_events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+        match &event.logical_key {
+            _ if modifier_pressed && event.key_code == KeyCode::KeyV => match paste_from_clipboard() {
+                Some(pasted) => entry.code.push_str(&pasted),
+                None => entry.message = Some("⚠️ Couldn't read the clipboard".to_string()),
+            },
+            Key::Character(text) if !modifier_pressed => {
+                entry.code.extend(text.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-'));
+            }
+            Key::Space if !modifier_pressed => entry.code.push(' '),
+            Key::Backspace => {
+                entry.code.pop();
+            }
+            Key::Escape => {
+                entry.active = false;
+                entry.code.clear();
+            }
+            Key::Enter => {
+                entry.active = false;
+                let code = std::mem::take(&mut entry.code);
+                entry.message = start.start(&code).err();
+            }
+            _ => {}
+        }
+        entry.code.truncate(MAX_CODE_LENGTH);
+    }
+}
+
+/// Everything needed to start a game from a share code.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct ShareCodeStart<'w, 's> {
+    commands: Commands<'w, 's>,
+    app_state: ResMut<'w, NextState<AppState>>,
+    board: ResMut<'w, BoardState>,
+    session: ResMut<'w, GameSession>,
+    history: ResMut<'w, GameHistory>,
+    solution: ResMut<'w, Solution>,
+    hint_system: ResMut<'w, HintSystem>,
+    last_generation: ResMut<'w, LastGeneration>,
+}
+
+impl ShareCodeStart<'_, '_> {
+    /// Start the puzzle behind a share code, like the Start Game button does
+    /// for a generated one. Explains what went wrong if the code is no good.
+    fn start(&mut self, code: &str) -> Result<(), String> {
+        let Some(shared) = BoardState::from_share_code(code) else {
+            return Err("⚠️ That code doesn't look right - check for typos".to_string());
+        };
+        let new_solution = match solve_puzzle(&shared.board) {
+            Ok(solution) => solution,
+            Err(NineLivesError::InvalidPuzzle { reason }) => return Err(format!("⚠️ That puzzle {}", reason)),
+            Err(e) => return Err(format!("⚠️ {}", e)),
+        };
+
+        let settings = shared.settings();
+        info!("🔗 Starting shared puzzle: {}", settings.description());
+        *self.board = shared.board;
+        *self.solution = new_solution;
+        self.session.reset();
+        self.history.clear();
+        self.hint_system.reset(settings.max_hints);
+        *self.last_generation = LastGeneration::default();
+        self.commands.insert_resource(settings);
+        self.app_state.set(AppState::Ready);
+        Ok(())
+    }
+}
+
+/// System that keeps the entry line in sync with the code being typed.
+pub fn update_share_code_entry_display(
+    entry: Res<ShareCodeEntry>,
+    mut display_query: Query<&mut Text, With<ShareCodeEntryDisplay>>,
+) {
+    for mut text in &mut display_query {
+        text.0 = entry.display_text();
+    }
+}
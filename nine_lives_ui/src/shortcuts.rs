@@ -0,0 +1,245 @@
+//! Keyboard shortcut rebinding for Nine Lives Cat Sudoku.
+//!
+//! Below the settings on the settings screen, each shortcut (see
+//! `nine_lives_core::KeyBindings`) has a button showing its keys. Pressing
+//! it waits for a new key, held with any modifiers; Escape cancels. Keys that
+//! are taken, by another shortcut or by playing, are refused with a note
+//! saying why.
+
+use bevy::prelude::*;
+use nine_lives_core::{KeyBinding, KeyBindings, PersistentData, ShortcutAction};
+
+use crate::{ErrorToast, Focusable, Locale};
+
+/// Keys that only modify others, and make no binding on their own
+const MODIFIER_KEYS: [KeyCode; 8] = [
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+];
+
+// --- Components & Resources ---
+
+/// Component for a shortcut's button, showing its keys.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShortcutButton(pub ShortcutAction);
+
+/// Component to tag the note under the shortcuts, saying why a key was refused.
+#[derive(Component)]
+pub struct ShortcutMessageDisplay;
+
+/// Resource tracking a shortcut waiting for its new key.
+#[derive(Resource, Debug, Default)]
+pub struct ShortcutCapture {
+    /// The shortcut being rebound, if any
+    pub action: Option<ShortcutAction>,
+    /// Why the last key was refused
+    pub message: Option<String>,
+}
+
+/// The key of a shortcut's name in the strings files (see `Locale`).
+fn shortcut_name_key(action: ShortcutAction) -> &'static str {
+    match action {
+        ShortcutAction::Undo => "shortcut.undo",
+        ShortcutAction::Redo => "shortcut.redo",
+        ShortcutAction::ToggleDebug => "shortcut.toggle_debug",
+    }
+}
+
+/// The binding made by pressing `key` with the modifiers held now. The
+/// command key is ⌘ on Mac and Ctrl elsewhere.
+pub fn key_binding_for(key: KeyCode, input: &ButtonInput<KeyCode>) -> KeyBinding {
+    let command = if cfg!(target_os = "macos") {
+        input.any_pressed([KeyCode::SuperLeft, KeyCode::SuperRight])
+    } else {
+        input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+    };
+    let shift = input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    KeyBinding::new(&format!("{:?}", key), command, shift)
+}
+
+/// The shortcuts bound to the keys just pressed.
+pub fn shortcuts_pressed(input: &ButtonInput<KeyCode>, bindings: &KeyBindings) -> Vec<ShortcutAction> {
+    input
+        .get_just_pressed()
+        .filter_map(|&key| bindings.action_for(&key_binding_for(key, input)))
+        .collect()
+}
+
+// --- Layout ---
+
+/// Spawn the shortcut rows of the settings screen as children of `parent`.
+/// The button labels are filled in by `update_shortcut_labels`.
+pub fn spawn_shortcut_rows(parent: &mut ChildSpawnerCommands, locale: &Locale) {
+    parent.spawn((
+        locale.text("settings.shortcuts"),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            margin: UiRect::top(Val::Px(16.0)),
+            ..default()
+        },
+    ));
+
+    for (index, action) in ShortcutAction::all().into_iter().enumerate() {
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(10.0),
+                ..default()
+            })
+            .with_children(|row| {
+                row.spawn((
+                    locale.text(shortcut_name_key(action)),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.85, 0.85, 0.95)),
+                    Node {
+                        width: Val::Px(260.0),
+                        ..default()
+                    },
+                ));
+                row.spawn((
+                    Button,
+                    ShortcutButton(action),
+                    Focusable::Shortcut(index),
+                    Node {
+                        width: Val::Px(252.0),
+                        height: Val::Px(32.0),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+            });
+    }
+
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.85, 0.6)),
+        ShortcutMessageDisplay,
+    ));
+}
+
+// --- Systems ---
+
+/// System that starts waiting for a shortcut's new key when its button is
+/// pressed, or stops waiting when it's pressed again.
+pub fn handle_shortcut_buttons(
+    interaction_query: Query<(&Interaction, &ShortcutButton), Changed<Interaction>>,
+    mut capture: ResMut<ShortcutCapture>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            capture.action = if capture.action == Some(button.0) { None } else { Some(button.0) };
+            capture.message = None;
+        }
+    }
+}
+
+/// System that binds the next key pressed to the shortcut waiting for one,
+/// and saves it. Escape cancels. Runs before `handle_shortcut_buttons`, so
+/// the Enter that pressed the button isn't taken as the new key.
+pub fn capture_shortcut_system(
+    input: Res<ButtonInput<KeyCode>>,
+    mut capture: ResMut<ShortcutCapture>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    let Some(action) = capture.action else {
+        return;
+    };
+    let Some(&key) = input.get_just_pressed().find(|key| !MODIFIER_KEYS.contains(key)) else {
+        return;
+    };
+    capture.action = None;
+    if key == KeyCode::Escape {
+        return;
+    }
+
+    let binding = key_binding_for(key, &input);
+    match persistent_data.user_settings.key_bindings.rebind(action, binding) {
+        Ok(()) => {
+            info!("⌨️ {} is now {}", action.display_name(), persistent_data.user_settings.key_bindings.label(action));
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+        }
+        Err(problem) => capture.message = Some(format!("⚠️ {}", problem)),
+    }
+}
+
+/// System that shows each shortcut's keys (or asks for new ones), and why
+/// the last key was refused.
+pub fn update_shortcut_labels(
+    capture: Res<ShortcutCapture>,
+    persistent_data: Res<PersistentData>,
+    locale: Res<Locale>,
+    button_query: Query<(&ShortcutButton, &Children)>,
+    mut text_query: Query<&mut Text, Without<ShortcutMessageDisplay>>,
+    mut message_query: Query<&mut Text, With<ShortcutMessageDisplay>>,
+) {
+    let bindings = &persistent_data.user_settings.key_bindings;
+    for (button, children) in &button_query {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = if capture.action == Some(button.0) {
+                    locale.get("settings.press_keys")
+                } else {
+                    bindings.label(button.0)
+                };
+                break;
+            }
+        }
+    }
+    for mut text in &mut message_query {
+        text.0 = capture.message.clone().unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortcuts_pressed() {
+        let bindings = KeyBindings::default();
+        let command = if cfg!(target_os = "macos") { KeyCode::SuperLeft } else { KeyCode::ControlLeft };
+        let mut input = ButtonInput::<KeyCode>::default();
+        input.press(KeyCode::KeyZ);
+        assert!(shortcuts_pressed(&input, &bindings).is_empty(), "Shortcuts need the command key");
+
+        input.press(command);
+        input.press(KeyCode::ShiftLeft);
+        assert_eq!(key_binding_for(KeyCode::KeyZ, &input), KeyBinding::new("KeyZ", true, true));
+        assert_eq!(shortcuts_pressed(&input, &bindings), vec![ShortcutAction::Redo]);
+    }
+}
@@ -0,0 +1,258 @@
+//! Solver step visualizer for Nine Lives Cat Sudoku.
+//!
+//! While debug mode is on (⌘D / Ctrl+D), a panel in the corner of the game
+//! screen runs the logical solver (see `nine_lives_core::StepSolver`) on the
+//! current board one step at a time. Each step outlines the cells its
+//! technique looked at (blue), the cell it filled (green), and the cells it
+//! ruled candidates out of (red), with a caption explaining it. The solver
+//! works on its own copy of the board, so the player's cats stay put; a move
+//! on the board starts the walkthrough over from there.
+
+use bevy::prelude::*;
+use nine_lives_core::{BoardState, DebugMode, SolverStep, StepSolver};
+
+use crate::{Cell, Focusable};
+
+/// Width of the solver panel
+const PANEL_WIDTH: f32 = 300.0;
+/// Outline of a cell the step looked at
+const LOOKED_AT_COLOR: Color = Color::srgb(0.3, 0.6, 1.0);
+/// Outline of the cell the step filled
+const PLACED_COLOR: Color = Color::srgb(0.3, 0.9, 0.5);
+/// Outline of a cell the step ruled candidates out of
+const ELIMINATED_COLOR: Color = Color::srgb(1.0, 0.4, 0.35);
+/// Width of the step outlines
+const MARK_WIDTH: f32 = 3.0;
+
+// --- Components & Resources ---
+
+/// Component to tag the solver step panel.
+#[derive(Component)]
+pub struct SolverStepsPanel;
+
+/// Component to tag the button that takes the next solver step.
+#[derive(Component)]
+pub struct SolverStepButton;
+
+/// Component to tag the button that starts the walkthrough over.
+#[derive(Component)]
+pub struct SolverRestartButton;
+
+/// Component to tag the caption explaining the last step.
+#[derive(Component)]
+pub struct SolverCaptionDisplay;
+
+/// Component on a cell outlined for the last solver step.
+#[derive(Component)]
+pub struct SolverStepMark;
+
+/// Resource holding the solve being stepped through, if one is under way.
+#[derive(Resource, Default)]
+pub struct SolverWalkthrough {
+    /// The solve, started from the board on the first step
+    pub solver: Option<StepSolver>,
+    pub steps_taken: usize,
+    pub last_step: Option<SolverStep>,
+}
+
+impl SolverWalkthrough {
+    /// Drop the solve, so the next step starts over from the board.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The caption for the panel: the last step, what it placed or ruled
+    /// out, and where the walkthrough stands.
+    pub fn caption(&self) -> String {
+        let Some(step) = &self.last_step else {
+            return if self.solver.is_some() {
+                "Nothing left to solve.".to_string()
+            } else {
+                "Press Next step to watch the solver work on this board.".to_string()
+            };
+        };
+        let mut caption = format!("Step {}: {}", self.steps_taken, step.caption);
+        if let Some((row, col, value)) = step.placed {
+            caption.push_str(&format!("\nPlaced: cat {} at R{}C{}", value + 1, row + 1, col + 1));
+        }
+        if !step.eliminated.is_empty() {
+            let ruled_out: Vec<String> = step
+                .eliminated
+                .iter()
+                .map(|(row, col, value)| format!("{} from R{}C{}", value + 1, row + 1, col + 1))
+                .collect();
+            caption.push_str(&format!("\nRuled out: {}", ruled_out.join(", ")));
+        }
+        caption
+    }
+}
+
+/// Outline color of each cell marked for a step: the filled cell, then the
+/// cells with candidates ruled out, then the cells looked at.
+fn step_marks(step: &SolverStep) -> Vec<((usize, usize), Color)> {
+    let mut marks: Vec<((usize, usize), Color)> = Vec::new();
+    let mut mark = |cell: (usize, usize), color: Color| {
+        if !marks.iter().any(|(marked, _)| *marked == cell) {
+            marks.push((cell, color));
+        }
+    };
+    if let Some((row, col, _)) = step.placed {
+        mark((row, col), PLACED_COLOR);
+    }
+    for &(row, col, _) in &step.eliminated {
+        mark((row, col), ELIMINATED_COLOR);
+    }
+    for &cell in &step.cells {
+        mark(cell, LOOKED_AT_COLOR);
+    }
+    marks
+}
+
+// --- Layout ---
+
+/// Spawn one of the panel's buttons, tagged with `tags`.
+fn spawn_panel_button(parent: &mut ChildSpawnerCommands, label: &str, tags: impl Bundle) {
+    parent
+        .spawn((
+            Button,
+            tags,
+            Node {
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(3.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.2, 0.3, 0.45)),
+            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+        ))
+        .with_children(|button_parent| {
+            button_parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Spawn the (initially hidden) solver step panel on the game screen.
+pub fn spawn_solver_steps_panel(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            SolverStepsPanel,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                width: Val::Px(PANEL_WIDTH),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.9)),
+            BorderColor(Color::srgb(0.4, 0.4, 0.5)),
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new("🔍 Solver steps"),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            panel
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(4.0),
+                    ..default()
+                })
+                .with_children(|buttons| {
+                    spawn_panel_button(buttons, "Next step", (SolverStepButton, Focusable::SolverStep));
+                    spawn_panel_button(buttons, "Start over", (SolverRestartButton, Focusable::SolverRestart));
+                });
+
+            panel.spawn((
+                Text::new(SolverWalkthrough::default().caption()),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.85, 1.0)),
+                SolverCaptionDisplay,
+            ));
+        });
+}
+
+// --- Systems ---
+
+/// System that shows the solver panel only while debug mode is on.
+pub fn update_solver_panel_visibility(
+    debug_mode: Res<DebugMode>,
+    mut panel_query: Query<&mut Node, With<SolverStepsPanel>>,
+) {
+    for mut node in &mut panel_query {
+        node.display = if debug_mode.enabled { Display::Flex } else { Display::None };
+    }
+}
+
+/// System that takes the next solver step, or starts over, when a panel
+/// button is pressed, and outlines the cells the step used. A move on the
+/// board (or leaving debug mode) starts the walkthrough over.
+pub fn handle_solver_step_buttons(
+    mut commands: Commands,
+    step_query: Query<&Interaction, (Changed<Interaction>, With<SolverStepButton>)>,
+    restart_query: Query<&Interaction, (Changed<Interaction>, With<SolverRestartButton>)>,
+    board: Res<BoardState>,
+    debug_mode: Res<DebugMode>,
+    mut walkthrough: ResMut<SolverWalkthrough>,
+    cell_query: Query<(Entity, &Cell, Has<SolverStepMark>)>,
+) {
+    let restart = restart_query.iter().any(|interaction| *interaction == Interaction::Pressed);
+    let step = step_query.iter().any(|interaction| *interaction == Interaction::Pressed);
+    let board_moved = board.is_changed() && walkthrough.solver.is_some();
+    let left_debug = !debug_mode.enabled && walkthrough.solver.is_some();
+    if !restart && !step && !board_moved && !left_debug {
+        return;
+    }
+
+    for (entity, _, marked) in &cell_query {
+        if marked {
+            commands.entity(entity).remove::<(SolverStepMark, Outline)>();
+        }
+    }
+    if restart || board_moved || left_debug {
+        walkthrough.reset();
+    }
+    if !step {
+        return;
+    }
+
+    let solver = walkthrough.solver.get_or_insert_with(|| StepSolver::new(&board));
+    let next = solver.step();
+    if let Some(next) = &next {
+        walkthrough.steps_taken += 1;
+        let marks = step_marks(next);
+        for (entity, cell, _) in &cell_query {
+            if let Some((_, color)) = marks.iter().find(|(marked, _)| *marked == (cell.row, cell.col)) {
+                commands.entity(entity).insert((SolverStepMark, Outline::new(Val::Px(MARK_WIDTH), Val::ZERO, *color)));
+            }
+        }
+    }
+    walkthrough.last_step = next;
+}
+
+/// System that shows the caption for the last solver step.
+pub fn update_solver_caption(
+    walkthrough: Res<SolverWalkthrough>,
+    mut caption_query: Query<&mut Text, With<SolverCaptionDisplay>>,
+) {
+    for mut text in &mut caption_query {
+        text.0 = walkthrough.caption();
+    }
+}
@@ -0,0 +1,587 @@
+//! Statistics screen for Nine Lives Cat Sudoku.
+//!
+//! Reads the persisted `GameStatistics` and lays them out as a simple
+//! read-only report, with the last few games and each preset's leaderboard
+//! underneath. Reached from the customization screen. A completion that
+//! makes a leaderboard is also announced on the game screen once the puzzle
+//! is solved, and records kept with their replay can be watched from here
+//! (see `best_replay`).
+//!
+//! The play history can be exported as CSV or JSON, to a file picked in the
+//! platform's save dialog. There's no file dialog in Bevy, so it goes through
+//! the platform's command-line tools (`osascript`, PowerShell, `zenity`,
+//! `kdialog`); without any, the file goes into the save directory.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use bevy::prelude::*;
+use nine_lives_core::{
+    CompletedGameRecord, Difficulty, LeaderboardEntry, PRACTICE_TECHNIQUES, PersistentData, PresetKind,
+    StatsExportFormat, SummaryExport, TREND_WINDOW, format_unix_date,
+};
+
+use crate::{AppState, ChangedButtons, ErrorToast, Focusable, Locale, spawn_best_replay_panel, spawn_watch_best_replay_button};
+
+// --- Components ---
+
+/// Component to tag the statistics screen root for cleanup.
+#[derive(Component)]
+pub struct StatisticsScreenRoot;
+
+/// Number of recent games listed on the statistics screen
+const RECENT_GAMES_SHOWN: usize = 5;
+
+/// Component to tag the "Statistics" button on the customization screen.
+#[derive(Component)]
+pub struct StatisticsButton;
+
+/// Component to tag the "Back" button on the statistics screen.
+#[derive(Component)]
+pub struct StatisticsBackButton;
+
+/// Component to tag the button that chooses the session summary export format.
+#[derive(Component)]
+pub struct SummaryExportButton;
+
+/// Component for a button that exports the play history in a format.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatsExportButton(pub StatsExportFormat);
+
+/// Component to tag the line saying where the play history was exported.
+#[derive(Component)]
+pub struct StatsExportMessageDisplay;
+
+/// Component to tag the line announcing a new leaderboard place.
+#[derive(Component)]
+pub struct NewRecordDisplay;
+
+// --- Resources ---
+
+/// Where the last completed puzzle landed on its preset's leaderboard.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LeaderboardPlacement {
+    pub preset: PresetKind,
+    /// Place on the leaderboard (0 for a new record), if it made it
+    pub rank: Option<usize>,
+}
+
+impl LeaderboardPlacement {
+    /// The announcement for the game screen, if the puzzle made the leaderboard.
+    pub fn message(&self, locale: &Locale) -> Option<String> {
+        let rank = self.rank?;
+        let preset = locale.preset_name(self.preset);
+        Some(if rank == 0 {
+            locale.format("win.record", &[("preset", &preset)])
+        } else {
+            locale.format("win.placed", &[("rank", &(rank + 1).to_string()), ("preset", &preset)])
+        })
+    }
+}
+
+// --- Helpers ---
+
+/// Format a number of seconds as MM:SS (or H:MM:SS for long sessions).
+pub fn format_duration_secs(total_seconds: u64) -> String {
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+/// Build the lines of text shown on the statistics screen.
+pub fn statistics_lines(data: &PersistentData) -> Vec<String> {
+    let stats = &data.statistics;
+    let mut lines = Vec::new();
+
+    lines.push(format!(
+        "Games completed: {}   •   Abandoned: {}",
+        stats.games_completed, stats.games_abandoned
+    ));
+
+    for difficulty in Difficulty::all() {
+        let name = difficulty.name();
+        let best = stats
+            .best_time_for(name)
+            .map(format_duration_secs)
+            .unwrap_or_else(|| "--:--".to_string());
+        lines.push(format!(
+            "{}: {} completed   •   best {}",
+            name,
+            stats.completed_for(name),
+            best
+        ));
+    }
+
+    let fastest = stats
+        .fastest_completion_seconds
+        .map(format_duration_secs)
+        .unwrap_or_else(|| "--:--".to_string());
+    let average = stats
+        .average_completion_seconds()
+        .map(format_duration_secs)
+        .unwrap_or_else(|| "--:--".to_string());
+    lines.push(format!("Fastest: {}   •   Average: {}", fastest, average));
+    lines.push(format!(
+        "Total play time: {}",
+        format_duration_secs(stats.total_play_time_seconds)
+    ));
+    lines.push(format!("Hints used: {}", stats.total_hints_used));
+    lines.push(format!(
+        "Win streak: {}   •   Best streak: {}",
+        stats.current_win_streak, stats.best_win_streak
+    ));
+    lines.push(format!("Longest streak: {} right cats in a row", stats.best_placement_streak));
+    let time_attack = &stats.time_attack;
+    lines.push(if time_attack.runs == 0 {
+        "Time attack: no runs yet".to_string()
+    } else {
+        format!(
+            "Time attack: {} runs   •   best {} points ({} solved)",
+            time_attack.runs, time_attack.best_score, time_attack.best_solved
+        )
+    });
+    let practiced: Vec<String> = PRACTICE_TECHNIQUES
+        .iter()
+        .map(|&technique| (technique, stats.practice_record(technique)))
+        .filter(|(_, record)| record.solved > 0)
+        .map(|(technique, record)| format!("{} {}", technique.name(), record.proficiency().name().to_lowercase()))
+        .collect();
+    if !practiced.is_empty() {
+        lines.push(format!("Practice: {}", practiced.join("   •   ")));
+    }
+    if let Some(average) = stats.average_duration(0, TREND_WINDOW) {
+        let trend = match stats.average_duration(TREND_WINDOW, TREND_WINDOW) {
+            Some(before) if average < before => {
+                format!(" ({} faster than the {} before)", format_duration_secs(before - average), TREND_WINDOW)
+            }
+            Some(before) if average > before => {
+                format!(" ({} slower than the {} before)", format_duration_secs(average - before), TREND_WINDOW)
+            }
+            _ => String::new(),
+        };
+        lines.push(format!("Last {} games: average {}{}", TREND_WINDOW, format_duration_secs(average), trend));
+    }
+
+    lines
+}
+
+/// Format one recent game: date, preset, time, hints, mistakes, and score.
+pub fn recent_game_line(record: &CompletedGameRecord) -> String {
+    let date = format_unix_date(record.date);
+    format!(
+        "{}  {}  {}   💡{}  ✗{}  {} pts",
+        date.split_whitespace().next().unwrap_or_default(),
+        record.preset.display_name(),
+        format_duration_secs(record.duration),
+        record.hints_used,
+        record.mistakes,
+        record.score
+    )
+}
+
+/// Format one leaderboard entry: place, time, and date, then hints,
+/// mistakes, and score.
+pub fn leaderboard_entry_line(rank: usize, entry: &LeaderboardEntry) -> String {
+    let date = format_unix_date(entry.completed_at);
+    format!(
+        "{}. {}  {}\n    💡{}  ✗{}  {} pts",
+        rank + 1,
+        format_duration_secs(entry.seconds),
+        date.split_whitespace().next().unwrap_or_default(),
+        entry.hints_used,
+        entry.mistakes,
+        entry.score
+    )
+}
+
+/// Label for the session summary export button.
+pub fn summary_export_label(export: SummaryExport) -> String {
+    format!("📓 Journal export: {}", export.display_name())
+}
+
+/// What came of asking the player where to save a file.
+enum SaveDialogResult {
+    Chosen(PathBuf),
+    Cancelled,
+    /// No save dialog could be shown on this system
+    Unavailable,
+}
+
+/// Ask the player where to save a file, offering `file_name`, in the
+/// platform's save dialog. Blocks until the dialog closes.
+fn ask_where_to_save(file_name: &str) -> SaveDialogResult {
+    let apple_script = format!(
+        "POSIX path of (choose file name with prompt \"Export play history\" default name \"{}\")",
+        file_name
+    );
+    let power_shell = format!(
+        "Add-Type -AssemblyName System.Windows.Forms; $dialog = New-Object System.Windows.Forms.SaveFileDialog; \
+         $dialog.FileName = '{}'; if ($dialog.ShowDialog() -eq 'OK') {{ $dialog.FileName }} else {{ exit 1 }}",
+        file_name
+    );
+    let zenity_name = format!("--filename={}", file_name);
+    let tools: Vec<(&str, Vec<&str>)> = if cfg!(target_os = "macos") {
+        vec![("osascript", vec!["-e", &apple_script])]
+    } else if cfg!(target_os = "windows") {
+        vec![("powershell", vec!["-NoProfile", "-Command", &power_shell])]
+    } else {
+        vec![
+            ("zenity", vec!["--file-selection", "--save", "--confirm-overwrite", &zenity_name]),
+            ("kdialog", vec!["--getsavefilename", file_name]),
+        ]
+    };
+
+    for (program, args) in tools {
+        let Ok(output) = Command::new(program).args(args).output() else {
+            continue;
+        };
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return if output.status.success() && !path.is_empty() {
+            SaveDialogResult::Chosen(PathBuf::from(path))
+        } else {
+            SaveDialogResult::Cancelled
+        };
+    }
+    SaveDialogResult::Unavailable
+}
+
+// --- Systems ---
+
+/// System that creates the statistics screen UI.
+pub fn setup_statistics_screen(mut commands: Commands, data: Res<PersistentData>) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+            StatisticsScreenRoot,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new("📊 Your Cat Stats"),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                },
+            ));
+
+            for line in statistics_lines(&data) {
+                parent.spawn((
+                    Text::new(line),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.85, 0.85, 0.95)),
+                ));
+            }
+
+            // The last few games played
+            let recent_games: Vec<String> =
+                data.statistics.recent_games(RECENT_GAMES_SHOWN).map(recent_game_line).collect();
+            if !recent_games.is_empty() {
+                parent.spawn((
+                    Text::new("Recent games"),
+                    TextFont {
+                        font_size: 13.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                    Node {
+                        margin: UiRect::top(Val::Px(12.0)),
+                        ..default()
+                    },
+                ));
+            }
+            for line in recent_games {
+                parent.spawn((
+                    Text::new(line),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.85, 0.85, 0.95)),
+                ));
+            }
+
+            // Leaderboards, one column per preset
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(16.0),
+                    margin: UiRect::top(Val::Px(20.0)),
+                    ..default()
+                })
+                .with_children(|boards| {
+                    for (index, preset) in PresetKind::all().into_iter().enumerate() {
+                        boards
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(4.0),
+                                width: Val::Px(150.0),
+                                ..default()
+                            })
+                            .with_children(|column| {
+                                column.spawn((
+                                    Text::new(preset.display_name()),
+                                    TextFont {
+                                        font_size: 13.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                                ));
+                                let entries = data.statistics.leaderboard.top(preset);
+                                if entries.is_empty() {
+                                    column.spawn((
+                                        Text::new("No times yet"),
+                                        TextFont {
+                                            font_size: 11.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::srgb(0.6, 0.6, 0.7)),
+                                    ));
+                                }
+                                for (rank, entry) in entries.iter().enumerate() {
+                                    column.spawn((
+                                        Text::new(leaderboard_entry_line(rank, entry)),
+                                        TextFont {
+                                            font_size: 11.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::srgb(0.85, 0.85, 0.95)),
+                                    ));
+                                }
+                                if data.statistics.leaderboard.best_replay(preset).is_some() {
+                                    spawn_watch_best_replay_button(column, preset, index);
+                                }
+                            });
+                    }
+                });
+
+            spawn_best_replay_panel(parent);
+
+            // Session summary export toggle
+            parent
+                .spawn((
+                    Button,
+                    SummaryExportButton,
+                    Focusable::SummaryExport,
+                    Node {
+                        width: Val::Px(260.0),
+                        height: Val::Px(40.0),
+                        margin: UiRect::top(Val::Px(20.0)),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        Text::new(summary_export_label(data.user_settings.summary_export)),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
+            // Play history export, one button per format
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(12.0),
+                    margin: UiRect::top(Val::Px(10.0)),
+                    ..default()
+                })
+                .with_children(|row| {
+                    for (index, format) in StatsExportFormat::all().into_iter().enumerate() {
+                        row.spawn((
+                            Button,
+                            StatsExportButton(format),
+                            Focusable::StatsExport(index),
+                            Node {
+                                width: Val::Px(124.0),
+                                height: Val::Px(36.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                        ))
+                        .with_children(|button_parent| {
+                            button_parent.spawn((
+                                Text::new(format!("📤 Export {}", format.display_name())),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+                    }
+                });
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.85, 0.6)),
+                StatsExportMessageDisplay,
+            ));
+
+            // Back button
+            parent
+                .spawn((
+                    Button,
+                    StatisticsBackButton,
+                    Focusable::StatisticsBack,
+                    Node {
+                        width: Val::Px(160.0),
+                        height: Val::Px(45.0),
+                        margin: UiRect::top(Val::Px(30.0)),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                    BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        Text::new("⬅ Back"),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+/// System to clean up the statistics screen when exiting that state.
+pub fn cleanup_statistics_screen(
+    mut commands: Commands,
+    query: Query<Entity, With<StatisticsScreenRoot>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// System that opens the statistics screen from the customization screen.
+pub fn handle_statistics_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<StatisticsButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::Statistics);
+        }
+    }
+}
+
+/// System that returns from the statistics screen to the customization screen.
+pub fn handle_statistics_back_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<StatisticsBackButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::Customization);
+        }
+    }
+}
+
+/// System to cycle the session summary export format (Off / Markdown / HTML).
+/// The choice is saved to the user's settings.
+pub fn handle_summary_export_button(
+    interaction_query: ChangedButtons<SummaryExportButton>,
+    mut text_query: Query<&mut Text>,
+    mut persistent_data: ResMut<PersistentData>,
+    mut toasts: EventWriter<ErrorToast>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let export = persistent_data.user_settings.summary_export.next();
+            persistent_data.user_settings.summary_export = export;
+            if let Err(e) = persistent_data.save() {
+                toasts.write(ErrorToast::new("Settings", &e));
+            }
+
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(child) {
+                    text.0 = summary_export_label(export);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// System that exports the play history when an export button is pressed,
+/// to a file the player picks, and says where it went.
+pub fn handle_stats_export_buttons(
+    interaction_query: Query<(&Interaction, &StatsExportButton), Changed<Interaction>>,
+    persistent_data: Res<PersistentData>,
+    mut message_query: Query<&mut Text, With<StatsExportMessageDisplay>>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let format = button.0;
+        let path = match ask_where_to_save(&format.file_name()) {
+            SaveDialogResult::Chosen(path) => Some(path),
+            SaveDialogResult::Cancelled => continue,
+            SaveDialogResult::Unavailable => None,
+        };
+        let message = match persistent_data.statistics.export_to_file(format, path) {
+            Ok(path) => {
+                info!("📤 Exported play history to {}", path.display());
+                format!("Exported to {}", path.display())
+            }
+            Err(e) => format!("⚠️ Couldn't export: {}", e),
+        };
+        for mut text in &mut message_query {
+            text.0 = message.clone();
+        }
+    }
+}
+
+/// System that announces a new leaderboard place on the game screen.
+pub fn update_new_record_display(
+    placement: Res<LeaderboardPlacement>,
+    locale: Res<Locale>,
+    mut display_query: Query<&mut Text, With<NewRecordDisplay>>,
+) {
+    for mut text in &mut display_query {
+        text.0 = placement.message(&locale).unwrap_or_default();
+    }
+}
@@ -0,0 +1,145 @@
+//! Streak feedback for Nine Lives Cat Sudoku.
+//!
+//! When the player's run of right cats reaches a milestone (see
+//! `nine_lives_core::StreakMilestone`), a cheer like "3 purr-fect in a row!"
+//! floats up over the board and fades, with a purr. Each level of cheer has
+//! a few wordings in each language (see `Locale::variant`). Longer streaks cheer
+//! bigger: larger, warmer text and a higher, louder purr. The purr is loaded
+//! from `assets/sounds/purr.ogg`; without it the cheer is silent.
+
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use nine_lives_core::{PersistentData, StreakMilestone};
+
+use crate::{Locale, fill};
+
+/// How long a cheer stays on screen, in seconds
+const POPUP_SECONDS: f32 = 1.4;
+/// How far a cheer floats up as it fades, in pixels
+const POPUP_RISE: f32 = 40.0;
+/// Where the cheer starts, from the top of the window, in pixels
+const POPUP_TOP: f32 = 190.0;
+
+/// Sent by the controller when the player's streak reaches a milestone.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreakReached(pub StreakMilestone);
+
+/// Component on a cheer while it floats up and fades.
+#[derive(Component)]
+pub struct StreakPopup {
+    pub timer: Timer,
+    pub color: Color,
+}
+
+/// The color of a cheer: greener for short streaks, warmer for long ones.
+pub fn streak_color(level: u8) -> Color {
+    match level {
+        1 => Color::srgb(0.6, 0.95, 0.6),
+        2 => Color::srgb(1.0, 0.85, 0.35),
+        _ => Color::srgb(1.0, 0.55, 0.25),
+    }
+}
+
+/// The font size of a cheer, growing with its level.
+pub fn streak_font_size(level: u8) -> f32 {
+    20.0 + 6.0 * level.min(StreakMilestone::MAX_LEVEL) as f32
+}
+
+// --- Systems ---
+
+/// System that cheers each streak milestone with a floating message and a purr.
+pub fn show_streak_feedback(
+    mut commands: Commands,
+    mut reached: EventReader<StreakReached>,
+    asset_server: Res<AssetServer>,
+    persistent_data: Res<PersistentData>,
+    locale: Res<Locale>,
+    time: Res<Time>,
+) {
+    let volume = persistent_data.user_settings.volume.clamp(0.0, 1.0);
+    for StreakReached(milestone) in reached.read() {
+        let color = streak_color(milestone.level);
+        let wording = locale.variant(&format!("streak.{}", milestone.level), time.elapsed().as_millis() as usize);
+        let message = fill(&wording, &[("count", &milestone.count.to_string())]);
+        commands
+            .spawn((
+                StreakPopup {
+                    timer: Timer::from_seconds(POPUP_SECONDS, TimerMode::Once),
+                    color,
+                },
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(POPUP_TOP),
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                Pickable::IGNORE,
+                GlobalZIndex(5),
+            ))
+            .with_children(|popup| {
+                popup.spawn((
+                    Text::new(message),
+                    TextFont {
+                        font_size: streak_font_size(milestone.level),
+                        ..default()
+                    },
+                    TextColor(color),
+                    TextShadow::default(),
+                ));
+            });
+
+        // Higher and louder purrs for longer streaks
+        let level = milestone.level as f32;
+        commands.spawn((
+            AudioPlayer::new(asset_server.load("sounds/purr.ogg")),
+            PlaybackSettings::DESPAWN
+                .with_speed(0.85 + 0.15 * level)
+                .with_volume(Volume::Linear(volume * (0.5 + 0.5 * level / StreakMilestone::MAX_LEVEL as f32))),
+        ));
+    }
+}
+
+/// System that floats each cheer up while fading it out, then removes it.
+pub fn animate_streak_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut popup_query: Query<(Entity, &mut StreakPopup, &mut Node, &Children)>,
+    mut text_query: Query<&mut TextColor>,
+) {
+    for (entity, mut popup, mut node, children) in &mut popup_query {
+        popup.timer.tick(time.delta());
+        if popup.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let progress = popup.timer.fraction();
+        node.top = Val::Px(POPUP_TOP - POPUP_RISE * progress);
+        for child in children.iter() {
+            if let Ok(mut color) = text_query.get_mut(child) {
+                // Hold at full strength for the first half, then fade
+                let alpha = (2.0 - 2.0 * progress).min(1.0);
+                color.0 = popup.color.with_alpha(alpha);
+            }
+        }
+    }
+}
+
+/// System that clears away any cheers still showing when leaving the game screen.
+pub fn clear_streak_popups(mut commands: Commands, popup_query: Query<Entity, With<StreakPopup>>) {
+    for entity in &popup_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cheers_grow_with_the_streak() {
+        assert!(streak_font_size(2) > streak_font_size(1));
+        assert_eq!(streak_font_size(9), streak_font_size(StreakMilestone::MAX_LEVEL));
+        assert_ne!(streak_color(1), streak_color(StreakMilestone::MAX_LEVEL));
+    }
+}
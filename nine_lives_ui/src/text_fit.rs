@@ -0,0 +1,216 @@
+//! Fitting text into a fixed space for Nine Lives Cat Sudoku.
+//!
+//! Text tagged with `TextFit` is kept within a width and a number of lines.
+//! It's wrapped at word boundaries, with words too long for a line broken
+//! and hyphenated. If it still runs past the line limit, the font shrinks
+//! (down to a floor), and whatever doesn't fit even then is cut off with an
+//! ellipsis. Glyph widths are estimated from the font size rather than
+//! measured, erring on the wide side.
+
+use bevy::prelude::*;
+
+/// Average glyph width as a fraction of the font size (a little generous,
+/// so estimated lines don't overflow the real ones)
+const GLYPH_WIDTH_RATIO: f32 = 0.6;
+/// Line height as a fraction of the font size (Bevy's default spacing)
+const LINE_HEIGHT_RATIO: f32 = 1.2;
+/// How much the font shrinks per step while looking for a size that fits
+const SHRINK_STEP: f32 = 1.0;
+/// Marks text cut off at the line limit
+const ELLIPSIS: char = '…';
+
+// --- Components ---
+
+/// Component that keeps a `Text` within `max_width` pixels and `max_lines`
+/// lines, shrinking its font from `base_size` towards `min_size` if it has to.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct TextFit {
+    pub max_width: f32,
+    pub max_lines: usize,
+    pub base_size: f32,
+    pub min_size: f32,
+}
+
+/// Text wrapped (and maybe cut off) to fit, with the font size it fits at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FittedText {
+    pub text: String,
+    pub font_size: f32,
+}
+
+impl TextFit {
+    /// Fit text at `base_size`, letting the font shrink to three quarters of it.
+    pub fn new(max_width: f32, max_lines: usize, base_size: f32) -> Self {
+        Self {
+            max_width,
+            max_lines: max_lines.max(1),
+            base_size,
+            min_size: base_size * 0.75,
+        }
+    }
+
+    /// Set the smallest the font may shrink to (no shrinking at `base_size`).
+    pub fn with_min_size(mut self, min_size: f32) -> Self {
+        self.min_size = min_size.min(self.base_size);
+        self
+    }
+
+    /// How many narrow glyphs fit on a line at the given font size.
+    fn columns_at(&self, font_size: f32) -> usize {
+        ((self.max_width / (font_size * GLYPH_WIDTH_RATIO)).floor() as usize).max(2)
+    }
+
+    /// Wrap `text` to fit: at the largest font size where it fits in the
+    /// line limit, or else at the smallest, cut off with an ellipsis.
+    pub fn fit(&self, text: &str) -> FittedText {
+        let mut font_size = self.base_size;
+        loop {
+            let columns = self.columns_at(font_size);
+            let lines = wrap_text(text, columns);
+            if lines.len() <= self.max_lines {
+                return FittedText { text: lines.join("\n"), font_size };
+            }
+            if font_size <= self.min_size {
+                return FittedText { text: truncate_lines(lines, self.max_lines, columns).join("\n"), font_size };
+            }
+            font_size = (font_size - SHRINK_STEP).max(self.min_size);
+        }
+    }
+}
+
+/// Columns a character takes up: two for emoji and East Asian scripts,
+/// one for everything else.
+fn char_columns(c: char) -> usize {
+    if c >= '\u{2E80}' { 2 } else { 1 }
+}
+
+fn str_columns(text: &str) -> usize {
+    text.chars().map(char_columns).sum()
+}
+
+/// Estimated size in pixels of `text` drawn as-is (no wrapping) at
+/// `font_size`: its widest line by its number of lines.
+pub fn estimated_text_size(text: &str, font_size: f32) -> Vec2 {
+    let columns = text.lines().map(str_columns).max().unwrap_or(0);
+    let lines = text.lines().count().max(1);
+    Vec2::new(
+        columns as f32 * font_size * GLYPH_WIDTH_RATIO,
+        lines as f32 * font_size * LINE_HEIGHT_RATIO,
+    )
+}
+
+/// Wrap text into lines of at most `columns` columns, breaking at spaces.
+/// Words longer than a line are split across lines with a hyphen. Line
+/// breaks already in the text are kept.
+pub fn wrap_text(text: &str, columns: usize) -> Vec<String> {
+    let columns = columns.max(2);
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let needed = if line.is_empty() { str_columns(word) } else { str_columns(&line) + 1 + str_columns(word) };
+            if needed <= columns {
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+
+            // Hyphenate a word too long for a line of its own
+            let mut rest = word;
+            while str_columns(rest) > columns {
+                let mut piece_columns = 0;
+                let split = rest
+                    .char_indices()
+                    .find(|&(_, c)| {
+                        piece_columns += char_columns(c);
+                        piece_columns > columns - 1
+                    })
+                    .map_or(rest.len(), |(index, _)| index)
+                    .max(rest.chars().next().map_or(0, char::len_utf8));
+                lines.push(format!("{}-", &rest[..split]));
+                rest = &rest[split..];
+            }
+            line.push_str(rest);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Keep the first `max_lines` lines, ending the last one with an ellipsis
+/// (trimmed so it still fits in `columns`) if anything was cut.
+pub fn truncate_lines(mut lines: Vec<String>, max_lines: usize, columns: usize) -> Vec<String> {
+    if lines.len() <= max_lines {
+        return lines;
+    }
+    lines.truncate(max_lines.max(1));
+    if let Some(last) = lines.last_mut() {
+        let mut kept = last.trim_end_matches('-').trim_end().to_string();
+        while !kept.is_empty() && str_columns(&kept) + 1 > columns {
+            kept.pop();
+        }
+        kept.push(ELLIPSIS);
+        *last = kept;
+    }
+    lines
+}
+
+// --- Systems ---
+
+/// System that re-fits tagged text whenever it (or its `TextFit`) changes.
+/// Fitted text fits as it is, so the rewrite doesn't set off another one.
+#[allow(clippy::type_complexity)]
+pub fn fit_text_system(
+    mut text_query: Query<(&TextFit, &mut Text, &mut TextFont), Or<(Changed<Text>, Changed<TextFit>)>>,
+) {
+    for (fit, mut text, mut font) in &mut text_query {
+        let fitted = fit.fit(&text.0);
+        if text.0 != fitted.text {
+            text.0 = fitted.text;
+        }
+        if font.font_size != fitted.font_size {
+            font.font_size = fitted.font_size;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(wrap_text("the quick brown fox", 10), vec!["the quick", "brown fox"]);
+        // Long words are hyphenated, and existing line breaks kept
+        assert_eq!(wrap_text("purrrrrrrrfect\nnap", 6), vec!["purrr-", "rrrrr-", "fect", "nap"]);
+        // Emoji take two columns
+        assert_eq!(wrap_text("🐱🐱🐱 ok", 6), vec!["🐱🐱🐱", "ok"]);
+    }
+
+    #[test]
+    fn test_text_fit() {
+        let fit = TextFit::new(120.0, 2, 10.0).with_min_size(8.0);
+
+        // Short text fits at the base size
+        assert_eq!(fit.fit("A cozy cat").font_size, 10.0);
+
+        // Longer text shrinks the font until it fits
+        let fitted = fit.fit("Lots of clues and helpful hints for beginners");
+        assert!(fitted.font_size < 10.0 && fitted.font_size >= 8.0);
+        assert!(fitted.text.lines().count() <= 2);
+
+        // Text that can't fit even at the smallest size is cut off
+        let fitted = fit.fit(&"meow ".repeat(40));
+        assert_eq!(fitted.font_size, 8.0);
+        assert_eq!(fitted.text.lines().count(), 2);
+        assert!(fitted.text.ends_with(ELLIPSIS));
+
+        // Fitting fitted text changes nothing
+        assert_eq!(fit.fit(&fitted.text), fitted);
+    }
+}
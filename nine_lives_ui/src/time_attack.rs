@@ -0,0 +1,312 @@
+//! Time attack mode for Nine Lives Cat Sudoku.
+//!
+//! The ⏱️ Time Attack button on the customization screen starts a run (see
+//! `nine_lives_core::TimeAttack`): classic 9×9 puzzles, Easy and then Medium,
+//! played against one shared clock. The game screen shows the clock, the
+//! puzzles solved, and the score; each solve goes straight on to the next
+//! puzzle, taken from the pre-generated pool when one is ready. When the clock
+//! runs out, an overlay sums up the run and offers another go.
+
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use nine_lives_core::{PersistentData, TimeAttack, TimeAttackRecord};
+
+use crate::{AppState, Focusable, NewGameButton, PuzzleGeneration, PuzzleSwap, format_duration_secs};
+
+/// Color of the run's clock and score line
+const HUD_COLOR: Color = Color::srgb(1.0, 0.8, 0.45);
+
+// --- Components ---
+
+/// Component to tag the "Time Attack" button on the customization screen.
+#[derive(Component)]
+pub struct TimeAttackButton;
+
+/// Component to tag the run's clock and score line on the game screen.
+#[derive(Component)]
+pub struct TimeAttackDisplay;
+
+/// Component to tag the overlay shown when a run's clock runs out.
+#[derive(Component)]
+pub struct TimeAttackOverlay;
+
+/// Component to tag the overlay's summary text.
+#[derive(Component)]
+pub struct TimeAttackSummaryText;
+
+/// Component to tag the overlay's "Play Again" button.
+#[derive(Component)]
+pub struct TimeAttackAgainButton;
+
+/// The run's clock and score, like "⏱️ 04:32 left  •  3 solved  •  450 points".
+/// The clock rounds up, so it only reads 00:00 once the time is gone.
+pub fn time_attack_hud_line(run: &TimeAttack) -> String {
+    format!(
+        "⏱️ {} left  •  {} solved  •  {} points",
+        format_duration_secs(run.remaining.as_secs_f64().ceil() as u64),
+        run.solved,
+        run.score
+    )
+}
+
+/// The overlay's look back at a finished run, once it's been recorded.
+pub fn time_attack_summary_lines(run: &TimeAttack, record: &TimeAttackRecord) -> Vec<String> {
+    let puzzles = if run.solved == 1 { "puzzle" } else { "puzzles" };
+    vec![
+        format!("You solved {} {} for {} points.", run.solved, puzzles, run.score),
+        if run.score > 0 && run.score == record.best_score {
+            "🏆 Your best score yet!".to_string()
+        } else {
+            format!("Best: {} points ({} solved)", record.best_score, record.best_solved)
+        },
+    ]
+}
+
+// --- Layout ---
+
+/// Spawn the run's clock and score line (hidden unless a run is on).
+pub fn spawn_time_attack_display(parent: &mut ChildSpawnerCommands) {
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(HUD_COLOR),
+        Node {
+            display: Display::None,
+            margin: UiRect::bottom(Val::Px(8.0)),
+            ..default()
+        },
+        TimeAttackDisplay,
+    ));
+}
+
+/// Spawn the (initially hidden) overlay for when a run's clock runs out.
+pub fn spawn_time_attack_overlay(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            TimeAttackOverlay,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.05, 0.75)),
+            FocusPolicy::Block,
+            GlobalZIndex(10),
+        ))
+        .with_children(|overlay| {
+            overlay
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(12.0),
+                        padding: UiRect::all(Val::Px(20.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        max_width: Val::Px(480.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.16, 0.13, 0.1)),
+                    BorderColor(Color::srgb(0.7, 0.55, 0.3)),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::new("⏱️ Time's up!"),
+                        TextFont {
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(HUD_COLOR),
+                    ));
+                    panel.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        TimeAttackSummaryText,
+                    ));
+
+                    panel
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(16.0),
+                            ..default()
+                        })
+                        .with_children(|buttons| {
+                            buttons
+                                .spawn((
+                                    Button,
+                                    TimeAttackAgainButton,
+                                    Focusable::TimeAttackAgain,
+                                    Node {
+                                        width: Val::Px(120.0),
+                                        height: Val::Px(40.0),
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::Center,
+                                        border: UiRect::all(Val::Px(2.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(Color::srgb(0.55, 0.4, 0.15)),
+                                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                                ))
+                                .with_children(|button_parent| {
+                                    button_parent.spawn((
+                                        Text::new("Play Again"),
+                                        TextFont {
+                                            font_size: 14.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::WHITE),
+                                    ));
+                                });
+
+                            // Same handler as the game screen's New Game button
+                            buttons
+                                .spawn((
+                                    Button,
+                                    NewGameButton,
+                                    Focusable::TimeAttackNewGame,
+                                    Node {
+                                        width: Val::Px(120.0),
+                                        height: Val::Px(40.0),
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::Center,
+                                        border: UiRect::all(Val::Px(2.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(Color::srgb(0.2, 0.6, 0.2)),
+                                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                                ))
+                                .with_children(|button_parent| {
+                                    button_parent.spawn((
+                                        Text::new("New Game"),
+                                        TextFont {
+                                            font_size: 14.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::WHITE),
+                                    ));
+                                });
+                        });
+                });
+        });
+}
+
+// --- Systems ---
+
+/// Everything needed to start a run from the customization screen.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct TimeAttackStart<'w, 's> {
+    app_state: ResMut<'w, NextState<AppState>>,
+    puzzles: PuzzleSwap<'w, 's>,
+}
+
+impl TimeAttackStart<'_, '_> {
+    /// Start a run with its first puzzle and go to the game screen, once the
+    /// puzzle's ready (see `PuzzleSwap::load_fresh`).
+    fn start(&mut self) {
+        let run = TimeAttack::default();
+        info!("⏱️ Starting a time attack run");
+        let loaded = self.puzzles.load_next(&run);
+        self.puzzles.commands.insert_resource(run);
+        if loaded {
+            self.app_state.set(AppState::Ready);
+        }
+    }
+}
+
+/// System that starts a run when the Time Attack button is pressed.
+pub fn handle_time_attack_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<TimeAttackButton>)>,
+    generation: Option<Res<PuzzleGeneration>>,
+    mut start: TimeAttackStart,
+) {
+    if generation.is_some() {
+        return;
+    }
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            start.start();
+        }
+    }
+}
+
+/// System that keeps the run's clock and score line up to date, and shows
+/// it only while a run is on.
+pub fn update_time_attack_display(
+    run: Option<Res<TimeAttack>>,
+    mut display_query: Query<(&mut Text, &mut Node), With<TimeAttackDisplay>>,
+) {
+    for (mut text, mut node) in &mut display_query {
+        let display = if run.is_some() { Display::Flex } else { Display::None };
+        if node.display != display {
+            node.display = display;
+        }
+        if let Some(run) = run.as_ref().filter(|run| run.is_changed()) {
+            text.0 = time_attack_hud_line(run);
+        }
+    }
+}
+
+/// System that shows the overlay once the run's clock runs out.
+pub fn update_time_attack_overlay(
+    run: Option<Res<TimeAttack>>,
+    data: Res<PersistentData>,
+    mut overlay_query: Query<&mut Node, With<TimeAttackOverlay>>,
+    mut text_query: Query<&mut Text, With<TimeAttackSummaryText>>,
+) {
+    let over = run.as_ref().is_some_and(|run| run.is_over());
+    let display = if over { Display::Flex } else { Display::None };
+    for mut node in &mut overlay_query {
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    if let Some(run) = run.filter(|run| run.is_over() && (run.is_changed() || data.is_changed())) {
+        let summary = time_attack_summary_lines(&run, &data.statistics.time_attack).join("\n");
+        for mut text in &mut text_query {
+            text.0.clone_from(&summary);
+        }
+    }
+}
+
+/// System that ends the run when leaving the game screen.
+pub fn end_time_attack(mut commands: Commands) {
+    commands.remove_resource::<TimeAttack>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_time_attack_lines() {
+        let mut run = TimeAttack::default();
+        assert_eq!(time_attack_hud_line(&run), "⏱️ 05:00 left  •  0 solved  •  0 points");
+        run.tick(Duration::from_millis(500));
+        assert_eq!(time_attack_hud_line(&run), "⏱️ 05:00 left  •  0 solved  •  0 points");
+
+        run.record_solve(nine_lives_core::Difficulty::Easy);
+        run.remaining = Duration::ZERO;
+        let mut record = TimeAttackRecord::default();
+        record.record(&run);
+        assert_eq!(
+            time_attack_summary_lines(&run, &record),
+            ["You solved 1 puzzle for 100 points.", "🏆 Your best score yet!"]
+        );
+        record.best_score = 900;
+        record.best_solved = 5;
+        assert_eq!(time_attack_summary_lines(&run, &record)[1], "Best: 900 points (5 solved)");
+    }
+}
@@ -0,0 +1,155 @@
+//! Error toasts for Nine Lives Cat Sudoku.
+//!
+//! When something the player should know about goes wrong (a save that
+//! didn't write, a puzzle that wouldn't generate), the system that hit it
+//! sends an `ErrorToast` instead of printing to the console. Each one shows
+//! as a small note along the bottom of the window for a few seconds, then
+//! fades. Toasts stack, newest at the bottom, and show on every screen.
+
+use bevy::prelude::*;
+use std::fmt;
+
+/// How long a toast stays on screen, in seconds
+const TOAST_SECONDS: f32 = 4.0;
+/// How long a toast takes to fade at the end, in seconds
+const TOAST_FADE_SECONDS: f32 = 0.6;
+/// The most toasts on screen at once; older ones make way
+const MAX_TOASTS: usize = 3;
+
+const TOAST_BACKGROUND: Color = Color::srgba(0.35, 0.12, 0.12, 0.92);
+const TOAST_TEXT: Color = Color::srgb(1.0, 0.9, 0.85);
+
+/// Sent to tell the player something went wrong.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ErrorToast(pub String);
+
+impl ErrorToast {
+    /// A toast for `error` while doing `context`, like
+    /// "⚠️ Settings: couldn't save (disk full)".
+    pub fn new(context: &str, error: &impl fmt::Display) -> Self {
+        Self(format!("⚠️ {}: {}", context, error))
+    }
+}
+
+/// The column the toasts stack in.
+#[derive(Component)]
+pub struct ToastStack;
+
+/// Component on a toast while it's showing.
+#[derive(Component)]
+pub struct Toast {
+    pub timer: Timer,
+}
+
+/// How opaque a toast is, `elapsed` seconds after it appeared.
+pub fn toast_alpha(elapsed: f32) -> f32 {
+    ((TOAST_SECONDS - elapsed) / TOAST_FADE_SECONDS).clamp(0.0, 1.0)
+}
+
+// --- Systems ---
+
+/// System that shows each error toast sent this frame, making the stack
+/// the first time one is needed.
+pub fn show_error_toasts(
+    mut commands: Commands,
+    mut toasts: EventReader<ErrorToast>,
+    stack_query: Query<(Entity, Option<&Children>), With<ToastStack>>,
+) {
+    if toasts.is_empty() {
+        return;
+    }
+    let (stack, mut showing) = match stack_query.single() {
+        Ok((stack, children)) => (stack, children.map(|children| children.to_vec()).unwrap_or_default()),
+        Err(_) => {
+            let stack = commands
+                .spawn((
+                    ToastStack,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        bottom: Val::Px(16.0),
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(6.0),
+                        ..default()
+                    },
+                    Pickable::IGNORE,
+                    GlobalZIndex(20),
+                ))
+                .id();
+            (stack, Vec::new())
+        }
+    };
+
+    for ErrorToast(message) in toasts.read() {
+        warn!("{}", message);
+        let toast = commands
+            .spawn((
+                Toast { timer: Timer::from_seconds(TOAST_SECONDS, TimerMode::Once) },
+                Node {
+                    padding: UiRect::axes(Val::Px(14.0), Val::Px(8.0)),
+                    max_width: Val::Percent(80.0),
+                    ..default()
+                },
+                BackgroundColor(TOAST_BACKGROUND),
+                BorderRadius::all(Val::Px(8.0)),
+                Pickable::IGNORE,
+            ))
+            .with_children(|toast| {
+                toast.spawn((
+                    Text::new(message.clone()),
+                    TextFont { font_size: 16.0, ..default() },
+                    TextColor(TOAST_TEXT),
+                ));
+            })
+            .id();
+        commands.entity(stack).add_child(toast);
+        showing.push(toast);
+    }
+
+    // Too many at once: the oldest go first
+    let extra = showing.len().saturating_sub(MAX_TOASTS);
+    for &toast in &showing[..extra] {
+        commands.entity(toast).despawn();
+    }
+}
+
+/// System that fades each toast out at the end of its time, then removes it.
+pub fn fade_error_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toast_query: Query<(Entity, &mut Toast, &mut BackgroundColor, &Children)>,
+    mut text_query: Query<&mut TextColor>,
+) {
+    for (entity, mut toast, mut background, children) in &mut toast_query {
+        toast.timer.tick(time.delta());
+        if toast.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let alpha = toast_alpha(toast.timer.elapsed_secs());
+        background.0 = TOAST_BACKGROUND.with_alpha(TOAST_BACKGROUND.alpha() * alpha);
+        for child in children.iter() {
+            if let Ok(mut color) = text_query.get_mut(child) {
+                color.0 = TOAST_TEXT.with_alpha(alpha);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nine_lives_core::NineLivesError;
+
+    #[test]
+    fn test_error_toasts() {
+        let error = NineLivesError::SaveError { reason: "disk full".to_string() };
+        assert_eq!(ErrorToast::new("Settings", &error).0, "⚠️ Settings: couldn't save (disk full)");
+
+        // Full strength until the fade, then gone by the end
+        assert_eq!(toast_alpha(0.0), 1.0);
+        assert!(toast_alpha(TOAST_SECONDS - TOAST_FADE_SECONDS / 2.0) < 1.0);
+        assert_eq!(toast_alpha(TOAST_SECONDS), 0.0);
+    }
+}
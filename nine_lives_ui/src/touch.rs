@@ -0,0 +1,39 @@
+//! Touch screen layout for Nine Lives Cat Sudoku.
+//!
+//! Buttons sized for a mouse are fiddly under a finger. Once the controller
+//! sees the screen touched (see `TouchMode`), every button except the board's
+//! cells, which are sized to fit the board, grows to at least
+//! `TOUCH_TARGET_SIZE` each way.
+
+use bevy::prelude::*;
+
+use crate::Cell;
+
+/// Smallest a button is drawn on a touch screen, each way, in pixels
+pub const TOUCH_TARGET_SIZE: f32 = 44.0;
+
+/// Resource noting whether the game is being played by touch. Set by the
+/// controller on the first touch, and kept for the rest of the session.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TouchMode {
+    pub active: bool,
+}
+
+// --- Systems ---
+
+/// System that grows buttons to finger size on touch screens: all of them
+/// when touch is first seen, and new ones as they're spawned.
+pub fn enlarge_touch_targets(
+    touch_mode: Res<TouchMode>,
+    mut button_query: Query<(Ref<Button>, &mut Node), Without<Cell>>,
+) {
+    if !touch_mode.active {
+        return;
+    }
+    for (button, mut node) in &mut button_query {
+        if touch_mode.is_changed() || button.is_added() {
+            node.min_width = Val::Px(TOUCH_TARGET_SIZE);
+            node.min_height = Val::Px(TOUCH_TARGET_SIZE);
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Training Grounds screen for Nine Lives Cat Sudoku.
+//!
+//! Reached with the 🥋 Training button on the customization screen. Lists
+//! each technique that can be practiced (see `nine_lives_core::practice`)
+//! with what it is and how well the player knows it, and a button to play a
+//! puzzle that needs it. Solving a practice puzzle counts toward that
+//! technique, and New Game comes back here.
+
+use bevy::prelude::*;
+use nine_lives_core::{
+    BoardState, GameHistory, GameSession, HintSystem, PRACTICE_TECHNIQUES, PersistentData, PracticeRecord, Solution,
+    Technique, generate_practice_puzzle,
+};
+
+use crate::{AppState, ErrorToast, Focusable, LastGeneration};
+
+/// Background of a Practice button
+const PRACTICE_BG: Color = Color::srgb(0.25, 0.45, 0.3);
+/// Background of a Practice button while hovered
+const PRACTICE_HOVER_BG: Color = Color::srgb(0.3, 0.55, 0.36);
+/// Color of a technique's proficiency line
+const PROFICIENCY_COLOR: Color = Color::srgb(1.0, 0.85, 0.5);
+
+// --- Components ---
+
+/// Component to tag the Training Grounds screen root for cleanup.
+#[derive(Component)]
+pub struct TrainingScreenRoot;
+
+/// Component to tag the "Training" button on the customization screen.
+#[derive(Component)]
+pub struct TrainingButton;
+
+/// Component to tag the "Back" button on the Training Grounds screen.
+#[derive(Component)]
+pub struct TrainingBackButton;
+
+/// Component for a technique's Practice button.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PracticeButton(pub Technique);
+
+/// A technique's proficiency line, like "Practiced: 5 solved, 4 clean".
+pub fn proficiency_line(record: &PracticeRecord) -> String {
+    if record.solved == 0 {
+        record.proficiency().name().to_string()
+    } else {
+        format!("{}: {} solved, {} clean", record.proficiency().name(), record.solved, record.clean)
+    }
+}
+
+// --- Layout ---
+
+/// System to set up the Training Grounds screen.
+pub fn setup_training_screen(mut commands: Commands, data: Res<PersistentData>) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+            TrainingScreenRoot,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new("🥋 Training Grounds"),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::new("Each puzzle here needs its technique, and nothing harder, to solve."),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.8)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+            ));
+
+            for (index, technique) in PRACTICE_TECHNIQUES.into_iter().enumerate() {
+                let record = data.statistics.practice_record(technique);
+                parent
+                    .spawn(Node {
+                        width: Val::Px(560.0),
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceBetween,
+                        column_gap: Val::Px(12.0),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    })
+                    .insert(BorderColor(Color::srgb(0.35, 0.35, 0.5)))
+                    .with_children(|row| {
+                        row.spawn(Node {
+                            flex_direction: FlexDirection::Column,
+                            flex_shrink: 1.0,
+                            row_gap: Val::Px(2.0),
+                            ..default()
+                        })
+                        .with_children(|info| {
+                            info.spawn((
+                                Text::new(technique.name()),
+                                TextFont {
+                                    font_size: 16.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                            info.spawn((
+                                Text::new(technique.description()),
+                                TextFont {
+                                    font_size: 11.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(0.7, 0.7, 0.8)),
+                            ));
+                            info.spawn((
+                                Text::new(proficiency_line(&record)),
+                                TextFont {
+                                    font_size: 12.0,
+                                    ..default()
+                                },
+                                TextColor(PROFICIENCY_COLOR),
+                            ));
+                        });
+
+                        row.spawn((
+                            Button,
+                            PracticeButton(technique),
+                            Focusable::Practice(index),
+                            Node {
+                                width: Val::Px(100.0),
+                                height: Val::Px(36.0),
+                                flex_shrink: 0.0,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(PRACTICE_BG),
+                            BorderColor(Color::srgb(0.4, 0.7, 0.45)),
+                        ))
+                        .with_child((
+                            Text::new("Practice"),
+                            TextFont {
+                                font_size: 14.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            }
+
+            // Back button
+            parent
+                .spawn((
+                    Button,
+                    TrainingBackButton,
+                    Focusable::TrainingBack,
+                    Node {
+                        width: Val::Px(160.0),
+                        height: Val::Px(45.0),
+                        margin: UiRect::top(Val::Px(20.0)),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.6)),
+                    BorderColor(Color::srgb(0.4, 0.4, 0.8)),
+                ))
+                .with_children(|button_parent| {
+                    button_parent.spawn((
+                        Text::new("⬅ Back"),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+/// System to clean up the Training Grounds screen when exiting that state.
+pub fn cleanup_training_screen(mut commands: Commands, query: Query<Entity, With<TrainingScreenRoot>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+// --- Systems ---
+
+/// System that opens the Training Grounds from the customization screen.
+pub fn handle_training_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<TrainingButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::TrainingGrounds);
+        }
+    }
+}
+
+/// System that returns from the Training Grounds to the customization screen.
+pub fn handle_training_back_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<TrainingBackButton>)>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            app_state.set(AppState::Customization);
+        }
+    }
+}
+
+/// Everything needed to start a practice puzzle.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct PracticeStart<'w, 's> {
+    commands: Commands<'w, 's>,
+    app_state: ResMut<'w, NextState<AppState>>,
+    data: Res<'w, PersistentData>,
+    board: ResMut<'w, BoardState>,
+    session: ResMut<'w, GameSession>,
+    history: ResMut<'w, GameHistory>,
+    solution: ResMut<'w, Solution>,
+    hint_system: ResMut<'w, HintSystem>,
+    last_generation: ResMut<'w, LastGeneration>,
+    toasts: EventWriter<'w, ErrorToast>,
+}
+
+impl PracticeStart<'_, '_> {
+    /// Find a puzzle that needs `technique` and go to the game screen.
+    fn start(&mut self, technique: Technique) {
+        let solved = self.data.statistics.practice_record(technique).solved;
+        let Some((settings, board, solution, report)) = generate_practice_puzzle(technique, solved) else {
+            self.toasts.write(ErrorToast(format!("⚠️ No practice puzzle found for {}", technique.name())));
+            return;
+        };
+        info!("🥋 Practicing {}: {}", technique.name(), report.summary());
+        *self.board = board;
+        *self.solution = solution;
+        self.session.reset();
+        self.history.clear();
+        self.hint_system.reset(settings.max_hints);
+        *self.last_generation = LastGeneration { report: Some(report), pre_generated: false };
+        self.commands.insert_resource(settings);
+        self.app_state.set(AppState::Ready);
+    }
+}
+
+/// System that starts a practice puzzle for the technique whose button is pressed.
+pub fn handle_practice_buttons(
+    interaction_query: Query<(&Interaction, &PracticeButton), Changed<Interaction>>,
+    mut start: PracticeStart,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            start.start(button.0);
+        }
+    }
+}
+
+/// System to handle Practice button hover effects.
+#[allow(clippy::type_complexity)]
+pub fn update_practice_button_colors(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), (With<PracticeButton>, Changed<Interaction>)>,
+) {
+    for (interaction, mut bg_color) in &mut button_query {
+        bg_color.0 = match interaction {
+            Interaction::Pressed | Interaction::Hovered => PRACTICE_HOVER_BG,
+            Interaction::None => PRACTICE_BG,
+        };
+    }
+}
@@ -0,0 +1,274 @@
+//! Tutorial for Nine Lives Cat Sudoku.
+//!
+//! The 🎓 Tutorial button on the customization screen starts the guided first
+//! puzzle (see `nine_lives_core::Tutorial`) on a 4×4 board. A callout above
+//! the board gives each step's prompt, and the cell the step is about glows.
+//! Steps that explain a rule wait for Next; the others wait for the right cat
+//! in the glowing cell, and no other cell can be changed meanwhile. Skip goes
+//! back to the customization screen at any point.
+
+use bevy::prelude::*;
+use nine_lives_core::{
+    BoardState, GameHistory, GameSession, GridSize, HintSystem, PresetKind, PuzzleSettings, Solution, Tutorial,
+};
+
+use crate::{AppState, Cell, Focusable, LastGeneration, Locale, TextFit};
+
+/// Color of the glow around the cell the step is about
+const MARK_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+/// Width of the glow
+const MARK_WIDTH: f32 = 4.0;
+/// How many times a second the glow pulses
+const PULSE_RATE: f32 = 1.5;
+
+// --- Components ---
+
+/// Component to tag the "Tutorial" button on the customization screen.
+#[derive(Component)]
+pub struct TutorialButton;
+
+/// Component to tag the tutorial callout on the game screen.
+#[derive(Component)]
+pub struct TutorialPanel;
+
+/// Component to tag the callout's prompt.
+#[derive(Component)]
+pub struct TutorialPromptDisplay;
+
+/// Component to tag the button that moves on from a step that explains something.
+#[derive(Component)]
+pub struct TutorialNextButton;
+
+/// Component to tag the button that leaves the tutorial.
+#[derive(Component)]
+pub struct TutorialSkipButton;
+
+/// Component on the cell the current step is about.
+#[derive(Component)]
+pub struct TutorialMark;
+
+/// The callout's prompt for where the tutorial is.
+pub fn tutorial_prompt(tutorial: &Tutorial, locale: &Locale) -> String {
+    match tutorial.current() {
+        Some(step) => locale.get(&format!("tutorial.{}", step.name)),
+        None => locale.get("tutorial.done"),
+    }
+}
+
+// --- Layout ---
+
+/// Spawn one of the callout's buttons, tagged with `tags`.
+fn spawn_callout_button(parent: &mut ChildSpawnerCommands, tags: impl Bundle, color: Color) {
+    parent
+        .spawn((
+            Button,
+            tags,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(color),
+            BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+        ))
+        .with_children(|button_parent| {
+            button_parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Spawn the (initially hidden) tutorial callout on the game screen.
+pub fn spawn_tutorial_panel(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            TutorialPanel,
+            Node {
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                margin: UiRect::bottom(Val::Px(10.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.2, 0.17, 0.05, 0.95)),
+            BorderColor(MARK_COLOR),
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 15.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(JustifyText::Center),
+                TextFit::new(460.0, 3, 15.0),
+                TutorialPromptDisplay,
+            ));
+
+            panel
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(8.0),
+                    ..default()
+                })
+                .with_children(|buttons| {
+                    spawn_callout_button(
+                        buttons,
+                        (TutorialNextButton, Focusable::TutorialNext),
+                        Color::srgb(0.2, 0.6, 0.2),
+                    );
+                    spawn_callout_button(
+                        buttons,
+                        (TutorialSkipButton, Focusable::TutorialSkip),
+                        Color::srgb(0.4, 0.4, 0.4),
+                    );
+                });
+        });
+}
+
+// --- Systems ---
+
+/// Everything needed to start the tutorial from the customization screen.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct TutorialStart<'w, 's> {
+    commands: Commands<'w, 's>,
+    app_state: ResMut<'w, NextState<AppState>>,
+    board: ResMut<'w, BoardState>,
+    session: ResMut<'w, GameSession>,
+    history: ResMut<'w, GameHistory>,
+    solution: ResMut<'w, Solution>,
+    hint_system: ResMut<'w, HintSystem>,
+    last_generation: ResMut<'w, LastGeneration>,
+}
+
+impl TutorialStart<'_, '_> {
+    /// Set up the tutorial puzzle and go to the game screen.
+    fn start(&mut self) {
+        let settings = PuzzleSettings::from_preset(PresetKind::CozyKitten).with_grid_size(GridSize::Mini);
+        info!("🎓 Starting the tutorial");
+        let (board, solution) = Tutorial::puzzle();
+        *self.board = board;
+        *self.solution = solution;
+        *self.last_generation = LastGeneration::default();
+        self.session.reset();
+        self.history.clear();
+        self.hint_system.reset(settings.max_hints);
+        self.commands.insert_resource(settings);
+        self.commands.insert_resource(Tutorial::default());
+        self.app_state.set(AppState::Ready);
+    }
+}
+
+/// System that starts the tutorial when the Tutorial button is pressed.
+pub fn handle_tutorial_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<TutorialButton>)>,
+    mut start: TutorialStart,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            start.start();
+        }
+    }
+}
+
+/// System that handles the callout's Next and Skip buttons.
+pub fn handle_tutorial_buttons(
+    next_query: Query<&Interaction, (Changed<Interaction>, With<TutorialNextButton>)>,
+    skip_query: Query<&Interaction, (Changed<Interaction>, With<TutorialSkipButton>)>,
+    mut tutorial: ResMut<Tutorial>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    if next_query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        tutorial.next();
+    }
+    if skip_query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        if !tutorial.is_finished() {
+            info!("🎓 Tutorial skipped");
+        }
+        app_state.set(AppState::Customization);
+    }
+}
+
+/// System that shows the callout only during the tutorial, with the current
+/// step's prompt, Next only for steps that wait for it, and Skip turning
+/// into Finish once every step is done.
+#[allow(clippy::type_complexity)]
+pub fn update_tutorial_panel(
+    tutorial: Option<Res<Tutorial>>,
+    locale: Res<Locale>,
+    mut panel_query: Query<&mut Node, With<TutorialPanel>>,
+    mut prompt_query: Query<&mut Text, With<TutorialPromptDisplay>>,
+    mut next_query: Query<(&mut Node, &Children), (With<TutorialNextButton>, Without<TutorialPanel>)>,
+    skip_query: Query<&Children, With<TutorialSkipButton>>,
+    mut text_query: Query<&mut Text, Without<TutorialPromptDisplay>>,
+) {
+    let display = if tutorial.is_some() { Display::Flex } else { Display::None };
+    for mut node in &mut panel_query {
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    let Some(tutorial) = tutorial.filter(|tutorial| tutorial.is_changed() || locale.is_changed()) else {
+        return;
+    };
+
+    let prompt = tutorial_prompt(&tutorial, &locale);
+    for mut text in &mut prompt_query {
+        text.0.clone_from(&prompt);
+    }
+    for (mut node, children) in &mut next_query {
+        node.display = if tutorial.waits_for_next() { Display::Flex } else { Display::None };
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = locale.get("tutorial.next");
+            }
+        }
+    }
+    let skip_label = locale.get(if tutorial.is_finished() { "tutorial.finish" } else { "tutorial.skip" });
+    for children in &skip_query {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0.clone_from(&skip_label);
+            }
+        }
+    }
+}
+
+/// System that makes the cell the current step is about glow, pulsing so
+/// it catches the eye.
+pub fn update_tutorial_mark(
+    mut commands: Commands,
+    time: Res<Time>,
+    tutorial: Option<Res<Tutorial>>,
+    mut cell_query: Query<(Entity, &Cell, Option<&mut Outline>, Has<TutorialMark>)>,
+) {
+    let target = tutorial.as_ref().and_then(|tutorial| tutorial.current()).and_then(|step| step.cell);
+    let pulse = 0.65 + 0.35 * (time.elapsed_secs() * PULSE_RATE * std::f32::consts::TAU).sin();
+    for (entity, cell, outline, marked) in &mut cell_query {
+        let is_target = target == Some((cell.row, cell.col));
+        match (is_target, marked, outline) {
+            (true, true, Some(mut outline)) => outline.color = MARK_COLOR.with_alpha(pulse),
+            (true, false, _) => {
+                commands.entity(entity).insert((TutorialMark, Outline::new(Val::Px(MARK_WIDTH), Val::ZERO, MARK_COLOR)));
+            }
+            (false, true, _) => {
+                commands.entity(entity).remove::<(TutorialMark, Outline)>();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// System that ends the tutorial when leaving the game screen.
+pub fn end_tutorial(mut commands: Commands) {
+    commands.remove_resource::<Tutorial>();
+}